@@ -25,6 +25,31 @@ macro_rules! expand {
         pub fn sum_array(map: &[Option<u32>; $len]) -> u32 {
             map.iter().flatten().copied().sum()
         }
+
+        #[cfg(feature = "ops")]
+        #[no_mangle]
+        #[inline(never)]
+        pub fn add_fixed(
+            a: fixed_map::Map<FixedKey, u32>,
+            b: fixed_map::Map<FixedKey, u32>,
+        ) -> fixed_map::Map<FixedKey, u32> {
+            a + b
+        }
+
+        #[no_mangle]
+        #[inline(never)]
+        pub fn add_array(a: [Option<u32>; $len], b: [Option<u32>; $len]) -> [Option<u32>; $len] {
+            let mut output = [None; $len];
+
+            for ((output, a), b) in output.iter_mut().zip(a).zip(b) {
+                *output = match (a, b) {
+                    (Some(a), Some(b)) => Some(a + b),
+                    (a, b) => a.or(b),
+                };
+            }
+
+            output
+        }
     }
 }
 
@@ -45,6 +70,16 @@ fn benches(criterion: &mut Criterion) {
 
             iter.iter(|| sum_array(&array))
         });
+
+        group.bench_function("add", |iter| {
+            let mut a = [None; 16];
+            a[ArrayKey::T07 as usize] = Some(4);
+            let mut b = [None; 16];
+            b[ArrayKey::T07 as usize] = Some(2);
+            b[ArrayKey::T10 as usize] = Some(13);
+
+            iter.iter(|| add_array(a, b))
+        });
     }
 
     {
@@ -57,6 +92,17 @@ fn benches(criterion: &mut Criterion) {
 
             iter.iter(|| sum_fixed(&map))
         });
+
+        #[cfg(feature = "ops")]
+        group.bench_function("add", |iter| {
+            let mut a = fixed_map::Map::<_, u32>::new();
+            a.insert(FixedKey::T07, 4);
+            let mut b = fixed_map::Map::<_, u32>::new();
+            b.insert(FixedKey::T07, 2);
+            b.insert(FixedKey::T10, 13);
+
+            iter.iter(|| add_fixed(a, b))
+        });
     }
 }
 
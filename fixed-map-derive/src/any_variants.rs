@@ -1,22 +1,35 @@
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::ext::IdentExt;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 
 const MAP_STORAGE: &str = "__MapStorage";
 const SET_STORAGE: &str = "__SetStorage";
 
-use crate::context::Ctxt;
+use crate::context::{predicates, Ctxt, Opts};
 
 /// Implement the `Key` trait for an enum.
-pub(crate) fn implement(cx: &Ctxt<'_>, en: &syn::DataEnum) -> Result<TokenStream, ()> {
+pub(crate) fn implement(cx: &Ctxt<'_>, opts: &Opts, en: &syn::DataEnum) -> Result<TokenStream, ()> {
     let ident = &cx.ast.ident;
+    let ref_ident = opts.borrow.map(|_| format_ident!("{}Ref", ident));
+
+    if let Some(span) = opts.compact {
+        cx.span_error(
+            span,
+            "`compact` is only supported for enums with unit variants",
+        );
+        return Err(());
+    }
 
     let key_t = cx.toks.key_t();
     let map_storage_t = cx.toks.map_storage_t();
     let set_storage_t = cx.toks.set_storage_t();
 
-    let mut fields = Fields::default();
+    let mut fields = Fields {
+        ref_ident: ref_ident.as_ref(),
+        ..Fields::default()
+    };
 
     for (index, variant) in en.variants.iter().enumerate() {
         let var = &variant.ident;
@@ -27,6 +40,13 @@ pub(crate) fn implement(cx: &Ctxt<'_>, en: &syn::DataEnum) -> Result<TokenStream
                 fields
                     .patterns
                     .push(build_tuple_struct_pat(ident, var, None));
+
+                if let Some(ref_ident) = &ref_ident {
+                    fields
+                        .ref_patterns
+                        .push(build_tuple_struct_pat(ref_ident, var, None));
+                }
+
                 Kind::Simple
             }
             syn::Fields::Unnamed(unnamed) => {
@@ -39,8 +59,8 @@ pub(crate) fn implement(cx: &Ctxt<'_>, en: &syn::DataEnum) -> Result<TokenStream
                 }
 
                 let element = unnamed.unnamed.first().expect("Expected one element");
-                let map_storage = quote!(<#element as #key_t>::MapStorage::<V>);
-                let as_map_storage = quote!(<#map_storage as #map_storage_t<#element, V>>);
+                let map_storage = quote!(<#element as #key_t>::MapStorage::<__V>);
+                let as_map_storage = quote!(<#map_storage as #map_storage_t<#element, __V>>);
                 let set_storage = quote!(<#element as #key_t>::SetStorage);
                 let as_set_storage = quote!(<#set_storage as #set_storage_t<#element>>);
 
@@ -49,6 +69,15 @@ pub(crate) fn implement(cx: &Ctxt<'_>, en: &syn::DataEnum) -> Result<TokenStream
 
                 fields.patterns.push(pat);
 
+                if let Some(ref_ident) = &ref_ident {
+                    let pat = build_tuple_struct_pat(
+                        ref_ident,
+                        var,
+                        Some(syn::Ident::new("v", unnamed.span())),
+                    );
+                    fields.ref_patterns.push(pat);
+                }
+
                 Kind::Complex(Complex {
                     element,
                     map_storage,
@@ -72,23 +101,169 @@ pub(crate) fn implement(cx: &Ctxt<'_>, en: &syn::DataEnum) -> Result<TokenStream
         });
     }
 
+    // Generic keys can't rely on the storage of their fields being known, so
+    // require every field to implement `Key`.
+    if !cx.ast.generics.params.is_empty() {
+        for Complex { element, .. } in fields.complex() {
+            let ty = &element.ty;
+            cx.bound(syn::parse_quote!(#ty: #key_t));
+        }
+    }
+
     let (map_storage_type_name, map_storage_impl) = impl_map_storage(cx, &fields)?;
     let (set_storage_type_name, set_storage_impl) = impl_set_storage(cx, &fields)?;
 
+    let key = cx.key_type();
+    let lt = cx.lt;
+    let generics = cx.generics(false, false);
+    let (impl_generics, set_generics, where_clause) = generics.split_for_impl();
+    let generics = cx.generics(false, true);
+    let (_, map_generics, _) = generics.split_for_impl();
+
+    let variant_selectors = impl_variant_selectors(cx, &fields);
+
+    let finite_impl = match opts.finite {
+        Some(span) => impl_finite(cx, &fields, span)?,
+        None => TokenStream::new(),
+    };
+
+    let (ref_type, ref_impl) = match &ref_ident {
+        Some(ref_ident) => {
+            let generics = ref_generics(cx);
+            let (_, ty_generics, _) = generics.split_for_impl();
+            let ref_impl = impl_ref(cx, &fields, ref_ident)?;
+            (quote!(#ref_ident #ty_generics), ref_impl)
+        }
+        None => (quote!(Self), TokenStream::new()),
+    };
+
     Ok(quote! {
+        #ref_impl
+
         const _: () = {
             #map_storage_impl
             #set_storage_impl
 
             #[automatically_derived]
-            impl #key_t for #ident {
-                type MapStorage<V> = #map_storage_type_name<V>;
-                type SetStorage = #set_storage_type_name;
+            impl #impl_generics #key_t for #key #where_clause {
+                type MapStorage<__V> = #map_storage_type_name #map_generics;
+                type SetStorage = #set_storage_type_name #set_generics;
+                type Ref<#lt> = #ref_type;
             }
+
+            #finite_impl
+            #variant_selectors
         };
     })
 }
 
+/// Implement `Finite` for a key with the `finite` attribute, where the
+/// inhabitants of every variant follow each other in declaration order.
+fn impl_finite(cx: &Ctxt<'_>, fields: &Fields<'_>, span: Span) -> Result<TokenStream, ()> {
+    if !cx.ast.generics.params.is_empty() {
+        cx.span_error(span, "`finite` is not supported for generic keys");
+        return Err(());
+    }
+
+    let ident = &cx.ast.ident;
+    let array_from_fn = cx.toks.array_from_fn();
+    let finite_t = cx.toks.finite_t();
+    let iter_empty = cx.toks.iter_empty();
+    let iterator_t = cx.toks.iterator_t();
+    let option = cx.toks.option();
+
+    let mut count = quote!(0);
+    let mut indexes = Vec::new();
+    let mut keys = quote!(#iter_empty());
+
+    for Field { var, kind, .. } in fields.iter() {
+        match kind {
+            Kind::Complex(Complex { element, .. }) => {
+                let ty = &element.ty;
+                indexes.push(quote!(#ident::#var(v) => #count + #finite_t::index(v)));
+                keys = quote!(#iterator_t::chain(#keys, <#ty as #finite_t>::array_from_fn(#ident::#var)));
+                count = quote!(#count + <#ty as #finite_t>::COUNT);
+            }
+            Kind::Simple => {
+                indexes.push(quote!(#ident::#var => #count));
+                keys = quote!(#iterator_t::chain(#keys, [#ident::#var]));
+                count = quote!(#count + 1);
+            }
+        }
+    }
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #finite_t for #ident {
+            const COUNT: usize = #count;
+
+            type Array<__V> = [__V; #count];
+
+            #[inline]
+            fn index(&self) -> usize {
+                match self {
+                    #(#indexes,)*
+                }
+            }
+
+            #[inline]
+            fn array_from_fn<__V, __F>(mut f: __F) -> [__V; #count]
+            where
+                __F: FnMut(Self) -> __V
+            {
+                let mut keys = #keys;
+
+                #array_from_fn(|_| match #iterator_t::next(&mut keys) {
+                    #option::Some(key) => f(key),
+                    #option::None => ::core::unreachable!("fewer keys than inhabitants"),
+                })
+            }
+        }
+    })
+}
+
+/// Implement the borrowed form of the key, which mirrors its variants but
+/// holds the [`Key::Ref`] of every field.
+fn impl_ref(cx: &Ctxt<'_>, fields: &Fields<'_>, ref_ident: &syn::Ident) -> Result<TokenStream, ()> {
+    let vis = &cx.ast.vis;
+    let ident = &cx.ast.ident;
+    let lt = cx.lt;
+
+    let key_t = cx.toks.key_t();
+    let clone_t = cx.toks.clone_t();
+
+    let generics = ref_generics(cx);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let variants = fields.iter().map(|Field { var, kind, .. }| match kind {
+        Kind::Complex(Complex { element, .. }) => {
+            let ty = &element.ty;
+            quote!(#var(<#ty as #key_t>::Ref<#lt>))
+        }
+        Kind::Simple => quote!(#var),
+    });
+
+    let doc =
+        format!("Borrowed form of [`{ident}`], used to look up keys without constructing them.");
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(#clone_t)]
+        #vis enum #ref_ident #impl_generics #where_clause {
+            #(#variants,)*
+        }
+    })
+}
+
+/// Generics of the borrowed form of the key, which are the generics of the
+/// key extended with the lifetime of the borrow.
+fn ref_generics(cx: &Ctxt<'_>) -> syn::Generics {
+    let lt = cx.lt;
+    let mut generics = cx.generics(false, false);
+    generics.params.push(syn::parse_quote!(#lt));
+    generics
+}
+
 fn build_tuple_struct_pat(
     ident: &syn::Ident,
     var: &syn::Ident,
@@ -142,6 +317,11 @@ fn build_tuple_struct_pat(
 fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, TokenStream), ()> {
     let vis = &cx.ast.vis;
     let ident = &cx.ast.ident;
+    let key = cx.key_type();
+
+    let generics = cx.generics(false, true);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = predicates(&generics);
 
     let option = cx.toks.option();
     let map_storage_t = cx.toks.map_storage_t();
@@ -157,15 +337,20 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, T
     map_storage_values_mut(cx, "ValuesMut", fields, &mut output)?;
     map_storage_into_iter(cx, "IntoIter", fields, &mut output)?;
     map_storage_entry(cx, fields, &type_name, &mut output)?;
+    map_storage_zip(cx, "Zip", fields, &mut output)?;
 
     {
         let partial_eq_t = cx.toks.partial_eq_t();
         let eq_t = cx.toks.eq_t();
+        let bounds = fields
+            .complex()
+            .map(|Complex { map_storage, .. }| map_storage)
+            .collect::<Vec<_>>();
         let names = fields.names();
 
         output.impls.extend(quote! {
             #[automatically_derived]
-            impl<V> #partial_eq_t for #type_name<V> where V: #partial_eq_t {
+            impl #impl_generics #partial_eq_t for #type_name #ty_generics where #(#predicates,)* __V: #partial_eq_t, #(#bounds: #partial_eq_t,)* {
                 #[inline]
                 fn eq(&self, other: &Self) -> bool {
                     #(if #partial_eq_t::ne(&self.#names, &other.#names) {
@@ -177,7 +362,60 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, T
             }
 
             #[automatically_derived]
-            impl<V> #eq_t for #type_name<V> where V: #eq_t {}
+            impl #impl_generics #eq_t for #type_name #ty_generics where #(#predicates,)* __V: #eq_t, #(#bounds: #eq_t,)* {}
+        });
+    }
+
+    {
+        let hash_t = cx.toks.hash_t();
+        let hasher_t = cx.toks.hasher_t();
+        let partial_ord_t = cx.toks.partial_ord_t();
+        let ord_t = cx.toks.ord_t();
+        let ordering = cx.toks.ordering();
+        let option = cx.toks.option();
+        let bounds = fields
+            .complex()
+            .map(|Complex { map_storage, .. }| map_storage)
+            .collect::<Vec<_>>();
+        let names = fields.names().collect::<Vec<_>>();
+
+        output.impls.extend(quote! {
+            #[automatically_derived]
+            impl #impl_generics #hash_t for #type_name #ty_generics where #(#predicates,)* __V: #hash_t, #(#bounds: #hash_t,)* {
+                #[inline]
+                fn hash<__H>(&self, state: &mut __H)
+                where
+                    __H: #hasher_t,
+                {
+                    #(#hash_t::hash(&self.#names, state);)*
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #partial_ord_t for #type_name #ty_generics where #(#predicates,)* __V: #partial_ord_t, #(#bounds: #partial_ord_t,)* {
+                #[inline]
+                fn partial_cmp(&self, other: &Self) -> #option<#ordering> {
+                    #(match #partial_ord_t::partial_cmp(&self.#names, &other.#names) {
+                        #option::Some(#ordering::Equal) => {}
+                        ordering => return ordering,
+                    })*
+
+                    #option::Some(#ordering::Equal)
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #ord_t for #type_name #ty_generics where #(#predicates,)* __V: #ord_t, #(#bounds: #ord_t,)* {
+                #[inline]
+                fn cmp(&self, other: &Self) -> #ordering {
+                    #(match #ord_t::cmp(&self.#names, &other.#names) {
+                        #ordering::Equal => {}
+                        ordering => return ordering,
+                    })*
+
+                    #ordering::Equal
+                }
+            }
         });
     }
 
@@ -186,12 +424,13 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, T
         let copy_t = cx.toks.copy_t();
         let bounds = fields
             .complex()
-            .map(|Complex { map_storage, .. }| map_storage);
+            .map(|Complex { map_storage, .. }| map_storage)
+            .collect::<Vec<_>>();
         let names = fields.names();
 
         output.impls.extend(quote! {
             #[automatically_derived]
-            impl<V> #clone_t for #type_name<V> where V: #clone_t {
+            impl #impl_generics #clone_t for #type_name #ty_generics where #(#predicates,)* __V: #clone_t, #(#bounds: #clone_t,)* {
                 #[inline]
                 fn clone(&self) -> Self {
                     Self {
@@ -201,7 +440,7 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, T
             }
 
             #[automatically_derived]
-            impl<V> #copy_t for #type_name<V> where V: #copy_t, #(#bounds: #copy_t,)* {}
+            impl #impl_generics #copy_t for #type_name #ty_generics where #(#predicates,)* __V: #copy_t, #(#bounds: #copy_t,)* {}
         });
     }
 
@@ -235,7 +474,7 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, T
 
         output.items.extend(quote! {
             #[inline]
-            fn insert(&mut self, key: #ident, value: V) -> #option<V> {
+            fn insert(&mut self, key: #key, value: __V) -> #option<__V> {
                 match key {
                     #(#patterns => #insert,)*
                 }
@@ -287,7 +526,7 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, T
 
         output.items.extend(quote! {
             #[inline]
-            fn contains_key(&self, value: #ident) -> bool {
+            fn contains_key(&self, value: #key) -> bool {
                 match value {
                     #(#patterns => #contains_key,)*
                 }
@@ -307,7 +546,7 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, T
 
         output.items.extend(quote! {
             #[inline]
-            fn get(&self, value: #ident) -> #option<&V> {
+            fn get(&self, value: #key) -> #option<&__V> {
                 match value {
                     #(#patterns => #get,)*
                 }
@@ -327,7 +566,7 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, T
 
         output.items.extend(quote! {
             #[inline]
-            fn get_mut(&mut self, value: #ident) -> #option<&mut V> {
+            fn get_mut(&mut self, value: #key) -> #option<&mut __V> {
                 match value {
                     #(#patterns => #get_mut,)*
                 }
@@ -347,7 +586,7 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, T
 
         output.items.extend(quote! {
             #[inline]
-            fn remove(&mut self, value: #ident) -> #option<V> {
+            fn remove(&mut self, value: #key) -> #option<__V> {
                 match value {
                     #(#patterns => #remove,)*
                 }
@@ -355,6 +594,76 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, T
         });
     }
 
+    {
+        let key_t = cx.toks.key_t();
+
+        let contains_key_by = lookup_by(
+            fields,
+            fields.iter().map(|Field { name, kind, .. }| match kind {
+                Kind::Complex(Complex { as_map_storage, .. }) => {
+                    quote!(#as_map_storage::contains_key_by(&self.#name, v))
+                }
+                Kind::Simple => quote!(#option::is_some(&self.#name)),
+            }),
+            quote!(#map_storage_t::contains_key(self, value)),
+        );
+
+        let get_by = lookup_by(
+            fields,
+            fields.iter().map(|Field { name, kind, .. }| match kind {
+                Kind::Complex(Complex { as_map_storage, .. }) => {
+                    quote!(#as_map_storage::get_by(&self.#name, v))
+                }
+                Kind::Simple => quote!(#option::as_ref(&self.#name)),
+            }),
+            quote!(#map_storage_t::get(self, value)),
+        );
+
+        let get_mut_by = lookup_by(
+            fields,
+            fields.iter().map(|Field { name, kind, .. }| match kind {
+                Kind::Complex(Complex { as_map_storage, .. }) => {
+                    quote!(#as_map_storage::get_mut_by(&mut self.#name, v))
+                }
+                Kind::Simple => quote!(#option::as_mut(&mut self.#name)),
+            }),
+            quote!(#map_storage_t::get_mut(self, value)),
+        );
+
+        let remove_by = lookup_by(
+            fields,
+            fields.iter().map(|Field { name, kind, .. }| match kind {
+                Kind::Complex(Complex { as_map_storage, .. }) => {
+                    quote!(#as_map_storage::remove_by(&mut self.#name, v))
+                }
+                Kind::Simple => quote!(#option::take(&mut self.#name)),
+            }),
+            quote!(#map_storage_t::remove(self, value)),
+        );
+
+        output.items.extend(quote! {
+            #[inline]
+            fn contains_key_by(&self, value: <#key as #key_t>::Ref<'_>) -> bool {
+                #contains_key_by
+            }
+
+            #[inline]
+            fn get_by(&self, value: <#key as #key_t>::Ref<'_>) -> #option<&__V> {
+                #get_by
+            }
+
+            #[inline]
+            fn get_mut_by(&mut self, value: <#key as #key_t>::Ref<'_>) -> #option<&mut __V> {
+                #get_mut_by
+            }
+
+            #[inline]
+            fn remove_by(&mut self, value: <#key as #key_t>::Ref<'_>) -> #option<__V> {
+                #remove_by
+            }
+        });
+    }
+
     {
         let retain = fields.iter().map(
             |Field {
@@ -375,9 +684,9 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, T
 
         output.items.extend(quote! {
             #[inline]
-            fn retain<F>(&mut self, mut func: F)
+            fn retain<__F>(&mut self, mut func: __F)
             where
-                F: FnMut(#ident, &mut V) -> bool
+                __F: FnMut(#key, &mut __V) -> bool
             {
                 #(#retain;)*
             }
@@ -402,108 +711,775 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, T
         });
     }
 
+    {
+        let key_t = cx.toks.key_t();
+
+        let map_values = fields.iter().map(
+            |Field {
+                 var, name, kind, ..
+             }| match kind {
+                Kind::Complex(Complex { as_map_storage, .. }) => quote! {
+                    #name: #as_map_storage::map_values(self.#name, |k, v| f(#ident::#var(k), v))
+                },
+                Kind::Simple => quote! {
+                    #name: #option::map(self.#name, |v| f(#ident::#var, v))
+                },
+            },
+        );
+
+        let merge = fields.iter().map(
+            |Field {
+                 var, name, kind, ..
+             }| match kind {
+                Kind::Complex(Complex { as_map_storage, .. }) => quote! {
+                    #name: #as_map_storage::merge(self.#name, other.#name, |k, a, b| f(#ident::#var(k), a, b))
+                },
+                Kind::Simple => quote! {
+                    #name: match (self.#name, other.#name) {
+                        (#option::None, #option::None) => #option::None,
+                        (a, b) => f(#ident::#var, a, b),
+                    }
+                },
+            },
+        );
+
+        output.items.extend(quote! {
+            #[inline]
+            fn map_values<__U, __F>(self, mut f: __F) -> <#key as #key_t>::MapStorage<__U>
+            where
+                __F: FnMut(#key, __V) -> __U,
+            {
+                #type_name {
+                    #(#map_values,)*
+                }
+            }
+
+            #[inline]
+            fn merge<__U, __W, __F>(self, other: <#key as #key_t>::MapStorage<__U>, mut f: __F) -> <#key as #key_t>::MapStorage<__W>
+            where
+                __F: FnMut(#key, #option<__V>, #option<__U>) -> #option<__W>,
+            {
+                #type_name {
+                    #(#merge,)*
+                }
+            }
+        });
+    }
+
     let field_decls = fields.iter().map(|Field { name, kind, .. }| match kind {
         Kind::Complex(Complex { map_storage, .. }) => quote!(#name: #map_storage),
-        Kind::Simple => quote!(#name: #option<V>),
+        Kind::Simple => quote!(#name: #option<__V>),
     });
 
     let Output { impls, items } = output;
+    let variant_impls = map_storage_variants(cx, fields, &type_name);
+    let parallel_impl = impl_parallel_map_storage(cx, fields, &type_name);
+    let archive_impl = impl_archive_map_storage(cx, fields, &type_name);
 
     let map_storage_impl = quote! {
-        #vis struct #type_name<V> {
+        #vis struct #type_name #impl_generics #where_clause {
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<V> #map_storage_t<#ident, V> for #type_name<V> {
+        impl #impl_generics #map_storage_t<#key, __V> for #type_name #ty_generics #where_clause {
             #items
         }
 
         #impls
+        #variant_impls
+        #parallel_impl
+        #archive_impl
     };
 
     Ok((type_name, map_storage_impl))
 }
 
-/// Implement `SetStorage` implementation.
-fn impl_set_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, TokenStream), ()> {
-    let vis = &cx.ast.vis;
-    let ident = &cx.ast.ident;
-
-    let mem = cx.toks.mem();
-    let set_storage_t = cx.toks.set_storage_t();
-
-    let type_name = format_ident!("{SET_STORAGE}");
+/// Implement access to the storage of every variant with a field, where the
+/// variant is selected through its index.
+fn map_storage_variants(cx: &Ctxt<'_>, fields: &Fields<'_>, type_name: &syn::Ident) -> TokenStream {
+    let key_t = cx.toks.key_t();
+    let variant_map_storage_t = cx.toks.variant_map_storage_t();
 
-    let mut output = Output::default();
+    let key = cx.key_type();
+    let generics = cx.generics(false, true);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    set_storage_iter(cx, "Iter", fields, &mut output)?;
-    set_storage_into_iter(cx, "IntoIter", fields, &mut output)?;
+    let mut impls = Vec::new();
 
+    for Field {
+        index, name, kind, ..
+    } in fields.iter()
     {
-        let partial_eq_t = cx.toks.partial_eq_t();
-        let eq_t = cx.toks.eq_t();
-        let names = fields.names();
+        let Kind::Complex(Complex { element, .. }) = kind else {
+            continue;
+        };
 
-        output.impls.extend(quote! {
+        let ty = &element.ty;
+
+        impls.push(quote! {
             #[automatically_derived]
-            impl #partial_eq_t for #type_name {
+            impl #impl_generics #variant_map_storage_t<#key, __V, #index> for #type_name #ty_generics #where_clause {
+                type Field = #ty;
+
                 #[inline]
-                fn eq(&self, other: &Self) -> bool {
-                    #(if #partial_eq_t::ne(&self.#names, &other.#names) {
-                        return false;
-                    })*
+                fn variant(&self) -> &<#ty as #key_t>::MapStorage<__V> {
+                    &self.#name
+                }
 
-                    true
+                #[inline]
+                fn variant_mut(&mut self) -> &mut <#ty as #key_t>::MapStorage<__V> {
+                    &mut self.#name
                 }
-            }
 
-            #[automatically_derived]
-            impl #eq_t for #type_name  {}
+                #[inline]
+                fn into_variant(self) -> <#ty as #key_t>::MapStorage<__V> {
+                    self.#name
+                }
+            }
         });
     }
 
+    quote!(#(#impls)*)
+}
+
+/// Implement an associated constant on the key holding the selector of every
+/// variant with a field, named after the variant in upper snake case.
+fn impl_variant_selectors(cx: &Ctxt<'_>, fields: &Fields<'_>) -> TokenStream {
+    let vis = &cx.ast.vis;
+    let ident = &cx.ast.ident;
+    let variant = cx.toks.variant();
+
+    let key = cx.key_type();
+    let generics = cx.generics(false, false);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let mut consts = Vec::new();
+
+    for Field {
+        index, var, kind, ..
+    } in fields.iter()
     {
-        let clone_t = cx.toks.clone_t();
-        let copy_t = cx.toks.copy_t();
-        let bounds = fields
-            .complex()
-            .map(|Complex { set_storage, .. }| set_storage)
-            .collect::<Vec<_>>();
-        let names = fields.names();
+        let Kind::Complex(..) = kind else {
+            continue;
+        };
 
-        output.impls.extend(quote! {
-            #[automatically_derived]
-            impl #clone_t for #type_name where #(for<'trivial_bounds> #bounds: #clone_t,)* {
-                #[inline]
-                fn clone(&self) -> Self {
-                    Self {
-                        #(#names: #clone_t::clone(&self.#names),)*
-                    }
-                }
-            }
+        let name = syn::Ident::new(&screaming_snake_case(&var.unraw().to_string()), var.span());
+        let doc = format!("Selects the entries under [`{ident}::{var}`] in a map.");
 
-            #[automatically_derived]
-            impl #copy_t for #type_name where #(for<'trivial_bounds> #bounds: #copy_t,)* {}
+        consts.push(quote! {
+            #[doc = #doc]
+            #vis const #name: #variant<Self, #index> = #variant::new();
         });
     }
 
-    {
-        let inits = fields.iter().map(|f| match &f.kind {
-            Kind::Complex(Complex { as_set_storage, .. }) => quote!(#as_set_storage::empty()),
-            Kind::Simple => quote!(false),
-        });
+    if consts.is_empty() {
+        return TokenStream::new();
+    }
 
-        let names = fields.names();
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #key #where_clause {
+            #(#consts)*
+        }
+    }
+}
 
-        output.items.extend(quote! {
-            #[inline]
-            fn empty() -> Self {
-                Self {
-                    #(#names: #inits,)*
-                }
+/// Convert a variant name from upper camel case into upper snake case, keeping
+/// acronyms together so that `HttpStatus` and `HTTPStatus` both become
+/// `HTTP_STATUS`.
+fn screaming_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut out = String::with_capacity(name.len() + 4);
+
+    for (n, &c) in chars.iter().enumerate() {
+        if n > 0 && c.is_uppercase() {
+            let prev = chars[n - 1];
+            let next_lower = chars.get(n + 1).is_some_and(|c| c.is_lowercase());
+
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_lower) {
+                out.push('_');
             }
-        });
+        }
+
+        out.extend(c.to_uppercase());
+    }
+
+    out
+}
+
+/// Implement parallel iteration over map storage, which chains the parallel
+/// iterators of every variant. This is only emitted with the `rayon` feature.
+fn impl_parallel_map_storage(
+    cx: &Ctxt<'_>,
+    fields: &Fields<'_>,
+    type_name: &syn::Ident,
+) -> TokenStream {
+    if !cfg!(feature = "rayon") {
+        return TokenStream::new();
+    }
+
+    let ident = &cx.ast.ident;
+    let key = cx.key_type();
+
+    let generics = cx.generics(false, true);
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let predicates = predicates(&generics);
+
+    let into_parallel_iterator_t = cx.toks.into_parallel_iterator_t();
+    let parallel_iterator_t = cx.toks.parallel_iterator_t();
+    let parallel_map_storage_t = cx.toks.parallel_map_storage_t();
+    let rayon = cx.toks.rayon();
+
+    let mut par_iter = Vec::new();
+    let mut par_iter_mut = Vec::new();
+    let mut par_values_mut = Vec::new();
+    let mut into_par_iter = Vec::new();
+
+    for Field {
+        name, var, kind, ..
+    } in fields
+    {
+        match kind {
+            Kind::Simple => {
+                par_iter.push((
+                    quote!(#rayon::iter::Map<#rayon::option::Iter<'__this, __V>, fn(&'__this __V) -> (#key, &'__this __V)>),
+                    quote!({
+                        let map: fn(_) -> _ = |v| (#ident::#var, v);
+                        #parallel_iterator_t::map(#into_parallel_iterator_t::into_par_iter(&self.#name), map)
+                    }),
+                ));
+                par_iter_mut.push((
+                    quote!(#rayon::iter::Map<#rayon::option::IterMut<'__this, __V>, fn(&'__this mut __V) -> (#key, &'__this mut __V)>),
+                    quote!({
+                        let map: fn(_) -> _ = |v| (#ident::#var, v);
+                        #parallel_iterator_t::map(#into_parallel_iterator_t::into_par_iter(&mut self.#name), map)
+                    }),
+                ));
+                par_values_mut.push((
+                    quote!(#rayon::option::IterMut<'__this, __V>),
+                    quote!(#into_parallel_iterator_t::into_par_iter(&mut self.#name)),
+                ));
+                into_par_iter.push((
+                    quote!(#rayon::iter::Map<#rayon::option::IntoIter<__V>, fn(__V) -> (#key, __V)>),
+                    quote!({
+                        let map: fn(_) -> _ = |v| (#ident::#var, v);
+                        #parallel_iterator_t::map(#into_parallel_iterator_t::into_par_iter(self.#name), map)
+                    }),
+                ));
+            }
+            Kind::Complex(Complex {
+                element,
+                map_storage,
+                ..
+            }) => {
+                let as_parallel = quote!(<#map_storage as #parallel_map_storage_t<#element, __V>>);
+
+                par_iter.push((
+                    quote!(#rayon::iter::Map<#as_parallel::ParIter<'__this>, fn((#element, &'__this __V)) -> (#key, &'__this __V)>),
+                    quote!({
+                        let map: fn(_) -> _ = |(k, v)| (#ident::#var(k), v);
+                        #parallel_iterator_t::map(#as_parallel::par_iter(&self.#name), map)
+                    }),
+                ));
+                par_iter_mut.push((
+                    quote!(#rayon::iter::Map<#as_parallel::ParIterMut<'__this>, fn((#element, &'__this mut __V)) -> (#key, &'__this mut __V)>),
+                    quote!({
+                        let map: fn(_) -> _ = |(k, v)| (#ident::#var(k), v);
+                        #parallel_iterator_t::map(#as_parallel::par_iter_mut(&mut self.#name), map)
+                    }),
+                ));
+                par_values_mut.push((
+                    quote!(#as_parallel::ParValuesMut<'__this>),
+                    quote!(#as_parallel::par_values_mut(&mut self.#name)),
+                ));
+                into_par_iter.push((
+                    quote!(#rayon::iter::Map<#as_parallel::IntoParIter, fn((#element, __V)) -> (#key, __V)>),
+                    quote!({
+                        let map: fn(_) -> _ = |(k, v)| (#ident::#var(k), v);
+                        #parallel_iterator_t::map(#as_parallel::into_par_iter(self.#name), map)
+                    }),
+                ));
+            }
+        }
+    }
+
+    let (par_iter_type, par_iter) = chain_parallel(cx, par_iter, quote!((#key, &'__this __V)));
+    let (par_iter_mut_type, par_iter_mut) =
+        chain_parallel(cx, par_iter_mut, quote!((#key, &'__this mut __V)));
+    let (par_values_mut_type, par_values_mut) =
+        chain_parallel(cx, par_values_mut, quote!(&'__this mut __V));
+    let (into_par_iter_type, into_par_iter) =
+        chain_parallel(cx, into_par_iter, quote!((#key, __V)));
+
+    let bounds = fields.complex().map(
+        |Complex {
+             element,
+             map_storage,
+             ..
+         }| quote!(#map_storage: #parallel_map_storage_t<#element, __V>),
+    );
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #parallel_map_storage_t<#key, __V> for #type_name #ty_generics where #(#predicates,)* __V: Send + Sync, for<'__trivial_bounds> #key: Send, #(#bounds,)* {
+            type ParIter<'__this> = #par_iter_type where Self: '__this, __V: '__this;
+            type ParIterMut<'__this> = #par_iter_mut_type where Self: '__this, __V: '__this;
+            type ParValuesMut<'__this> = #par_values_mut_type where Self: '__this, __V: '__this;
+            type IntoParIter = #into_par_iter_type;
+
+            #[inline]
+            fn par_iter(&self) -> Self::ParIter<'_> {
+                #par_iter
+            }
+
+            #[inline]
+            fn par_iter_mut(&mut self) -> Self::ParIterMut<'_> {
+                #par_iter_mut
+            }
+
+            #[inline]
+            fn par_values_mut(&mut self) -> Self::ParValuesMut<'_> {
+                #par_values_mut
+            }
+
+            #[inline]
+            fn into_par_iter(self) -> Self::IntoParIter {
+                #into_par_iter
+            }
+        }
+    }
+}
+
+/// Chain the parallel iterators of every variant in order, returning the type
+/// and the expression of the combined iterator.
+fn chain_parallel(
+    cx: &Ctxt<'_>,
+    parts: Vec<(TokenStream, TokenStream)>,
+    item: TokenStream,
+) -> (TokenStream, TokenStream) {
+    let parallel_iterator_t = cx.toks.parallel_iterator_t();
+    let rayon = cx.toks.rayon();
+
+    let mut parts = parts.into_iter();
+
+    let Some((mut ty, mut expr)) = parts.next() else {
+        return (
+            quote!(#rayon::iter::Empty<#item>),
+            quote!(#rayon::iter::empty()),
+        );
+    };
+
+    for (next_ty, next_expr) in parts {
+        ty = quote!(#rayon::iter::Chain<#ty, #next_ty>);
+        expr = quote!(#parallel_iterator_t::chain(#expr, #next_expr));
+    }
+
+    (ty, expr)
+}
+
+/// Implement validation of archived map storage, which checks every variant
+/// in place. This is only emitted with the `rkyv-validation` feature.
+///
+/// Complex variants are checked through the storage of their field rather
+/// than by bounding their archived form, since the compiler doesn't normalize
+/// such bounds consistently.
+fn impl_check_archived_map_storage(
+    cx: &Ctxt<'_>,
+    fields: &Fields<'_>,
+    archived_name: &syn::Ident,
+    bounds: &[TokenStream],
+) -> TokenStream {
+    if !cfg!(feature = "rkyv-validation") {
+        return TokenStream::new();
+    }
+
+    let generics = cx.generics(false, true);
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let predicates = predicates(&generics);
+
+    let mut check_generics = generics.clone();
+    check_generics.params.push(syn::parse_quote!(__C: ?Sized));
+    let (impl_generics, _, _) = check_generics.split_for_impl();
+
+    let addr_of = cx.toks.addr_of();
+    let check_archived_map_storage_t = cx.toks.check_archived_map_storage_t();
+    let option = cx.toks.option();
+    let result = cx.toks.result();
+    let rkyv = cx.toks.rkyv();
+
+    let mut check_bounds = Vec::new();
+    let mut checks = Vec::new();
+
+    for Field { name, kind, .. } in fields {
+        let field_name = name.to_string();
+
+        let check = match kind {
+            Kind::Simple => quote! {
+                <#rkyv::Archived<#option<__V>> as #rkyv::CheckBytes<__C>>::check_bytes(#addr_of!((*value).#name), context).map_err(|error| #rkyv::bytecheck::ErrorBox::new(error) as _)
+            },
+            Kind::Complex(Complex {
+                element,
+                map_storage,
+                ..
+            }) => {
+                let check_archived =
+                    quote!(<#map_storage as #check_archived_map_storage_t<#element, __V, __C>>);
+                check_bounds
+                    .push(quote!(#map_storage: #check_archived_map_storage_t<#element, __V, __C>));
+                quote!(#check_archived::check_archived(#addr_of!((*value).#name), context))
+            }
+        };
+
+        checks.push(quote! {
+            if let #result::Err(inner) = #check {
+                return #result::Err(#rkyv::bytecheck::StructCheckError { field_name: #field_name, inner });
+            }
+        });
+    }
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #rkyv::CheckBytes<__C> for #archived_name #ty_generics where #(#predicates,)* __V: #rkyv::Archive, <__V as #rkyv::Archive>::Archived: #rkyv::CheckBytes<__C>, #(#bounds,)* #(#check_bounds,)* {
+            type Error = #rkyv::bytecheck::StructCheckError;
+
+            #[inline]
+            unsafe fn check_bytes<'__a>(value: *const Self, context: &mut __C) -> #result<&'__a Self, Self::Error> {
+                #(#checks)*
+                #result::Ok(&*value)
+            }
+        }
+    }
+}
+
+/// Implement archiving of map storage through `rkyv`, where every variant is
+/// archived in place. This is only emitted with the `rkyv` feature.
+fn impl_archive_map_storage(
+    cx: &Ctxt<'_>,
+    fields: &Fields<'_>,
+    type_name: &syn::Ident,
+) -> TokenStream {
+    if !cfg!(feature = "rkyv") {
+        return TokenStream::new();
+    }
+
+    let vis = &cx.ast.vis;
+    let ident = &cx.ast.ident;
+    let key = cx.key_type();
+
+    let generics = cx.generics(false, true);
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let predicates = predicates(&generics);
+
+    let archive_map_storage_t = cx.toks.archive_map_storage_t();
+    let archived_map_storage_t = cx.toks.archived_map_storage_t();
+    let into_iterator_t = cx.toks.into_iterator_t();
+    let iterator_map = cx.toks.iterator_map();
+    let iterator_t = cx.toks.iterator_t();
+    let option = cx.toks.option();
+    let option_into_iter = cx.toks.option_into_iter();
+    let result = cx.toks.result();
+    let rkyv = cx.toks.rkyv();
+
+    let archived_name = format_ident!("__Archived{type_name}");
+    let archived = quote!(<__V as #rkyv::Archive>::Archived);
+    let patterns = &fields.patterns;
+
+    let mut field_decls = Vec::new();
+    let mut resolvers = Vec::new();
+    let mut serialize = Vec::new();
+    let mut resolve = Vec::new();
+    let mut len = Vec::new();
+    let mut get = Vec::new();
+    let mut iter = Vec::new();
+
+    for Field {
+        name, var, kind, ..
+    } in fields
+    {
+        match kind {
+            Kind::Simple => {
+                field_decls.push(quote!(#name: #rkyv::Archived<#option<__V>>));
+                resolvers.push(quote!(#rkyv::Resolver<#option<__V>>));
+                serialize.push(quote!(#rkyv::Serialize::serialize(&self.#name, serializer)?));
+                resolve
+                    .push(quote!(#rkyv::Archive::resolve(&self.#name, pos + offset, #name, field)));
+                len.push(quote!(usize::from(self.#name.is_some())));
+                get.push(quote!(self.#name.as_ref()));
+                iter.push((
+                    quote!(#iterator_map<#option_into_iter<&'__this #archived>, fn(&'__this #archived) -> (#key, &'__this #archived)>),
+                    quote!({
+                        let map: fn(_) -> _ = |v| (#ident::#var, v);
+                        #iterator_t::map(#into_iterator_t::into_iter(self.#name.as_ref()), map)
+                    }),
+                ));
+            }
+            Kind::Complex(Complex {
+                element,
+                map_storage,
+                ..
+            }) => {
+                let as_archive = quote!(<#map_storage as #archive_map_storage_t<#element, __V>>);
+                let as_archived =
+                    quote!(<#as_archive::Archived as #archived_map_storage_t<#element, __V>>);
+
+                field_decls.push(quote!(#name: #as_archive::Archived));
+                resolvers.push(quote!(#as_archive::Resolver));
+                serialize.push(quote!(#as_archive::serialize(&self.#name, serializer)?));
+                resolve.push(quote!(#as_archive::resolve(&self.#name, pos + offset, #name, field)));
+                len.push(quote!(#as_archived::len(&self.#name)));
+                get.push(quote!(#as_archived::get(&self.#name, v)));
+                iter.push((
+                    quote!(#iterator_map<#as_archived::Iter<'__this>, fn((#element, &'__this #archived)) -> (#key, &'__this #archived)>),
+                    quote!({
+                        let map: fn(_) -> _ = |(k, v)| (#ident::#var(k), v);
+                        #iterator_t::map(#as_archived::iter(&self.#name), map)
+                    }),
+                ));
+            }
+        }
+    }
+
+    let (iter_type, iter) = chain_iter(cx, iter, quote!((#key, &'__this #archived)));
+    let names = fields.names().collect::<Vec<_>>();
+
+    let bounds = fields
+        .complex()
+        .map(
+            |Complex {
+                 element,
+                 map_storage,
+                 ..
+             }| quote!(#map_storage: #archive_map_storage_t<#element, __V>),
+        )
+        .collect::<Vec<_>>();
+
+    let check_bytes = impl_check_archived_map_storage(cx, fields, &archived_name, &bounds);
+
+    quote! {
+        #[repr(C)]
+        #vis struct #archived_name #impl_generics where #(#predicates,)* __V: #rkyv::Archive, #(#bounds,)* {
+            #(#field_decls,)*
+        }
+
+        #check_bytes
+
+        #[automatically_derived]
+        impl #impl_generics #archive_map_storage_t<#key, __V> for #type_name #ty_generics where #(#predicates,)* __V: #rkyv::Archive, #(#bounds,)* {
+            type Archived = #archived_name #ty_generics;
+            type Resolver = (#(#resolvers,)*);
+
+            #[inline]
+            fn serialize<__S>(&self, serializer: &mut __S) -> #result<Self::Resolver, __S::Error>
+            where
+                __S: #rkyv::Fallible + ?Sized,
+                __V: #rkyv::Serialize<__S>,
+            {
+                #result::Ok((#(#serialize,)*))
+            }
+
+            #[inline]
+            unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+                let (#(#names,)*) = resolver;
+
+                #({
+                    let (offset, field) = #rkyv::out_field!(out.#names);
+                    #resolve;
+                })*
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #archived_map_storage_t<#key, __V> for #archived_name #ty_generics where #(#predicates,)* __V: #rkyv::Archive, #(#bounds,)* {
+            type Iter<'__this> = #iter_type where Self: '__this, __V: '__this;
+
+            #[inline]
+            fn len(&self) -> usize {
+                0 #(+ #len)*
+            }
+
+            #[inline]
+            fn get(&self, key: #key) -> #option<&#archived> {
+                match key {
+                    #(#patterns => #get,)*
+                }
+            }
+
+            #[inline]
+            fn iter(&self) -> Self::Iter<'_> {
+                #iter
+            }
+        }
+    }
+}
+
+/// Chain the iterators of every variant in order, returning the type and the
+/// expression of the combined iterator.
+fn chain_iter(
+    cx: &Ctxt<'_>,
+    parts: Vec<(TokenStream, TokenStream)>,
+    item: TokenStream,
+) -> (TokenStream, TokenStream) {
+    let iter_empty = cx.toks.iter_empty();
+    let iterator_chain = cx.toks.iterator_chain();
+    let iterator_empty = cx.toks.iterator_empty();
+    let iterator_t = cx.toks.iterator_t();
+
+    let mut parts = parts.into_iter();
+
+    let Some((mut ty, mut expr)) = parts.next() else {
+        return (quote!(#iterator_empty<#item>), quote!(#iter_empty()));
+    };
+
+    for (next_ty, next_expr) in parts {
+        ty = quote!(#iterator_chain<#ty, #next_ty>);
+        expr = quote!(#iterator_t::chain(#expr, #next_expr));
+    }
+
+    (ty, expr)
+}
+
+/// Implement `SetStorage` implementation.
+fn impl_set_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, TokenStream), ()> {
+    let vis = &cx.ast.vis;
+    let ident = &cx.ast.ident;
+    let key = cx.key_type();
+
+    let generics = cx.generics(false, false);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = predicates(&generics);
+
+    let mem = cx.toks.mem();
+    let set_storage_t = cx.toks.set_storage_t();
+
+    let type_name = format_ident!("{SET_STORAGE}");
+
+    let mut output = Output::default();
+
+    set_storage_iter(cx, "Iter", fields, &mut output)?;
+    set_storage_into_iter(cx, "IntoIter", fields, &mut output)?;
+
+    {
+        let partial_eq_t = cx.toks.partial_eq_t();
+        let eq_t = cx.toks.eq_t();
+        let bounds = fields
+            .complex()
+            .map(|Complex { set_storage, .. }| set_storage)
+            .collect::<Vec<_>>();
+        let names = fields.names();
+
+        output.impls.extend(quote! {
+            #[automatically_derived]
+            impl #impl_generics #partial_eq_t for #type_name #ty_generics where #(#predicates,)* #(for<'__trivial_bounds> #bounds: #partial_eq_t,)* {
+                #[inline]
+                fn eq(&self, other: &Self) -> bool {
+                    #(if #partial_eq_t::ne(&self.#names, &other.#names) {
+                        return false;
+                    })*
+
+                    true
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #eq_t for #type_name #ty_generics where #(#predicates,)* #(for<'__trivial_bounds> #bounds: #eq_t,)* {}
+        });
+    }
+
+    {
+        let hash_t = cx.toks.hash_t();
+        let hasher_t = cx.toks.hasher_t();
+        let partial_ord_t = cx.toks.partial_ord_t();
+        let ord_t = cx.toks.ord_t();
+        let ordering = cx.toks.ordering();
+        let option = cx.toks.option();
+        let bounds = fields
+            .complex()
+            .map(|Complex { set_storage, .. }| set_storage)
+            .collect::<Vec<_>>();
+        let names = fields.names().collect::<Vec<_>>();
+
+        output.impls.extend(quote! {
+            #[automatically_derived]
+            impl #impl_generics #hash_t for #type_name #ty_generics where #(#predicates,)* #(for<'__trivial_bounds> #bounds: #hash_t,)* {
+                #[inline]
+                fn hash<__H>(&self, state: &mut __H)
+                where
+                    __H: #hasher_t,
+                {
+                    #(#hash_t::hash(&self.#names, state);)*
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #partial_ord_t for #type_name #ty_generics where #(#predicates,)* #(for<'__trivial_bounds> #bounds: #partial_ord_t,)* {
+                #[inline]
+                fn partial_cmp(&self, other: &Self) -> #option<#ordering> {
+                    #(match #partial_ord_t::partial_cmp(&self.#names, &other.#names) {
+                        #option::Some(#ordering::Equal) => {}
+                        ordering => return ordering,
+                    })*
+
+                    #option::Some(#ordering::Equal)
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #ord_t for #type_name #ty_generics where #(#predicates,)* #(for<'__trivial_bounds> #bounds: #ord_t,)* {
+                #[inline]
+                fn cmp(&self, other: &Self) -> #ordering {
+                    #(match #ord_t::cmp(&self.#names, &other.#names) {
+                        #ordering::Equal => {}
+                        ordering => return ordering,
+                    })*
+
+                    #ordering::Equal
+                }
+            }
+        });
+    }
+
+    {
+        let clone_t = cx.toks.clone_t();
+        let copy_t = cx.toks.copy_t();
+        let bounds = fields
+            .complex()
+            .map(|Complex { set_storage, .. }| set_storage)
+            .collect::<Vec<_>>();
+        let names = fields.names();
+
+        output.impls.extend(quote! {
+            #[automatically_derived]
+            impl #impl_generics #clone_t for #type_name #ty_generics where #(#predicates,)* #(for<'__trivial_bounds> #bounds: #clone_t,)* {
+                #[inline]
+                fn clone(&self) -> Self {
+                    Self {
+                        #(#names: #clone_t::clone(&self.#names),)*
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #copy_t for #type_name #ty_generics where #(#predicates,)* #(for<'__trivial_bounds> #bounds: #copy_t,)* {}
+        });
+    }
+
+    {
+        let inits = fields.iter().map(|f| match &f.kind {
+            Kind::Complex(Complex { as_set_storage, .. }) => quote!(#as_set_storage::empty()),
+            Kind::Simple => quote!(false),
+        });
+
+        let names = fields.names();
+
+        output.items.extend(quote! {
+            #[inline]
+            fn empty() -> Self {
+                Self {
+                    #(#names: #inits,)*
+                }
+            }
+        });
     }
 
     {
@@ -518,7 +1494,7 @@ fn impl_set_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, T
 
         output.items.extend(quote! {
             #[inline]
-            fn insert(&mut self, key: #ident) -> bool {
+            fn insert(&mut self, key: #key) -> bool {
                 match key {
                     #(#patterns => #insert,)*
                 }
@@ -570,7 +1546,7 @@ fn impl_set_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, T
 
         output.items.extend(quote! {
             #[inline]
-            fn contains(&self, value: #ident) -> bool {
+            fn contains(&self, value: #key) -> bool {
                 match value {
                     #(#patterns => #contains,)*
                 }
@@ -590,80 +1566,432 @@ fn impl_set_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(syn::Ident, T
 
         output.items.extend(quote! {
             #[inline]
-            fn remove(&mut self, value: #ident) -> bool {
+            fn remove(&mut self, value: #key) -> bool {
                 match value {
                     #(#patterns => #remove,)*
                 }
             }
-        });
+        });
+    }
+
+    {
+        let key_t = cx.toks.key_t();
+
+        let contains_by = lookup_by(
+            fields,
+            fields.iter().map(|Field { name, kind, .. }| match kind {
+                Kind::Complex(Complex { as_set_storage, .. }) => {
+                    quote!(#as_set_storage::contains_by(&self.#name, v))
+                }
+                Kind::Simple => quote!(self.#name),
+            }),
+            quote!(#set_storage_t::contains(self, value)),
+        );
+
+        let remove_by = lookup_by(
+            fields,
+            fields.iter().map(|Field { name, kind, .. }| match kind {
+                Kind::Complex(Complex { as_set_storage, .. }) => {
+                    quote!(#as_set_storage::remove_by(&mut self.#name, v))
+                }
+                Kind::Simple => quote!(#mem::replace(&mut self.#name, false)),
+            }),
+            quote!(#set_storage_t::remove(self, value)),
+        );
+
+        output.items.extend(quote! {
+            #[inline]
+            fn contains_by(&self, value: <#key as #key_t>::Ref<'_>) -> bool {
+                #contains_by
+            }
+
+            #[inline]
+            fn remove_by(&mut self, value: <#key as #key_t>::Ref<'_>) -> bool {
+                #remove_by
+            }
+        });
+    }
+
+    {
+        let retain = fields.iter().map(
+            |Field {
+                 var, name, kind, ..
+             }| match kind {
+                Kind::Complex(Complex { as_set_storage, .. }) => quote! {
+                    #as_set_storage::retain(&mut self.#name, |k| func(#ident::#var(k)));
+                },
+                Kind::Simple => quote! {
+                    if self.#name {
+                        self.#name = func(#ident::#var);
+                    }
+                },
+            },
+        );
+
+        output.items.extend(quote! {
+            #[inline]
+            fn retain<__F>(&mut self, mut func: __F)
+            where
+                __F: FnMut(#key) -> bool
+            {
+                #(#retain;)*
+            }
+        });
+    }
+
+    {
+        let clear = fields.iter().map(|Field { name, kind, .. }| match kind {
+            Kind::Complex(Complex { as_set_storage, .. }) => quote! {
+                #as_set_storage::clear(&mut self.#name)
+            },
+            Kind::Simple => quote! {
+                self.#name = false
+            },
+        });
+
+        output.items.extend(quote! {
+            #[inline]
+            fn clear(&mut self) {
+                #(#clear;)*
+            }
+        });
+    }
+
+    let field_decls = fields.iter().map(|Field { name, kind, .. }| match kind {
+        Kind::Complex(Complex { set_storage, .. }) => quote!(#name: #set_storage),
+        Kind::Simple => quote!(#name: bool),
+    });
+
+    let Output { impls, items } = output;
+    let parallel_impl = impl_parallel_set_storage(cx, fields, &type_name);
+    let archive_impl = impl_archive_set_storage(cx, fields, &type_name);
+
+    let map_storage_impl = quote! {
+        #vis struct #type_name #impl_generics #where_clause {
+            #(#field_decls,)*
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #set_storage_t<#key> for #type_name #ty_generics #where_clause {
+            #items
+        }
+
+        #impls
+        #parallel_impl
+        #archive_impl
+    };
+
+    Ok((type_name, map_storage_impl))
+}
+
+/// Implement parallel iteration over set storage, which chains the parallel
+/// iterators of every variant. This is only emitted with the `rayon` feature.
+fn impl_parallel_set_storage(
+    cx: &Ctxt<'_>,
+    fields: &Fields<'_>,
+    type_name: &syn::Ident,
+) -> TokenStream {
+    if !cfg!(feature = "rayon") {
+        return TokenStream::new();
+    }
+
+    let ident = &cx.ast.ident;
+    let key = cx.key_type();
+
+    let generics = cx.generics(false, false);
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let predicates = predicates(&generics);
+
+    let into_parallel_iterator_t = cx.toks.into_parallel_iterator_t();
+    let parallel_iterator_t = cx.toks.parallel_iterator_t();
+    let parallel_set_storage_t = cx.toks.parallel_set_storage_t();
+    let option = cx.toks.option();
+    let rayon = cx.toks.rayon();
+
+    let parts = fields
+        .iter()
+        .map(|Field { name, var, kind, .. }| match kind {
+            Kind::Simple => (
+                quote!(#rayon::option::IntoIter<#key>),
+                quote!(#into_parallel_iterator_t::into_par_iter(if self.#name {
+                    #option::Some(#ident::#var)
+                } else {
+                    #option::None
+                })),
+            ),
+            Kind::Complex(Complex {
+                element,
+                set_storage,
+                ..
+            }) => {
+                let as_parallel = quote!(<#set_storage as #parallel_set_storage_t<#element>>);
+
+                (
+                    quote!(#rayon::iter::Map<#as_parallel::ParIter<'__this>, fn(#element) -> #key>),
+                    quote!({
+                        let map: fn(_) -> _ = #ident::#var;
+                        #parallel_iterator_t::map(#as_parallel::par_iter(&self.#name), map)
+                    }),
+                )
+            }
+        })
+        .collect();
+
+    let (par_iter_type, par_iter) = chain_parallel(cx, parts, quote!(#key));
+
+    let bounds = fields.complex().map(
+        |Complex {
+             element,
+             set_storage,
+             ..
+         }| quote!(#set_storage: #parallel_set_storage_t<#element>),
+    );
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #parallel_set_storage_t<#key> for #type_name #ty_generics where #(#predicates,)* for<'__trivial_bounds> #key: Send, #(for<'__trivial_bounds> #bounds,)* {
+            type ParIter<'__this> = #par_iter_type where Self: '__this;
+
+            #[inline]
+            fn par_iter(&self) -> Self::ParIter<'_> {
+                #par_iter
+            }
+        }
+    }
+}
+
+/// Implement validation of archived set storage, which checks every variant
+/// in place. This is only emitted with the `rkyv-validation` feature.
+///
+/// Complex variants are checked through the storage of their field, like in
+/// [`impl_check_archived_map_storage`].
+fn impl_check_archived_set_storage(
+    cx: &Ctxt<'_>,
+    fields: &Fields<'_>,
+    archived_name: &syn::Ident,
+    bounds: &[TokenStream],
+) -> TokenStream {
+    if !cfg!(feature = "rkyv-validation") {
+        return TokenStream::new();
+    }
+
+    let generics = cx.generics(false, false);
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let predicates = predicates(&generics);
+
+    let mut check_generics = generics.clone();
+    check_generics.params.push(syn::parse_quote!(__C: ?Sized));
+    let (impl_generics, _, _) = check_generics.split_for_impl();
+
+    let addr_of = cx.toks.addr_of();
+    let bool_type = cx.toks.bool_type();
+    let check_archived_set_storage_t = cx.toks.check_archived_set_storage_t();
+    let result = cx.toks.result();
+    let rkyv = cx.toks.rkyv();
+
+    let mut check_bounds = Vec::new();
+    let mut checks = Vec::new();
+
+    for Field { name, kind, .. } in fields {
+        let field_name = name.to_string();
+
+        let check = match kind {
+            Kind::Simple => quote! {
+                <#rkyv::Archived<#bool_type> as #rkyv::CheckBytes<__C>>::check_bytes(#addr_of!((*value).#name), context).map_err(|error| #rkyv::bytecheck::ErrorBox::new(error) as _)
+            },
+            Kind::Complex(Complex {
+                element,
+                set_storage,
+                ..
+            }) => {
+                let check_archived =
+                    quote!(<#set_storage as #check_archived_set_storage_t<#element, __C>>);
+                check_bounds
+                    .push(quote!(#set_storage: #check_archived_set_storage_t<#element, __C>));
+                quote!(#check_archived::check_archived(#addr_of!((*value).#name), context))
+            }
+        };
+
+        checks.push(quote! {
+            if let #result::Err(inner) = #check {
+                return #result::Err(#rkyv::bytecheck::StructCheckError { field_name: #field_name, inner });
+            }
+        });
+    }
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #rkyv::CheckBytes<__C> for #archived_name #ty_generics where #(#predicates,)* #(for<'__trivial_bounds> #bounds,)* #(for<'__trivial_bounds> #check_bounds,)* {
+            type Error = #rkyv::bytecheck::StructCheckError;
+
+            #[inline]
+            unsafe fn check_bytes<'__a>(value: *const Self, context: &mut __C) -> #result<&'__a Self, Self::Error> {
+                #(#checks)*
+                #result::Ok(&*value)
+            }
+        }
+    }
+}
+
+/// Implement archiving of set storage through `rkyv`, where every variant is
+/// archived in place. This is only emitted with the `rkyv` feature.
+fn impl_archive_set_storage(
+    cx: &Ctxt<'_>,
+    fields: &Fields<'_>,
+    type_name: &syn::Ident,
+) -> TokenStream {
+    if !cfg!(feature = "rkyv") {
+        return TokenStream::new();
+    }
+
+    let vis = &cx.ast.vis;
+    let ident = &cx.ast.ident;
+    let key = cx.key_type();
+
+    let generics = cx.generics(false, false);
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let predicates = predicates(&generics);
+
+    let archive_set_storage_t = cx.toks.archive_set_storage_t();
+    let archived_set_storage_t = cx.toks.archived_set_storage_t();
+    let into_iterator_t = cx.toks.into_iterator_t();
+    let iterator_map = cx.toks.iterator_map();
+    let iterator_t = cx.toks.iterator_t();
+    let option = cx.toks.option();
+    let option_into_iter = cx.toks.option_into_iter();
+    let result = cx.toks.result();
+    let rkyv = cx.toks.rkyv();
+
+    let archived_name = format_ident!("__Archived{type_name}");
+    let patterns = &fields.patterns;
+
+    let mut field_decls = Vec::new();
+    let mut resolvers = Vec::new();
+    let mut serialize = Vec::new();
+    let mut resolve = Vec::new();
+    let mut len = Vec::new();
+    let mut contains = Vec::new();
+    let mut iter = Vec::new();
+
+    for Field {
+        name, var, kind, ..
+    } in fields
+    {
+        match kind {
+            Kind::Simple => {
+                field_decls.push(quote!(#name: #rkyv::Archived<bool>));
+                resolvers.push(quote!(#rkyv::Resolver<bool>));
+                serialize
+                    .push(quote!(#rkyv::Serialize::<__S>::serialize(&self.#name, serializer)?));
+                resolve
+                    .push(quote!(#rkyv::Archive::resolve(&self.#name, pos + offset, #name, field)));
+                len.push(quote!(usize::from(self.#name)));
+                contains.push(quote!(self.#name));
+                iter.push((
+                    quote!(#option_into_iter<#key>),
+                    quote!(#into_iterator_t::into_iter(if self.#name {
+                        #option::Some(#ident::#var)
+                    } else {
+                        #option::None
+                    })),
+                ));
+            }
+            Kind::Complex(Complex {
+                element,
+                set_storage,
+                ..
+            }) => {
+                let as_archive = quote!(<#set_storage as #archive_set_storage_t<#element>>);
+                let as_archived =
+                    quote!(<#as_archive::Archived as #archived_set_storage_t<#element>>);
+
+                field_decls.push(quote!(#name: #as_archive::Archived));
+                resolvers.push(quote!(#as_archive::Resolver));
+                serialize.push(quote!(#as_archive::serialize(&self.#name, serializer)?));
+                resolve.push(quote!(#as_archive::resolve(&self.#name, pos + offset, #name, field)));
+                len.push(quote!(#as_archived::len(&self.#name)));
+                contains.push(quote!(#as_archived::contains(&self.#name, v)));
+                iter.push((
+                    quote!(#iterator_map<#as_archived::Iter<'__this>, fn(#element) -> #key>),
+                    quote!({
+                        let map: fn(_) -> _ = #ident::#var;
+                        #iterator_t::map(#as_archived::iter(&self.#name), map)
+                    }),
+                ));
+            }
+        }
     }
 
-    {
-        let retain = fields.iter().map(
-            |Field {
-                 var, name, kind, ..
-             }| match kind {
-                Kind::Complex(Complex { as_set_storage, .. }) => quote! {
-                    #as_set_storage::retain(&mut self.#name, |k| func(#ident::#var(k)));
-                },
-                Kind::Simple => quote! {
-                    if self.#name {
-                        self.#name = func(#ident::#var);
-                    }
-                },
-            },
-        );
+    let (iter_type, iter) = chain_iter(cx, iter, quote!(#key));
+    let names = fields.names().collect::<Vec<_>>();
+
+    let bounds = fields
+        .complex()
+        .map(
+            |Complex {
+                 element,
+                 set_storage,
+                 ..
+             }| quote!(#set_storage: #archive_set_storage_t<#element>),
+        )
+        .collect::<Vec<_>>();
+
+    let check_bytes = impl_check_archived_set_storage(cx, fields, &archived_name, &bounds);
+
+    quote! {
+        #[repr(C)]
+        #vis struct #archived_name #impl_generics where #(#predicates,)* #(for<'__trivial_bounds> #bounds,)* {
+            #(#field_decls,)*
+        }
+
+        #check_bytes
+
+        #[automatically_derived]
+        impl #impl_generics #archive_set_storage_t<#key> for #type_name #ty_generics where #(#predicates,)* #(for<'__trivial_bounds> #bounds,)* {
+            type Archived = #archived_name #ty_generics;
+            type Resolver = (#(#resolvers,)*);
 
-        output.items.extend(quote! {
             #[inline]
-            fn retain<F>(&mut self, mut func: F)
+            fn serialize<__S>(&self, serializer: &mut __S) -> #result<Self::Resolver, __S::Error>
             where
-                F: FnMut(#ident) -> bool
+                __S: #rkyv::Fallible + ?Sized,
             {
-                #(#retain;)*
+                #result::Ok((#(#serialize,)*))
             }
-        });
-    }
-
-    {
-        let clear = fields.iter().map(|Field { name, kind, .. }| match kind {
-            Kind::Complex(Complex { as_set_storage, .. }) => quote! {
-                #as_set_storage::clear(&mut self.#name)
-            },
-            Kind::Simple => quote! {
-                self.#name = false
-            },
-        });
 
-        output.items.extend(quote! {
             #[inline]
-            fn clear(&mut self) {
-                #(#clear;)*
-            }
-        });
-    }
-
-    let field_decls = fields.iter().map(|Field { name, kind, .. }| match kind {
-        Kind::Complex(Complex { set_storage, .. }) => quote!(#name: #set_storage),
-        Kind::Simple => quote!(#name: bool),
-    });
-
-    let Output { impls, items } = output;
+            unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+                let (#(#names,)*) = resolver;
 
-    let map_storage_impl = quote! {
-        #vis struct #type_name {
-            #(#field_decls,)*
+                #({
+                    let (offset, field) = #rkyv::out_field!(out.#names);
+                    #resolve;
+                })*
+            }
         }
 
         #[automatically_derived]
-        impl #set_storage_t<#ident> for #type_name {
-            #items
-        }
+        impl #impl_generics #archived_set_storage_t<#key> for #archived_name #ty_generics where #(#predicates,)* #(for<'__trivial_bounds> #bounds,)* {
+            type Iter<'__this> = #iter_type where Self: '__this;
 
-        #impls
-    };
+            #[inline]
+            fn len(&self) -> usize {
+                0 #(+ #len)*
+            }
 
-    Ok((type_name, map_storage_impl))
+            #[inline]
+            fn contains(&self, value: #key) -> bool {
+                match value {
+                    #(#patterns => #contains,)*
+                }
+            }
+
+            #[inline]
+            fn iter(&self) -> Self::Iter<'_> {
+                #iter
+            }
+        }
+    }
 }
 
 /// Build iterator next.
@@ -674,6 +2002,7 @@ fn build_iter_next(
     fields: &Fields<'_>,
     assoc_type: &syn::Ident,
     lt: Option<&syn::Lifetime>,
+    value: &TokenStream,
 ) -> Result<(), ()> {
     let option = cx.toks.option();
     let iterator_t = cx.toks.iterator_t();
@@ -707,7 +2036,11 @@ fn build_iter_next(
                     }
                 });
             }
-            Kind::Complex(Complex { as_map_storage, .. }) => {
+            Kind::Complex(Complex {
+                as_map_storage,
+                element,
+                ..
+            }) => {
                 step_forward.next.push(quote! {
                     #index => {
                         if let #option::Some((key, value)) = #iterator_t::next(&mut self.#name) {
@@ -724,19 +2057,17 @@ fn build_iter_next(
                     }
                 });
 
-                // NB: The `Item = ..` component of the bound is technically
-                // superflous but currently necessary to satisfy rustc.
-                let where_clause = step_backward.make_where_clause();
-
                 let assoc_type = if let Some(lt) = lt {
                     quote!(#as_map_storage::#assoc_type<#lt>)
                 } else {
                     quote!(#as_map_storage::#assoc_type)
                 };
 
-                where_clause.predicates.push(cx.fallible(|| syn::parse2(quote_spanned! {
-                    *span => #assoc_type: #double_ended_iterator_t<Item = <#assoc_type as #iterator_t>::Item>
-                }))?);
+                step_backward.predicates.push(cx.fallible(|| {
+                    syn::parse2(quote_spanned! {
+                        *span => #assoc_type: #double_ended_iterator_t<Item = (#element, #value)>
+                    })
+                })?);
             }
         }
     }
@@ -755,10 +2086,16 @@ fn map_storage_iter(
     let assoc_type = syn::Ident::new(assoc_type, Span::call_site());
 
     let lt = cx.lt;
-    let ident = &cx.ast.ident;
     let vis = &cx.ast.vis;
+    let key = cx.key_type();
+
+    let generics = cx.generics(true, true);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = predicates(&generics);
 
+    let exact_iter = cx.toks.exact_iter();
     let option = cx.toks.option();
+    let map_storage_t = cx.toks.map_storage_t();
     let iterator_t = cx.toks.iterator_t();
     let double_ended_iterator_t = cx.toks.double_ended_iterator_t();
     let clone_t = cx.toks.clone_t();
@@ -776,12 +2113,13 @@ fn map_storage_iter(
         fields,
         &assoc_type,
         Some(cx.lt),
+        &quote!(&#lt __V),
     )?;
 
     for Field { name, kind, .. } in fields {
         match kind {
             Kind::Simple => {
-                field_decls.push(quote!(#name: #option<&#lt V>));
+                field_decls.push(quote!(#name: #option<&#lt __V>));
                 init.push(quote!(#name: #option::as_ref(&self.#name)));
             }
             Kind::Complex(Complex { as_map_storage, .. }) => {
@@ -791,23 +2129,21 @@ fn map_storage_iter(
         }
     }
 
-    step_backward
-        .make_where_clause()
-        .predicates
-        .push(cx.fallible(|| syn::parse2(quote!(V: #lt)))?);
-
-    let double_ended_where_clause = &step_backward.where_clause;
+    let double_ended_predicates = &step_backward.predicates;
     let names = fields.names();
+    let clone_bounds = fields
+        .complex()
+        .map(|Complex { as_map_storage, .. }| quote!(#as_map_storage::#assoc_type<#lt>: #clone_t));
 
     output.impls.extend(quote! {
-        #vis struct #type_name<#lt, V> where V: #lt {
+        #vis struct #type_name #impl_generics #where_clause {
             start: usize,
             end: usize,
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<#lt, V> #clone_t for #type_name<#lt, V> where V: #lt {
+        impl #impl_generics #clone_t for #type_name #ty_generics where #(#predicates,)* #(#clone_bounds,)* {
             #[inline]
             fn clone(&self) -> Self {
                 Self {
@@ -819,8 +2155,8 @@ fn map_storage_iter(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #iterator_t for #type_name<#lt, V> where V: #lt {
-            type Item = (#ident, &#lt V);
+        impl #impl_generics #iterator_t for #type_name #ty_generics #where_clause {
+            type Item = (#key, &#lt __V);
 
             #[inline]
             fn next(&mut self) -> #option<Self::Item> {
@@ -830,7 +2166,7 @@ fn map_storage_iter(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #double_ended_iterator_t for #type_name<#lt, V> #double_ended_where_clause {
+        impl #impl_generics #double_ended_iterator_t for #type_name #ty_generics where #(#predicates,)* #(#double_ended_predicates,)* {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -842,11 +2178,12 @@ fn map_storage_iter(
     let end = fields.len();
 
     output.items.extend(quote! {
-        type #assoc_type<#lt> = #type_name<#lt, V> where V: #lt;
+        type #assoc_type<#lt> = #exact_iter<#type_name #ty_generics> #where_clause;
 
         #[inline]
         fn iter(&self) -> Self::#assoc_type<'_> {
-            #type_name { start: 0, end: #end, #(#init,)* }
+            let len = #map_storage_t::len(self);
+            #exact_iter::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
         }
     });
 
@@ -866,13 +2203,20 @@ fn map_storage_keys(
     let lt = cx.lt;
     let ident = &cx.ast.ident;
     let vis = &cx.ast.vis;
+    let key = cx.key_type();
+
+    let generics = cx.generics(true, true);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = predicates(&generics);
 
     let bool_type = cx.toks.bool_type();
     let clone_t = cx.toks.clone_t();
     let double_ended_iterator_t = cx.toks.double_ended_iterator_t();
     let iterator_t = cx.toks.iterator_t();
     let mem = cx.toks.mem();
+    let exact_iter = cx.toks.exact_iter();
     let option = cx.toks.option();
+    let map_storage_t = cx.toks.map_storage_t();
 
     let mut step_forward = IteratorNext::default();
     let mut step_backward = IteratorNextBack::default();
@@ -910,7 +2254,11 @@ fn map_storage_keys(
                     }
                 });
             }
-            Kind::Complex(Complex { as_map_storage, .. }) => {
+            Kind::Complex(Complex {
+                as_map_storage,
+                element,
+                ..
+            }) => {
                 field_decls.push(quote!(#name: #as_map_storage::#assoc_type<#lt>));
                 init.push(quote!(#name: #as_map_storage::keys(&self.#name)));
 
@@ -930,34 +2278,32 @@ fn map_storage_keys(
                     }
                 });
 
-                let where_clause = step_backward.make_where_clause();
-
                 let assoc_type = quote!(#as_map_storage::#assoc_type<#lt>);
 
-                where_clause.predicates.push(cx.fallible(|| syn::parse2(quote_spanned! {
-                    *span => #assoc_type: #double_ended_iterator_t<Item = <#assoc_type as #iterator_t>::Item>
-                }))?);
+                step_backward.predicates.push(cx.fallible(|| {
+                    syn::parse2(quote_spanned! {
+                        *span => #assoc_type: #double_ended_iterator_t<Item = #element>
+                    })
+                })?);
             }
         }
     }
 
-    step_backward
-        .make_where_clause()
-        .predicates
-        .push(cx.fallible(|| syn::parse2(quote!(V: #lt)))?);
-
-    let double_ended_where_clause = &step_backward.where_clause;
+    let double_ended_predicates = &step_backward.predicates;
     let names = fields.names();
+    let clone_bounds = fields
+        .complex()
+        .map(|Complex { as_map_storage, .. }| quote!(#as_map_storage::#assoc_type<#lt>: #clone_t));
 
     output.impls.extend(quote! {
-        #vis struct #type_name<#lt, V> where V: #lt {
+        #vis struct #type_name #impl_generics #where_clause {
             start: usize,
             end: usize,
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<#lt, V> #clone_t for #type_name<#lt, V> where V: #lt {
+        impl #impl_generics #clone_t for #type_name #ty_generics where #(#predicates,)* #(#clone_bounds,)* {
             #[inline]
             fn clone(&self) -> Self {
                 Self {
@@ -969,8 +2315,8 @@ fn map_storage_keys(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #iterator_t for #type_name<#lt, V> where V: #lt {
-            type Item = #ident;
+        impl #impl_generics #iterator_t for #type_name #ty_generics #where_clause {
+            type Item = #key;
 
             #[inline]
             fn next(&mut self) -> #option<Self::Item> {
@@ -980,7 +2326,7 @@ fn map_storage_keys(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #double_ended_iterator_t for #type_name<#lt, V> #double_ended_where_clause {
+        impl #impl_generics #double_ended_iterator_t for #type_name #ty_generics where #(#predicates,)* #(#double_ended_predicates,)* {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -992,11 +2338,12 @@ fn map_storage_keys(
     let end = fields.len();
 
     output.items.extend(quote! {
-        type #assoc_type<#lt> = #type_name<#lt, V> where V: #lt;
+        type #assoc_type<#lt> = #exact_iter<#type_name #ty_generics> #where_clause;
 
         #[inline]
         fn keys(&self) -> Self::#assoc_type<'_> {
-            #type_name { start: 0, end: #end, #(#init,)* }
+            let len = #map_storage_t::len(self);
+            #exact_iter::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
         }
     });
 
@@ -1016,10 +2363,16 @@ fn map_storage_values(
     let lt = cx.lt;
     let vis = &cx.ast.vis;
 
+    let generics = cx.generics(true, true);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = predicates(&generics);
+
     let clone_t = cx.toks.clone_t();
     let double_ended_iterator_t = cx.toks.double_ended_iterator_t();
     let iterator_t = cx.toks.iterator_t();
+    let exact_iter = cx.toks.exact_iter();
     let option = cx.toks.option();
+    let map_storage_t = cx.toks.map_storage_t();
 
     let mut step_forward = IteratorNext::default();
     let mut step_backward = IteratorNextBack::default();
@@ -1037,7 +2390,7 @@ fn map_storage_values(
     {
         match kind {
             Kind::Simple => {
-                field_decls.push(quote!(#name: #option<&#lt V>));
+                field_decls.push(quote!(#name: #option<&#lt __V>));
                 init.push(quote!(#name: #option::as_ref(&self.#name)));
 
                 step_forward.next.push(quote! {
@@ -1076,34 +2429,32 @@ fn map_storage_values(
                     }
                 });
 
-                let where_clause = step_backward.make_where_clause();
-
                 let assoc_type = quote!(#as_map_storage::#assoc_type<#lt>);
 
-                where_clause.predicates.push(cx.fallible(|| syn::parse2(quote_spanned! {
-                    *span => #assoc_type: #double_ended_iterator_t<Item = <#assoc_type as #iterator_t>::Item>
-                }))?);
+                step_backward.predicates.push(cx.fallible(|| {
+                    syn::parse2(quote_spanned! {
+                        *span => #assoc_type: #double_ended_iterator_t<Item = &#lt __V>
+                    })
+                })?);
             }
         }
     }
 
-    step_backward
-        .make_where_clause()
-        .predicates
-        .push(cx.fallible(|| syn::parse2(quote!(V: #lt)))?);
-
-    let double_ended_where_clause = &step_backward.where_clause;
+    let double_ended_predicates = &step_backward.predicates;
     let names = fields.names();
+    let clone_bounds = fields
+        .complex()
+        .map(|Complex { as_map_storage, .. }| quote!(#as_map_storage::#assoc_type<#lt>: #clone_t));
 
     output.impls.extend(quote! {
-        #vis struct #type_name<#lt, V> where V: #lt {
+        #vis struct #type_name #impl_generics #where_clause {
             start: usize,
             end: usize,
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<#lt, V> #clone_t for #type_name<#lt, V> where V: #lt {
+        impl #impl_generics #clone_t for #type_name #ty_generics where #(#predicates,)* #(#clone_bounds,)* {
             #[inline]
             fn clone(&self) -> Self {
                 Self {
@@ -1115,8 +2466,8 @@ fn map_storage_values(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #iterator_t for #type_name<#lt, V> where V: #lt {
-            type Item = &#lt V;
+        impl #impl_generics #iterator_t for #type_name #ty_generics #where_clause {
+            type Item = &#lt __V;
 
             #[inline]
             fn next(&mut self) -> #option<Self::Item> {
@@ -1126,7 +2477,7 @@ fn map_storage_values(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #double_ended_iterator_t for #type_name<#lt, V> #double_ended_where_clause {
+        impl #impl_generics #double_ended_iterator_t for #type_name #ty_generics where #(#predicates,)* #(#double_ended_predicates,)* {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -1138,11 +2489,12 @@ fn map_storage_values(
     let end = fields.len();
 
     output.items.extend(quote! {
-        type #assoc_type<#lt> = #type_name<#lt, V> where V: #lt;
+        type #assoc_type<#lt> = #exact_iter<#type_name #ty_generics> #where_clause;
 
         #[inline]
         fn values(&self) -> Self::#assoc_type<'_> {
-            #type_name { start: 0, end: #end, #(#init,)* }
+            let len = #map_storage_t::len(self);
+            #exact_iter::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
         }
     });
 
@@ -1159,13 +2511,19 @@ fn map_storage_iter_mut(
     let type_name = format_ident!("{MAP_STORAGE}{assoc_type}");
     let assoc_type = syn::Ident::new(assoc_type, Span::call_site());
 
-    let ident = &cx.ast.ident;
     let lt = cx.lt;
     let vis = &cx.ast.vis;
+    let key = cx.key_type();
+
+    let generics = cx.generics(true, true);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = predicates(&generics);
 
     let double_ended_iterator_t = cx.toks.double_ended_iterator_t();
     let iterator_t = cx.toks.iterator_t();
+    let exact_iter = cx.toks.exact_iter();
     let option = cx.toks.option();
+    let map_storage_t = cx.toks.map_storage_t();
 
     let mut step_forward = IteratorNext::default();
     let mut step_backward = IteratorNextBack::default();
@@ -1180,12 +2538,13 @@ fn map_storage_iter_mut(
         fields,
         &assoc_type,
         Some(cx.lt),
+        &quote!(&#lt mut __V),
     )?;
 
     for Field { name, kind, .. } in fields {
         match kind {
             Kind::Simple => {
-                field_decls.push(quote!(#name: #option<&#lt mut V>));
+                field_decls.push(quote!(#name: #option<&#lt mut __V>));
                 init.push(quote!(#name: #option::as_mut(&mut self.#name)));
             }
             Kind::Complex(Complex {
@@ -1199,23 +2558,18 @@ fn map_storage_iter_mut(
         }
     }
 
-    step_backward
-        .make_where_clause()
-        .predicates
-        .push(cx.fallible(|| syn::parse2(quote!(V: #lt)))?);
-
-    let double_ended_where = &step_backward.where_clause;
+    let double_ended_predicates = &step_backward.predicates;
 
     output.impls.extend(quote! {
-        #vis struct #type_name<#lt, V> where V: #lt {
+        #vis struct #type_name #impl_generics #where_clause {
             start: usize,
             end: usize,
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<#lt, V> #iterator_t for #type_name<#lt, V> where V: #lt {
-            type Item = (#ident, &#lt mut V);
+        impl #impl_generics #iterator_t for #type_name #ty_generics #where_clause {
+            type Item = (#key, &#lt mut __V);
 
             #[inline]
             fn next(&mut self) -> #option<Self::Item> {
@@ -1225,7 +2579,7 @@ fn map_storage_iter_mut(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #double_ended_iterator_t for #type_name<#lt, V> #double_ended_where {
+        impl #impl_generics #double_ended_iterator_t for #type_name #ty_generics where #(#predicates,)* #(#double_ended_predicates,)* {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -1237,11 +2591,12 @@ fn map_storage_iter_mut(
     let end = fields.len();
 
     output.items.extend(quote! {
-        type #assoc_type<#lt> = #type_name<#lt, V> where V: #lt;
+        type #assoc_type<#lt> = #exact_iter<#type_name #ty_generics> #where_clause;
 
         #[inline]
         fn iter_mut(&mut self) -> Self::#assoc_type<'_> {
-            #type_name { start: 0, end: #end, #(#init,)* }
+            let len = #map_storage_t::len(self);
+            #exact_iter::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
         }
     });
 
@@ -1261,7 +2616,13 @@ fn map_storage_values_mut(
     let lt = cx.lt;
     let vis = &cx.ast.vis;
 
+    let generics = cx.generics(true, true);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = predicates(&generics);
+
+    let exact_iter = cx.toks.exact_iter();
     let option = cx.toks.option();
+    let map_storage_t = cx.toks.map_storage_t();
     let iterator_t = cx.toks.iterator_t();
     let double_ended_iterator_t = cx.toks.double_ended_iterator_t();
 
@@ -1281,7 +2642,7 @@ fn map_storage_values_mut(
     {
         match kind {
             Kind::Simple => {
-                field_decls.push(quote!(#name: #option<&#lt mut V>));
+                field_decls.push(quote!(#name: #option<&#lt mut __V>));
                 init.push(quote!(#name: #option::as_mut(&mut self.#name)));
 
                 step_forward.next.push(quote! {
@@ -1320,34 +2681,29 @@ fn map_storage_values_mut(
                     }
                 });
 
-                let where_clause = step_backward.make_where_clause();
-
                 let assoc_type = quote!(#as_map_storage::#assoc_type<#lt>);
 
-                where_clause.predicates.push(cx.fallible(|| syn::parse2(quote_spanned! {
-                    *span => #assoc_type: #double_ended_iterator_t<Item = <#assoc_type as #iterator_t>::Item>
-                }))?);
+                step_backward.predicates.push(cx.fallible(|| {
+                    syn::parse2(quote_spanned! {
+                        *span => #assoc_type: #double_ended_iterator_t<Item = &#lt mut __V>
+                    })
+                })?);
             }
         }
     }
 
-    step_backward
-        .make_where_clause()
-        .predicates
-        .push(cx.fallible(|| syn::parse2(quote!(V: #lt)))?);
-
-    let double_ended_where_clause = &step_backward.where_clause;
+    let double_ended_predicates = &step_backward.predicates;
 
     output.impls.extend(quote! {
-        #vis struct #type_name<#lt, V> where V: #lt {
+        #vis struct #type_name #impl_generics #where_clause {
             start: usize,
             end: usize,
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<#lt, V> #iterator_t for #type_name<#lt, V> where V: #lt {
-            type Item = &#lt mut V;
+        impl #impl_generics #iterator_t for #type_name #ty_generics #where_clause {
+            type Item = &#lt mut __V;
 
             #[inline]
             fn next(&mut self) -> #option<Self::Item> {
@@ -1357,7 +2713,7 @@ fn map_storage_values_mut(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #double_ended_iterator_t for #type_name<#lt, V> #double_ended_where_clause {
+        impl #impl_generics #double_ended_iterator_t for #type_name #ty_generics where #(#predicates,)* #(#double_ended_predicates,)* {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -1369,11 +2725,12 @@ fn map_storage_values_mut(
     let end = fields.len();
 
     output.items.extend(quote! {
-        type #assoc_type<#lt> = #type_name<#lt, V> where V: #lt;
+        type #assoc_type<#lt> = #exact_iter<#type_name #ty_generics> #where_clause;
 
         #[inline]
         fn values_mut(&mut self) -> Self::#assoc_type<'_> {
-            #type_name { start: 0, end: #end, #(#init,)* }
+            let len = #map_storage_t::len(self);
+            #exact_iter::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
         }
     });
 
@@ -1390,10 +2747,16 @@ fn map_storage_into_iter(
     let type_name = format_ident!("{MAP_STORAGE}{assoc_type}");
     let assoc_type = syn::Ident::new(assoc_type, Span::call_site());
 
-    let ident = &cx.ast.ident;
     let vis = &cx.ast.vis;
+    let key = cx.key_type();
+
+    let generics = cx.generics(false, true);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = predicates(&generics);
 
+    let exact_iter = cx.toks.exact_iter();
     let option = cx.toks.option();
+    let map_storage_t = cx.toks.map_storage_t();
     let clone_t = cx.toks.clone_t();
     let iterator_t = cx.toks.iterator_t();
     let double_ended_iterator_t = cx.toks.double_ended_iterator_t();
@@ -1411,12 +2774,13 @@ fn map_storage_into_iter(
         fields,
         &assoc_type,
         None,
+        &quote!(__V),
     )?;
 
     for Field { name, kind, .. } in fields {
         match kind {
             Kind::Simple => {
-                field_decls.push(quote!(#name: #option<V>));
+                field_decls.push(quote!(#name: #option<__V>));
                 init.push(quote!(#name: self.#name));
             }
             Kind::Complex(Complex {
@@ -1430,21 +2794,21 @@ fn map_storage_into_iter(
         }
     }
 
-    let double_ended_where = &step_backward.where_clause;
+    let double_ended_predicates = &step_backward.predicates;
     let names = fields.names();
     let clone_bounds = fields
         .complex()
         .map(|Complex { as_map_storage, .. }| quote!(#as_map_storage::#assoc_type: #clone_t));
 
     output.impls.extend(quote! {
-        #vis struct #type_name<V> {
+        #vis struct #type_name #impl_generics #where_clause {
             start: usize,
             end: usize,
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<V> #clone_t for #type_name<V> where V: Clone, #(#clone_bounds,)* {
+        impl #impl_generics #clone_t for #type_name #ty_generics where #(#predicates,)* __V: #clone_t, #(#clone_bounds,)* {
             #[inline]
             fn clone(&self) -> Self {
                 Self {
@@ -1456,8 +2820,8 @@ fn map_storage_into_iter(
         }
 
         #[automatically_derived]
-        impl<V> #iterator_t for #type_name<V> {
-            type Item = (#ident, V);
+        impl #impl_generics #iterator_t for #type_name #ty_generics #where_clause {
+            type Item = (#key, __V);
 
             #[inline]
             fn next(&mut self) -> Option<Self::Item> {
@@ -1467,7 +2831,7 @@ fn map_storage_into_iter(
         }
 
         #[automatically_derived]
-        impl<V> #double_ended_iterator_t for #type_name<V> #double_ended_where {
+        impl #impl_generics #double_ended_iterator_t for #type_name #ty_generics where #(#predicates,)* #(#double_ended_predicates,)* {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -1479,11 +2843,12 @@ fn map_storage_into_iter(
     let end = fields.len();
 
     output.items.extend(quote! {
-        type #assoc_type = #type_name<V>;
+        type #assoc_type = #exact_iter<#type_name #ty_generics>;
 
         #[inline]
         fn into_iter(self) -> Self::#assoc_type {
-            #type_name { start: 0, end: #end, #(#init,)* }
+            let len = #map_storage_t::len(&self);
+            #exact_iter::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
         }
     });
 
@@ -1503,13 +2868,20 @@ fn set_storage_iter(
     let lt = cx.lt;
     let ident = &cx.ast.ident;
     let vis = &cx.ast.vis;
+    let key = cx.key_type();
+
+    let generics = cx.generics(true, false);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = predicates(&generics);
 
     let bool_type = cx.toks.bool_type();
     let clone_t = cx.toks.clone_t();
     let double_ended_iterator_t = cx.toks.double_ended_iterator_t();
     let iterator_t = cx.toks.iterator_t();
     let mem = cx.toks.mem();
+    let exact_iter = cx.toks.exact_iter();
     let option = cx.toks.option();
+    let set_storage_t = cx.toks.set_storage_t();
 
     let mut step_forward = IteratorNext::default();
     let mut step_backward = IteratorNextBack::default();
@@ -1547,7 +2919,11 @@ fn set_storage_iter(
                     }
                 });
             }
-            Kind::Complex(Complex { as_set_storage, .. }) => {
+            Kind::Complex(Complex {
+                as_set_storage,
+                element,
+                ..
+            }) => {
                 field_decls.push(quote!(#name: #as_set_storage::#assoc_type<#lt>));
                 init.push(quote!(#name: #as_set_storage::iter(&self.#name)));
 
@@ -1567,29 +2943,29 @@ fn set_storage_iter(
                     }
                 });
 
-                let where_clause = step_backward.make_where_clause();
-
                 let assoc_type = quote!(#as_set_storage::#assoc_type<#lt>);
 
-                where_clause.predicates.push(cx.fallible(|| syn::parse2(quote_spanned! {
-                    *span => #assoc_type: #double_ended_iterator_t<Item = <#assoc_type as #iterator_t>::Item>
-                }))?);
+                step_backward.predicates.push(cx.fallible(|| {
+                    syn::parse2(quote_spanned! {
+                        *span => #assoc_type: #double_ended_iterator_t<Item = #element>
+                    })
+                })?);
             }
         }
     }
 
-    let double_ended_where_clause = &step_backward.where_clause;
+    let double_ended_predicates = &step_backward.predicates;
     let names = fields.names();
 
     output.impls.extend(quote! {
-        #vis struct #type_name<#lt> {
+        #vis struct #type_name #impl_generics #where_clause {
             start: usize,
             end: usize,
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<#lt> #clone_t for #type_name<#lt> {
+        impl #impl_generics #clone_t for #type_name #ty_generics #where_clause {
             #[inline]
             fn clone(&self) -> Self {
                 Self {
@@ -1601,8 +2977,8 @@ fn set_storage_iter(
         }
 
         #[automatically_derived]
-        impl<#lt> #iterator_t for #type_name<#lt> {
-            type Item = #ident;
+        impl #impl_generics #iterator_t for #type_name #ty_generics #where_clause {
+            type Item = #key;
 
             #[inline]
             fn next(&mut self) -> #option<Self::Item> {
@@ -1612,7 +2988,7 @@ fn set_storage_iter(
         }
 
         #[automatically_derived]
-        impl<#lt> #double_ended_iterator_t for #type_name<#lt> #double_ended_where_clause {
+        impl #impl_generics #double_ended_iterator_t for #type_name #ty_generics where #(#predicates,)* #(#double_ended_predicates,)* {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -1624,11 +3000,12 @@ fn set_storage_iter(
     let end = fields.len();
 
     output.items.extend(quote! {
-        type #assoc_type<#lt> = #type_name<#lt>;
+        type #assoc_type<#lt> = #exact_iter<#type_name #ty_generics> #where_clause;
 
         #[inline]
         fn iter(&self) -> Self::#assoc_type<'_> {
-            #type_name { start: 0, end: #end, #(#init,)* }
+            let len = #set_storage_t::len(self);
+            #exact_iter::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
         }
     });
 
@@ -1647,13 +3024,20 @@ fn set_storage_into_iter(
 
     let ident = &cx.ast.ident;
     let vis = &cx.ast.vis;
+    let key = cx.key_type();
+
+    let generics = cx.generics(false, false);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = predicates(&generics);
 
     let bool_type = cx.toks.bool_type();
     let clone_t = cx.toks.clone_t();
     let double_ended_iterator_t = cx.toks.double_ended_iterator_t();
     let iterator_t = cx.toks.iterator_t();
     let mem = cx.toks.mem();
+    let exact_iter = cx.toks.exact_iter();
     let option = cx.toks.option();
+    let set_storage_t = cx.toks.set_storage_t();
 
     let mut step_forward = IteratorNext::default();
     let mut step_backward = IteratorNextBack::default();
@@ -1691,7 +3075,11 @@ fn set_storage_into_iter(
                     }
                 });
             }
-            Kind::Complex(Complex { as_set_storage, .. }) => {
+            Kind::Complex(Complex {
+                as_set_storage,
+                element,
+                ..
+            }) => {
                 field_decls.push(quote!(#name: #as_set_storage::#assoc_type));
                 init.push(quote!(#name: #as_set_storage::into_iter(self.#name)));
 
@@ -1711,12 +3099,10 @@ fn set_storage_into_iter(
                     }
                 });
 
-                let where_clause = step_backward.make_where_clause();
-
                 let assoc_type = quote!(#as_set_storage::#assoc_type);
 
-                where_clause.predicates.push(cx.fallible(|| syn::parse2(quote_spanned! {
-                    *span => for<'trivial_bounds> #assoc_type: #double_ended_iterator_t<Item = <#assoc_type as #iterator_t>::Item>
+                step_backward.predicates.push(cx.fallible(|| syn::parse2(quote_spanned! {
+                    *span => for<'__trivial_bounds> #assoc_type: #double_ended_iterator_t<Item = #element>
                 }))?);
             }
         }
@@ -1725,7 +3111,7 @@ fn set_storage_into_iter(
     let names = fields.names();
 
     output.impls.extend(quote! {
-        #vis struct #type_name {
+        #vis struct #type_name #impl_generics #where_clause {
             start: usize,
             end: usize,
             #(#field_decls,)*
@@ -1739,7 +3125,7 @@ fn set_storage_into_iter(
 
         output.impls.extend(quote! {
             #[automatically_derived]
-            impl #clone_t for #type_name where #(for<'trivial_bounds> #bounds: #clone_t,)* {
+            impl #impl_generics #clone_t for #type_name #ty_generics where #(#predicates,)* #(for<'__trivial_bounds> #bounds: #clone_t,)* {
                 #[inline]
                 fn clone(&self) -> Self {
                     Self {
@@ -1754,8 +3140,8 @@ fn set_storage_into_iter(
 
     output.impls.extend(quote! {
         #[automatically_derived]
-        impl #iterator_t for #type_name {
-            type Item = #ident;
+        impl #impl_generics #iterator_t for #type_name #ty_generics #where_clause {
+            type Item = #key;
 
             #[inline]
             fn next(&mut self) -> #option<Self::Item> {
@@ -1765,11 +3151,11 @@ fn set_storage_into_iter(
         }
     });
 
-    let double_ended_where_clause = &step_backward.where_clause;
+    let double_ended_predicates = &step_backward.predicates;
 
     output.impls.extend(quote! {
         #[automatically_derived]
-        impl #double_ended_iterator_t for #type_name #double_ended_where_clause {
+        impl #impl_generics #double_ended_iterator_t for #type_name #ty_generics where #(#predicates,)* #(#double_ended_predicates,)* {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -1781,10 +3167,124 @@ fn set_storage_into_iter(
     let end = fields.len();
 
     output.items.extend(quote! {
-        type #assoc_type = #type_name;
+        type #assoc_type = #exact_iter<#type_name #ty_generics>;
 
         #[inline]
         fn into_iter(self) -> Self::#assoc_type {
+            let len = #set_storage_t::len(&self);
+            #exact_iter::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
+        }
+    });
+
+    Ok(())
+}
+
+/// Construct an iterator walking two storages in lockstep.
+fn map_storage_zip(
+    cx: &Ctxt<'_>,
+    assoc_type: &str,
+    fields: &Fields<'_>,
+    output: &mut Output,
+) -> Result<(), ()> {
+    let type_name = format_ident!("{MAP_STORAGE}{assoc_type}");
+    let assoc_type = syn::Ident::new(assoc_type, Span::call_site());
+
+    let lt = cx.lt;
+    let ident = &cx.ast.ident;
+    let vis = &cx.ast.vis;
+    let key = cx.key_type();
+
+    let mut generics = cx.generics(true, true);
+    generics.params.push(syn::parse_quote!(__U));
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote!(__U: #lt));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fused_iterator_t = cx.toks.fused_iterator_t();
+    let iterator_t = cx.toks.iterator_t();
+    let key_t = cx.toks.key_t();
+    let mem = cx.toks.mem();
+    let option = cx.toks.option();
+
+    let mut step_forward = IteratorNext::default();
+
+    let mut field_decls = Vec::new();
+    let mut init = Vec::new();
+
+    for Field {
+        index,
+        name,
+        var,
+        kind,
+        ..
+    } in fields
+    {
+        match kind {
+            Kind::Simple => {
+                field_decls.push(quote!(#name: (#option<&#lt __V>, #option<&#lt __U>)));
+                init.push(
+                    quote!(#name: (#option::as_ref(&self.#name), #option::as_ref(&other.#name))),
+                );
+
+                step_forward.next.push(quote! {
+                    #index => {
+                        let (a, b) = #mem::take(&mut self.#name);
+
+                        if #option::is_some(&a) || #option::is_some(&b) {
+                            return #option::Some((#ident::#var, a, b));
+                        }
+                    }
+                });
+            }
+            Kind::Complex(Complex { as_map_storage, .. }) => {
+                field_decls.push(quote!(#name: #as_map_storage::#assoc_type<#lt, __U>));
+                init.push(quote!(#name: #as_map_storage::zip(&self.#name, &other.#name)));
+
+                step_forward.next.push(quote! {
+                    #index => {
+                        if let #option::Some((key, a, b)) = #iterator_t::next(&mut self.#name) {
+                            return #option::Some((#ident::#var(key), a, b));
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    output.impls.extend(quote! {
+        #vis struct #type_name #impl_generics #where_clause {
+            start: usize,
+            end: usize,
+            #(#field_decls,)*
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #iterator_t for #type_name #ty_generics #where_clause {
+            type Item = (#key, #option<&#lt __V>, #option<&#lt __U>);
+
+            #[inline]
+            fn next(&mut self) -> #option<Self::Item> {
+                #step_forward
+                #option::None
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #fused_iterator_t for #type_name #ty_generics #where_clause {}
+    });
+
+    let end = fields.len();
+
+    output.items.extend(quote! {
+        type #assoc_type<#lt, __U> = #type_name #ty_generics #where_clause;
+
+        #[inline]
+        fn zip<#lt, __U>(&#lt self, other: &#lt <#key as #key_t>::MapStorage<__U>) -> Self::#assoc_type<#lt, __U>
+        where
+            __U: #lt,
+        {
             #type_name { start: 0, end: #end, #(#init,)* }
         }
     });
@@ -1817,17 +3317,9 @@ impl ToTokens for IteratorNext {
 #[derive(Default)]
 struct IteratorNextBack {
     next: Vec<TokenStream>,
-    where_clause: Option<syn::WhereClause>,
-}
-
-impl IteratorNextBack {
-    /// Initializes an empty `where`-clause if there is not one present already.
-    pub(crate) fn make_where_clause(&mut self) -> &mut syn::WhereClause {
-        self.where_clause.get_or_insert_with(|| syn::WhereClause {
-            where_token: <syn::Token![where]>::default(),
-            predicates: Punctuated::new(),
-        })
-    }
+    /// Additional predicates required by the `DoubleEndedIterator`
+    /// implementation.
+    predicates: Vec<syn::WherePredicate>,
 }
 
 impl ToTokens for IteratorNextBack {
@@ -1859,7 +3351,14 @@ fn map_storage_entry(
     let ident = &cx.ast.ident;
     let vis = &cx.ast.vis;
     let lt = cx.lt;
+    let key = cx.key_type();
 
+    let generics = cx.generics(true, true);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let map_generics = cx.generics(false, true);
+    let (_, map_generics, _) = map_generics.split_for_impl();
+
+    let clone_t = cx.toks.clone_t();
     let entry_enum = cx.toks.entry_enum();
     let occupied_entry_t = cx.toks.occupied_entry_t();
     let option = cx.toks.option();
@@ -1898,7 +3397,7 @@ fn map_storage_entry(
                 map_storage,
                 ..
             }) => {
-                let as_map_storage = quote!(<#map_storage as #map_storage_t<#element, V>>);
+                let as_map_storage = quote!(<#map_storage as #map_storage_t<#element, __V>>);
 
                 occupied_variant.push(quote!( #name(#as_map_storage::Occupied<#lt>) ));
                 vacant_variant.push(quote!( #name(#as_map_storage::Vacant<#lt>) ));
@@ -1911,7 +3410,7 @@ fn map_storage_entry(
                 });
 
                 let as_vacant_entry =
-                    quote!(<#as_map_storage::Vacant<#lt> as #vacant_entry_t<#lt, #element, V>>);
+                    quote!(<#as_map_storage::Vacant<#lt> as #vacant_entry_t<#lt, #element, __V>>);
 
                 vacant_key.push(
                     quote!( VacantEntry::#name(entry) => #pattern(#as_vacant_entry::key(entry)) ),
@@ -1920,8 +3419,7 @@ fn map_storage_entry(
                     quote!( VacantEntry::#name(entry) => #as_vacant_entry::insert(entry, value) ),
                 );
 
-                let as_occupied_entry =
-                    quote!(<#as_map_storage::Occupied<#lt> as #occupied_entry_t<#lt, #element, V>>);
+                let as_occupied_entry = quote!(<#as_map_storage::Occupied<#lt> as #occupied_entry_t<#lt, #element, __V>>);
 
                 occupied_key.push(quote!( OccupiedEntry::#name(entry) => #pattern(#as_occupied_entry::key(entry)) ));
                 occupied_get
@@ -1941,72 +3439,72 @@ fn map_storage_entry(
     }
 
     output.impls.extend(quote! {
-        #vis struct SimpleVacantEntry<#lt, V> {
-            key: #ident,
-            inner: #option_bucket_none<#lt, V>,
+        #vis struct SimpleVacantEntry #impl_generics #where_clause {
+            key: #key,
+            inner: #option_bucket_none<#lt, __V>,
         }
 
-        impl<#lt, V> SimpleVacantEntry<#lt, V> {
+        impl #impl_generics SimpleVacantEntry #ty_generics #where_clause {
             #[inline]
-            fn insert(self, value: V) -> &#lt mut V {
+            fn insert(self, value: __V) -> &#lt mut __V {
                 #option_bucket_none::insert(self.inner, value)
             }
         }
 
-        #vis struct SimpleOccupiedEntry<#lt, V> {
-            key: #ident,
-            inner: #option_bucket_some<#lt, V>,
+        #vis struct SimpleOccupiedEntry #impl_generics #where_clause {
+            key: #key,
+            inner: #option_bucket_some<#lt, __V>,
         }
 
-        impl<#lt, V> SimpleOccupiedEntry<#lt, V> {
+        impl #impl_generics SimpleOccupiedEntry #ty_generics #where_clause {
             #[inline]
-            fn get(&self) -> &V {
+            fn get(&self) -> &__V {
                 #option_bucket_some::as_ref(&self.inner)
             }
 
             #[inline]
-            fn get_mut(&mut self) -> &mut V {
+            fn get_mut(&mut self) -> &mut __V {
                 #option_bucket_some::as_mut(&mut self.inner)
             }
 
             #[inline]
-            fn into_mut(self) -> &#lt mut V {
+            fn into_mut(self) -> &#lt mut __V {
                 #option_bucket_some::into_mut(self.inner)
             }
 
             #[inline]
-            fn insert(&mut self, value: V) -> V {
+            fn insert(&mut self, value: __V) -> __V {
                 #option_bucket_some::replace(&mut self.inner, value)
             }
 
             #[inline]
-            fn remove(self) -> V {
+            fn remove(self) -> __V {
                 #option_bucket_some::take(self.inner)
             }
         }
 
-        #vis enum VacantEntry<#lt, V> {
-            Simple(SimpleVacantEntry<#lt, V>),
+        #vis enum VacantEntry #impl_generics #where_clause {
+            Simple(SimpleVacantEntry #ty_generics),
             #(#vacant_variant,)*
         }
 
-        #vis enum OccupiedEntry<#lt, V> {
-            Simple(SimpleOccupiedEntry<#lt, V>),
+        #vis enum OccupiedEntry #impl_generics #where_clause {
+            Simple(SimpleOccupiedEntry #ty_generics),
             #(#occupied_variant,)*
         }
 
         #[automatically_derived]
-        impl<#lt, V> #vacant_entry_t<#lt, #ident, V> for VacantEntry<#lt, V> {
+        impl #impl_generics #vacant_entry_t<#lt, #key, __V> for VacantEntry #ty_generics #where_clause {
             #[inline]
-            fn key(&self) -> #ident {
+            fn key(&self) -> #key {
                 match self {
-                    VacantEntry::Simple(entry) => entry.key,
+                    VacantEntry::Simple(entry) => #clone_t::clone(&entry.key),
                     #(#vacant_key,)*
                 }
             }
 
             #[inline]
-            fn insert(self, value: V) -> &#lt mut V {
+            fn insert(self, value: __V) -> &#lt mut __V {
                 match self {
                     VacantEntry::Simple(entry) => entry.insert(value),
                     #(#vacant_insert,)*
@@ -2015,17 +3513,17 @@ fn map_storage_entry(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #occupied_entry_t<#lt, #ident, V> for OccupiedEntry<#lt, V> {
+        impl #impl_generics #occupied_entry_t<#lt, #key, __V> for OccupiedEntry #ty_generics #where_clause {
             #[inline]
-            fn key(&self) -> #ident {
+            fn key(&self) -> #key {
                 match self {
-                    OccupiedEntry::Simple(entry) => entry.key,
+                    OccupiedEntry::Simple(entry) => #clone_t::clone(&entry.key),
                     #(#occupied_key,)*
                 }
             }
 
             #[inline]
-            fn get(&self) -> &V {
+            fn get(&self) -> &__V {
                 match self {
                     OccupiedEntry::Simple(entry) => entry.get(),
                     #(#occupied_get,)*
@@ -2033,7 +3531,7 @@ fn map_storage_entry(
             }
 
             #[inline]
-            fn get_mut(&mut self) -> &mut V {
+            fn get_mut(&mut self) -> &mut __V {
                 match self {
                     OccupiedEntry::Simple(entry) => entry.get_mut(),
                     #(#occupied_get_mut,)*
@@ -2041,7 +3539,7 @@ fn map_storage_entry(
             }
 
             #[inline]
-            fn into_mut(self) -> &#lt mut V {
+            fn into_mut(self) -> &#lt mut __V {
                 match self {
                     OccupiedEntry::Simple(entry) => entry.into_mut(),
                     #(#occupied_into_mut,)*
@@ -2049,7 +3547,7 @@ fn map_storage_entry(
             }
 
             #[inline]
-            fn insert(&mut self, value: V) -> V {
+            fn insert(&mut self, value: __V) -> __V {
                 match self {
                     OccupiedEntry::Simple(entry) => entry.insert(value),
                     #(#occupied_insert,)*
@@ -2057,7 +3555,7 @@ fn map_storage_entry(
             }
 
             #[inline]
-            fn remove(self) -> V {
+            fn remove(self) -> __V {
                 match self {
                     OccupiedEntry::Simple(entry) => entry.remove(),
                     #(#occupied_remove,)*
@@ -2066,7 +3564,7 @@ fn map_storage_entry(
         }
 
         #[inline]
-        fn option_to_entry<V>(opt: &mut #option<V>, key: #ident) -> #entry_enum<'_, #map_storage<V>, #ident, V> {
+        fn option_to_entry #impl_generics(opt: &#lt mut #option<__V>, key: #key) -> #entry_enum<#lt, #map_storage #map_generics, #key, __V> #where_clause {
             match #option_bucket_option::new(opt) {
                 #option_bucket_option::Some(inner) => #entry_enum::Occupied(OccupiedEntry::Simple(SimpleOccupiedEntry { key, inner })),
                 #option_bucket_option::None(inner) => #entry_enum::Vacant(VacantEntry::Simple(SimpleVacantEntry { key, inner })),
@@ -2075,11 +3573,11 @@ fn map_storage_entry(
     });
 
     output.items.extend(quote! {
-        type Occupied<#lt> = OccupiedEntry<#lt, V> where V: #lt;
-        type Vacant<#lt> = VacantEntry<#lt, V> where V: #lt;
+        type Occupied<#lt> = OccupiedEntry #ty_generics #where_clause;
+        type Vacant<#lt> = VacantEntry #ty_generics #where_clause;
 
         #[inline]
-        fn entry(&mut self, key: #ident) -> #entry_enum<'_, Self, #ident, V> {
+        fn entry(&mut self, key: #key) -> #entry_enum<'_, Self, #key, __V> {
             match key {
                 #(#init,)*
             }
@@ -2089,6 +3587,28 @@ fn map_storage_entry(
     Ok(())
 }
 
+/// Construct the body of a lookup through the borrowed form of the key.
+///
+/// If the key has a borrowed form, `route` is used for each of its variants.
+/// Otherwise the key is its own borrowed form and `fallback` is used.
+fn lookup_by(
+    fields: &Fields<'_>,
+    route: impl Iterator<Item = TokenStream>,
+    fallback: TokenStream,
+) -> TokenStream {
+    if fields.ref_ident.is_none() {
+        return fallback;
+    }
+
+    let patterns = &fields.ref_patterns;
+
+    quote! {
+        match value {
+            #(#patterns => #route,)*
+        }
+    }
+}
+
 /// Output collector.
 #[derive(Default)]
 struct Output {
@@ -2117,9 +3637,9 @@ pub(crate) enum Kind<'a> {
 pub(crate) struct Complex<'a> {
     /// Type of variant field
     pub(crate) element: &'a syn::Field,
-    /// `<E as Key>::MapStorage::<V>` (`E` = type of variant field)
+    /// `<E as Key>::MapStorage::<__V>` (`E` = type of variant field)
     pub(crate) map_storage: TokenStream,
-    /// `<<E as Key>::MapStorage::<V> as MapStorage<E, V>>` (`E` = type of variant field)
+    /// `<<E as Key>::MapStorage::<__V> as MapStorage<E, __V>>` (`E` = type of variant field)
     pub(crate) as_map_storage: TokenStream,
     /// `<E as Key>::SetStorage` (`E` = type of variant field)
     pub(crate) set_storage: TokenStream,
@@ -2131,6 +3651,10 @@ pub(crate) struct Complex<'a> {
 pub(crate) struct Fields<'a> {
     fields: Vec<Field<'a>>,
     patterns: Vec<syn::Pat>,
+    /// Name of the borrowed form of the key, if one is generated.
+    ref_ident: Option<&'a syn::Ident>,
+    /// Patterns matching the borrowed form of the key.
+    ref_patterns: Vec<syn::Pat>,
 }
 
 impl<'a> Fields<'a> {
@@ -0,0 +1,187 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::DataEnum;
+
+use crate::context::Ctxt;
+
+/// Implement `Arbitrary` for the key from every integration which is enabled.
+///
+/// Keys with the `finite` attribute are picked uniformly from every possible
+/// key. Otherwise both integrations pick a variant uniformly and then generate
+/// its field, if any, through the `Arbitrary` implementation of the field.
+pub(crate) fn implement(
+    cx: &Ctxt<'_>,
+    span: Span,
+    en: &DataEnum,
+    finite: bool,
+) -> Result<TokenStream, ()> {
+    if !cfg!(feature = "arbitrary") && !cfg!(feature = "proptest") {
+        cx.span_error(
+            span,
+            "`arbitrary` requires the `arbitrary` or `proptest` feature of `fixed-map`",
+        );
+        return Err(());
+    }
+
+    if en.variants.is_empty() {
+        cx.span_error(span, "`arbitrary` requires at least one variant");
+        return Err(());
+    }
+
+    let arbitrary_impl = impl_arbitrary(cx, en, finite);
+    let proptest_impl = impl_proptest(cx, en, finite);
+    Ok(quote!(#arbitrary_impl #proptest_impl))
+}
+
+/// Collect the types of the fields of every variant.
+fn field_types(en: &DataEnum) -> Vec<&syn::Type> {
+    en.variants
+        .iter()
+        .flat_map(|variant| variant.fields.iter().map(|field| &field.ty))
+        .collect()
+}
+
+/// Implement `arbitrary::Arbitrary` for the key. This is only emitted with the
+/// `arbitrary` feature.
+fn impl_arbitrary(cx: &Ctxt<'_>, en: &DataEnum, finite: bool) -> TokenStream {
+    if !cfg!(feature = "arbitrary") {
+        return TokenStream::new();
+    }
+
+    let arbitrary = cx.toks.arbitrary();
+    let result = cx.toks.result();
+    let ident = &cx.ast.ident;
+    let key = cx.key_type();
+
+    let mut generics = cx.ast.generics.clone();
+    generics.params.insert(0, syn::parse_quote!('__arbitrary));
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let predicates = crate::context::predicates(&cx.ast.generics);
+    let bounds = field_types(en);
+
+    if finite && !bounds.is_empty() {
+        let finite_t = cx.toks.finite_t();
+        let into_iterator_t = cx.toks.into_iterator_t();
+        let iterator_t = cx.toks.iterator_t();
+        let option = cx.toks.option();
+
+        return quote! {
+            #[automatically_derived]
+            impl #impl_generics #arbitrary::Arbitrary<'__arbitrary> for #key {
+                #[inline]
+                fn arbitrary(u: &mut #arbitrary::Unstructured<'__arbitrary>) -> #arbitrary::Result<Self> {
+                    let index = u.choose_index(<Self as #finite_t>::COUNT)?;
+                    let mut keys = #into_iterator_t::into_iter(<Self as #finite_t>::array_from_fn(|key| key));
+
+                    match #iterator_t::nth(&mut keys, index) {
+                        #option::Some(key) => #result::Ok(key),
+                        #option::None => ::core::unreachable!("index out of bounds"),
+                    }
+                }
+            }
+        };
+    }
+
+    let count = en.variants.len();
+    let mut arms = Vec::new();
+
+    for (index, variant) in en.variants.iter().enumerate() {
+        let var = &variant.ident;
+
+        if variant.fields.is_empty() {
+            arms.push(quote!(#index => #ident::#var));
+        } else {
+            arms.push(quote!(#index => #ident::#var(#arbitrary::Arbitrary::arbitrary(u)?)));
+        }
+    }
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #arbitrary::Arbitrary<'__arbitrary> for #key where #(#predicates,)* #(for<'__trivial_bounds> #bounds: #arbitrary::Arbitrary<'__arbitrary>,)* {
+            #[inline]
+            fn arbitrary(u: &mut #arbitrary::Unstructured<'__arbitrary>) -> #arbitrary::Result<Self> {
+                let value = match u.choose_index(#count)? {
+                    #(#arms,)*
+                    _ => ::core::unreachable!("index out of bounds"),
+                };
+
+                #result::Ok(value)
+            }
+        }
+    }
+}
+
+/// Implement `proptest::arbitrary::Arbitrary` for the key. This is only
+/// emitted with the `proptest` feature.
+fn impl_proptest(cx: &Ctxt<'_>, en: &DataEnum, finite: bool) -> TokenStream {
+    if !cfg!(feature = "proptest") {
+        return TokenStream::new();
+    }
+
+    let proptest = cx.toks.proptest();
+    let ident = &cx.ast.ident;
+    let key = cx.key_type();
+
+    let (impl_generics, _, _) = cx.ast.generics.split_for_impl();
+    let predicates = crate::context::predicates(&cx.ast.generics);
+    let bounds = field_types(en);
+
+    if bounds.is_empty() {
+        let variants = en.variants.iter().map(|variant| &variant.ident);
+
+        return quote! {
+            #[automatically_derived]
+            impl #impl_generics #proptest::arbitrary::Arbitrary for #key where #(#predicates,)* {
+                type Parameters = ();
+                type Strategy = #proptest::strategy::Union<#proptest::strategy::Just<Self>>;
+
+                #[inline]
+                fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+                    #proptest::strategy::Union::new([#(#proptest::strategy::Just(#ident::#variants),)*])
+                }
+            }
+        };
+    }
+
+    let finite_t = cx.toks.finite_t();
+    let vec = cx.toks.vec();
+
+    let mut strategies = Vec::new();
+
+    // Unit variants are built lazily, since generic keys aren't necessarily
+    // `Clone` for every parameter. Finite keys weigh every variant by the
+    // number of keys it holds, so that every key is equally likely.
+    for variant in &en.variants {
+        let var = &variant.ident;
+
+        match variant.fields.iter().next() {
+            Some(field) => {
+                let ty = &field.ty;
+
+                let weight = if finite {
+                    quote!(<#ty as #finite_t>::COUNT as u32)
+                } else {
+                    quote!(1)
+                };
+
+                strategies.push(quote!((#weight, #proptest::strategy::Strategy::boxed(#proptest::strategy::Strategy::prop_map(#proptest::arbitrary::any::<#ty>(), #ident::#var)))));
+            }
+            None => {
+                strategies.push(quote!((1, #proptest::strategy::Strategy::boxed(#proptest::strategy::LazyJust::new(|| #ident::#var)))));
+            }
+        }
+    }
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #proptest::arbitrary::Arbitrary for #key where #(#predicates,)* for<'__trivial_bounds> #key: 'static, #(for<'__trivial_bounds> #bounds: #proptest::arbitrary::Arbitrary + 'static,)* #(for<'__trivial_bounds> <#bounds as #proptest::arbitrary::Arbitrary>::Strategy: 'static,)* {
+            type Parameters = ();
+            type Strategy = #proptest::strategy::Union<#proptest::strategy::BoxedStrategy<Self>>;
+
+            #[inline]
+            fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+                #proptest::strategy::Union::new_weighted(#vec::from([#(#strategies,)*]))
+            }
+        }
+    }
+}
@@ -11,8 +11,18 @@ pub(crate) fn parse(cx: &Ctxt<'_>) -> Result<Opts, ()> {
         }
 
         let result = attr.parse_nested_meta(|input| {
-            if input.path == symbol::BITSET {
+            if input.path == symbol::ARBITRARY {
+                opts.arbitrary = Some(input.input.span());
+            } else if input.path == symbol::BITSET {
                 opts.bitset = Some(input.input.span());
+            } else if input.path == symbol::BORROW {
+                opts.borrow = Some(input.input.span());
+            } else if input.path == symbol::COMPACT {
+                opts.compact = Some(input.input.span());
+            } else if input.path == symbol::FINITE {
+                opts.finite = Some(input.input.span());
+            } else if input.path == symbol::SCHEMA {
+                opts.schema = Some(input.input.span());
             } else {
                 return Err(syn::Error::new(input.input.span(), "Unsupported attribute"));
             }
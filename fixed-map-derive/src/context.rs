@@ -1,7 +1,8 @@
 use core::cell::RefCell;
 use core::fmt;
 
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
 use syn::{DeriveInput, Path};
 
 // Builder function to use when constructing token.
@@ -50,20 +51,46 @@ macro_rules! toks {
 
 toks! {
     pub(crate) struct Toks<'a> {
+        arbitrary = [crate::macro_support::arbitrary],
+        addr_of = [core::ptr::addr_of],
+        archive_map_storage_t = [crate::map::ArchiveMapStorage],
+        archive_set_storage_t = [crate::set::ArchiveSetStorage],
+        archived_map_storage_t = [crate::map::ArchivedMapStorage],
+        archived_set_storage_t = [crate::set::ArchivedSetStorage],
+        archived_value = [crate::macro_support::__archived_value],
+        array_from_fn = [core::array::from_fn],
         array_into_iter = [core::array::IntoIter],
+        bits_check_error = [crate::set::BitsCheckError],
         bool_type = [core::primitive::bool],
+        borsh = [crate::macro_support::borsh],
+        borsh_invalid_bits = [crate::macro_support::__borsh_invalid_bits],
+        borsh_read_bitmap = [crate::macro_support::__borsh_read_bitmap],
+        borsh_write_bitmap = [crate::macro_support::__borsh_write_bitmap],
+        check_archived_map_storage_t = [crate::macro_support::CheckArchivedMapStorage],
+        check_archived_set_storage_t = [crate::macro_support::CheckArchivedSetStorage],
+        check_bits = [crate::macro_support::__check_bits],
         clone_t = [core::clone::Clone],
+        compact_map_storage = [crate::macro_support::CompactMapStorage],
+        compact_t = [crate::macro_support::Compact],
         copy_t = [core::marker::Copy],
         double_ended_iterator_t = [core::iter::DoubleEndedIterator],
         entry_enum = [crate::map::Entry],
         eq_t = [core::cmp::Eq],
+        exact_iter = [crate::macro_support::ExactIter],
+        finite_t = [crate::Finite],
+        from_t = [core::convert::From],
+        fused_iterator_t = [core::iter::FusedIterator],
         hash_t = [core::hash::Hash],
         hasher_t = [core::hash::Hasher],
         into_iterator_t = [core::iter::IntoIterator],
+        iter_empty = [core::iter::empty],
         iterator_cmp = [crate::macro_support::__storage_iterator_cmp],
         iterator_cmp_bool = [crate::macro_support::__storage_iterator_cmp_bool],
         iterator_flat_map = [core::iter::FlatMap],
+        iterator_chain = [core::iter::Chain],
+        iterator_empty = [core::iter::Empty],
         iterator_flatten = [core::iter::Flatten],
+        iterator_map = [core::iter::Map],
         iterator_partial_cmp = [crate::macro_support::__storage_iterator_partial_cmp],
         iterator_partial_cmp_bool = [crate::macro_support::__storage_iterator_partial_cmp_bool],
         iterator_t = [core::iter::Iterator],
@@ -71,19 +98,38 @@ toks! {
         mem = [core::mem],
         occupied_entry_t = [crate::map::OccupiedEntry],
         option = [core::option::Option],
+        option_into_iter = [core::option::IntoIter],
         option_bucket_none = [crate::option_bucket::NoneBucket],
         option_bucket_option = [crate::option_bucket::OptionBucket],
         option_bucket_some = [crate::option_bucket::SomeBucket],
         ord_t = [core::cmp::Ord],
         ordering = [core::cmp::Ordering],
         partial_eq_t = [core::cmp::PartialEq],
+        proptest = [crate::macro_support::proptest],
         partial_ord_t = [core::cmp::PartialOrd],
+        parallel_iterator_t = [crate::macro_support::rayon::iter::ParallelIterator],
+        parallel_map_storage_t = [crate::map::ParallelMapStorage],
+        parallel_set_storage_t = [crate::set::ParallelSetStorage],
+        indexed_parallel_iterator_t = [crate::macro_support::rayon::iter::IndexedParallelIterator],
+        into_parallel_iterator_t = [crate::macro_support::rayon::iter::IntoParallelIterator],
+        rayon = [crate::macro_support::rayon],
+        rkyv = [crate::macro_support::rkyv],
+        result = [core::result::Result],
+        schema_key = [crate::macro_support::__schema_key],
+        schema_name = [crate::macro_support::__schema_name],
+        schemars = [crate::macro_support::schemars],
         slice_iter = [core::slice::Iter],
         slice_iter_mut = [core::slice::IterMut],
         map_storage_t = [crate::map::MapStorage],
         set_storage_t = [crate::set::SetStorage],
+        string = [crate::macro_support::String],
         raw_storage_t = [crate::raw::RawStorage],
         vacant_entry_t = [crate::map::VacantEntry],
+        variant = [crate::map::Variant],
+        variant_map_storage_t = [crate::map::VariantMapStorage],
+        vec = [crate::macro_support::Vec],
+        zip_slots = [crate::macro_support::__zip_slots],
+        zip_slots_type = [crate::macro_support::ZipSlots],
     }
 }
 
@@ -114,16 +160,39 @@ fn suffixed<const N: usize>(prefix: &Path, parts: [&'static str; N]) -> Path {
     path
 }
 
+/// Get the `where` predicates of the given generics, so that they can be
+/// extended with additional predicates.
+pub(crate) fn predicates(generics: &syn::Generics) -> Vec<&syn::WherePredicate> {
+    match &generics.where_clause {
+        Some(where_clause) => where_clause.predicates.iter().collect(),
+        None => Vec::new(),
+    }
+}
+
 /// Options for derive.
 #[derive(Default)]
 pub(crate) struct Opts {
+    /// Implements `Arbitrary` for the key from the enabled fuzzing and
+    /// property testing integrations.
+    pub(crate) arbitrary: Option<Span>,
     /// Implements sets as bitsets when possible.
     pub(crate) bitset: Option<Span>,
+    /// Generates a borrowed form of the key used for lookups.
+    pub(crate) borrow: Option<Span>,
+    /// Implements maps with a bitset of present values when possible.
+    pub(crate) compact: Option<Span>,
+    /// Implements `Finite` for enums with fields.
+    pub(crate) finite: Option<Span>,
+    /// Implements `JsonSchema` for the key.
+    pub(crate) schema: Option<Span>,
 }
 
 pub(crate) struct Ctxt<'a> {
     /// Errors collected in the context.
     errors: RefCell<Vec<syn::Error>>,
+    /// Additional bounds which must hold for the key, such as `T: Key` for
+    /// fields which are generic.
+    bounds: RefCell<Vec<syn::WherePredicate>>,
     /// Generated tokens.
     pub(crate) toks: &'a Toks<'a>,
     /// Input ast.
@@ -136,6 +205,7 @@ impl<'a> Ctxt<'a> {
     pub(crate) fn new(tokens: &'a Toks<'a>, ast: &'a DeriveInput, lt: &'a syn::Lifetime) -> Self {
         Self {
             errors: RefCell::new(Vec::new()),
+            bounds: RefCell::new(Vec::new()),
             toks: tokens,
             ast,
             lt,
@@ -152,6 +222,96 @@ impl<'a> Ctxt<'a> {
         self.error(syn::Error::new(span, message));
     }
 
+    /// Add a bound which must hold for every generated item.
+    pub(crate) fn bound(&self, predicate: syn::WherePredicate) {
+        self.bounds.borrow_mut().push(predicate);
+    }
+
+    /// Attributes deriving `CheckBytes` for a generated archived storage, which
+    /// are only emitted with the `rkyv-validation` feature.
+    pub(crate) fn derive_check_bytes(&self) -> TokenStream {
+        if !cfg!(feature = "rkyv-validation") {
+            return TokenStream::new();
+        }
+
+        let rkyv = self.toks.rkyv();
+        let bytecheck = quote!(#rkyv::bytecheck).to_string();
+
+        quote! {
+            #[derive(#rkyv::CheckBytes)]
+            #[check_bytes(crate = #bytecheck)]
+        }
+    }
+
+    /// The type of the key, including its generic arguments.
+    pub(crate) fn key_type(&self) -> TokenStream {
+        let ident = &self.ast.ident;
+        let (_, ty_generics, _) = self.ast.generics.split_for_impl();
+        quote!(#ident #ty_generics)
+    }
+
+    /// Construct generics for a generated item.
+    ///
+    /// These are the generics of the key, extended with the usable lifetime
+    /// parameter if `lt` is set and with the value parameter `__V` if `value` is
+    /// set. When the lifetime is present, every other parameter is required to
+    /// outlive it.
+    pub(crate) fn generics(&self, lt: bool, value: bool) -> syn::Generics {
+        let mut generics = self.ast.generics.clone();
+
+        for param in &mut generics.params {
+            match param {
+                syn::GenericParam::Type(param) => {
+                    param.eq_token = None;
+                    param.default = None;
+                }
+                syn::GenericParam::Const(param) => {
+                    param.eq_token = None;
+                    param.default = None;
+                }
+                syn::GenericParam::Lifetime(_) => {}
+            }
+        }
+
+        let mut outlives = Vec::<syn::WherePredicate>::new();
+
+        if lt {
+            let lt = self.lt;
+
+            for param in &generics.params {
+                match param {
+                    syn::GenericParam::Type(param) => {
+                        let ident = &param.ident;
+                        outlives.push(syn::parse_quote!(#ident: #lt));
+                    }
+                    syn::GenericParam::Lifetime(param) => {
+                        let lifetime = &param.lifetime;
+                        outlives.push(syn::parse_quote!(#lifetime: #lt));
+                    }
+                    syn::GenericParam::Const(_) => {}
+                }
+            }
+
+            generics.params.push(syn::parse_quote!(#lt));
+        }
+
+        if value {
+            generics.params.push(syn::parse_quote!(__V));
+
+            if lt {
+                let lt = self.lt;
+                outlives.push(syn::parse_quote!(__V: #lt));
+            }
+        }
+
+        let where_clause = generics.make_where_clause();
+        where_clause.predicates.extend(outlives);
+        where_clause
+            .predicates
+            .extend(self.bounds.borrow().iter().cloned());
+        generics
+    }
+
     /// Convert into interior errors.
     pub(crate) fn into_errors(self) -> Vec<syn::Error> {
         self.errors.into_inner()
@@ -21,8 +21,10 @@ use syn::spanned::Spanned;
 use syn::{Data, DataEnum, DeriveInput, Fields};
 
 mod any_variants;
+mod arbitrary;
 mod attrs;
 mod context;
+mod schema;
 mod symbol;
 mod unit_variants;
 
@@ -31,7 +33,10 @@ mod unit_variants;
 pub fn storage_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let ast = syn::parse_macro_input!(input as DeriveInput);
 
-    let lt = syn::Lifetime::new("'a", ast.span());
+    // Lifetime used by generated iterators and entries, which must not collide
+    // with any lifetime declared on the key.
+    let lt = syn::Lifetime::new("'__a", ast.span());
+
     let crate_prefix = context::leading_path(["fixed_map"]);
     let tokens = context::Toks::new(&crate_prefix);
     let cx = context::Ctxt::new(&tokens, &ast, &lt);
@@ -51,11 +56,23 @@ fn impl_storage(cx: &context::Ctxt<'_>) -> Result<TokenStream, ()> {
     let opts = attrs::parse(cx)?;
 
     if let Data::Enum(en) = &cx.ast.data {
-        if is_all_unit_variants(en) {
-            unit_variants::implement(cx, &opts, en)
+        let storage_impl = if is_all_unit_variants(en) {
+            unit_variants::implement(cx, &opts, en)?
         } else {
-            any_variants::implement(cx, en)
-        }
+            any_variants::implement(cx, &opts, en)?
+        };
+
+        let arbitrary_impl = match opts.arbitrary {
+            Some(span) => arbitrary::implement(cx, span, en, opts.finite.is_some())?,
+            None => TokenStream::new(),
+        };
+
+        let schema_impl = match opts.schema {
+            Some(span) => schema::implement(cx, span, en)?,
+            None => TokenStream::new(),
+        };
+
+        Ok(quote!(#storage_impl #arbitrary_impl #schema_impl))
     } else {
         cx.span_error(cx.ast.span(), "named fields are not supported");
         Err(())
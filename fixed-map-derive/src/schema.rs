@@ -0,0 +1,72 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::DataEnum;
+
+use crate::context::Ctxt;
+
+/// Implement `schemars::JsonSchema` for the key.
+///
+/// The schema matches the externally tagged representation used by
+/// `#[derive(Serialize)]`, so unit variants are enumerated as strings and
+/// variants with a field are objects with the name of the variant as their
+/// only property.
+pub(crate) fn implement(cx: &Ctxt<'_>, span: Span, en: &DataEnum) -> Result<TokenStream, ()> {
+    if !cfg!(feature = "schemars") {
+        cx.span_error(
+            span,
+            "`schema` requires the `schemars` feature of `fixed-map`",
+        );
+        return Err(());
+    }
+
+    let schemars = cx.toks.schemars();
+    let schema_key = cx.toks.schema_key();
+    let schema_name = cx.toks.schema_name();
+    let string = cx.toks.string();
+    let key = cx.key_type();
+
+    let (impl_generics, _, _) = cx.ast.generics.split_for_impl();
+    let predicates = crate::context::predicates(&cx.ast.generics);
+    let params = cx
+        .ast
+        .generics
+        .type_params()
+        .map(|param| &param.ident)
+        .collect::<Vec<_>>();
+
+    let mut units = Vec::new();
+    let mut variants = Vec::new();
+    let mut bounds = Vec::new();
+
+    for variant in &en.variants {
+        let name = variant.ident.to_string();
+
+        match variant.fields.iter().next() {
+            Some(field) => {
+                let ty = &field.ty;
+                variants.push(quote!((#name, generator.subschema_for::<#ty>())));
+                bounds.push(ty);
+            }
+            None => {
+                units.push(name);
+            }
+        }
+    }
+
+    let name = cx.ast.ident.to_string();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #schemars::JsonSchema for #key where #(#predicates,)* #(#params: #schemars::JsonSchema,)* #(for<'__trivial_bounds> #bounds: #schemars::JsonSchema,)* {
+            #[inline]
+            fn schema_name() -> #string {
+                #schema_name(#name, &[#(<#params as #schemars::JsonSchema>::schema_name(),)*])
+            }
+
+            #[inline]
+            fn json_schema(generator: &mut #schemars::r#gen::SchemaGenerator) -> #schemars::schema::Schema {
+                #schema_key(&[#(#units,)*], [#(#variants,)*])
+            }
+        }
+    })
+}
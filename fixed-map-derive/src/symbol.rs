@@ -6,7 +6,12 @@ use syn::{Ident, Path};
 pub struct Symbol(&'static str);
 
 pub(crate) const KEY: Symbol = Symbol("key");
+pub(crate) const ARBITRARY: Symbol = Symbol("arbitrary");
 pub(crate) const BITSET: Symbol = Symbol("bitset");
+pub(crate) const BORROW: Symbol = Symbol("borrow");
+pub(crate) const COMPACT: Symbol = Symbol("compact");
+pub(crate) const FINITE: Symbol = Symbol("finite");
+pub(crate) const SCHEMA: Symbol = Symbol("schema");
 
 impl PartialEq<Symbol> for Ident {
     fn eq(&self, word: &Symbol) -> bool {
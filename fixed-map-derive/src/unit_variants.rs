@@ -3,7 +3,7 @@ use quote::{format_ident, quote};
 use syn::spanned::Spanned;
 use syn::{DataEnum, Ident, LitInt};
 
-use crate::context::{Ctxt, Opts};
+use crate::context::{predicates, Ctxt, Opts};
 
 /// Every variant is a unit variant.
 pub(crate) fn implement(cx: &Ctxt<'_>, opts: &Opts, en: &DataEnum) -> Result<TokenStream, ()> {
@@ -17,38 +17,134 @@ pub(crate) fn implement(cx: &Ctxt<'_>, opts: &Opts, en: &DataEnum) -> Result<Tok
         names.push(format_ident!("_{}", index));
     }
 
-    let entry_impl = impl_entry(cx, &map_storage)?;
-    let map_storage_impl = impl_map(cx, en, &map_storage, &names)?;
+    if let Some(span) = opts.borrow {
+        cx.span_error(span, "`borrow` is only supported for enums with fields");
+        return Err(());
+    }
+
+    let generics = cx.generics(false, true);
+    let (_, map_generics, _) = generics.split_for_impl();
+
+    let (map_storage_impl, map_storage_type) = if opts.compact.is_some() {
+        impl_compact(cx, en)?
+    } else {
+        let entry_impl = impl_entry(cx, &map_storage)?;
+        let map_storage_impl = impl_map(cx, en, &map_storage, &names)?;
+        let parallel_impl = impl_parallel_map(cx, en, &map_storage);
+        let archive_impl = impl_archive_map(cx, en, &map_storage, &names);
+        (
+            quote!(#entry_impl #map_storage_impl #parallel_impl #archive_impl),
+            quote!(#map_storage #map_generics),
+        )
+    };
 
     let set_storage_impl = if opts.bitset.is_some() {
-        impl_bitset(cx, en, &set_storage)?
+        let set_storage_impl = impl_bitset(cx, en, &set_storage)?;
+        let archive_impl = impl_archive_bitset(cx, en, &set_storage)?;
+        let borsh_impl = impl_borsh_bitset(cx, en, &set_storage)?;
+        quote!(#set_storage_impl #archive_impl #borsh_impl)
     } else {
-        impl_set(cx, en, &set_storage, &names)?
+        let set_storage_impl = impl_set(cx, en, &set_storage, &names)?;
+        let parallel_impl = impl_parallel_set(cx, en, &set_storage);
+        let archive_impl = impl_archive_set(cx, en, &set_storage, &names);
+        let borsh_impl = impl_borsh_set(cx, en, &set_storage);
+        quote!(#set_storage_impl #parallel_impl #archive_impl #borsh_impl)
     };
 
-    let ident = &cx.ast.ident;
+    let key = cx.key_type();
     let key_t = cx.toks.key_t();
+    let lt = cx.lt;
+
+    let generics = cx.generics(false, false);
+    let (impl_generics, set_generics, where_clause) = generics.split_for_impl();
+
+    let finite_t = cx.toks.finite_t();
+    let ident = &cx.ast.ident;
+    let variants = en.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+    let indexes = (0..count).map(|index| LitInt::new(&index.to_string(), Span::call_site()));
 
     Ok(quote! {
         const _: () = {
-            #entry_impl
             #map_storage_impl
             #set_storage_impl
 
             #[automatically_derived]
-            impl #key_t for #ident {
-                type MapStorage<V> = #map_storage<V>;
-                type SetStorage = #set_storage;
+            impl #impl_generics #key_t for #key #where_clause {
+                type MapStorage<__V> = #map_storage_type;
+                type SetStorage = #set_storage #set_generics;
+                type Ref<#lt> = Self;
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #finite_t for #key #where_clause {
+                const COUNT: usize = #count;
+
+                type Array<__V> = [__V; #count];
+
+                #[inline]
+                fn index(&self) -> usize {
+                    match *self {
+                        #(#ident::#variants => #indexes,)*
+                    }
+                }
+
+                #[inline]
+                fn array_from_fn<__V, __F>(mut f: __F) -> [__V; #count]
+                where
+                    __F: FnMut(Self) -> __V
+                {
+                    [#(f(#ident::#variants),)*]
+                }
             }
         };
     })
 }
 
-fn impl_entry(cx: &Ctxt<'_>, map_storage: &Ident) -> Result<TokenStream, ()> {
+/// Implement compact map storage, which keeps a bitset of present values.
+fn impl_compact(cx: &Ctxt<'_>, en: &DataEnum) -> Result<(TokenStream, TokenStream), ()> {
+    let (ty, _) = determine_bits(cx, en)?;
+
     let ident = &cx.ast.ident;
+    let key = cx.key_type();
+
+    let generics = cx.generics(false, false);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let compact_map_storage = cx.toks.compact_map_storage();
+    let compact_t = cx.toks.compact_t();
+
+    let variants = en.variants.iter().map(|v| &v.ident);
+    let indexes =
+        (0..en.variants.len()).map(|index| LitInt::new(&index.to_string(), Span::call_site()));
+
+    let compact_impl = quote! {
+        #[automatically_derived]
+        impl #impl_generics #compact_t for #key #where_clause {
+            type Bits = #ty;
+
+            #[inline]
+            fn from_index(index: usize) -> Self {
+                match index {
+                    #(#indexes => #ident::#variants,)*
+                    _ => ::core::unreachable!("index out of bounds"),
+                }
+            }
+        }
+    };
+
+    Ok((compact_impl, quote!(#compact_map_storage<Self, __V>)))
+}
+
+fn impl_entry(cx: &Ctxt<'_>, map_storage: &Ident) -> Result<TokenStream, ()> {
+    let key = cx.key_type();
     let lt = cx.lt;
     let vis = &cx.ast.vis;
 
+    let generics = cx.generics(true, true);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let map_generics = cx.generics(false, true);
+    let (_, map_generics, _) = map_generics.split_for_impl();
+
     let vacant_entry_t = cx.toks.vacant_entry_t();
     let occupied_entry_t = cx.toks.occupied_entry_t();
     let option_bucket_none = cx.toks.option_bucket_none();
@@ -56,66 +152,67 @@ fn impl_entry(cx: &Ctxt<'_>, map_storage: &Ident) -> Result<TokenStream, ()> {
     let option_bucket_some = cx.toks.option_bucket_some();
     let option = cx.toks.option();
     let entry_enum = cx.toks.entry_enum();
+    let clone_t = cx.toks.clone_t();
 
     Ok(quote! {
-        #vis struct VacantEntry<#lt, V> {
-            key: #ident,
-            inner: #option_bucket_none<#lt, V>,
+        #vis struct VacantEntry #impl_generics #where_clause {
+            key: #key,
+            inner: #option_bucket_none<#lt, __V>,
         }
 
         #[automatically_derived]
-        impl<#lt, V> #vacant_entry_t<#lt, #ident, V> for VacantEntry<#lt, V> {
+        impl #impl_generics #vacant_entry_t<#lt, #key, __V> for VacantEntry #ty_generics #where_clause {
             #[inline]
-            fn key(&self) -> #ident {
-                self.key
+            fn key(&self) -> #key {
+                #clone_t::clone(&self.key)
             }
 
             #[inline]
-            fn insert(self, value: V) -> &#lt mut V {
+            fn insert(self, value: __V) -> &#lt mut __V {
                 #option_bucket_none::insert(self.inner, value)
             }
         }
 
-        #vis struct OccupiedEntry<#lt, V> {
-            key: #ident,
-            inner: #option_bucket_some<#lt, V>,
+        #vis struct OccupiedEntry #impl_generics #where_clause {
+            key: #key,
+            inner: #option_bucket_some<#lt, __V>,
         }
 
         #[automatically_derived]
-        impl<#lt, V> #occupied_entry_t<#lt, #ident, V> for OccupiedEntry<#lt, V> {
+        impl #impl_generics #occupied_entry_t<#lt, #key, __V> for OccupiedEntry #ty_generics #where_clause {
             #[inline]
-            fn key(&self) -> #ident {
-                self.key
+            fn key(&self) -> #key {
+                #clone_t::clone(&self.key)
             }
 
             #[inline]
-            fn get(&self) -> &V {
+            fn get(&self) -> &__V {
                 #option_bucket_some::as_ref(&self.inner)
             }
 
             #[inline]
-            fn get_mut(&mut self) -> &mut V {
+            fn get_mut(&mut self) -> &mut __V {
                 #option_bucket_some::as_mut(&mut self.inner)
             }
 
             #[inline]
-            fn into_mut(self) -> &#lt mut V {
+            fn into_mut(self) -> &#lt mut __V {
                 #option_bucket_some::into_mut(self.inner)
             }
 
             #[inline]
-            fn insert(&mut self, value: V) -> V {
+            fn insert(&mut self, value: __V) -> __V {
                 #option_bucket_some::replace(&mut self.inner, value)
             }
 
             #[inline]
-            fn remove(self) -> V {
+            fn remove(self) -> __V {
                 #option_bucket_some::take(self.inner)
             }
         }
 
         #[inline]
-        fn option_to_entry<V>(opt: &mut #option<V>, key: #ident) -> #entry_enum<'_, #map_storage<V>, #ident, V> {
+        fn option_to_entry #impl_generics(opt: &#lt mut #option<__V>, key: #key) -> #entry_enum<#lt, #map_storage #map_generics, #key, __V> #where_clause {
             match #option_bucket_option::new(opt) {
                 #option_bucket_option::Some(inner) => #entry_enum::Occupied(OccupiedEntry { key, inner }),
                 #option_bucket_option::None(inner) => #entry_enum::Vacant(VacantEntry { key, inner }),
@@ -141,6 +238,7 @@ fn impl_map(
     let copy_t = cx.toks.copy_t();
     let entry_enum = cx.toks.entry_enum();
     let eq_t = cx.toks.eq_t();
+    let exact_iter = cx.toks.exact_iter();
     let hash_t = cx.toks.hash_t();
     let hasher_t = cx.toks.hasher_t();
     let iterator_cmp = cx.toks.iterator_cmp();
@@ -156,6 +254,16 @@ fn impl_map(
     let slice_iter = cx.toks.slice_iter();
     let slice_iter_mut = cx.toks.slice_iter_mut();
     let map_storage_t = cx.toks.map_storage_t();
+    let key_t = cx.toks.key_t();
+    let zip_slots = cx.toks.zip_slots();
+    let zip_slots_type = cx.toks.zip_slots_type();
+
+    let key = cx.key_type();
+    let generics = cx.generics(false, true);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = predicates(&generics);
+    let entry_generics = cx.generics(true, true);
+    let (_, entry_generics, entry_where_clause) = entry_generics.split_for_impl();
 
     let variants = en.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
     let init = en
@@ -164,15 +272,19 @@ fn impl_map(
         .map(|_| quote!(#option::None))
         .collect::<Vec<_>>();
     let count = en.variants.len();
+    let other_names = names
+        .iter()
+        .map(|name| format_ident!("{name}_other"))
+        .collect::<Vec<_>>();
 
     Ok(quote! {
         #[repr(transparent)]
-        #vis struct #map_storage<V> {
-            data: [#option<V>; #count],
+        #vis struct #map_storage #impl_generics #where_clause {
+            data: [#option<__V>; #count],
         }
 
         #[automatically_derived]
-        impl<V> #clone_t for #map_storage<V> where V: #clone_t {
+        impl #impl_generics #clone_t for #map_storage #ty_generics where #(#predicates,)* __V: #clone_t {
             #[inline]
             fn clone(&self) -> Self {
                 Self {
@@ -182,11 +294,11 @@ fn impl_map(
         }
 
         #[automatically_derived]
-        impl<V> #copy_t for #map_storage<V> where V: #copy_t {
+        impl #impl_generics #copy_t for #map_storage #ty_generics where #(#predicates,)* __V: #copy_t {
         }
 
         #[automatically_derived]
-        impl<V> #partial_eq_t for #map_storage<V> where V: #partial_eq_t {
+        impl #impl_generics #partial_eq_t for #map_storage #ty_generics where #(#predicates,)* __V: #partial_eq_t {
             #[inline]
             fn eq(&self, other: &Self) -> bool {
                 #partial_eq_t::eq(&self.data, &other.data)
@@ -194,21 +306,21 @@ fn impl_map(
         }
 
         #[automatically_derived]
-        impl<V> #eq_t for #map_storage<V> where V: #eq_t {}
+        impl #impl_generics #eq_t for #map_storage #ty_generics where #(#predicates,)* __V: #eq_t {}
 
         #[automatically_derived]
-        impl<V> #hash_t for #map_storage<V> where V: #hash_t {
+        impl #impl_generics #hash_t for #map_storage #ty_generics where #(#predicates,)* __V: #hash_t {
             #[inline]
-            fn hash<H>(&self, state: &mut H)
+            fn hash<__H>(&self, state: &mut __H)
             where
-                H: #hasher_t,
+                __H: #hasher_t,
             {
                 #hash_t::hash(&self.data, state);
             }
         }
 
         #[automatically_derived]
-        impl<V> #partial_ord_t for #map_storage<V> where V: #partial_ord_t {
+        impl #impl_generics #partial_ord_t for #map_storage #ty_generics where #(#predicates,)* __V: #partial_ord_t {
             #[inline]
             fn partial_cmp(&self, other: &Self) -> Option<#ordering> {
                 #iterator_partial_cmp(&self.data, &other.data)
@@ -216,7 +328,7 @@ fn impl_map(
         }
 
         #[automatically_derived]
-        impl<V> #ord_t for #map_storage<V> where V: #ord_t {
+        impl #impl_generics #ord_t for #map_storage #ty_generics where #(#predicates,)* __V: #ord_t {
             #[inline]
             fn cmp(&self, other: &Self) -> #ordering {
                 #iterator_cmp(&self.data, &other.data)
@@ -224,27 +336,34 @@ fn impl_map(
         }
 
         #[automatically_derived]
-        impl<V> #map_storage_t<#ident, V> for #map_storage<V> {
-            type Iter<#lt> = #iterator_flat_map<
-                #array_into_iter<(#ident, &#lt #option<V>), #count>,
-                #option<(#ident, &#lt V)>,
-                fn((#ident, &#lt #option<V>)) -> #option<(#ident, &#lt V)>
-            > where V: #lt;
-            type Keys<#lt> = #iterator_flatten<#array_into_iter<#option<#ident>, #count>> where V: #lt;
-            type Values<#lt> = #iterator_flatten<#slice_iter<#lt, #option<V>>> where V: #lt;
-            type IterMut<#lt> = #iterator_flat_map<
-                #array_into_iter<(#ident, &#lt mut #option<V>), #count>,
-                #option<(#ident, &#lt mut V)>,
-                fn((#ident, &#lt mut #option<V>)) -> #option<(#ident, &#lt mut V)>
-            > where V: #lt;
-            type ValuesMut<#lt> = #iterator_flatten<#slice_iter_mut<#lt, #option<V>>> where V: #lt;
-            type IntoIter = #iterator_flat_map<
-                #array_into_iter<(#ident, #option<V>), #count>,
-                #option<(#ident, V)>,
-                fn((#ident, #option<V>)) -> #option<(#ident, V)>
-            >;
-            type Occupied<#lt> = OccupiedEntry<#lt, V> where V: #lt;
-            type Vacant<#lt> = VacantEntry<#lt, V> where V: #lt;
+        impl #impl_generics #map_storage_t<#key, __V> for #map_storage #ty_generics #where_clause {
+            type Iter<#lt> = #exact_iter<#iterator_flat_map<
+                #array_into_iter<(#key, &#lt #option<__V>), #count>,
+                #option<(#key, &#lt __V)>,
+                fn((#key, &#lt #option<__V>)) -> #option<(#key, &#lt __V)>
+            >> where __V: #lt;
+            type Keys<#lt> = #exact_iter<#iterator_flatten<#array_into_iter<#option<#key>, #count>>> where __V: #lt;
+            type Values<#lt> = #exact_iter<#iterator_flatten<#slice_iter<#lt, #option<__V>>>> where __V: #lt;
+            type IterMut<#lt> = #exact_iter<#iterator_flat_map<
+                #array_into_iter<(#key, &#lt mut #option<__V>), #count>,
+                #option<(#key, &#lt mut __V)>,
+                fn((#key, &#lt mut #option<__V>)) -> #option<(#key, &#lt mut __V)>
+            >> where __V: #lt;
+            type ValuesMut<#lt> = #exact_iter<#iterator_flatten<#slice_iter_mut<#lt, #option<__V>>>> where __V: #lt;
+            type IntoIter = #exact_iter<#iterator_flat_map<
+                #array_into_iter<(#key, #option<__V>), #count>,
+                #option<(#key, __V)>,
+                fn((#key, #option<__V>)) -> #option<(#key, __V)>
+            >>;
+            type Zip<#lt, __U> = #zip_slots_type<
+                #lt,
+                #array_into_iter<(#key, #option<&#lt __V>, #option<&#lt __U>), #count>,
+                #key,
+                __V,
+                __U
+            > where __V: #lt, __U: #lt;
+            type Occupied<#lt> = OccupiedEntry #entry_generics #entry_where_clause;
+            type Vacant<#lt> = VacantEntry #entry_generics #entry_where_clause;
 
             #[inline]
             fn empty() -> Self {
@@ -266,7 +385,7 @@ fn impl_map(
             }
 
             #[inline]
-            fn insert(&mut self, key: #ident, value: V) -> #option<V> {
+            fn insert(&mut self, key: #key, value: __V) -> #option<__V> {
                 let [#(#names),*] = &mut self.data;
 
                 match key {
@@ -275,7 +394,7 @@ fn impl_map(
             }
 
             #[inline]
-            fn contains_key(&self, value: #ident) -> bool {
+            fn contains_key(&self, value: #key) -> bool {
                 let [#(#names),*] = &self.data;
 
                 match value {
@@ -284,7 +403,7 @@ fn impl_map(
             }
 
             #[inline]
-            fn get(&self, value: #ident) -> #option<&V> {
+            fn get(&self, value: #key) -> #option<&__V> {
                 let [#(#names),*] = &self.data;
 
                 match value {
@@ -293,7 +412,7 @@ fn impl_map(
             }
 
             #[inline]
-            fn get_mut(&mut self, value: #ident) -> #option<&mut V> {
+            fn get_mut(&mut self, value: #key) -> #option<&mut __V> {
                 let [#(#names),*] = &mut self.data;
 
                 match value {
@@ -302,7 +421,7 @@ fn impl_map(
             }
 
             #[inline]
-            fn remove(&mut self, value: #ident) -> #option<V> {
+            fn remove(&mut self, value: #key) -> #option<__V> {
                 let [#(#names),*] = &mut self.data;
 
                 match value {
@@ -311,9 +430,29 @@ fn impl_map(
             }
 
             #[inline]
-            fn retain<F>(&mut self, mut func: F)
+            fn contains_key_by(&self, value: <#key as #key_t>::Ref<'_>) -> bool {
+                #map_storage_t::contains_key(self, value)
+            }
+
+            #[inline]
+            fn get_by(&self, value: <#key as #key_t>::Ref<'_>) -> #option<&__V> {
+                #map_storage_t::get(self, value)
+            }
+
+            #[inline]
+            fn get_mut_by(&mut self, value: <#key as #key_t>::Ref<'_>) -> #option<&mut __V> {
+                #map_storage_t::get_mut(self, value)
+            }
+
+            #[inline]
+            fn remove_by(&mut self, value: <#key as #key_t>::Ref<'_>) -> #option<__V> {
+                #map_storage_t::remove(self, value)
+            }
+
+            #[inline]
+            fn retain<__F>(&mut self, mut func: __F)
             where
-                F: FnMut(#ident, &mut V) -> bool
+                __F: FnMut(#key, &mut __V) -> bool
             {
                 let [#(#names),*] = &mut self.data;
 
@@ -331,58 +470,281 @@ fn impl_map(
 
             #[inline]
             fn iter(&self) -> Self::Iter<'_> {
+                let len = #map_storage_t::len(self);
                 let [#(#names),*] = &self.data;
-                #iterator_t::flat_map(#into_iterator_t::into_iter([#((#ident::#variants, #names)),*]), |(k, v)| #option::Some((k, #option::as_ref(v)?)))
+                #exact_iter::new(#iterator_t::flat_map(#into_iterator_t::into_iter([#((#ident::#variants, #names)),*]), |(k, v)| #option::Some((k, #option::as_ref(v)?))), len)
             }
 
             #[inline]
             fn keys(&self) -> Self::Keys<'_> {
+                let len = #map_storage_t::len(self);
                 let [#(#names),*] = &self.data;
-                #iterator_t::flatten(#into_iterator_t::into_iter([#(if #names.is_some() { Some(#ident::#variants) } else { None }),*]))
+                #exact_iter::new(#iterator_t::flatten(#into_iterator_t::into_iter([#(if #names.is_some() { Some(#ident::#variants) } else { None }),*])), len)
             }
 
             #[inline]
             fn values(&self) -> Self::Values<'_> {
-                #iterator_t::flatten(#into_iterator_t::into_iter(&self.data))
+                let len = #map_storage_t::len(self);
+                #exact_iter::new(#iterator_t::flatten(#into_iterator_t::into_iter(&self.data)), len)
             }
 
             #[inline]
             fn iter_mut(&mut self) -> Self::IterMut<'_> {
+                let len = #map_storage_t::len(self);
                 let [#(#names),*] = &mut self.data;
-                #iterator_t::flat_map(#into_iterator_t::into_iter([#((#ident::#variants, #names)),*]), |(k, v)| #option::Some((k, #option::as_mut(v)?)))
+                #exact_iter::new(#iterator_t::flat_map(#into_iterator_t::into_iter([#((#ident::#variants, #names)),*]), |(k, v)| #option::Some((k, #option::as_mut(v)?))), len)
             }
 
             #[inline]
             fn values_mut(&mut self) -> Self::ValuesMut<'_> {
-                #iterator_t::flatten(#into_iterator_t::into_iter(&mut self.data))
+                let len = #map_storage_t::len(self);
+                #exact_iter::new(#iterator_t::flatten(#into_iterator_t::into_iter(&mut self.data)), len)
             }
 
             #[inline]
             fn into_iter(self) -> Self::IntoIter {
+                let len = #map_storage_t::len(&self);
                 let [#(#names),*] = self.data;
-                #iterator_t::flat_map(#into_iterator_t::into_iter([#((#ident::#variants, #names)),*]), |(k, v)| #option::Some((k, v?)))
+                #exact_iter::new(#iterator_t::flat_map(#into_iterator_t::into_iter([#((#ident::#variants, #names)),*]), |(k, v)| #option::Some((k, v?))), len)
             }
 
             #[inline]
-            fn entry(&mut self, key: #ident) -> #entry_enum<'_, Self, #ident, V> {
+            fn entry(&mut self, key: #key) -> #entry_enum<'_, Self, #key, __V> {
                 let [#(#names),*] = &mut self.data;
 
                 match key {
                     #(#ident::#variants => option_to_entry(#names, key),)*
                 }
             }
+
+            #[inline]
+            fn zip<#lt, __U>(&#lt self, other: &#lt <#key as #key_t>::MapStorage<__U>) -> Self::Zip<#lt, __U>
+            where
+                __U: #lt,
+            {
+                let [#(#names),*] = &self.data;
+                let [#(#other_names),*] = &other.data;
+                #zip_slots([#((#ident::#variants, #option::as_ref(#names), #option::as_ref(#other_names))),*])
+            }
+
+            #[inline]
+            fn map_values<__U, __F>(self, mut f: __F) -> <#key as #key_t>::MapStorage<__U>
+            where
+                __F: FnMut(#key, __V) -> __U,
+            {
+                let [#(#names),*] = self.data;
+
+                #map_storage {
+                    data: [#(#option::map(#names, |value| f(#ident::#variants, value))),*],
+                }
+            }
+
+            #[inline]
+            fn merge<__U, __W, __F>(self, other: <#key as #key_t>::MapStorage<__U>, mut f: __F) -> <#key as #key_t>::MapStorage<__W>
+            where
+                __F: FnMut(#key, #option<__V>, #option<__U>) -> #option<__W>,
+            {
+                let [#(#names),*] = self.data;
+                let [#(#other_names),*] = other.data;
+
+                #map_storage {
+                    data: [#(match (#names, #other_names) {
+                        (#option::None, #option::None) => #option::None,
+                        (a, b) => f(#ident::#variants, a, b),
+                    }),*],
+                }
+            }
         }
     })
 }
 
+/// Implement parallel iteration over map storage, which splits the work by
+/// slot. This is only emitted with the `rayon` feature.
+fn impl_parallel_map(cx: &Ctxt<'_>, en: &DataEnum, map_storage: &Ident) -> TokenStream {
+    if !cfg!(feature = "rayon") {
+        return TokenStream::new();
+    }
+
+    let ident = &cx.ast.ident;
+    let key = cx.key_type();
+
+    let generics = cx.generics(false, true);
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let predicates = predicates(&generics);
+
+    let indexed_parallel_iterator_t = cx.toks.indexed_parallel_iterator_t();
+    let into_parallel_iterator_t = cx.toks.into_parallel_iterator_t();
+    let parallel_iterator_t = cx.toks.parallel_iterator_t();
+    let parallel_map_storage_t = cx.toks.parallel_map_storage_t();
+    let option = cx.toks.option();
+    let rayon = cx.toks.rayon();
+
+    let count = en.variants.len();
+    let variants = en.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #parallel_map_storage_t<#key, __V> for #map_storage #ty_generics where #(#predicates,)* __V: Send + Sync {
+            type ParIter<'__this> = #rayon::iter::FilterMap<
+                #rayon::iter::Zip<#rayon::array::IntoIter<#key, #count>, #rayon::slice::Iter<'__this, #option<__V>>>,
+                fn((#key, &'__this #option<__V>)) -> #option<(#key, &'__this __V)>,
+            > where __V: '__this;
+            type ParIterMut<'__this> = #rayon::iter::FilterMap<
+                #rayon::iter::Zip<#rayon::array::IntoIter<#key, #count>, #rayon::slice::IterMut<'__this, #option<__V>>>,
+                fn((#key, &'__this mut #option<__V>)) -> #option<(#key, &'__this mut __V)>,
+            > where __V: '__this;
+            type ParValuesMut<'__this> = #rayon::iter::FilterMap<
+                #rayon::slice::IterMut<'__this, #option<__V>>,
+                fn(&'__this mut #option<__V>) -> #option<&'__this mut __V>,
+            > where __V: '__this;
+            type IntoParIter = #rayon::iter::FilterMap<
+                #rayon::iter::Zip<#rayon::array::IntoIter<#key, #count>, #rayon::array::IntoIter<#option<__V>, #count>>,
+                fn((#key, #option<__V>)) -> #option<(#key, __V)>,
+            >;
+
+            #[inline]
+            fn par_iter(&self) -> Self::ParIter<'_> {
+                let map: fn(_) -> _ = |(k, v): (_, &#option<_>)| #option::Some((k, #option::as_ref(v)?));
+                let keys = #into_parallel_iterator_t::into_par_iter([#(#ident::#variants,)*]);
+                let values = #into_parallel_iterator_t::into_par_iter(&self.data);
+                #parallel_iterator_t::filter_map(#indexed_parallel_iterator_t::zip(keys, values), map)
+            }
+
+            #[inline]
+            fn par_iter_mut(&mut self) -> Self::ParIterMut<'_> {
+                let map: fn(_) -> _ = |(k, v): (_, &mut #option<_>)| #option::Some((k, #option::as_mut(v)?));
+                let keys = #into_parallel_iterator_t::into_par_iter([#(#ident::#variants,)*]);
+                let values = #into_parallel_iterator_t::into_par_iter(&mut self.data);
+                #parallel_iterator_t::filter_map(#indexed_parallel_iterator_t::zip(keys, values), map)
+            }
+
+            #[inline]
+            fn par_values_mut(&mut self) -> Self::ParValuesMut<'_> {
+                let map: fn(_) -> _ = #option::as_mut;
+                #parallel_iterator_t::filter_map(#into_parallel_iterator_t::into_par_iter(&mut self.data), map)
+            }
+
+            #[inline]
+            fn into_par_iter(self) -> Self::IntoParIter {
+                let map: fn(_) -> _ = |(k, v): (_, #option<_>)| #option::Some((k, v?));
+                let keys = #into_parallel_iterator_t::into_par_iter([#(#ident::#variants,)*]);
+                let values = #into_parallel_iterator_t::into_par_iter(self.data);
+                #parallel_iterator_t::filter_map(#indexed_parallel_iterator_t::zip(keys, values), map)
+            }
+        }
+    }
+}
+
+/// Implement archiving of map storage through `rkyv`, where the archived form
+/// is an array of archived optional values. This is only emitted with the
+/// `rkyv` feature.
+fn impl_archive_map(
+    cx: &Ctxt<'_>,
+    en: &DataEnum,
+    map_storage: &Ident,
+    names: &[Ident],
+) -> TokenStream {
+    if !cfg!(feature = "rkyv") {
+        return TokenStream::new();
+    }
+
+    let vis = &cx.ast.vis;
+    let ident = &cx.ast.ident;
+    let key = cx.key_type();
+
+    let generics = cx.generics(false, true);
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let predicates = predicates(&generics);
+
+    let archive_map_storage_t = cx.toks.archive_map_storage_t();
+    let archived_map_storage_t = cx.toks.archived_map_storage_t();
+    let array_into_iter = cx.toks.array_into_iter();
+    let exact_iter = cx.toks.exact_iter();
+    let into_iterator_t = cx.toks.into_iterator_t();
+    let iterator_flat_map = cx.toks.iterator_flat_map();
+    let iterator_t = cx.toks.iterator_t();
+    let option = cx.toks.option();
+    let result = cx.toks.result();
+    let rkyv = cx.toks.rkyv();
+
+    let check_bytes = cx.derive_check_bytes();
+    let archived_storage = format_ident!("__Archived{map_storage}");
+    let count = en.variants.len();
+    let variants = en.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+
+    quote! {
+        #check_bytes
+        #[repr(transparent)]
+        #vis struct #archived_storage #impl_generics where #(#predicates,)* __V: #rkyv::Archive {
+            data: #rkyv::Archived<[#option<__V>; #count]>,
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #archive_map_storage_t<#key, __V> for #map_storage #ty_generics where #(#predicates,)* __V: #rkyv::Archive {
+            type Archived = #archived_storage #ty_generics;
+            type Resolver = #rkyv::Resolver<[#option<__V>; #count]>;
+
+            #[inline]
+            fn serialize<__S>(&self, serializer: &mut __S) -> #result<Self::Resolver, __S::Error>
+            where
+                __S: #rkyv::Fallible + ?Sized,
+                __V: #rkyv::Serialize<__S>,
+            {
+                #rkyv::Serialize::serialize(&self.data, serializer)
+            }
+
+            #[inline]
+            unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+                let (offset, field) = #rkyv::out_field!(out.data);
+                #rkyv::Archive::resolve(&self.data, pos + offset, resolver, field);
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #archived_map_storage_t<#key, __V> for #archived_storage #ty_generics where #(#predicates,)* __V: #rkyv::Archive {
+            type Iter<'__this> = #exact_iter<#iterator_flat_map<
+                #array_into_iter<(#key, &'__this #rkyv::Archived<#option<__V>>), #count>,
+                #option<(#key, &'__this __V::Archived)>,
+                fn((#key, &'__this #rkyv::Archived<#option<__V>>)) -> #option<(#key, &'__this __V::Archived)>
+            >> where __V: '__this;
+
+            #[inline]
+            fn len(&self) -> usize {
+                let [#(#names),*] = &self.data;
+                0 #(+ usize::from(#names.is_some()))*
+            }
+
+            #[inline]
+            fn get(&self, key: #key) -> #option<&__V::Archived> {
+                let [#(#names),*] = &self.data;
+
+                match key {
+                    #(#ident::#variants => #names.as_ref(),)*
+                }
+            }
+
+            #[inline]
+            fn iter(&self) -> Self::Iter<'_> {
+                let len = #archived_map_storage_t::len(self);
+                let [#(#names),*] = &self.data;
+                #exact_iter::new(#iterator_t::flat_map(#into_iterator_t::into_iter([#((#ident::#variants, #names)),*]), |(k, v)| #option::Some((k, v.as_ref()?))), len)
+            }
+        }
+    }
+}
+
 /// Implement as bitset storage.
 fn impl_bitset(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> Result<TokenStream, ()> {
     let (ty, _) = determine_bits(cx, en)?;
 
     let vis = &cx.ast.vis;
     let ident = &cx.ast.ident;
+    let key = cx.key_type();
     let lt = cx.lt;
 
+    let generics = cx.generics(false, false);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
     let iterator_t = cx.toks.iterator_t();
     let count = en.variants.len();
     let into_iterator_t = cx.toks.into_iterator_t();
@@ -390,6 +752,7 @@ fn impl_bitset(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> Result<Toke
     let clone_t = cx.toks.clone_t();
     let copy_t = cx.toks.copy_t();
     let eq_t = cx.toks.eq_t();
+    let exact_iter = cx.toks.exact_iter();
     let hash_t = cx.toks.hash_t();
     let iterator_flatten = cx.toks.iterator_flatten();
     let mem = cx.toks.mem();
@@ -399,6 +762,7 @@ fn impl_bitset(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> Result<Toke
     let partial_eq_t = cx.toks.partial_eq_t();
     let partial_ord_t = cx.toks.partial_ord_t();
     let set_storage_t = cx.toks.set_storage_t();
+    let key_t = cx.toks.key_t();
     let raw_storage_t = cx.toks.raw_storage_t();
 
     let variants = en.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
@@ -412,7 +776,7 @@ fn impl_bitset(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> Result<Toke
 
     Ok(quote! {
         #[inline]
-        const fn to_bits(value: #ident) -> #ty {
+        const fn to_bits #impl_generics(value: #key) -> #ty #where_clause {
             match value {
                 #(#ident::#variants => #numbers,)*
             }
@@ -420,12 +784,12 @@ fn impl_bitset(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> Result<Toke
 
         #[repr(transparent)]
         #[derive(#clone_t, #copy_t, #partial_eq_t, #eq_t, #hash_t)]
-        #vis struct #set_storage {
+        #vis struct #set_storage #impl_generics #where_clause {
             data: #ty,
         }
 
         #[automatically_derived]
-        impl #partial_ord_t for #set_storage {
+        impl #impl_generics #partial_ord_t for #set_storage #ty_generics #where_clause {
             #[inline]
             fn partial_cmp(&self, other: &Self) -> Option<#ordering> {
                 #partial_ord_t::partial_cmp(&self.data, &other.data)
@@ -433,7 +797,7 @@ fn impl_bitset(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> Result<Toke
         }
 
         #[automatically_derived]
-        impl #ord_t for #set_storage {
+        impl #impl_generics #ord_t for #set_storage #ty_generics #where_clause {
             #[inline]
             fn cmp(&self, other: &Self) -> #ordering {
                 #ord_t::cmp(&self.data, &other.data)
@@ -441,9 +805,9 @@ fn impl_bitset(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> Result<Toke
         }
 
         #[automatically_derived]
-        impl #set_storage_t<#ident> for #set_storage {
-            type Iter<#lt> = #iterator_flatten<#array_into_iter<#option<#ident>, #count>>;
-            type IntoIter = #iterator_flatten<#array_into_iter<#option<#ident>, #count>>;
+        impl #impl_generics #set_storage_t<#key> for #set_storage #ty_generics #where_clause {
+            type Iter<#lt> = #exact_iter<#iterator_flatten<#array_into_iter<#option<#key>, #count>>>;
+            type IntoIter = #exact_iter<#iterator_flatten<#array_into_iter<#option<#key>, #count>>>;
 
             #[inline]
             fn empty() -> Self {
@@ -463,28 +827,38 @@ fn impl_bitset(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> Result<Toke
             }
 
             #[inline]
-            fn insert(&mut self, value: #ident) -> bool {
+            fn insert(&mut self, value: #key) -> bool {
                 let mask = to_bits(value);
                 let update = self.data | mask;
                 #mem::replace(&mut self.data, update) & mask == 0
             }
 
             #[inline]
-            fn contains(&self, value: #ident) -> bool {
+            fn contains(&self, value: #key) -> bool {
                 self.data & to_bits(value) != 0
             }
 
             #[inline]
-            fn remove(&mut self, value: #ident) -> bool {
+            fn remove(&mut self, value: #key) -> bool {
                 let mask = to_bits(value);
                 let update = self.data & !mask;
                 #mem::replace(&mut self.data, update) & mask != 0
             }
 
             #[inline]
-            fn retain<F>(&mut self, mut f: F)
+            fn contains_by(&self, value: <#key as #key_t>::Ref<'_>) -> bool {
+                #set_storage_t::contains(self, value)
+            }
+
+            #[inline]
+            fn remove_by(&mut self, value: <#key as #key_t>::Ref<'_>) -> bool {
+                #set_storage_t::remove(self, value)
+            }
+
+            #[inline]
+            fn retain<__F>(&mut self, mut f: __F)
             where
-                F: FnMut(#ident) -> bool
+                __F: FnMut(#key) -> bool
             {
                 let mut update = 0;
 
@@ -504,17 +878,19 @@ fn impl_bitset(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> Result<Toke
 
             #[inline]
             fn iter(&self) -> Self::Iter<'_> {
-                #iterator_t::flatten(#into_iterator_t::into_iter([#(if self.data & #numbers != 0 { Some(#ident::#variants) } else { None }),*]))
+                let len = #set_storage_t::len(self);
+                #exact_iter::new(#iterator_t::flatten(#into_iterator_t::into_iter([#(if self.data & #numbers != 0 { Some(#ident::#variants) } else { None }),*])), len)
             }
 
             #[inline]
             fn into_iter(self) -> Self::IntoIter {
-                #iterator_t::flatten(#into_iterator_t::into_iter([#(if self.data & #numbers != 0 { Some(#ident::#variants) } else { None }),*]))
+                let len = #set_storage_t::len(&self);
+                #exact_iter::new(#iterator_t::flatten(#into_iterator_t::into_iter([#(if self.data & #numbers != 0 { Some(#ident::#variants) } else { None }),*])), len)
             }
         }
 
         #[automatically_derived]
-        impl #raw_storage_t for #set_storage {
+        impl #impl_generics #raw_storage_t for #set_storage #ty_generics #where_clause {
             type Value = #ty;
 
             #[inline]
@@ -523,8 +899,201 @@ fn impl_bitset(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> Result<Toke
             }
 
             #[inline]
-            fn from_raw(data: #ty) -> #set_storage {
-                #set_storage { data }
+            fn from_raw(data: #ty) -> Self {
+                Self { data }
+            }
+        }
+    })
+}
+
+/// Implement archiving of bitset storage through `rkyv`, where the archived
+/// form is the raw integer. This is only emitted with the `rkyv` feature.
+fn impl_archive_bitset(
+    cx: &Ctxt<'_>,
+    en: &DataEnum,
+    set_storage: &Ident,
+) -> Result<TokenStream, ()> {
+    if !cfg!(feature = "rkyv") {
+        return Ok(TokenStream::new());
+    }
+
+    let (ty, _) = determine_bits(cx, en)?;
+
+    let vis = &cx.ast.vis;
+    let ident = &cx.ast.ident;
+    let key = cx.key_type();
+
+    let generics = cx.generics(false, false);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let archive_set_storage_t = cx.toks.archive_set_storage_t();
+    let archived_set_storage_t = cx.toks.archived_set_storage_t();
+    let archived_value = cx.toks.archived_value();
+    let array_into_iter = cx.toks.array_into_iter();
+    let exact_iter = cx.toks.exact_iter();
+    let into_iterator_t = cx.toks.into_iterator_t();
+    let iterator_flatten = cx.toks.iterator_flatten();
+    let iterator_t = cx.toks.iterator_t();
+    let option = cx.toks.option();
+    let result = cx.toks.result();
+    let rkyv = cx.toks.rkyv();
+
+    let archived_storage = format_ident!("__Archived{set_storage}");
+    let count = en.variants.len();
+    let variants = en.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+
+    let numbers = en
+        .variants
+        .iter()
+        .enumerate()
+        .map(|(n, v)| LitInt::new(&format!("{}", 1u128 << n), v.span()))
+        .collect::<Vec<_>>();
+
+    let check_bytes = impl_check_bits(cx, &archived_storage, &ty, count);
+
+    Ok(quote! {
+        #[repr(transparent)]
+        #vis struct #archived_storage #impl_generics #where_clause {
+            data: #rkyv::Archived<#ty>,
+        }
+
+        #check_bytes
+
+        #[automatically_derived]
+        impl #impl_generics #archive_set_storage_t<#key> for #set_storage #ty_generics #where_clause {
+            type Archived = #archived_storage #ty_generics;
+            type Resolver = #rkyv::Resolver<#ty>;
+
+            #[inline]
+            fn serialize<__S>(&self, serializer: &mut __S) -> #result<Self::Resolver, __S::Error>
+            where
+                __S: #rkyv::Fallible + ?Sized,
+            {
+                #rkyv::Serialize::<__S>::serialize(&self.data, serializer)
+            }
+
+            #[inline]
+            unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+                let (offset, field) = #rkyv::out_field!(out.data);
+                #rkyv::Archive::resolve(&self.data, pos + offset, resolver, field);
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #archived_set_storage_t<#key> for #archived_storage #ty_generics #where_clause {
+            type Iter<'__this> = #exact_iter<#iterator_flatten<#array_into_iter<#option<#key>, #count>>>;
+
+            #[inline]
+            fn len(&self) -> usize {
+                <#ty>::count_ones(#archived_value::<#ty>(&self.data)) as usize
+            }
+
+            #[inline]
+            fn contains(&self, value: #key) -> bool {
+                #archived_value::<#ty>(&self.data) & to_bits(value) != 0
+            }
+
+            #[inline]
+            fn iter(&self) -> Self::Iter<'_> {
+                let data = #archived_value::<#ty>(&self.data);
+                let len = <#ty>::count_ones(data) as usize;
+                #exact_iter::new(#iterator_t::flatten(#into_iterator_t::into_iter([#(if data & #numbers != 0 { Some(#ident::#variants) } else { None }),*])), len)
+            }
+        }
+    })
+}
+
+/// Implement validation of an archived bitset, which rejects any bit that
+/// doesn't correspond to a variant. This is only emitted with the
+/// `rkyv-validation` feature.
+fn impl_check_bits(
+    cx: &Ctxt<'_>,
+    archived_storage: &Ident,
+    ty: &Ident,
+    count: usize,
+) -> TokenStream {
+    if !cfg!(feature = "rkyv-validation") {
+        return TokenStream::new();
+    }
+
+    let generics = cx.generics(false, false);
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut check_generics = generics.clone();
+    check_generics.params.push(syn::parse_quote!(__C: ?Sized));
+    let (impl_generics, _, _) = check_generics.split_for_impl();
+
+    let addr_of = cx.toks.addr_of();
+    let archived_value = cx.toks.archived_value();
+    let bits_check_error = cx.toks.bits_check_error();
+    let check_bits = cx.toks.check_bits();
+    let from_t = cx.toks.from_t();
+    let result = cx.toks.result();
+    let rkyv = cx.toks.rkyv();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #rkyv::CheckBytes<__C> for #archived_storage #ty_generics #where_clause {
+            type Error = #bits_check_error;
+
+            #[inline]
+            unsafe fn check_bytes<'__a>(value: *const Self, _: &mut __C) -> #result<&'__a Self, Self::Error> {
+                let data = #archived_value::<#ty>(&*#addr_of!((*value).data));
+                #check_bits(#from_t::from(data), #count)?;
+                #result::Ok(&*value)
+            }
+        }
+    }
+}
+
+/// Implement `borsh` encoding of bitset storage, which is written as the raw
+/// integer. This is only emitted with the `borsh` feature.
+fn impl_borsh_bitset(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> Result<TokenStream, ()> {
+    if !cfg!(feature = "borsh") {
+        return Ok(TokenStream::new());
+    }
+
+    let (ty, _) = determine_bits(cx, en)?;
+
+    let generics = cx.generics(false, false);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let borsh = cx.toks.borsh();
+    let borsh_invalid_bits = cx.toks.borsh_invalid_bits();
+    let result = cx.toks.result();
+
+    let numbers = en
+        .variants
+        .iter()
+        .enumerate()
+        .map(|(n, v)| LitInt::new(&format!("{}", 1u128 << n), v.span()));
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #borsh::BorshSerialize for #set_storage #ty_generics #where_clause {
+            #[inline]
+            fn serialize<__W>(&self, writer: &mut __W) -> #borsh::io::Result<()>
+            where
+                __W: #borsh::io::Write,
+            {
+                #borsh::BorshSerialize::serialize(&self.data, writer)
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #borsh::BorshDeserialize for #set_storage #ty_generics #where_clause {
+            #[inline]
+            fn deserialize_reader<__R>(reader: &mut __R) -> #borsh::io::Result<Self>
+            where
+                __R: #borsh::io::Read,
+            {
+                let data = <#ty as #borsh::BorshDeserialize>::deserialize_reader(reader)?;
+
+                if data & !(0 #(| #numbers)*) != 0 {
+                    return #result::Err(#borsh_invalid_bits());
+                }
+
+                #result::Ok(Self { data })
             }
         }
     })
@@ -556,8 +1125,12 @@ fn impl_set(
 ) -> Result<TokenStream, ()> {
     let vis = &cx.ast.vis;
     let ident = &cx.ast.ident;
+    let key = cx.key_type();
     let lt = cx.lt;
 
+    let generics = cx.generics(false, false);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
     let iterator_t = cx.toks.iterator_t();
     let count = en.variants.len();
     let into_iterator_t = cx.toks.into_iterator_t();
@@ -565,6 +1138,7 @@ fn impl_set(
     let clone_t = cx.toks.clone_t();
     let copy_t = cx.toks.copy_t();
     let eq_t = cx.toks.eq_t();
+    let exact_iter = cx.toks.exact_iter();
     let hash_t = cx.toks.hash_t();
     let iterator_cmp_bool = cx.toks.iterator_cmp_bool();
     let iterator_flatten = cx.toks.iterator_flatten();
@@ -576,6 +1150,7 @@ fn impl_set(
     let partial_eq_t = cx.toks.partial_eq_t();
     let partial_ord_t = cx.toks.partial_ord_t();
     let set_storage_t = cx.toks.set_storage_t();
+    let key_t = cx.toks.key_t();
 
     let variants = en.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
     let init = en
@@ -587,12 +1162,12 @@ fn impl_set(
     Ok(quote! {
         #[repr(transparent)]
         #[derive(#clone_t, #copy_t, #partial_eq_t, #eq_t, #hash_t)]
-        #vis struct #set_storage {
+        #vis struct #set_storage #impl_generics #where_clause {
             data: [bool; #count],
         }
 
         #[automatically_derived]
-        impl #partial_ord_t for #set_storage {
+        impl #impl_generics #partial_ord_t for #set_storage #ty_generics #where_clause {
             #[inline]
             fn partial_cmp(&self, other: &Self) -> Option<#ordering> {
                 #iterator_partial_cmp_bool(&self.data, &other.data)
@@ -600,7 +1175,7 @@ fn impl_set(
         }
 
         #[automatically_derived]
-        impl #ord_t for #set_storage {
+        impl #impl_generics #ord_t for #set_storage #ty_generics #where_clause {
             #[inline]
             fn cmp(&self, other: &Self) -> #ordering {
                 #iterator_cmp_bool(&self.data, &other.data)
@@ -608,9 +1183,9 @@ fn impl_set(
         }
 
         #[automatically_derived]
-        impl #set_storage_t<#ident> for #set_storage {
-            type Iter<#lt> = #iterator_flatten<#array_into_iter<#option<#ident>, #count>>;
-            type IntoIter = #iterator_flatten<#array_into_iter<#option<#ident>, #count>>;
+        impl #impl_generics #set_storage_t<#key> for #set_storage #ty_generics #where_clause {
+            type Iter<#lt> = #exact_iter<#iterator_flatten<#array_into_iter<#option<#key>, #count>>>;
+            type IntoIter = #exact_iter<#iterator_flatten<#array_into_iter<#option<#key>, #count>>>;
 
             #[inline]
             fn empty() -> Self {
@@ -632,7 +1207,7 @@ fn impl_set(
             }
 
             #[inline]
-            fn insert(&mut self, value: #ident) -> bool {
+            fn insert(&mut self, value: #key) -> bool {
                 let [#(#names),*] = &mut self.data;
 
                 match value {
@@ -641,7 +1216,7 @@ fn impl_set(
             }
 
             #[inline]
-            fn contains(&self, value: #ident) -> bool {
+            fn contains(&self, value: #key) -> bool {
                 let [#(#names),*] = &self.data;
 
                 match value {
@@ -650,7 +1225,7 @@ fn impl_set(
             }
 
             #[inline]
-            fn remove(&mut self, value: #ident) -> bool {
+            fn remove(&mut self, value: #key) -> bool {
                 let [#(#names),*] = &mut self.data;
 
                 match value {
@@ -659,9 +1234,19 @@ fn impl_set(
             }
 
             #[inline]
-            fn retain<F>(&mut self, mut f: F)
+            fn contains_by(&self, value: <#key as #key_t>::Ref<'_>) -> bool {
+                #set_storage_t::contains(self, value)
+            }
+
+            #[inline]
+            fn remove_by(&mut self, value: <#key as #key_t>::Ref<'_>) -> bool {
+                #set_storage_t::remove(self, value)
+            }
+
+            #[inline]
+            fn retain<__F>(&mut self, mut f: __F)
             where
-                F: FnMut(#ident) -> bool
+                __F: FnMut(#key) -> bool
             {
                 let [#(#names),*] = &mut self.data;
 
@@ -677,15 +1262,195 @@ fn impl_set(
 
             #[inline]
             fn iter(&self) -> Self::Iter<'_> {
+                let len = #set_storage_t::len(self);
                 let [#(#names),*] = &self.data;
-                #iterator_t::flatten(#into_iterator_t::into_iter([#(if *#names { Some(#ident::#variants) } else { None }),*]))
+                #exact_iter::new(#iterator_t::flatten(#into_iterator_t::into_iter([#(if *#names { Some(#ident::#variants) } else { None }),*])), len)
             }
 
             #[inline]
             fn into_iter(self) -> Self::IntoIter {
+                let len = #set_storage_t::len(&self);
                 let [#(#names),*] = &self.data;
-                #iterator_t::flatten(#into_iterator_t::into_iter([#(if *#names { Some(#ident::#variants) } else { None }),*]))
+                #exact_iter::new(#iterator_t::flatten(#into_iterator_t::into_iter([#(if *#names { Some(#ident::#variants) } else { None }),*])), len)
             }
         }
     })
 }
+
+/// Implement parallel iteration over set storage, which splits the work by
+/// slot. This is only emitted with the `rayon` feature.
+fn impl_parallel_set(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> TokenStream {
+    if !cfg!(feature = "rayon") {
+        return TokenStream::new();
+    }
+
+    let ident = &cx.ast.ident;
+    let key = cx.key_type();
+
+    let generics = cx.generics(false, false);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let indexed_parallel_iterator_t = cx.toks.indexed_parallel_iterator_t();
+    let into_parallel_iterator_t = cx.toks.into_parallel_iterator_t();
+    let parallel_iterator_t = cx.toks.parallel_iterator_t();
+    let parallel_set_storage_t = cx.toks.parallel_set_storage_t();
+    let option = cx.toks.option();
+    let rayon = cx.toks.rayon();
+
+    let count = en.variants.len();
+    let variants = en.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #parallel_set_storage_t<#key> for #set_storage #ty_generics #where_clause {
+            type ParIter<'__this> = #rayon::iter::FilterMap<
+                #rayon::iter::Zip<#rayon::array::IntoIter<#key, #count>, #rayon::array::IntoIter<bool, #count>>,
+                fn((#key, bool)) -> #option<#key>,
+            >;
+
+            #[inline]
+            fn par_iter(&self) -> Self::ParIter<'_> {
+                let map: fn(_) -> _ = |(k, v): (_, bool)| v.then_some(k);
+                let keys = #into_parallel_iterator_t::into_par_iter([#(#ident::#variants,)*]);
+                let values = #into_parallel_iterator_t::into_par_iter(self.data);
+                #parallel_iterator_t::filter_map(#indexed_parallel_iterator_t::zip(keys, values), map)
+            }
+        }
+    }
+}
+
+/// Implement archiving of set storage through `rkyv`, where the archived form
+/// is an array of archived flags. This is only emitted with the `rkyv` feature.
+fn impl_archive_set(
+    cx: &Ctxt<'_>,
+    en: &DataEnum,
+    set_storage: &Ident,
+    names: &[Ident],
+) -> TokenStream {
+    if !cfg!(feature = "rkyv") {
+        return TokenStream::new();
+    }
+
+    let vis = &cx.ast.vis;
+    let ident = &cx.ast.ident;
+    let key = cx.key_type();
+
+    let generics = cx.generics(false, false);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let archive_set_storage_t = cx.toks.archive_set_storage_t();
+    let archived_set_storage_t = cx.toks.archived_set_storage_t();
+    let array_into_iter = cx.toks.array_into_iter();
+    let exact_iter = cx.toks.exact_iter();
+    let into_iterator_t = cx.toks.into_iterator_t();
+    let iterator_flatten = cx.toks.iterator_flatten();
+    let iterator_t = cx.toks.iterator_t();
+    let option = cx.toks.option();
+    let result = cx.toks.result();
+    let rkyv = cx.toks.rkyv();
+
+    let check_bytes = cx.derive_check_bytes();
+    let archived_storage = format_ident!("__Archived{set_storage}");
+    let count = en.variants.len();
+    let variants = en.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+
+    quote! {
+        #check_bytes
+        #[repr(transparent)]
+        #vis struct #archived_storage #impl_generics #where_clause {
+            data: #rkyv::Archived<[bool; #count]>,
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #archive_set_storage_t<#key> for #set_storage #ty_generics #where_clause {
+            type Archived = #archived_storage #ty_generics;
+            type Resolver = #rkyv::Resolver<[bool; #count]>;
+
+            #[inline]
+            fn serialize<__S>(&self, serializer: &mut __S) -> #result<Self::Resolver, __S::Error>
+            where
+                __S: #rkyv::Fallible + ?Sized,
+            {
+                #rkyv::Serialize::<__S>::serialize(&self.data, serializer)
+            }
+
+            #[inline]
+            unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+                let (offset, field) = #rkyv::out_field!(out.data);
+                #rkyv::Archive::resolve(&self.data, pos + offset, resolver, field);
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #archived_set_storage_t<#key> for #archived_storage #ty_generics #where_clause {
+            type Iter<'__this> = #exact_iter<#iterator_flatten<#array_into_iter<#option<#key>, #count>>>;
+
+            #[inline]
+            fn len(&self) -> usize {
+                let [#(#names),*] = &self.data;
+                0 #(+ usize::from(*#names))*
+            }
+
+            #[inline]
+            fn contains(&self, value: #key) -> bool {
+                let [#(#names),*] = &self.data;
+
+                match value {
+                    #(#ident::#variants => *#names,)*
+                }
+            }
+
+            #[inline]
+            fn iter(&self) -> Self::Iter<'_> {
+                let len = #archived_set_storage_t::len(self);
+                let [#(#names),*] = &self.data;
+                #exact_iter::new(#iterator_t::flatten(#into_iterator_t::into_iter([#(if *#names { Some(#ident::#variants) } else { None }),*])), len)
+            }
+        }
+    }
+}
+
+/// Implement `borsh` encoding of set storage, which is written as a bitmap of
+/// the values which are present. This is only emitted with the `borsh`
+/// feature.
+fn impl_borsh_set(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> TokenStream {
+    if !cfg!(feature = "borsh") {
+        return TokenStream::new();
+    }
+
+    let generics = cx.generics(false, false);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let borsh = cx.toks.borsh();
+    let borsh_read_bitmap = cx.toks.borsh_read_bitmap();
+    let borsh_write_bitmap = cx.toks.borsh_write_bitmap();
+    let result = cx.toks.result();
+
+    let count = en.variants.len();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #borsh::BorshSerialize for #set_storage #ty_generics #where_clause {
+            #[inline]
+            fn serialize<__W>(&self, writer: &mut __W) -> #borsh::io::Result<()>
+            where
+                __W: #borsh::io::Write,
+            {
+                #borsh_write_bitmap(self.data, writer)
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #borsh::BorshDeserialize for #set_storage #ty_generics #where_clause {
+            #[inline]
+            fn deserialize_reader<__R>(reader: &mut __R) -> #borsh::io::Result<Self>
+            where
+                __R: #borsh::io::Read,
+            {
+                let mut data = [false; #count];
+                #borsh_read_bitmap(&mut data, reader)?;
+                #result::Ok(Self { data })
+            }
+        }
+    }
+}
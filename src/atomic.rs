@@ -0,0 +1,792 @@
+//! Contains the lock-free [`AtomicSet`] and [`AtomicCounters`] types.
+
+use core::fmt;
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering;
+
+use crate::raw::RawStorage;
+#[cfg(target_has_atomic = "ptr")]
+use crate::{Finite, TotalMap};
+use crate::{Key, Set};
+
+type Raw<K> = <<K as Key>::SetStorage as RawStorage>::Value;
+
+/// A raw bitset value which has a matching atomic integer type.
+///
+/// This is implemented for the unsigned integers which back the set storage
+/// of `#[key(bitset)]` keys, as long as the target supports atomic operations
+/// on them.
+pub trait AtomicBits: Copy {
+    /// The atomic integer type.
+    type Atomic;
+
+    /// Construct a new atomic holding the given value.
+    fn new(value: Self) -> Self::Atomic;
+
+    /// Consume the atomic and return the value it holds.
+    fn into_inner(atomic: Self::Atomic) -> Self;
+
+    /// Load the value of the atomic.
+    fn load(atomic: &Self::Atomic, order: Ordering) -> Self;
+
+    /// Store a value in the atomic.
+    fn store(atomic: &Self::Atomic, value: Self, order: Ordering);
+
+    /// Store a value in the atomic, returning the previous value.
+    fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+
+    /// Bitwise or with the atomic, returning the previous value.
+    fn fetch_or(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+
+    /// Bitwise and with the atomic, returning the previous value.
+    fn fetch_and(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+
+    /// Test if any bit is set in both values.
+    fn intersects(self, other: Self) -> bool;
+
+    /// Invert every bit of the value.
+    fn not(self) -> Self;
+}
+
+macro_rules! atomic_bits {
+    ($($ty:ty, $atomic:ident, $size:literal;)*) => {
+        $(
+            #[cfg(target_has_atomic = $size)]
+            impl AtomicBits for $ty {
+                type Atomic = core::sync::atomic::$atomic;
+
+                #[inline]
+                fn new(value: Self) -> Self::Atomic {
+                    core::sync::atomic::$atomic::new(value)
+                }
+
+                #[inline]
+                fn into_inner(atomic: Self::Atomic) -> Self {
+                    atomic.into_inner()
+                }
+
+                #[inline]
+                fn load(atomic: &Self::Atomic, order: Ordering) -> Self {
+                    atomic.load(order)
+                }
+
+                #[inline]
+                fn store(atomic: &Self::Atomic, value: Self, order: Ordering) {
+                    atomic.store(value, order);
+                }
+
+                #[inline]
+                fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.swap(value, order)
+                }
+
+                #[inline]
+                fn fetch_or(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.fetch_or(value, order)
+                }
+
+                #[inline]
+                fn fetch_and(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.fetch_and(value, order)
+                }
+
+                #[inline]
+                fn intersects(self, other: Self) -> bool {
+                    self & other != 0
+                }
+
+                #[inline]
+                fn not(self) -> Self {
+                    !self
+                }
+            }
+        )*
+    };
+}
+
+atomic_bits! {
+    u8, AtomicU8, "8";
+    u16, AtomicU16, "16";
+    u32, AtomicU32, "32";
+    u64, AtomicU64, "64";
+}
+
+/// A lock-free set of keys using the `#[key(bitset)]` attribute, stored as a
+/// single atomic integer.
+///
+/// Every operation takes an [`Ordering`] which has the same meaning as for
+/// the atomic integers in [`core::sync::atomic`].
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::Ordering;
+///
+/// use fixed_map::atomic::AtomicSet;
+/// use fixed_map::{Key, Set};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// #[key(bitset)]
+/// enum Flag {
+///     Dirty,
+///     Visible,
+///     Locked,
+/// }
+///
+/// let flags = AtomicSet::new();
+///
+/// std::thread::scope(|s| {
+///     s.spawn(|| flags.insert(Flag::Dirty, Ordering::Relaxed));
+///     s.spawn(|| flags.insert(Flag::Locked, Ordering::Relaxed));
+/// });
+///
+/// assert_eq!(flags.load(Ordering::Relaxed), Set::from([Flag::Dirty, Flag::Locked]));
+/// ```
+pub struct AtomicSet<K>
+where
+    K: Key,
+    K::SetStorage: RawStorage,
+    Raw<K>: AtomicBits,
+{
+    bits: <Raw<K> as AtomicBits>::Atomic,
+}
+
+impl<K> AtomicSet<K>
+where
+    K: Key,
+    K::SetStorage: RawStorage,
+    Raw<K>: AtomicBits,
+{
+    /// Creates an empty [`AtomicSet`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicSet;
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// #[key(bitset)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let set = AtomicSet::<MyKey>::new();
+    /// assert!(set.load(Ordering::Relaxed).is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> AtomicSet<K> {
+        AtomicSet::from(Set::new())
+    }
+
+    /// Creates an [`AtomicSet`] from the raw value of its storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicSet;
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// #[key(bitset)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let set = AtomicSet::<MyKey>::from_raw(Set::from([MyKey::Second]).as_raw());
+    /// assert!(set.contains(MyKey::Second, Ordering::Relaxed));
+    /// ```
+    #[inline]
+    pub fn from_raw(raw: Raw<K>) -> AtomicSet<K> {
+        AtomicSet {
+            bits: <Raw<K> as AtomicBits>::new(raw),
+        }
+    }
+
+    /// Insert a key into the set, returning `true` if it wasn't present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicSet;
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// #[key(bitset)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let set = AtomicSet::new();
+    /// assert!(set.insert(MyKey::First, Ordering::Relaxed));
+    /// assert!(!set.insert(MyKey::First, Ordering::Relaxed));
+    /// ```
+    #[inline]
+    pub fn insert(&self, key: K, order: Ordering) -> bool {
+        let mask = mask(key);
+        !<Raw<K> as AtomicBits>::fetch_or(&self.bits, mask, order).intersects(mask)
+    }
+
+    /// Remove a key from the set, returning `true` if it was present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicSet;
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// #[key(bitset)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let set = AtomicSet::new();
+    /// set.insert(MyKey::First, Ordering::Relaxed);
+    ///
+    /// assert!(set.remove(MyKey::First, Ordering::Relaxed));
+    /// assert!(!set.remove(MyKey::First, Ordering::Relaxed));
+    /// ```
+    #[inline]
+    pub fn remove(&self, key: K, order: Ordering) -> bool {
+        let mask = mask(key);
+        <Raw<K> as AtomicBits>::fetch_and(&self.bits, mask.not(), order).intersects(mask)
+    }
+
+    /// Test if the set contains the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicSet;
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// #[key(bitset)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let set = AtomicSet::new();
+    /// set.insert(MyKey::First, Ordering::Relaxed);
+    ///
+    /// assert!(set.contains(MyKey::First, Ordering::Relaxed));
+    /// assert!(!set.contains(MyKey::Second, Ordering::Relaxed));
+    /// ```
+    #[inline]
+    pub fn contains(&self, key: K, order: Ordering) -> bool {
+        <Raw<K> as AtomicBits>::load(&self.bits, order).intersects(mask(key))
+    }
+
+    /// Add every key in `other` to the set, returning the previous set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicSet;
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// #[key(bitset)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let set = AtomicSet::from(Set::from([MyKey::First]));
+    /// let old = set.fetch_union(Set::from([MyKey::Second, MyKey::Third]), Ordering::Relaxed);
+    ///
+    /// assert_eq!(old, Set::from([MyKey::First]));
+    /// assert_eq!(set.load(Ordering::Relaxed).len(), 3);
+    /// ```
+    #[inline]
+    pub fn fetch_union(&self, other: Set<K>, order: Ordering) -> Set<K> {
+        Set::from_raw(<Raw<K> as AtomicBits>::fetch_or(
+            &self.bits,
+            other.as_raw(),
+            order,
+        ))
+    }
+
+    /// Remove every key which isn't in `other` from the set, returning the
+    /// previous set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicSet;
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// #[key(bitset)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let set = AtomicSet::from(Set::from([MyKey::First, MyKey::Second]));
+    /// let old = set.fetch_intersection(Set::from([MyKey::Second, MyKey::Third]), Ordering::Relaxed);
+    ///
+    /// assert_eq!(old, Set::from([MyKey::First, MyKey::Second]));
+    /// assert_eq!(set.load(Ordering::Relaxed), Set::from([MyKey::Second]));
+    /// ```
+    #[inline]
+    pub fn fetch_intersection(&self, other: Set<K>, order: Ordering) -> Set<K> {
+        Set::from_raw(<Raw<K> as AtomicBits>::fetch_and(
+            &self.bits,
+            other.as_raw(),
+            order,
+        ))
+    }
+
+    /// Load the current contents of the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicSet;
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// #[key(bitset)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let set = AtomicSet::new();
+    /// set.insert(MyKey::Second, Ordering::Relaxed);
+    ///
+    /// assert_eq!(set.load(Ordering::Relaxed), Set::from([MyKey::Second]));
+    /// ```
+    #[inline]
+    pub fn load(&self, order: Ordering) -> Set<K> {
+        Set::from_raw(<Raw<K> as AtomicBits>::load(&self.bits, order))
+    }
+
+    /// Replace the contents of the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicSet;
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// #[key(bitset)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let set = AtomicSet::new();
+    /// set.store(Set::from([MyKey::First]), Ordering::Relaxed);
+    ///
+    /// assert_eq!(set.load(Ordering::Relaxed), Set::from([MyKey::First]));
+    /// ```
+    #[inline]
+    pub fn store(&self, set: Set<K>, order: Ordering) {
+        <Raw<K> as AtomicBits>::store(&self.bits, set.as_raw(), order);
+    }
+
+    /// Replace the contents of the set, returning the previous set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicSet;
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// #[key(bitset)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let set = AtomicSet::from(Set::from([MyKey::First]));
+    /// let old = set.swap(Set::new(), Ordering::Relaxed);
+    ///
+    /// assert_eq!(old, Set::from([MyKey::First]));
+    /// assert!(set.load(Ordering::Relaxed).is_empty());
+    /// ```
+    #[inline]
+    pub fn swap(&self, set: Set<K>, order: Ordering) -> Set<K> {
+        Set::from_raw(<Raw<K> as AtomicBits>::swap(
+            &self.bits,
+            set.as_raw(),
+            order,
+        ))
+    }
+
+    /// Consume the atomic set and return its contents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::atomic::AtomicSet;
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// #[key(bitset)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let set = AtomicSet::from(Set::from([MyKey::First]));
+    /// assert_eq!(set.into_inner(), Set::from([MyKey::First]));
+    /// ```
+    #[inline]
+    pub fn into_inner(self) -> Set<K> {
+        Set::from_raw(<Raw<K> as AtomicBits>::into_inner(self.bits))
+    }
+}
+
+impl<K> Default for AtomicSet<K>
+where
+    K: Key,
+    K::SetStorage: RawStorage,
+    Raw<K>: AtomicBits,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> From<Set<K>> for AtomicSet<K>
+where
+    K: Key,
+    K::SetStorage: RawStorage,
+    Raw<K>: AtomicBits,
+{
+    #[inline]
+    fn from(set: Set<K>) -> Self {
+        Self::from_raw(set.as_raw())
+    }
+}
+
+/// The [`Debug`][fmt::Debug] implementation for an [`AtomicSet`], which
+/// performs a relaxed load of its contents.
+impl<K> fmt::Debug for AtomicSet<K>
+where
+    K: Key + fmt::Debug,
+    K::SetStorage: RawStorage,
+    Raw<K>: AtomicBits,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.load(Ordering::Relaxed).fmt(f)
+    }
+}
+
+/// The raw value of a set only holding the given key.
+#[inline]
+fn mask<K>(key: K) -> Raw<K>
+where
+    K: Key,
+    K::SetStorage: RawStorage,
+{
+    let mut set = Set::new();
+    set.insert(key);
+    set.as_raw()
+}
+
+/// Lock-free counters holding one atomic integer for every inhabitant of a
+/// [`Finite`] key.
+///
+/// Every operation takes an [`Ordering`] which has the same meaning as for
+/// the atomic integers in [`core::sync::atomic`].
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::Ordering;
+///
+/// use fixed_map::atomic::AtomicCounters;
+/// use fixed_map::Key;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Status {
+///     Ok,
+///     NotFound,
+///     Error,
+/// }
+///
+/// let counters = AtomicCounters::new();
+///
+/// std::thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| counters.fetch_add(Status::Ok, 1, Ordering::Relaxed));
+///     }
+///
+///     s.spawn(|| counters.fetch_add(Status::Error, 1, Ordering::Relaxed));
+/// });
+///
+/// let snapshot = counters.snapshot(Ordering::Relaxed);
+/// assert!(snapshot.values().copied().eq([4, 0, 1]));
+/// ```
+#[cfg(target_has_atomic = "ptr")]
+pub struct AtomicCounters<K>
+where
+    K: Finite,
+{
+    data: K::Array<AtomicUsize>,
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<K> AtomicCounters<K>
+where
+    K: Finite,
+{
+    /// Creates [`AtomicCounters`] where every counter is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicCounters;
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let counters = AtomicCounters::new();
+    /// assert_eq!(counters.load(MyKey::First, Ordering::Relaxed), 0);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> AtomicCounters<K> {
+        AtomicCounters {
+            data: K::array_from_fn(|_| AtomicUsize::new(0)),
+        }
+    }
+
+    /// Add to the counter of the key, returning its previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicCounters;
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let counters = AtomicCounters::new();
+    /// assert_eq!(counters.fetch_add(MyKey::First, 2, Ordering::Relaxed), 0);
+    /// assert_eq!(counters.fetch_add(MyKey::First, 1, Ordering::Relaxed), 2);
+    /// ```
+    #[inline]
+    pub fn fetch_add(&self, key: K, value: usize, order: Ordering) -> usize {
+        self.counter(key).fetch_add(value, order)
+    }
+
+    /// Subtract from the counter of the key, returning its previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicCounters;
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let counters = AtomicCounters::new();
+    /// counters.fetch_add(MyKey::First, 2, Ordering::Relaxed);
+    ///
+    /// assert_eq!(counters.fetch_sub(MyKey::First, 1, Ordering::Relaxed), 2);
+    /// assert_eq!(counters.load(MyKey::First, Ordering::Relaxed), 1);
+    /// ```
+    #[inline]
+    pub fn fetch_sub(&self, key: K, value: usize, order: Ordering) -> usize {
+        self.counter(key).fetch_sub(value, order)
+    }
+
+    /// Load the counter of the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicCounters;
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let counters = AtomicCounters::new();
+    /// counters.fetch_add(MyKey::Second, 3, Ordering::Relaxed);
+    ///
+    /// assert_eq!(counters.load(MyKey::Second, Ordering::Relaxed), 3);
+    /// ```
+    #[inline]
+    pub fn load(&self, key: K, order: Ordering) -> usize {
+        self.counter(key).load(order)
+    }
+
+    /// Store a value in the counter of the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicCounters;
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let counters = AtomicCounters::new();
+    /// counters.store(MyKey::Second, 3, Ordering::Relaxed);
+    ///
+    /// assert_eq!(counters.load(MyKey::Second, Ordering::Relaxed), 3);
+    /// ```
+    #[inline]
+    pub fn store(&self, key: K, value: usize, order: Ordering) {
+        self.counter(key).store(value, order);
+    }
+
+    /// Load every counter.
+    ///
+    /// Counters are loaded one at a time, so concurrent updates to different
+    /// keys might not be observed in the order they happened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicCounters;
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let counters = AtomicCounters::new();
+    /// counters.fetch_add(MyKey::Second, 3, Ordering::Relaxed);
+    ///
+    /// let snapshot = counters.snapshot(Ordering::Relaxed);
+    /// assert_eq!(snapshot[MyKey::First], 0);
+    /// assert_eq!(snapshot[MyKey::Second], 3);
+    /// ```
+    #[inline]
+    pub fn snapshot(&self, order: Ordering) -> TotalMap<K, usize> {
+        TotalMap::from_fn(|key| self.load(key, order))
+    }
+
+    /// Reset every counter to zero, returning the values they held.
+    ///
+    /// Counters are reset one at a time, so no increment is lost even if they
+    /// are concurrently updated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// use fixed_map::atomic::AtomicCounters;
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let counters = AtomicCounters::new();
+    /// counters.fetch_add(MyKey::First, 3, Ordering::Relaxed);
+    ///
+    /// assert_eq!(counters.take(Ordering::Relaxed)[MyKey::First], 3);
+    /// assert_eq!(counters.load(MyKey::First, Ordering::Relaxed), 0);
+    /// ```
+    #[inline]
+    pub fn take(&self, order: Ordering) -> TotalMap<K, usize> {
+        TotalMap::from_fn(|key| self.counter(key).swap(0, order))
+    }
+
+    #[inline]
+    fn counter(&self, key: K) -> &AtomicUsize {
+        &self.data.as_ref()[key.index()]
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<K> Default for AtomicCounters<K>
+where
+    K: Finite,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The [`Debug`][fmt::Debug] implementation for [`AtomicCounters`], which
+/// performs a relaxed load of every counter.
+#[cfg(target_has_atomic = "ptr")]
+impl<K> fmt::Debug for AtomicCounters<K>
+where
+    K: Finite + fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.snapshot(Ordering::Relaxed).fmt(f)
+    }
+}
@@ -0,0 +1,525 @@
+//! Contains the fixed [`ConcurrentMap`] implementation.
+
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::{Finite, Map};
+
+/// A map which can be shared between threads, where every inhabitant of a
+/// [`Finite`] key has its own [`RwLock`].
+///
+/// This supports enums with only unit variants, and enums with fields using
+/// the `#[key(finite)]` attribute.
+///
+/// Operations on different keys never contend with each other, since there is
+/// no lock guarding the map as a whole.
+///
+/// Poisoning is ignored, so a slot whose lock was poisoned is still used. A
+/// thread which panics inside of [`update`] or while holding a [`RefMut`] can
+/// leave a partially modified value behind, which later operations observe
+/// as-is.
+///
+/// [`update`]: ConcurrentMap::update
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+///
+/// use fixed_map::{ConcurrentMap, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Worker {
+///     First,
+///     Second,
+/// }
+///
+/// let map = ConcurrentMap::new();
+///
+/// thread::scope(|s| {
+///     s.spawn(|| map.insert(Worker::First, 1));
+///     s.spawn(|| map.insert(Worker::Second, 2));
+/// });
+///
+/// map.update(Worker::First, |value| *value.get_or_insert(0) += 10);
+///
+/// assert_eq!(*map.get(Worker::First).unwrap(), 11);
+/// assert!(map.snapshot().into_iter().eq([(Worker::First, 11), (Worker::Second, 2)]));
+/// ```
+pub struct ConcurrentMap<K, V>
+where
+    K: Finite,
+{
+    slots: K::Array<RwLock<Option<V>>>,
+}
+
+impl<K, V> ConcurrentMap<K, V>
+where
+    K: Finite,
+{
+    /// Creates an empty [`ConcurrentMap`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{ConcurrentMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = ConcurrentMap::<MyKey, u32>::new();
+    /// assert!(map.snapshot().is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> ConcurrentMap<K, V> {
+        ConcurrentMap {
+            slots: K::array_from_fn(|_| RwLock::new(None)),
+        }
+    }
+
+    /// Returns a guard to the value corresponding to the key, or `None` if
+    /// it isn't present.
+    ///
+    /// The slot of the key is read-locked for as long as the guard is held.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{ConcurrentMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = ConcurrentMap::new();
+    /// map.insert(MyKey::First, 42);
+    ///
+    /// assert_eq!(*map.get(MyKey::First).unwrap(), 42);
+    /// assert!(map.get(MyKey::Second).is_none());
+    /// ```
+    #[inline]
+    pub fn get(&self, key: K) -> Option<Ref<'_, V>> {
+        let guard = self
+            .slot(key)
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if guard.is_some() {
+            Some(Ref { guard })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable guard to the value corresponding to the key, or
+    /// `None` if it isn't present.
+    ///
+    /// The slot of the key is write-locked for as long as the guard is held.
+    /// Accessing the same key from the current thread in the meantime, which
+    /// includes taking a [`snapshot`][ConcurrentMap::snapshot] or formatting
+    /// the map with [`Debug`][fmt::Debug], may deadlock or panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{ConcurrentMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = ConcurrentMap::new();
+    /// map.insert(MyKey::First, 42);
+    ///
+    /// if let Some(mut value) = map.get_mut(MyKey::First) {
+    ///     *value += 1;
+    /// }
+    ///
+    /// assert_eq!(*map.get(MyKey::First).unwrap(), 43);
+    /// ```
+    #[inline]
+    pub fn get_mut(&self, key: K) -> Option<RefMut<'_, V>> {
+        let guard = self
+            .slot(key)
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if guard.is_some() {
+            Some(RefMut { guard })
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{ConcurrentMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = ConcurrentMap::new();
+    /// map.insert(MyKey::First, 42);
+    ///
+    /// assert!(map.contains_key(MyKey::First));
+    /// assert!(!map.contains_key(MyKey::Second));
+    /// ```
+    #[inline]
+    pub fn contains_key(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Inserts a key-value pair into the map, returning the previous value if
+    /// one was present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{ConcurrentMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = ConcurrentMap::new();
+    /// assert_eq!(map.insert(MyKey::First, 1), None);
+    /// assert_eq!(map.insert(MyKey::First, 2), Some(1));
+    /// ```
+    #[inline]
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.update(key, |slot| slot.replace(value))
+    }
+
+    /// Removes a key from the map, returning the value at the key if it was
+    /// present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{ConcurrentMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = ConcurrentMap::new();
+    /// map.insert(MyKey::First, 1);
+    ///
+    /// assert_eq!(map.remove(MyKey::First), Some(1));
+    /// assert_eq!(map.remove(MyKey::First), None);
+    /// ```
+    #[inline]
+    pub fn remove(&self, key: K) -> Option<V> {
+        self.update(key, Option::take)
+    }
+
+    /// Call `f` with the slot of the key while it is write-locked, returning
+    /// whatever `f` returns.
+    ///
+    /// The slot is `None` if the key isn't present, and setting it to `None`
+    /// removes the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{ConcurrentMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = ConcurrentMap::new();
+    ///
+    /// for _ in 0..3 {
+    ///     map.update(MyKey::First, |value| *value.get_or_insert(0) += 1);
+    /// }
+    ///
+    /// assert_eq!(*map.get(MyKey::First).unwrap(), 3);
+    /// ```
+    #[inline]
+    pub fn update<F, R>(&self, key: K, f: F) -> R
+    where
+        F: FnOnce(&mut Option<V>) -> R,
+    {
+        let mut guard = self
+            .slot(key)
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        f(&mut guard)
+    }
+
+    /// Clears the map, removing all key-value pairs.
+    ///
+    /// Slots are cleared one at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{ConcurrentMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = ConcurrentMap::new();
+    /// map.insert(MyKey::First, 1);
+    /// map.clear();
+    ///
+    /// assert!(map.snapshot().is_empty());
+    /// ```
+    #[inline]
+    pub fn clear(&self) {
+        for slot in self.slots.as_ref() {
+            *slot.write().unwrap_or_else(PoisonError::into_inner) = None;
+        }
+    }
+
+    /// Clones the contents of the map into a [`Map`].
+    ///
+    /// Slots are read-locked and cloned one at a time, so the snapshot isn't
+    /// atomic: a slot which is modified while the snapshot is being taken may
+    /// or may not be reflected in it.
+    ///
+    /// Taking a snapshot may deadlock or panic if the current thread holds a
+    /// [`RefMut`] into the same map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{ConcurrentMap, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = ConcurrentMap::new();
+    /// map.insert(MyKey::Second, 2);
+    ///
+    /// let snapshot = map.snapshot();
+    /// assert_eq!(snapshot.get(MyKey::First), None);
+    /// assert_eq!(snapshot.get(MyKey::Second), Some(&2));
+    /// ```
+    pub fn snapshot(&self) -> Map<K, V>
+    where
+        V: Clone,
+    {
+        let mut map = Map::new();
+
+        for key in K::array_from_fn(|key| key) {
+            if let Some(value) = &*self.read(key.clone()) {
+                map.insert(key, value.clone());
+            }
+        }
+
+        map
+    }
+
+    #[inline]
+    fn read(&self, key: K) -> RwLockReadGuard<'_, Option<V>> {
+        self.slot(key)
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[inline]
+    fn slot(&self, key: K) -> &RwLock<Option<V>> {
+        &self.slots.as_ref()[key.index()]
+    }
+}
+
+impl<K, V> Default for ConcurrentMap<K, V>
+where
+    K: Finite,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The [`Debug`][fmt::Debug] implementation for a [`ConcurrentMap`], which
+/// read-locks one slot at a time.
+///
+/// Formatting may deadlock or panic if the current thread holds a [`RefMut`]
+/// into the same map.
+impl<K, V> fmt::Debug for ConcurrentMap<K, V>
+where
+    K: Finite + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut map = f.debug_map();
+
+        for key in K::array_from_fn(|key| key) {
+            if let Some(value) = &*self.read(key.clone()) {
+                map.entry(&key, value);
+            }
+        }
+
+        map.finish()
+    }
+}
+
+/// Construct a [`ConcurrentMap`] from the contents of a [`Map`].
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{ConcurrentMap, Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(MyKey::First, 1);
+///
+/// let map = ConcurrentMap::from(map);
+/// assert_eq!(*map.get(MyKey::First).unwrap(), 1);
+/// ```
+impl<K, V> From<Map<K, V>> for ConcurrentMap<K, V>
+where
+    K: Finite,
+{
+    #[inline]
+    fn from(mut map: Map<K, V>) -> Self {
+        ConcurrentMap {
+            slots: K::array_from_fn(|key| RwLock::new(map.remove(key))),
+        }
+    }
+}
+
+/// Convert a [`ConcurrentMap`] into a [`Map`] without cloning its values.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{ConcurrentMap, Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let map = ConcurrentMap::new();
+/// map.insert(MyKey::Second, 2);
+///
+/// let map = Map::from(map);
+/// assert!(map.into_iter().eq([(MyKey::Second, 2)]));
+/// ```
+impl<K, V> From<ConcurrentMap<K, V>> for Map<K, V>
+where
+    K: Finite,
+{
+    #[inline]
+    fn from(concurrent: ConcurrentMap<K, V>) -> Self {
+        let mut map = Map::new();
+
+        for (key, slot) in K::array_from_fn(|key| key)
+            .into_iter()
+            .zip(concurrent.slots)
+        {
+            if let Some(value) = slot.into_inner().unwrap_or_else(PoisonError::into_inner) {
+                map.insert(key, value);
+            }
+        }
+
+        map
+    }
+}
+
+/// A guard holding a read lock on a value in a [`ConcurrentMap`], returned
+/// by [`ConcurrentMap::get`].
+pub struct Ref<'a, V> {
+    guard: RwLockReadGuard<'a, Option<V>>,
+}
+
+impl<V> Deref for Ref<'_, V> {
+    type Target = V;
+
+    #[inline]
+    fn deref(&self) -> &V {
+        match &*self.guard {
+            Some(value) => value,
+            None => unreachable!("guard is only constructed for present values"),
+        }
+    }
+}
+
+impl<V> fmt::Debug for Ref<'_, V>
+where
+    V: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// A guard holding a write lock on a value in a [`ConcurrentMap`], returned
+/// by [`ConcurrentMap::get_mut`].
+pub struct RefMut<'a, V> {
+    guard: RwLockWriteGuard<'a, Option<V>>,
+}
+
+impl<V> Deref for RefMut<'_, V> {
+    type Target = V;
+
+    #[inline]
+    fn deref(&self) -> &V {
+        match &*self.guard {
+            Some(value) => value,
+            None => unreachable!("guard is only constructed for present values"),
+        }
+    }
+}
+
+impl<V> DerefMut for RefMut<'_, V> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut V {
+        match &mut *self.guard {
+            Some(value) => value,
+            None => unreachable!("guard is only constructed for present values"),
+        }
+    }
+}
+
+impl<V> fmt::Debug for RefMut<'_, V>
+where
+    V: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
@@ -0,0 +1,643 @@
+//! Contains the fixed [`Counter`] implementation.
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use crate::map;
+use crate::{Key, Map};
+
+/// The iterator produced by [`Counter::iter`].
+pub type Iter<'a, K> = map::Iter<'a, K, usize>;
+
+/// The iterator produced by [`Counter::into_iter`].
+pub type IntoIter<K> = map::IntoIter<K, usize>;
+
+/// A multiset counting how many times every key has been added, backed by a
+/// [`Map`] from keys to counts.
+///
+/// Keys which have a count of zero are not stored.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Counter, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Event {
+///     Click,
+///     Scroll,
+///     Key(u8),
+/// }
+///
+/// let counter: Counter<_> = [Event::Click, Event::Key(b'a'), Event::Click].into_iter().collect();
+///
+/// assert_eq!(counter.count(Event::Click), 2);
+/// assert_eq!(counter.count(Event::Scroll), 0);
+/// assert_eq!(counter.total(), 3);
+/// assert_eq!(counter.most_common(), Some((Event::Click, 2)));
+/// ```
+pub struct Counter<K>
+where
+    K: Key,
+{
+    map: Map<K, usize>,
+}
+
+impl<K> Counter<K>
+where
+    K: Key,
+{
+    /// Creates an empty [`Counter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Counter, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let counter = Counter::<MyKey>::new();
+    /// assert!(counter.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> Counter<K> {
+        Counter { map: Map::new() }
+    }
+
+    /// Add a single occurrence of the key, returning its new count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Counter, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut counter = Counter::new();
+    /// assert_eq!(counter.add(MyKey::First), 1);
+    /// assert_eq!(counter.add(MyKey::First), 2);
+    /// ```
+    #[inline]
+    pub fn add(&mut self, key: K) -> usize {
+        self.add_n(key, 1)
+    }
+
+    /// Add `n` occurrences of the key, returning its new count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Counter, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut counter = Counter::new();
+    /// assert_eq!(counter.add_n(MyKey::First, 3), 3);
+    /// assert_eq!(counter.add_n(MyKey::Second, 0), 0);
+    /// assert!(!counter.contains(MyKey::Second));
+    /// ```
+    #[inline]
+    pub fn add_n(&mut self, key: K, n: usize) -> usize {
+        if n == 0 {
+            return self.count(key);
+        }
+
+        let count = self.map.entry(key).or_default();
+        *count += n;
+        *count
+    }
+
+    /// Remove a single occurrence of the key, returning `true` if it was
+    /// present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Counter, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut counter = Counter::new();
+    /// counter.add_n(MyKey::First, 2);
+    ///
+    /// assert!(counter.remove(MyKey::First));
+    /// assert!(counter.remove(MyKey::First));
+    /// assert!(!counter.remove(MyKey::First));
+    /// assert!(counter.is_empty());
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, key: K) -> bool {
+        let Some(count) = self.map.get_mut(key.clone()) else {
+            return false;
+        };
+
+        if *count > 1 {
+            *count -= 1;
+        } else {
+            self.map.remove(key);
+        }
+
+        true
+    }
+
+    /// Get the number of occurrences of the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Counter, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut counter = Counter::new();
+    /// counter.add(MyKey::First);
+    ///
+    /// assert_eq!(counter.count(MyKey::First), 1);
+    /// assert_eq!(counter.count(MyKey::Second), 0);
+    /// ```
+    #[inline]
+    pub fn count(&self, key: K) -> usize {
+        self.map.get(key).copied().unwrap_or_default()
+    }
+
+    /// Test if the key has been added at least once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Counter, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut counter = Counter::new();
+    /// counter.add(MyKey::First);
+    ///
+    /// assert!(counter.contains(MyKey::First));
+    /// assert!(!counter.contains(MyKey::Second));
+    /// ```
+    #[inline]
+    pub fn contains(&self, key: K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Get the sum of all counts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Counter, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut counter = Counter::new();
+    /// counter.add_n(MyKey::First, 2);
+    /// counter.add(MyKey::Second);
+    ///
+    /// assert_eq!(counter.total(), 3);
+    /// ```
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.map.values().sum()
+    }
+
+    /// Get the number of distinct keys in the counter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Counter, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut counter = Counter::new();
+    /// counter.add_n(MyKey::First, 2);
+    /// assert_eq!(counter.len(), 1);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Test if the counter is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Counter, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut counter = Counter::new();
+    /// assert!(counter.is_empty());
+    /// counter.add(MyKey::First);
+    /// assert!(!counter.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Get the key with the highest count together with its count, or `None`
+    /// if the counter is empty.
+    ///
+    /// If several keys share the highest count, the first one in iteration
+    /// order is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Counter, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let mut counter = Counter::new();
+    /// assert_eq!(counter.most_common(), None);
+    ///
+    /// counter.add(MyKey::First);
+    /// counter.add_n(MyKey::Second, 3);
+    /// counter.add_n(MyKey::Third, 3);
+    ///
+    /// assert_eq!(counter.most_common(), Some((MyKey::Second, 3)));
+    /// ```
+    #[inline]
+    pub fn most_common(&self) -> Option<(K, usize)> {
+        let mut iter = self.map.iter();
+        let (mut key, mut count) = iter.next()?;
+
+        for (k, c) in iter {
+            if c > count {
+                key = k;
+                count = c;
+            }
+        }
+
+        Some((key, *count))
+    }
+
+    /// Construct the multiset union of two counters, which holds the larger of
+    /// the two counts for every key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Counter, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let a = Counter::from_iter([MyKey::First, MyKey::First, MyKey::Second]);
+    /// let b = Counter::from_iter([MyKey::First, MyKey::Third]);
+    ///
+    /// let c = a.union(b);
+    /// assert!(c.iter().eq([(MyKey::First, &2), (MyKey::Second, &1), (MyKey::Third, &1)]));
+    /// ```
+    #[inline]
+    pub fn union(self, other: Counter<K>) -> Counter<K> {
+        Counter {
+            map: self.map.union_with(other.map, |_, a, b| a.max(b)),
+        }
+    }
+
+    /// Construct the multiset intersection of two counters, which holds the
+    /// smaller of the two counts for every key present in both.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Counter, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let a = Counter::from_iter([MyKey::First, MyKey::First, MyKey::Second]);
+    /// let b = Counter::from_iter([MyKey::First, MyKey::Third]);
+    ///
+    /// let c = a.intersection(b);
+    /// assert!(c.iter().eq([(MyKey::First, &1)]));
+    /// ```
+    #[inline]
+    pub fn intersection(self, other: Counter<K>) -> Counter<K> {
+        Counter {
+            map: self.map.intersection_with(other.map, |_, a, b| a.min(b)),
+        }
+    }
+
+    /// An iterator visiting every key with its count. The iterator element
+    /// type is `(K, &'a usize)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Counter, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let counter = Counter::from_iter([MyKey::Second, MyKey::Second]);
+    /// assert!(counter.iter().eq([(MyKey::Second, &2)]));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K> {
+        self.map.iter()
+    }
+
+    /// Clear the counter, removing every key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Counter, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut counter = Counter::from_iter([MyKey::First]);
+    /// counter.clear();
+    /// assert!(counter.is_empty());
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Access the underlying map from keys to counts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Counter, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let counter = Counter::from_iter([MyKey::First]);
+    /// assert_eq!(counter.as_map().get(MyKey::First), Some(&1));
+    /// ```
+    #[inline]
+    pub fn as_map(&self) -> &Map<K, usize> {
+        &self.map
+    }
+}
+
+impl<K> Default for Counter<K>
+where
+    K: Key,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> Clone for Counter<K>
+where
+    K: Key,
+    K::MapStorage<usize>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<K> Copy for Counter<K>
+where
+    K: Key,
+    K::MapStorage<usize>: Copy,
+{
+}
+
+impl<K> fmt::Debug for Counter<K>
+where
+    K: Key + fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<K> PartialEq for Counter<K>
+where
+    K: Key,
+    K::MapStorage<usize>: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K> Eq for Counter<K>
+where
+    K: Key,
+    K::MapStorage<usize>: Eq,
+{
+}
+
+impl<K> Hash for Counter<K>
+where
+    K: Key,
+    K::MapStorage<usize>: Hash,
+{
+    #[inline]
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.map.hash(state);
+    }
+}
+
+impl<'a, K> IntoIterator for &'a Counter<K>
+where
+    K: Key,
+{
+    type Item = (K, &'a usize);
+    type IntoIter = Iter<'a, K>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Produce an owning iterator visiting every key with its count.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Counter, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let counter = Counter::from_iter([MyKey::First, MyKey::Second, MyKey::First]);
+/// assert!(counter.into_iter().eq([(MyKey::First, 2), (MyKey::Second, 1)]));
+/// ```
+impl<K> IntoIterator for Counter<K>
+where
+    K: Key,
+{
+    type Item = (K, usize);
+    type IntoIter = IntoIter<K>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_iter()
+    }
+}
+
+/// Count every key produced by the iterator.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Counter, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let counter: Counter<_> = [MyKey::First, MyKey::First].into_iter().collect();
+/// assert_eq!(counter.count(MyKey::First), 2);
+/// ```
+impl<K> FromIterator<K> for Counter<K>
+where
+    K: Key,
+{
+    #[inline]
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = K>,
+    {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+/// Count every key produced by the iterator, adding to the existing counts.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Counter, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let mut counter = Counter::from_iter([MyKey::First]);
+/// counter.extend([MyKey::First, MyKey::Second]);
+/// assert_eq!(counter.count(MyKey::First), 2);
+/// assert_eq!(counter.count(MyKey::Second), 1);
+/// ```
+impl<K> Extend<K> for Counter<K>
+where
+    K: Key,
+{
+    #[inline]
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = K>,
+    {
+        for key in iter {
+            self.add(key);
+        }
+    }
+}
+
+/// Convert a [`Counter`] into a [`Map`] from keys to counts.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Counter, Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let map = Map::from(Counter::from_iter([MyKey::Second]));
+/// assert_eq!(map.get(MyKey::Second), Some(&1));
+/// ```
+impl<K> From<Counter<K>> for Map<K, usize>
+where
+    K: Key,
+{
+    #[inline]
+    fn from(counter: Counter<K>) -> Self {
+        counter.map
+    }
+}
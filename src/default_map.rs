@@ -0,0 +1,703 @@
+//! Contains the fixed [`DefaultMap`] implementation.
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Index, IndexMut};
+
+use crate::total_map::{IntoIter, Iter, IterMut, Keys, Values, ValuesMut};
+use crate::{Finite, Map, TotalMap};
+
+/// A fixed map over a [`Finite`] key where an absent value is the same as
+/// [`Default::default`].
+///
+/// Every key holds a value, so lookups can't fail. Values which are equal to
+/// the default are treated as absent when comparing, hashing, serializing and
+/// converting into a [`Map`].
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{DefaultMap, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     North,
+///     South,
+///     East,
+///     West,
+/// }
+///
+/// let mut map = DefaultMap::<MyKey, u32>::new();
+/// map[MyKey::North] += 1;
+/// *map.get_mut(MyKey::West) += 4;
+///
+/// assert_eq!(map[MyKey::North], 1);
+/// assert_eq!(*map.get(MyKey::South), 0);
+/// assert_eq!(format!("{:?}", map), "{North: 1, West: 4}");
+/// ```
+pub struct DefaultMap<K, V>
+where
+    K: Finite,
+{
+    inner: TotalMap<K, V>,
+}
+
+impl<K, V> DefaultMap<K, V>
+where
+    K: Finite,
+    V: Default,
+{
+    /// Creates a [`DefaultMap`] where every value is the default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{DefaultMap, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = DefaultMap::<MyKey, u32>::new();
+    /// assert_eq!(map[MyKey::First], 0);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> DefaultMap<K, V> {
+        DefaultMap {
+            inner: TotalMap::default(),
+        }
+    }
+
+    /// Resets the value corresponding to the key to the default, returning
+    /// the old value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{DefaultMap, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = DefaultMap::<MyKey, u32>::new();
+    /// map[MyKey::First] = 1;
+    ///
+    /// assert_eq!(map.remove(MyKey::First), 1);
+    /// assert_eq!(map[MyKey::First], 0);
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, key: K) -> V {
+        core::mem::take(self.inner.get_mut(key))
+    }
+
+    /// Resets every value to the default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{DefaultMap, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = DefaultMap::<MyKey, u32>::new();
+    /// map[MyKey::First] = 1;
+    /// map[MyKey::Second] = 2;
+    ///
+    /// map.clear();
+    /// assert_eq!(map, DefaultMap::new());
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        for value in self.inner.values_mut() {
+            *value = V::default();
+        }
+    }
+}
+
+impl<K, V> DefaultMap<K, V>
+where
+    K: Finite,
+{
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{DefaultMap, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = DefaultMap::<MyKey, u32>::new();
+    /// map[MyKey::Second] = 2;
+    ///
+    /// assert_eq!(*map.get(MyKey::First), 0);
+    /// assert_eq!(*map.get(MyKey::Second), 2);
+    /// ```
+    #[inline]
+    pub fn get(&self, key: K) -> &V {
+        self.inner.get(key)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{DefaultMap, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = DefaultMap::<MyKey, u32>::new();
+    /// *map.get_mut(MyKey::Second) += 1;
+    /// *map.get_mut(MyKey::Second) += 1;
+    ///
+    /// assert_eq!(*map.get(MyKey::Second), 2);
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, key: K) -> &mut V {
+        self.inner.get_mut(key)
+    }
+
+    /// Replaces the value corresponding to the key, returning the old value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{DefaultMap, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = DefaultMap::<MyKey, u32>::new();
+    /// assert_eq!(map.insert(MyKey::First, 1), 0);
+    /// assert_eq!(map.insert(MyKey::First, 2), 1);
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> V {
+        self.inner.insert(key, value)
+    }
+
+    /// An iterator visiting every key-value pair in index order, including
+    /// the ones holding the default value. The iterator element type is `(K,
+    /// &'a V)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{DefaultMap, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = DefaultMap::<MyKey, u32>::new();
+    /// map[MyKey::Second] = 2;
+    ///
+    /// assert!(map.iter().eq([(MyKey::First, &0), (MyKey::Second, &2)]));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.inner.iter()
+    }
+
+    /// An iterator visiting every key in index order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{DefaultMap, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = DefaultMap::<MyKey, u32>::new();
+    /// assert!(map.keys().eq([MyKey::First, MyKey::Second]));
+    /// ```
+    #[inline]
+    pub fn keys(&self) -> Keys<K> {
+        self.inner.keys()
+    }
+
+    /// An iterator visiting every value in index order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{DefaultMap, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = DefaultMap::<MyKey, u32>::new();
+    /// map[MyKey::First] = 1;
+    ///
+    /// assert!(map.values().copied().eq([1, 0]));
+    /// ```
+    #[inline]
+    pub fn values(&self) -> Values<'_, V> {
+        self.inner.values()
+    }
+
+    /// An iterator visiting every key-value pair in index order, with mutable
+    /// references to the values. The iterator element type is `(K, &'a mut
+    /// V)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{DefaultMap, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = DefaultMap::<MyKey, u32>::new();
+    ///
+    /// for (key, value) in map.iter_mut() {
+    ///     *value = key as u32 * 10;
+    /// }
+    ///
+    /// assert!(map.values().copied().eq([0, 10]));
+    /// ```
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.inner.iter_mut()
+    }
+
+    /// An iterator visiting every value mutably in index order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{DefaultMap, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = DefaultMap::<MyKey, u32>::new();
+    ///
+    /// for value in map.values_mut() {
+    ///     *value += 2;
+    /// }
+    ///
+    /// assert!(map.values().copied().eq([2, 2]));
+    /// ```
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, V> {
+        self.inner.values_mut()
+    }
+}
+
+impl<K, V> Default for DefaultMap<K, V>
+where
+    K: Finite,
+    V: Default,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Clone for DefaultMap<K, V>
+where
+    K: Finite,
+    V: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+/// The [`Debug`][fmt::Debug] implementation for a [`DefaultMap`], which only
+/// includes values that differ from the default.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{DefaultMap, Key};
+///
+/// #[derive(Debug, Clone, Copy, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let mut map = DefaultMap::<MyKey, u32>::new();
+/// assert_eq!(format!("{:?}", map), "{}");
+///
+/// map[MyKey::Second] = 2;
+/// assert_eq!(format!("{:?}", map), "{Second: 2}");
+/// ```
+impl<K, V> fmt::Debug for DefaultMap<K, V>
+where
+    K: Finite + fmt::Debug,
+    V: Default + PartialEq + fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let default = V::default();
+
+        f.debug_map()
+            .entries(self.iter().filter(|(_, value)| **value != default))
+            .finish()
+    }
+}
+
+/// Since absent values are stored as the default, two maps are equal if they
+/// hold the same non-default values.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{DefaultMap, Key};
+///
+/// #[derive(Debug, Clone, Copy, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let mut a = DefaultMap::<MyKey, u32>::new();
+/// let b = DefaultMap::<MyKey, u32>::new();
+///
+/// a[MyKey::First] = 1;
+/// assert_ne!(a, b);
+/// a[MyKey::First] = 0;
+/// assert_eq!(a, b);
+/// ```
+impl<K, V> PartialEq for DefaultMap<K, V>
+where
+    K: Finite,
+    V: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<K, V> Eq for DefaultMap<K, V>
+where
+    K: Finite,
+    V: Eq,
+{
+}
+
+impl<K, V> Hash for DefaultMap<K, V>
+where
+    K: Finite,
+    V: Hash,
+{
+    #[inline]
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.inner.hash(state);
+    }
+}
+
+/// Default maps are ordered by their values in key order.
+impl<K, V> PartialOrd for DefaultMap<K, V>
+where
+    K: Finite,
+    V: PartialOrd,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.inner.partial_cmp(&other.inner)
+    }
+}
+
+impl<K, V> Ord for DefaultMap<K, V>
+where
+    K: Finite,
+    V: Ord,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.inner.cmp(&other.inner)
+    }
+}
+
+/// [`Index`] implementation for a [`DefaultMap`], which can't fail since every
+/// key holds a value.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{DefaultMap, Key};
+///
+/// #[derive(Debug, Clone, Copy, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let mut map = DefaultMap::<MyKey, u32>::new();
+/// map[MyKey::Second] += 2;
+///
+/// assert_eq!(map[MyKey::First], 0);
+/// assert_eq!(map[MyKey::Second], 2);
+/// ```
+impl<K, V> Index<K> for DefaultMap<K, V>
+where
+    K: Finite,
+{
+    type Output = V;
+
+    #[inline]
+    fn index(&self, key: K) -> &V {
+        self.get(key)
+    }
+}
+
+impl<K, V> IndexMut<K> for DefaultMap<K, V>
+where
+    K: Finite,
+{
+    #[inline]
+    fn index_mut(&mut self, key: K) -> &mut V {
+        self.get_mut(key)
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a DefaultMap<K, V>
+where
+    K: Finite,
+{
+    type Item = (K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut DefaultMap<K, V>
+where
+    K: Finite,
+{
+    type Item = (K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Produce an owning iterator visiting every key-value pair of the
+/// [`DefaultMap`] in index order.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{DefaultMap, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let mut map = DefaultMap::<MyKey, u32>::new();
+/// map[MyKey::First] = 1;
+///
+/// assert!(map.into_iter().eq([(MyKey::First, 1), (MyKey::Second, 0)]));
+/// ```
+impl<K, V> IntoIterator for DefaultMap<K, V>
+where
+    K: Finite,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter()
+    }
+}
+
+/// Convert a [`Map`] into a [`DefaultMap`], where absent keys hold the
+/// default value.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{DefaultMap, Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(MyKey::Second, 2);
+///
+/// let map = DefaultMap::from(map);
+/// assert_eq!(map[MyKey::First], 0);
+/// assert_eq!(map[MyKey::Second], 2);
+/// ```
+impl<K, V> From<Map<K, V>> for DefaultMap<K, V>
+where
+    K: Finite,
+    V: Default,
+{
+    #[inline]
+    fn from(map: Map<K, V>) -> Self {
+        let mut output = Self::new();
+
+        for (key, value) in map {
+            output.insert(key, value);
+        }
+
+        output
+    }
+}
+
+/// Convert a [`DefaultMap`] into a [`Map`], which only contains the values
+/// that differ from the default.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{DefaultMap, Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let mut map = DefaultMap::<MyKey, u32>::new();
+/// map[MyKey::Second] = 2;
+///
+/// let map = Map::from(map);
+/// assert_eq!(map.get(MyKey::First), None);
+/// assert_eq!(map.get(MyKey::Second), Some(&2));
+/// ```
+impl<K, V> From<DefaultMap<K, V>> for Map<K, V>
+where
+    K: Finite,
+    V: Default + PartialEq,
+{
+    #[inline]
+    fn from(map: DefaultMap<K, V>) -> Self {
+        let default = V::default();
+        map.into_iter()
+            .filter(|(_, value)| *value != default)
+            .collect()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K, V> serde::Serialize for DefaultMap<K, V>
+where
+    K: Finite + serde::Serialize,
+    V: Default + PartialEq + serde::Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap as _;
+
+        let default = V::default();
+        let len = self.values().filter(|value| **value != default).count();
+        let mut map = serializer.serialize_map(Some(len))?;
+
+        for (k, v) in self {
+            if *v != default {
+                map.serialize_entry(&k, v)?;
+            }
+        }
+
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> serde::de::Deserialize<'de> for DefaultMap<K, V>
+where
+    K: Finite + serde::de::Deserialize<'de>,
+    V: Default + serde::Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct MapVisitor<K, V>(core::marker::PhantomData<(K, V)>);
+
+        impl<'de, K, V> serde::de::Visitor<'de> for MapVisitor<K, V>
+        where
+            K: Finite + serde::de::Deserialize<'de>,
+            V: Default + serde::Deserialize<'de>,
+        {
+            type Value = DefaultMap<K, V>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            #[inline]
+            fn visit_map<T>(self, mut visitor: T) -> Result<Self::Value, T::Error>
+            where
+                T: serde::de::MapAccess<'de>,
+            {
+                let mut map = DefaultMap::new();
+
+                while let Some((key, value)) = visitor.next_entry()? {
+                    map.insert(key, value);
+                }
+
+                Ok(map)
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor(core::marker::PhantomData))
+    }
+}
@@ -0,0 +1,72 @@
+use core::iter::FusedIterator;
+
+/// Iterator adapter which tracks the number of remaining items, so that the
+/// wrapped iterator reports an exact [`size_hint`][Iterator::size_hint] and
+/// implements [`ExactSizeIterator`] and [`FusedIterator`].
+///
+/// The length must be the exact number of items produced by the wrapped
+/// iterator, which storage knows up front.
+#[derive(Clone)]
+pub struct ExactIter<I> {
+    iter: I,
+    len: usize,
+}
+
+impl<I> ExactIter<I> {
+    /// Wrap an iterator which produces exactly `len` items.
+    #[inline]
+    pub fn new(iter: I, len: usize) -> Self {
+        Self { iter, len }
+    }
+}
+
+impl<I> Iterator for ExactIter<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let item = self.iter.next()?;
+        self.len -= 1;
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<I> DoubleEndedIterator for ExactIter<I>
+where
+    I: DoubleEndedIterator,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let item = self.iter.next_back()?;
+        self.len -= 1;
+        Some(item)
+    }
+}
+
+impl<I> ExactSizeIterator for ExactIter<I>
+where
+    I: Iterator,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<I> FusedIterator for ExactIter<I> where I: Iterator {}
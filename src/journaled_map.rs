@@ -0,0 +1,959 @@
+//! Contains the fixed [`JournaledMap`] implementation.
+
+use core::fmt;
+
+use std::vec::Vec;
+
+use crate::map::{Iter, Keys, MapStorage, Values};
+use crate::{map, Key, Map};
+
+/// A position in the journal of a [`JournaledMap`], returned by
+/// [`JournaledMap::checkpoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Checkpoint {
+    len: usize,
+    seq: usize,
+}
+
+/// The previous value of a key, together with a sequence number which is
+/// unique for the lifetime of the map.
+#[derive(Clone)]
+struct Record<K, V> {
+    key: K,
+    previous: Option<V>,
+    seq: usize,
+}
+
+#[derive(Clone)]
+struct Journal<K, V> {
+    records: Vec<Record<K, V>>,
+    // sequence number of the empty journal since the last commit
+    base: usize,
+    // next sequence number to hand out
+    next: usize,
+}
+
+impl<K, V> Journal<K, V> {
+    #[inline]
+    const fn new() -> Self {
+        Self {
+            records: Vec::new(),
+            base: 0,
+            next: 1,
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, key: K, previous: Option<V>) {
+        let seq = self.next;
+        self.next = self.next.wrapping_add(1);
+        self.records.push(Record { key, previous, seq });
+    }
+
+    /// The sequence number identifying the first `len` records, if there are
+    /// at least that many.
+    #[inline]
+    fn seq_at(&self, len: usize) -> Option<usize> {
+        match len.checked_sub(1) {
+            Some(index) => Some(self.records.get(index)?.seq),
+            None => Some(self.base),
+        }
+    }
+}
+
+/// A [`Map`] which journals the previous value of every key it modifies, so
+/// that modifications can be rolled back to an earlier [`Checkpoint`].
+///
+/// A checkpoint stays valid until the map is rolled back to a checkpoint
+/// taken before it, or until the journal is discarded with
+/// [`commit`][JournaledMap::commit].
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{JournaledMap, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Property {
+///     Color,
+///     Width,
+///     Height,
+/// }
+///
+/// let mut props = JournaledMap::new();
+/// props.insert(Property::Width, 10);
+///
+/// let before_resize = props.checkpoint();
+/// props.insert(Property::Width, 20);
+/// props.insert(Property::Height, 30);
+/// *props.entry(Property::Width).or_default() += 1;
+///
+/// assert_eq!(props.get(Property::Width), Some(&21));
+///
+/// assert!(props.rollback_to(before_resize));
+///
+/// assert_eq!(props.get(Property::Width), Some(&10));
+/// assert_eq!(props.get(Property::Height), None);
+/// ```
+pub struct JournaledMap<K, V>
+where
+    K: Key,
+{
+    map: Map<K, V>,
+    journal: Journal<K, V>,
+}
+
+impl<K, V> JournaledMap<K, V>
+where
+    K: Key,
+{
+    /// Creates an empty [`JournaledMap`] with an empty journal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = JournaledMap::<MyKey, u32>::new();
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> JournaledMap<K, V> {
+        JournaledMap {
+            map: Map::new(),
+            journal: Journal::new(),
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    /// map.insert(MyKey::First, 42);
+    ///
+    /// assert_eq!(map.get(MyKey::First), Some(&42));
+    /// assert_eq!(map.get(MyKey::Second), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, key: K) -> Option<&V> {
+        self.map.get(key)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    /// map.insert(MyKey::First, 42);
+    ///
+    /// assert!(map.contains_key(MyKey::First));
+    /// assert!(!map.contains_key(MyKey::Second));
+    /// ```
+    #[inline]
+    pub fn contains_key(&self, key: K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Returns the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    /// map.insert(MyKey::First, 42);
+    ///
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the map contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    /// assert!(map.is_empty());
+    ///
+    /// map.insert(MyKey::First, 42);
+    /// assert!(!map.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    /// map.insert(MyKey::Second, 2);
+    ///
+    /// assert!(map.iter().eq([(MyKey::Second, &2)]));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.map.iter()
+    }
+
+    /// An iterator visiting all keys in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    /// map.insert(MyKey::Second, 2);
+    ///
+    /// assert!(map.keys().eq([MyKey::Second]));
+    /// ```
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        self.map.keys()
+    }
+
+    /// An iterator visiting all values in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    /// map.insert(MyKey::Second, 2);
+    ///
+    /// assert!(map.values().copied().eq([2]));
+    /// ```
+    #[inline]
+    pub fn values(&self) -> Values<'_, K, V> {
+        self.map.values()
+    }
+
+    /// Inserts a key-value pair into the map, journaling and returning the
+    /// previous value of the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    /// let checkpoint = map.checkpoint();
+    ///
+    /// assert_eq!(map.insert(MyKey::First, 1), None);
+    /// assert_eq!(map.insert(MyKey::First, 2), Some(1));
+    ///
+    /// map.rollback_to(checkpoint);
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+    where
+        V: Clone,
+    {
+        let previous = self.map.insert(key.clone(), value);
+        self.journal.push(key, previous.clone());
+        previous
+    }
+
+    /// Removes a key from the map, journaling and returning its previous
+    /// value if it was present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    /// map.insert(MyKey::First, 1);
+    ///
+    /// let checkpoint = map.checkpoint();
+    /// assert_eq!(map.remove(MyKey::First), Some(1));
+    ///
+    /// map.rollback_to(checkpoint);
+    /// assert_eq!(map.get(MyKey::First), Some(&1));
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, key: K) -> Option<V>
+    where
+        V: Clone,
+    {
+        let previous = self.map.remove(key.clone())?;
+        self.journal.push(key, Some(previous.clone()));
+        Some(previous)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// The current value is journaled if the key is present, whether or not
+    /// it is actually changed through the reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    /// map.insert(MyKey::First, 1);
+    ///
+    /// let checkpoint = map.checkpoint();
+    /// *map.get_mut(MyKey::First).unwrap() += 1;
+    /// assert_eq!(map.get(MyKey::First), Some(&2));
+    ///
+    /// map.rollback_to(checkpoint);
+    /// assert_eq!(map.get(MyKey::First), Some(&1));
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, key: K) -> Option<&mut V>
+    where
+        V: Clone,
+    {
+        let value = self.map.get_mut(key.clone())?;
+        self.journal.push(key, Some(value.clone()));
+        Some(value)
+    }
+
+    /// Gets the given key's corresponding [`Entry`] in the map for in-place
+    /// manipulation.
+    ///
+    /// The previous state of the key is journaled the first time the entry is
+    /// modified, or when a mutable reference to its value is handed out.
+    /// Looking at an entry without changing it leaves the journal untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    /// let checkpoint = map.checkpoint();
+    ///
+    /// map.entry(MyKey::First).or_insert_with(Vec::new).push(1);
+    /// assert_eq!(map.get(MyKey::First), Some(&vec![1]));
+    ///
+    /// map.rollback_to(checkpoint);
+    /// assert_eq!(map.get(MyKey::First), None);
+    /// ```
+    #[inline]
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V>
+    where
+        V: Clone,
+    {
+        let journal = &mut self.journal;
+
+        match self.map.entry(key) {
+            map::Entry::Occupied(entry) => Entry::Occupied(OccupiedEntry {
+                entry,
+                journal,
+                journaled: false,
+            }),
+            map::Entry::Vacant(entry) => Entry::Vacant(VacantEntry { entry, journal }),
+        }
+    }
+
+    /// Returns a checkpoint which the map can later be rolled back to with
+    /// [`rollback_to`][JournaledMap::rollback_to].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    ///
+    /// let empty = map.checkpoint();
+    /// map.insert(MyKey::First, 1);
+    /// let first = map.checkpoint();
+    /// map.insert(MyKey::Second, 2);
+    ///
+    /// assert!(map.rollback_to(first));
+    /// assert_eq!(map.len(), 1);
+    ///
+    /// assert!(map.rollback_to(empty));
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn checkpoint(&self) -> Checkpoint {
+        let seq = match self.journal.records.last() {
+            Some(record) => record.seq,
+            None => self.journal.base,
+        };
+
+        Checkpoint {
+            len: self.journal.records.len(),
+            seq,
+        }
+    }
+
+    /// Undo every modification made since the checkpoint was taken, returning
+    /// `true` if the map was rolled back.
+    ///
+    /// Returns `false` without modifying the map if the checkpoint is no
+    /// longer valid, which is the case if the map has since been rolled back
+    /// to an earlier checkpoint or the journal has been discarded with
+    /// [`commit`][JournaledMap::commit]. The checkpoint itself stays valid,
+    /// so the map can be rolled back to it again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    /// map.insert(MyKey::First, 1);
+    ///
+    /// let checkpoint = map.checkpoint();
+    /// map.insert(MyKey::First, 2);
+    /// let later = map.checkpoint();
+    /// map.insert(MyKey::Second, 3);
+    ///
+    /// assert!(map.rollback_to(checkpoint));
+    /// assert_eq!(map.get(MyKey::First), Some(&1));
+    /// assert_eq!(map.get(MyKey::Second), None);
+    ///
+    /// // Rolling back invalidated the later checkpoint.
+    /// assert!(!map.rollback_to(later));
+    /// assert_eq!(map.get(MyKey::First), Some(&1));
+    /// ```
+    pub fn rollback_to(&mut self, checkpoint: Checkpoint) -> bool {
+        if self.journal.seq_at(checkpoint.len) != Some(checkpoint.seq) {
+            return false;
+        }
+
+        for Record { key, previous, .. } in self.journal.records.drain(checkpoint.len..).rev() {
+            match previous {
+                Some(value) => {
+                    self.map.insert(key, value);
+                }
+                None => {
+                    self.map.remove(key);
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Discard the journal, making every modification so far permanent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    /// let checkpoint = map.checkpoint();
+    ///
+    /// map.insert(MyKey::First, 1);
+    /// map.commit();
+    ///
+    /// assert!(!map.rollback_to(checkpoint));
+    /// assert_eq!(map.get(MyKey::First), Some(&1));
+    /// ```
+    #[inline]
+    pub fn commit(&mut self) {
+        self.journal.records.clear();
+        self.journal.base = self.journal.next;
+        self.journal.next = self.journal.next.wrapping_add(1);
+    }
+
+    /// Returns a reference to the underlying [`Map`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    /// map.insert(MyKey::First, 1);
+    ///
+    /// assert_eq!(map.as_map().get(MyKey::First), Some(&1));
+    /// ```
+    #[inline]
+    pub fn as_map(&self) -> &Map<K, V> {
+        &self.map
+    }
+}
+
+impl<K, V> Default for JournaledMap<K, V>
+where
+    K: Key,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Clone for JournaledMap<K, V>
+where
+    K: Key,
+    K::MapStorage<V>: Clone,
+    V: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+            journal: self.journal.clone(),
+        }
+    }
+}
+
+/// The [`Debug`][fmt::Debug] implementation for a [`JournaledMap`], which
+/// only includes the current contents of the map.
+impl<K, V> fmt::Debug for JournaledMap<K, V>
+where
+    K: Key + fmt::Debug,
+    V: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+/// Construct a [`JournaledMap`] from a [`Map`] with an empty journal.
+impl<K, V> From<Map<K, V>> for JournaledMap<K, V>
+where
+    K: Key,
+{
+    #[inline]
+    fn from(map: Map<K, V>) -> Self {
+        JournaledMap {
+            map,
+            journal: Journal::new(),
+        }
+    }
+}
+
+/// Convert a [`JournaledMap`] into its underlying [`Map`], discarding the
+/// journal.
+impl<K, V> From<JournaledMap<K, V>> for Map<K, V>
+where
+    K: Key,
+{
+    #[inline]
+    fn from(map: JournaledMap<K, V>) -> Self {
+        map.map
+    }
+}
+
+/// A view into a single entry in a [`JournaledMap`], which may either be
+/// vacant or occupied.
+///
+/// This enum is constructed from the [`entry`][JournaledMap::entry] method on
+/// [`JournaledMap`]. Its variants implement the [`OccupiedEntry`] and
+/// [`VacantEntry`] traits, which journal the previous state of the key the
+/// first time the entry is modified.
+///
+/// [`OccupiedEntry`]: crate::map::OccupiedEntry
+/// [`VacantEntry`]: crate::map::VacantEntry
+pub enum Entry<'a, K, V>
+where
+    K: Key,
+{
+    /// An occupied entry.
+    Occupied(OccupiedEntry<'a, K, V>),
+    /// A vacant entry.
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: Key,
+    V: Clone,
+{
+    /// Ensures a value is in the entry by inserting the default if empty,
+    /// and returns a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    ///
+    /// *map.entry(MyKey::First).or_insert(3) *= 2;
+    /// assert_eq!(map.get(MyKey::First), Some(&6));
+    /// ```
+    #[inline]
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => map::OccupiedEntry::into_mut(entry),
+            Entry::Vacant(entry) => map::VacantEntry::insert(entry, default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default
+    /// function if empty, and returns a mutable reference to the value in the
+    /// entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    ///
+    /// map.entry(MyKey::First).or_insert_with(|| 3);
+    /// assert_eq!(map.get(MyKey::First), Some(&3));
+    /// ```
+    #[inline]
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => map::OccupiedEntry::into_mut(entry),
+            Entry::Vacant(entry) => map::VacantEntry::insert(entry, default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result of
+    /// the default function called with the key, and returns a mutable
+    /// reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    ///
+    /// map.entry(MyKey::Second).or_insert_with_key(|key| key as u32);
+    /// assert_eq!(map.get(MyKey::Second), Some(&1));
+    /// ```
+    #[inline]
+    pub fn or_insert_with_key<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce(K) -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => map::OccupiedEntry::into_mut(entry),
+            Entry::Vacant(entry) => {
+                let value = default(map::VacantEntry::key(&entry));
+                map::VacantEntry::insert(entry, value)
+            }
+        }
+    }
+
+    /// Returns a copy of this entry's key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::<MyKey, u32>::new();
+    /// assert_eq!(map.entry(MyKey::First).key(), MyKey::First);
+    /// ```
+    #[inline]
+    pub fn key(&self) -> K {
+        match self {
+            Entry::Occupied(entry) => map::OccupiedEntry::key(entry),
+            Entry::Vacant(entry) => map::VacantEntry::key(entry),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::new();
+    ///
+    /// map.entry(MyKey::First).and_modify(|e| *e += 1).or_insert(42);
+    /// assert_eq!(map.get(MyKey::First), Some(&42));
+    ///
+    /// map.entry(MyKey::First).and_modify(|e| *e += 1).or_insert(42);
+    /// assert_eq!(map.get(MyKey::First), Some(&43));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(map::OccupiedEntry::get_mut(&mut entry));
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the default value if
+    /// empty, and returns a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{JournaledMap, Key};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = JournaledMap::<MyKey, u32>::new();
+    ///
+    /// map.entry(MyKey::First).or_default();
+    /// assert_eq!(map.get(MyKey::First), Some(&0));
+    /// ```
+    #[inline]
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+}
+
+/// A view into an occupied entry in a [`JournaledMap`]. It is part of the
+/// [`Entry`] enum.
+///
+/// The value of the key is journaled the first time it is modified or
+/// mutably borrowed through the entry.
+pub struct OccupiedEntry<'a, K, V>
+where
+    K: Key,
+{
+    entry: <K::MapStorage<V> as MapStorage<K, V>>::Occupied<'a>,
+    journal: &'a mut Journal<K, V>,
+    journaled: bool,
+}
+
+impl<K, V> OccupiedEntry<'_, K, V>
+where
+    K: Key,
+    V: Clone,
+{
+    #[inline]
+    fn journal(&mut self) {
+        if !self.journaled {
+            let key = map::OccupiedEntry::key(&self.entry);
+            let previous = map::OccupiedEntry::get(&self.entry).clone();
+            self.journal.push(key, Some(previous));
+            self.journaled = true;
+        }
+    }
+}
+
+impl<'a, K, V> map::OccupiedEntry<'a, K, V> for OccupiedEntry<'a, K, V>
+where
+    K: Key,
+    V: Clone,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.entry.key()
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.entry.get()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.journal();
+        self.entry.get_mut()
+    }
+
+    #[inline]
+    fn into_mut(mut self) -> &'a mut V {
+        self.journal();
+        self.entry.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.journal();
+        self.entry.insert(value)
+    }
+
+    #[inline]
+    fn remove(mut self) -> V {
+        self.journal();
+        self.entry.remove()
+    }
+}
+
+/// A view into a vacant entry in a [`JournaledMap`]. It is part of the
+/// [`Entry`] enum.
+///
+/// The absence of the key is journaled when a value is inserted.
+pub struct VacantEntry<'a, K, V>
+where
+    K: Key,
+{
+    entry: <K::MapStorage<V> as MapStorage<K, V>>::Vacant<'a>,
+    journal: &'a mut Journal<K, V>,
+}
+
+impl<'a, K, V> map::VacantEntry<'a, K, V> for VacantEntry<'a, K, V>
+where
+    K: Key,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.entry.key()
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        self.journal.push(self.entry.key(), None);
+        self.entry.insert(value)
+    }
+}
@@ -1,11 +1,30 @@
 //! Module for the trait to define a `Key`.
 
+#[cfg(feature = "hashbrown")]
+use alloc::boxed::Box;
+#[cfg(feature = "hashbrown")]
+use alloc::rc::Rc;
+#[cfg(feature = "hashbrown")]
+use alloc::string::String;
+#[cfg(feature = "hashbrown")]
+use alloc::sync::Arc;
+use core::cmp::Ordering;
+use core::convert::Infallible;
+#[cfg(feature = "hashbrown")]
+use core::hash::Hash;
+
 #[cfg(feature = "hashbrown")]
 use crate::map::storage::HashbrownMapStorage;
-use crate::map::storage::{BooleanMapStorage, MapStorage, OptionMapStorage, SingletonMapStorage};
+use crate::map::storage::{
+    Bits, BooleanMapStorage, ByteMapStorage, EmptyMapStorage, MapStorage, OptionMapStorage,
+    OrderingMapStorage, ProductMapStorage, ResultMapStorage, SingletonMapStorage,
+};
 #[cfg(feature = "hashbrown")]
 use crate::set::storage::HashbrownSetStorage;
-use crate::set::storage::{BooleanSetStorage, OptionSetStorage, SetStorage, SingletonSetStorage};
+use crate::set::storage::{
+    BooleanSetStorage, ByteSetStorage, EmptySetStorage, OptionSetStorage, OrderingSetStorage,
+    ProductSetStorage, ResultSetStorage, SetStorage, SingletonSetStorage,
+};
 
 /// The trait for a key that can be used to store values in a
 /// [`Map`][crate::Set] or [`Set`][crate::Set].
@@ -41,6 +60,28 @@ use crate::set::storage::{BooleanSetStorage, OptionSetStorage, SetStorage, Singl
 /// }
 /// ```
 ///
+/// Tuples of keys, [`Result`], [`Ordering`] and [`Infallible`] are keys as
+/// well, so ad-hoc compound keys can be used without defining a wrapper enum:
+///
+/// ```
+/// use core::cmp::Ordering;
+///
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert((MyKey::First, Ordering::Less), 1);
+/// map.insert((MyKey::Second, Ordering::Equal), 2);
+///
+/// assert_eq!(map.get((MyKey::First, Ordering::Less)), Some(&1));
+/// assert_eq!(map.get((MyKey::First, Ordering::Equal)), None);
+/// ```
+///
 /// Some composite keys require dynamic storage since they can inhabit a large
 /// number of values, and preferrably should be avoided in favor of using a
 /// `HashMap` directly. But if you absolutely have to you can enable the `map`
@@ -124,7 +165,16 @@ use crate::set::storage::{BooleanSetStorage, OptionSetStorage, SetStorage, Singl
 /// [`Map`]: crate::Map
 /// [`Set`]: crate::Set
 /// [key-derive]: derive@crate::Key
-pub trait Key: Copy {
+pub trait Key: Clone {
+    /// The borrowed form of the key, which is used to look up values without
+    /// constructing an owned key through methods such as
+    /// [`Map::get_by`][crate::Map::get_by].
+    ///
+    /// Keys which are [`Copy`] use themselves as their borrowed form, while
+    /// string keys such as `String` are borrowed as `&str`. Enums can generate
+    /// a borrowed form through [`#[key(borrow)]`][derive@crate::Key].
+    type Ref<'a>: Clone;
+
     /// The [`Map`][crate::Map] storage implementation to use for the key
     /// implementing this trait.
     type MapStorage<V>: MapStorage<Self, V>;
@@ -135,6 +185,7 @@ pub trait Key: Copy {
 }
 
 impl Key for bool {
+    type Ref<'a> = Self;
     type MapStorage<V> = BooleanMapStorage<V>;
     type SetStorage = BooleanSetStorage;
 }
@@ -143,23 +194,411 @@ impl<K> Key for Option<K>
 where
     K: Key,
 {
+    type Ref<'a> = Option<K::Ref<'a>>;
     type MapStorage<V> = OptionMapStorage<K, V>;
     type SetStorage = OptionSetStorage<K>;
 }
 
+impl<T, E> Key for Result<T, E>
+where
+    T: Key,
+    E: Key,
+{
+    type Ref<'a> = Result<T::Ref<'a>, E::Ref<'a>>;
+    type MapStorage<V> = ResultMapStorage<T, E, V>;
+    type SetStorage = ResultSetStorage<T, E>;
+}
+
+impl Key for Ordering {
+    type Ref<'a> = Self;
+    type MapStorage<V> = OrderingMapStorage<V>;
+    type SetStorage = OrderingSetStorage;
+}
+
+impl Key for Infallible {
+    type Ref<'a> = Self;
+    type MapStorage<V> = EmptyMapStorage<V>;
+    type SetStorage = EmptySetStorage;
+}
+
+/// A [`Key`] which can only inhabit a finite number of values, each of which
+/// has a dense index.
+///
+/// This is implemented by the [`Key` derive][derive@crate::Key] for enums
+/// which only have unit variants, for enums with fields using the
+/// `#[key(finite)]` attribute, and for [`bool`], [`Ordering`] and `()`. It
+/// allows containers such as [`TotalMap`][crate::TotalMap] to store a value for
+/// every key.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Finite, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// assert_eq!(MyKey::COUNT, 2);
+/// assert_eq!(MyKey::Second.index(), 1);
+/// assert_eq!(MyKey::array_from_fn(|key| key), [MyKey::First, MyKey::Second]);
+/// ```
+pub trait Finite: Key {
+    /// The number of inhabitants of the key.
+    const COUNT: usize;
+
+    /// An array holding one value for every inhabitant of the key, which is
+    /// `[V; N]` for a key with `N` inhabitants.
+    type Array<V>: AsRef<[V]> + AsMut<[V]> + IntoIterator<Item = V>;
+
+    /// The index of the key in its [`Array`][Finite::Array].
+    fn index(&self) -> usize;
+
+    /// Construct an array by calling `f` with every inhabitant of the key in
+    /// index order.
+    fn array_from_fn<V, F>(f: F) -> Self::Array<V>
+    where
+        F: FnMut(Self) -> V;
+}
+
+impl Finite for bool {
+    const COUNT: usize = 2;
+
+    type Array<V> = [V; 2];
+
+    #[inline]
+    fn index(&self) -> usize {
+        usize::from(*self)
+    }
+
+    #[inline]
+    fn array_from_fn<V, F>(mut f: F) -> [V; 2]
+    where
+        F: FnMut(Self) -> V,
+    {
+        [f(false), f(true)]
+    }
+}
+
+impl Finite for Ordering {
+    const COUNT: usize = 3;
+
+    type Array<V> = [V; 3];
+
+    #[inline]
+    fn index(&self) -> usize {
+        match self {
+            Ordering::Less => 0,
+            Ordering::Equal => 1,
+            Ordering::Greater => 2,
+        }
+    }
+
+    #[inline]
+    fn array_from_fn<V, F>(mut f: F) -> [V; 3]
+    where
+        F: FnMut(Self) -> V,
+    {
+        [f(Ordering::Less), f(Ordering::Equal), f(Ordering::Greater)]
+    }
+}
+
+impl Finite for () {
+    const COUNT: usize = 1;
+
+    type Array<V> = [V; 1];
+
+    #[inline]
+    fn index(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn array_from_fn<V, F>(mut f: F) -> [V; 1]
+    where
+        F: FnMut(Self) -> V,
+    {
+        [f(())]
+    }
+}
+
+/// A tuple key, which is stored as the product of its first element and the
+/// remaining elements.
+pub trait Tuple: Key {
+    /// The first element of the tuple.
+    type Head: Key;
+
+    /// The remaining elements of the tuple.
+    type Tail: Key;
+
+    /// Split the tuple into its first element and the remaining elements.
+    fn split(self) -> (Self::Head, Self::Tail);
+
+    /// Join a first element and the remaining elements into a tuple.
+    fn join(head: Self::Head, tail: Self::Tail) -> Self;
+
+    /// Split the borrowed form of the tuple into the borrowed forms of its
+    /// first element and the remaining elements.
+    fn split_ref<'a>(
+        key: Self::Ref<'a>,
+    ) -> (<Self::Head as Key>::Ref<'a>, <Self::Tail as Key>::Ref<'a>);
+
+    /// Access the storage of the tuple as a product of storages, for any value
+    /// type.
+    fn product<V>(storage: &Self::MapStorage<V>) -> &ProductMapStorage<Self, V>;
+}
+
+impl<A, B> Tuple for (A, B)
+where
+    A: Key,
+    B: Key,
+{
+    type Head = A;
+    type Tail = B;
+
+    #[inline]
+    fn split(self) -> (A, B) {
+        self
+    }
+
+    #[inline]
+    fn join(head: A, tail: B) -> Self {
+        (head, tail)
+    }
+
+    #[inline]
+    fn split_ref<'a>(key: (A::Ref<'a>, B::Ref<'a>)) -> (A::Ref<'a>, B::Ref<'a>) {
+        key
+    }
+
+    #[inline]
+    fn product<V>(storage: &Self::MapStorage<V>) -> &ProductMapStorage<Self, V> {
+        storage
+    }
+}
+
+impl<A, B, C> Tuple for (A, B, C)
+where
+    A: Key,
+    B: Key,
+    C: Key,
+{
+    type Head = A;
+    type Tail = (B, C);
+
+    #[inline]
+    fn split(self) -> (A, (B, C)) {
+        let (a, b, c) = self;
+        (a, (b, c))
+    }
+
+    #[inline]
+    fn join(a: A, (b, c): (B, C)) -> Self {
+        (a, b, c)
+    }
+
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    fn split_ref<'a>(
+        (a, b, c): (A::Ref<'a>, B::Ref<'a>, C::Ref<'a>),
+    ) -> (A::Ref<'a>, (B::Ref<'a>, C::Ref<'a>)) {
+        (a, (b, c))
+    }
+
+    #[inline]
+    fn product<V>(storage: &Self::MapStorage<V>) -> &ProductMapStorage<Self, V> {
+        storage
+    }
+}
+
+impl<A, B> Key for (A, B)
+where
+    A: Key,
+    B: Key,
+{
+    type Ref<'a> = (A::Ref<'a>, B::Ref<'a>);
+    type MapStorage<V> = ProductMapStorage<Self, V>;
+    type SetStorage = ProductSetStorage<Self>;
+}
+
+impl<A, B, C> Key for (A, B, C)
+where
+    A: Key,
+    B: Key,
+    C: Key,
+{
+    type Ref<'a> = (A::Ref<'a>, B::Ref<'a>, C::Ref<'a>);
+    type MapStorage<V> = ProductMapStorage<Self, V>;
+    type SetStorage = ProductSetStorage<Self>;
+}
+
+/// A byte-sized key, which is stored densely in one of 256 slots.
+pub trait Byte: Copy + for<'a> Key<Ref<'a> = Self> {
+    /// The slot the key is stored in.
+    fn index(self) -> usize;
+
+    /// Construct a key from the slot it is stored in.
+    fn from_index(index: usize) -> Self;
+}
+
+impl Byte for u8 {
+    #[inline]
+    fn index(self) -> usize {
+        usize::from(self)
+    }
+
+    #[inline]
+    fn from_index(index: usize) -> Self {
+        index as u8
+    }
+}
+
+impl Byte for i8 {
+    // Offset so that slots are in ascending numerical order.
+    #[inline]
+    fn index(self) -> usize {
+        usize::from(self as u8 ^ 0x80)
+    }
+
+    #[inline]
+    fn from_index(index: usize) -> Self {
+        (index as u8 ^ 0x80) as i8
+    }
+}
+
+/// A key with finitely many inhabitants which is stored compactly, by keeping a
+/// bitset of which slots are present next to uninitialized value slots.
+///
+/// This is implemented by the derive when the `#[key(compact)]` attribute is
+/// present.
+pub trait Compact: Finite + for<'a> Key<Ref<'a> = Self> {
+    /// The bitset used to track which slots are present.
+    type Bits: Bits;
+
+    /// Construct a key from its [index][Finite::index].
+    fn from_index(index: usize) -> Self;
+}
+
+macro_rules! byte_key {
+    ($ty:ty) => {
+        impl Key for $ty {
+            type Ref<'a> = Self;
+            type MapStorage<V> = ByteMapStorage<$ty, V>;
+            type SetStorage = ByteSetStorage<$ty>;
+        }
+    };
+}
+
+/// A key wrapper which stores the wrapped value in a hash table through
+/// [`hashbrown`], regardless of which storage it would otherwise use.
+///
+/// This can be used to opt out of dense storage for keys like [`u8`] and
+/// [`i8`], which would otherwise always reserve room for every possible value.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Hashed, Map};
+///
+/// let mut map = Map::new();
+/// map.insert(Hashed(42u8), "answer");
+///
+/// assert_eq!(map.get(Hashed(42)), Some(&"answer"));
+/// assert_eq!(map.get(Hashed(41)), None);
+/// ```
+///
+/// [`hashbrown`]: https://docs.rs/hashbrown
+#[cfg(feature = "hashbrown")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Hashed<T>(pub T);
+
+#[cfg(feature = "hashbrown")]
+impl<T> Key for Hashed<T>
+where
+    T: Clone + Eq + Hash,
+{
+    type Ref<'a> = Self;
+    type MapStorage<V> = HashbrownMapStorage<Self, V>;
+    type SetStorage = HashbrownSetStorage<Self>;
+}
+
+#[cfg(feature = "hashbrown")]
+impl<T> HashKey for Hashed<T>
+where
+    T: Clone + Eq + Hash,
+{
+    type Borrowed = Self;
+
+    #[inline]
+    fn borrowed(key: &Self) -> &Self {
+        key
+    }
+}
+
+/// A key which is stored in a hash table, and is looked up in it through the
+/// type its borrowed form refers to.
+#[cfg(feature = "hashbrown")]
+pub trait HashKey: Key + Eq + Hash {
+    /// The type which the key is looked up as.
+    type Borrowed: ?Sized + Eq + Hash;
+
+    /// Access the borrowed form of the key as the type it is looked up as.
+    fn borrowed<'a>(key: &'a Self::Ref<'_>) -> &'a Self::Borrowed;
+}
+
 macro_rules! map_key {
+    (<$($lt:lifetime)?> $ty:ty) => {
+        #[cfg(feature = "hashbrown")]
+        impl<$($lt)?> Key for $ty {
+            type Ref<'__a> = Self;
+            type MapStorage<V> = HashbrownMapStorage<$ty, V>;
+            type SetStorage = HashbrownSetStorage<$ty>;
+        }
+
+        #[cfg(feature = "hashbrown")]
+        impl<$($lt)?> HashKey for $ty {
+            type Borrowed = Self;
+
+            #[inline]
+            fn borrowed(key: &Self) -> &Self {
+                key
+            }
+        }
+    };
+
+    ($ty:ty) => {
+        map_key!(<> $ty);
+    };
+}
+
+macro_rules! str_key {
     ($ty:ty) => {
         #[cfg(feature = "hashbrown")]
         impl Key for $ty {
+            type Ref<'a> = &'a str;
             type MapStorage<V> = HashbrownMapStorage<$ty, V>;
             type SetStorage = HashbrownSetStorage<$ty>;
         }
+
+        #[cfg(feature = "hashbrown")]
+        impl HashKey for $ty {
+            type Borrowed = str;
+
+            #[inline]
+            fn borrowed<'a>(key: &'a &str) -> &'a str {
+                key
+            }
+        }
     };
 }
 
 macro_rules! singleton_key {
     ($ty:ty) => {
         impl Key for $ty {
+            type Ref<'a> = Self;
             type MapStorage<V> = SingletonMapStorage<V>;
             type SetStorage = SingletonSetStorage;
         }
@@ -167,16 +606,22 @@ macro_rules! singleton_key {
 }
 
 map_key!(char);
-map_key!(u8);
+map_key!(u16);
 map_key!(u32);
 map_key!(u64);
 map_key!(u128);
 map_key!(usize);
-map_key!(i8);
+map_key!(i16);
 map_key!(i32);
 map_key!(i64);
 map_key!(i128);
 map_key!(isize);
-map_key!(&'static str);
-map_key!(&'static [u8]);
+map_key!(<'a> &'a str);
+map_key!(<'a> &'a [u8]);
+str_key!(String);
+str_key!(Box<str>);
+str_key!(Rc<str>);
+str_key!(Arc<str>);
+byte_key!(u8);
+byte_key!(i8);
 singleton_key!(());
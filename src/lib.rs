@@ -64,19 +64,50 @@
 //!
 //! * `std` - Disabling this feature causes this crate to be no-std. This means
 //!   that dynamic types cannot be used in keys, like ones enabled by the `map`
-//!   feature (default).
+//!   feature, and that [`ConcurrentMap`], [`JournaledMap`] and [`OnceMap`] are
+//!   unavailable (default).
 //! * `hashbrown` - Causes [`Storage`] to be implemented by dynamic types such
 //!   as `&'static str` or `u32`. These are backed by a `hashbrown` (default).
+//!   Byte-sized keys like `u8` and `i8` always use dense storage, but can be
+//!   wrapped in [`Hashed`] to store them in a `hashbrown` instead.
 //! * `entry` - Enables an [`entry`] API similar to that found on [`HashMap`].
-//! * `serde` - Causes [`Map`] and [`Set`] to implement [`Serialize`] and
-//!   [`Deserialize`] if it's implemented by the key and value.
+//! * `ops` - Implements element-wise arithmetic such as [`Add`] and [`Mul`] for
+//!   [`Map`], treating missing keys as zero.
+//! * `arbitrary` - Implements [`arbitrary::Arbitrary`] for [`Map`] and [`Set`],
+//!   and for keys using the `#[key(arbitrary)]` attribute, so that they can be
+//!   generated by fuzzers.
+//! * `borsh` - Implements [`BorshSerialize`] and [`BorshDeserialize`] for
+//!   [`Map`] and [`Set`] with a compact layout. Maps are written as a bitmap of
+//!   the keys which are present followed by their values, and sets as a bitmap,
+//!   while `#[key(bitset)]` sets are written as their raw integer. Only keys with
+//!   unit variants and the built-in finite keys are supported.
+//! * `proptest` - Implements [`proptest::arbitrary::Arbitrary`] for [`Map`] and
+//!   [`Set`], and for keys using the `#[key(arbitrary)]` attribute, so that they
+//!   can be generated in property tests. This enables the `std` feature.
+//! * `rayon` - Adds parallel iterators like [`Map::par_iter`] and
+//!   [`Set::par_iter`] through [`rayon`]. Keys with a fixed layout split the
+//!   work by slot, while dynamic keys use the parallel iterators of `hashbrown`.
+//! * `rkyv` - Implements zero-copy archiving of [`Map`] and [`Set`] through
+//!   [`rkyv`]. Keys with unit variants archive maps as a fixed array of optional
+//!   values, so [`ArchivedMap::get`] is a direct lookup, while `#[key(bitset)]`
+//!   sets archive as their raw integer.
+//! * `rkyv-validation` - Implements `CheckBytes` for archived maps and sets, so
+//!   that untrusted archives can be validated with `rkyv::check_archived_root`.
+//!   This enables the `rkyv` and `std` features.
+//! * `schemars` - Implements [`JsonSchema`] for [`Map`] and [`Set`], and for keys
+//!   using the `#[key(schema)]` attribute. Maps are described as objects with a
+//!   property for each unit key, and sets as arrays of unique keys. This enables
+//!   the `std` feature.
+//! * `serde` - Causes [`Map`], [`Set`], [`DefaultMap`] and [`MapPatch`] to
+//!   implement [`Serialize`] and [`Deserialize`] if it's implemented by the key
+//!   and value.
 //!
 //! <br>
 //!
 //! ## Specialized storage through the [`Key`] trait
 //!
 //! The [`Key` derive] is provided to instruct our containers on how to build
-//! optimized storage for a given [`Key`]. We also require any key to be [`Copy`].
+//! optimized storage for a given [`Key`]. We also require any key to be [`Clone`].
 //!
 //! ```
 //! use fixed_map::Key;
@@ -187,6 +218,9 @@
 //! The Entry API uses `unwrap_unchecked` to obtain mutable references to the
 //! inner value of `Some`s, and to skip `drop` when overwriting `None`s.
 //!
+//! Keys with the `#[key(compact)]` attribute store values in uninitialized slots,
+//! which are only read while the bitset marks them as present.
+//!
 //! <br>
 //!
 //! ## Benchmarks
@@ -268,16 +302,34 @@
 //! ls target/release/examples/
 //! ```
 //!
-//! [`Copy`]: https://doc.rust-lang.org/std/marker/trait.Copy.html
+//! [`arbitrary::Arbitrary`]: https://docs.rs/arbitrary/1/arbitrary/trait.Arbitrary.html
+//! [`ArchivedMap::get`]: https://docs.rs/fixed-map/latest/fixed_map/map/struct.ArchivedMap.html#method.get
+//! [`BorshDeserialize`]: https://docs.rs/borsh/1/borsh/de/trait.BorshDeserialize.html
+//! [`BorshSerialize`]: https://docs.rs/borsh/1/borsh/ser/trait.BorshSerialize.html
+//! [`Clone`]: https://doc.rust-lang.org/std/clone/trait.Clone.html
 //! [`Deserialize`]: https://docs.rs/serde/1/serde/trait.Deserialize.html
 //! [`hashbrown`]: https://github.com/Amanieu/hashbrown
+//! [`JsonSchema`]: https://docs.rs/schemars/0.8/schemars/trait.JsonSchema.html
 //! [`Key` derive]: https://docs.rs/fixed-map/latest/fixed_map/derive.Key.html
 //! [`Key`]: https://docs.rs/fixed-map/latest/fixed_map/derive.Key.html
+//! [`ConcurrentMap`]: https://docs.rs/fixed-map/latest/fixed_map/concurrent_map/struct.ConcurrentMap.html
+//! [`DefaultMap`]: https://docs.rs/fixed-map/latest/fixed_map/default_map/struct.DefaultMap.html
 //! [`Map`]: https://docs.rs/fixed-map/latest/fixed_map/map/struct.Map.html
 //! [`entry`]: https://docs.rs/fixed-map/latest/fixed_map/map/struct.Map.html#method.entry
+//! [`Hashed`]: https://docs.rs/fixed-map/latest/fixed_map/struct.Hashed.html
 //! [`HashMap`]: https://doc.rust-lang.org/stable/std/collections/hash_map/struct.HashMap.html#method.entry
+//! [`Add`]: https://doc.rust-lang.org/stable/core/ops/trait.Add.html
+//! [`Mul`]: https://doc.rust-lang.org/stable/core/ops/trait.Mul.html
+//! [`JournaledMap`]: https://docs.rs/fixed-map/latest/fixed_map/journaled_map/struct.JournaledMap.html
+//! [`Map::par_iter`]: https://docs.rs/fixed-map/latest/fixed_map/map/struct.Map.html#method.par_iter
+//! [`MapPatch`]: https://docs.rs/fixed-map/latest/fixed_map/map/struct.MapPatch.html
+//! [`OnceMap`]: https://docs.rs/fixed-map/latest/fixed_map/once_map/struct.OnceMap.html
+//! [`proptest::arbitrary::Arbitrary`]: https://docs.rs/proptest/1/proptest/arbitrary/trait.Arbitrary.html
+//! [`rayon`]: https://docs.rs/rayon/1
+//! [`rkyv`]: https://docs.rs/rkyv/0.7
 //! [`Serialize`]: https://docs.rs/serde/1/serde/trait.Serialize.html
 //! [`Set`]: https://docs.rs/fixed-map/latest/fixed_map/set/struct.Set.html
+//! [`Set::par_iter`]: https://docs.rs/fixed-map/latest/fixed_map/set/struct.Set.html#method.par_iter
 //! [`Storage`]: https://docs.rs/fixed-map/latest/fixed_map/storage/trait.Storage.html
 //! [documentation]: https://docs.rs/fixed-map
 
@@ -287,10 +339,21 @@
 #![allow(clippy::module_name_repetitions)]
 #![allow(clippy::type_repetition_in_bounds)]
 
+#[cfg(any(feature = "hashbrown", feature = "rayon"))]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod macros;
+
 pub mod raw;
 
+mod exact_iter;
 mod key;
-pub use self::key::Key;
+#[cfg(feature = "hashbrown")]
+pub use self::key::Hashed;
+pub use self::key::{Finite, Key};
 
 pub mod map;
 #[doc(inline)]
@@ -300,6 +363,48 @@ pub mod set;
 #[doc(inline)]
 pub use self::set::Set;
 
+pub mod total_map;
+#[doc(inline)]
+pub use self::total_map::TotalMap;
+
+pub mod default_map;
+#[doc(inline)]
+pub use self::default_map::DefaultMap;
+
+pub mod counter;
+#[doc(inline)]
+pub use self::counter::Counter;
+
+pub mod tracked_map;
+#[doc(inline)]
+pub use self::tracked_map::TrackedMap;
+
+#[cfg(feature = "std")]
+pub mod journaled_map;
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use self::journaled_map::JournaledMap;
+
+pub mod atomic;
+#[cfg(target_has_atomic = "ptr")]
+#[doc(inline)]
+pub use self::atomic::AtomicCounters;
+#[doc(inline)]
+pub use self::atomic::AtomicSet;
+
+#[cfg(feature = "std")]
+pub mod concurrent_map;
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use self::concurrent_map::ConcurrentMap;
+
+pub mod once_map;
+#[doc(inline)]
+pub use self::once_map::OnceCellMap;
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use self::once_map::OnceMap;
+
 // Re-export the option bucket types for use in `derive(Key)`
 #[doc(hidden)]
 pub mod option_bucket;
@@ -363,6 +468,209 @@ pub mod macro_support;
 ///
 /// <br>
 ///
+/// #### `#[key(compact)]`
+///
+/// This stores values of a [`Map`] in uninitialized slots next to a bitset of
+/// which slots are present, rather than in an array of [`Option`]s. This avoids
+/// the space taken by the discriminant of every slot, and lets the length be
+/// computed by counting bits. It is only supported for enums with unit
+/// variants.
+///
+/// The bitset can be accessed through [`Map::as_raw_keys`].
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Clone, Copy, Key)]
+/// pub enum Regular {
+///     First,
+///     Second,
+///     Third,
+/// }
+///
+/// #[derive(Clone, Copy, Key)]
+/// #[key(compact)]
+/// pub enum Compact {
+///     First,
+///     Second,
+///     Third,
+/// }
+///
+/// // Normal storage uses an array of optional values:
+/// assert_eq!(core::mem::size_of::<Map<Regular, u64>>(), 48);
+///
+/// // Compact storage uses a single u8 (or other appropriate type based on size)
+/// // next to the values:
+/// assert_eq!(core::mem::size_of::<Map<Compact, u64>>(), 32);
+/// ```
+///
+/// > **Note:** compact storage needs to drop the values it holds, so a [`Map`]
+/// > using it never implements [`Copy`].
+///
+/// <br>
+///
+/// #### `#[key(finite)]`
+///
+/// Implements [`Finite`] for an enum with fields, so that it can be used with
+/// containers such as [`TotalMap`] which store a value for every key. Enums
+/// with only unit variants implement [`Finite`] without this attribute.
+///
+/// The type of every field must implement [`Finite`] as well, and the enum
+/// can't be generic. Keys are indexed in declaration order, with every
+/// inhabitant of a field taking up one index.
+///
+/// ```
+/// use fixed_map::{Finite, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// pub enum Part {
+///     One,
+///     Two,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// #[key(finite)]
+/// pub enum MyKey {
+///     First,
+///     Second(Part),
+///     Third(bool),
+/// }
+///
+/// assert_eq!(MyKey::COUNT, 5);
+/// assert_eq!(MyKey::Second(Part::Two).index(), 2);
+/// assert_eq!(MyKey::Third(false).index(), 3);
+/// ```
+///
+/// <br>
+///
+/// #### `#[key(borrow)]`
+///
+/// Generates a borrowed form of an enum with fields, named after the enum with
+/// a `Ref` suffix. It has the same variants, but each field is replaced by the
+/// [borrowed form][Key::Ref] of its type. This is used as the [`Key::Ref`] of
+/// the enum, so that methods like [`Map::get_by`] can look up keys without
+/// constructing them.
+///
+/// Without this attribute the enum is its own borrowed form.
+///
+/// ```
+/// # #[cfg(feature = "hashbrown")] {
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Clone, Key)]
+/// #[key(borrow)]
+/// pub enum MyKey {
+///     Name(String),
+///     Anonymous,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(MyKey::Name(String::from("a")), 1);
+///
+/// assert_eq!(map.get_by(MyKeyRef::Name("a")), Some(&1));
+/// assert_eq!(map.get_by(MyKeyRef::Anonymous), None);
+/// # }
+/// ```
+///
+/// <br>
+///
+/// #### `#[key(arbitrary)]`
+///
+/// Implements `Arbitrary` for the key from each of the `arbitrary` and
+/// `proptest` features which are enabled, and requires at least one of them.
+/// A variant is picked uniformly, after which its field is generated through
+/// its own `Arbitrary` implementation. So a variant holding many keys is as
+/// likely as a unit variant. Enums with unit variants, and keys using the
+/// [`#[key(finite)]`](#keyfinite) attribute, instead pick uniformly from every
+/// possible key.
+///
+/// The type of every field must implement `Arbitrary` as well, and with the
+/// `proptest` feature the key must implement [`Debug`][core::fmt::Debug].
+///
+/// ```
+/// # #[cfg(feature = "arbitrary")] {
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// #[key(arbitrary)]
+/// pub enum MyKey {
+///     First,
+///     Second(bool),
+/// }
+///
+/// let mut u = Unstructured::new(&[1, 1, 0, 7, 0, 0, 42]);
+/// let map = Map::<MyKey, u8>::arbitrary(&mut u).unwrap();
+/// assert!(map.len() <= 3);
+/// # }
+/// ```
+///
+/// <br>
+///
+/// #### `#[key(schema)]`
+///
+/// Implements `JsonSchema` for the key, which requires the `schemars` feature.
+/// The schema follows the representation used by `#[derive(Serialize)]`, so
+/// unit variants are enumerated as strings, while a variant with a field is
+/// described as an object with the name of the variant as its only property.
+/// Maps only describe the unit variants of the key as properties, and leave
+/// other properties open if the key has variants with fields.
+///
+/// The type of every field must implement `JsonSchema` as well.
+///
+/// ```
+/// # #[cfg(feature = "schemars")] {
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Clone, Copy, Key)]
+/// #[key(schema)]
+/// pub enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let schema = schemars::schema_for!(Map<MyKey, u32>);
+/// let object = schema.schema.object.unwrap();
+///
+/// assert!(object.properties.contains_key("First"));
+/// assert!(object.properties.contains_key("Second"));
+/// # }
+/// ```
+///
+/// <br>
+///
+/// ## Generic keys
+///
+/// Keys can be generic over types and lifetimes. Every type stored in a
+/// variant is then required to implement [`Key`] itself, which is added as a
+/// bound to the derived implementation.
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum Scoped<T> {
+///     Global,
+///     Local(T),
+/// }
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum Part {
+///     One,
+///     Two,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Scoped::Global, 1);
+/// map.insert(Scoped::Local(Part::Two), 2);
+///
+/// assert_eq!(map.get(Scoped::Global), Some(&1));
+/// assert_eq!(map.get(Scoped::Local(Part::One)), None);
+/// assert_eq!(map.get(Scoped::Local(Part::Two)), Some(&2));
+/// ```
+///
+/// <br>
+///
 /// ## Guide
 ///
 /// Given the following enum:
@@ -8,6 +8,27 @@
 
 use core::cmp::Ordering;
 
+pub use crate::exact_iter::ExactIter;
+pub use crate::key::Compact;
+pub use crate::map::storage::CompactMapStorage;
+pub use crate::map::storage::{zip_slots as __zip_slots, ZipSlots};
+#[cfg(feature = "arbitrary")]
+pub use ::arbitrary;
+#[cfg(feature = "borsh")]
+pub use ::borsh;
+#[cfg(feature = "proptest")]
+pub use ::proptest;
+#[cfg(feature = "rayon")]
+pub use ::rayon;
+#[cfg(feature = "rkyv")]
+pub use ::rkyv;
+#[cfg(feature = "schemars")]
+pub use ::schemars;
+#[cfg(feature = "schemars")]
+pub use ::std::string::String;
+#[cfg(feature = "proptest")]
+pub use ::std::vec::Vec;
+
 #[inline]
 fn flatten<T>(value: (usize, &Option<T>)) -> Option<(usize, &T)> {
     match value {
@@ -70,3 +91,241 @@ where
     let b = b.into_iter().enumerate().filter(filter_bool);
     a.cmp(b)
 }
+
+/// Used by the `exhaustive_map!` macro to convert a map into a
+/// [`TotalMap`][crate::TotalMap], once the match over every key listed has
+/// been type checked without ever calling it.
+pub fn __exhaustive_map<K, V, F>(mut map: crate::Map<K, V>, _: F) -> crate::TotalMap<K, V>
+where
+    K: crate::Finite,
+    F: FnOnce(K),
+{
+    crate::TotalMap::from_fn(|key| match map.remove(key) {
+        Some(value) => value,
+        None => panic!("key listed in `exhaustive_map!` doesn't match a single key"),
+    })
+}
+
+/// Read an archived primitive, such as the bits of an archived bitset,
+/// regardless of the endianness it was archived with.
+#[cfg(feature = "rkyv")]
+#[inline]
+pub fn __archived_value<T>(archived: &T::Archived) -> T
+where
+    T: rkyv::Archive,
+    T::Archived: rkyv::Deserialize<T, rkyv::Infallible>,
+{
+    match rkyv::Deserialize::deserialize(archived, &mut rkyv::Infallible) {
+        Ok(value) => value,
+        Err(error) => match error {},
+    }
+}
+
+/// Check that only the lowest `count` bits of an archived bitset are set,
+/// since every other bit doesn't correspond to a key.
+#[cfg(feature = "rkyv-validation")]
+#[inline]
+pub fn __check_bits(bits: u128, count: usize) -> Result<(), crate::set::BitsCheckError> {
+    match bits.checked_shr(count as u32) {
+        Some(invalid) if invalid != 0 => Err(crate::set::BitsCheckError::new(
+            127 - bits.leading_zeros() as usize,
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Validation of an archived map storage, which lets the derived storage be
+/// bounded on the storage of its fields instead of on their archived form.
+#[cfg(feature = "rkyv-validation")]
+pub trait CheckArchivedMapStorage<K, V, C>: crate::map::ArchiveMapStorage<K, V>
+where
+    V: rkyv::Archive,
+    C: ?Sized,
+{
+    /// Check the archived storage behind the given pointer.
+    ///
+    /// # Safety
+    ///
+    /// See [`rkyv::CheckBytes::check_bytes`].
+    unsafe fn check_archived(
+        value: *const Self::Archived,
+        context: &mut C,
+    ) -> Result<(), rkyv::bytecheck::ErrorBox<dyn rkyv::bytecheck::Error>>;
+}
+
+#[cfg(feature = "rkyv-validation")]
+impl<S, K, V, C> CheckArchivedMapStorage<K, V, C> for S
+where
+    S: crate::map::ArchiveMapStorage<K, V>,
+    S::Archived: rkyv::CheckBytes<C>,
+    V: rkyv::Archive,
+    C: ?Sized,
+{
+    #[inline]
+    unsafe fn check_archived(
+        value: *const Self::Archived,
+        context: &mut C,
+    ) -> Result<(), rkyv::bytecheck::ErrorBox<dyn rkyv::bytecheck::Error>> {
+        match rkyv::CheckBytes::check_bytes(value, context) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(rkyv::bytecheck::ErrorBox::new(error)),
+        }
+    }
+}
+
+/// Validation of an archived set storage, which lets the derived storage be
+/// bounded on the storage of its fields instead of on their archived form.
+#[cfg(feature = "rkyv-validation")]
+pub trait CheckArchivedSetStorage<T, C>: crate::set::ArchiveSetStorage<T>
+where
+    C: ?Sized,
+{
+    /// Check the archived storage behind the given pointer.
+    ///
+    /// # Safety
+    ///
+    /// See [`rkyv::CheckBytes::check_bytes`].
+    unsafe fn check_archived(
+        value: *const Self::Archived,
+        context: &mut C,
+    ) -> Result<(), rkyv::bytecheck::ErrorBox<dyn rkyv::bytecheck::Error>>;
+}
+
+#[cfg(feature = "rkyv-validation")]
+impl<S, T, C> CheckArchivedSetStorage<T, C> for S
+where
+    S: crate::set::ArchiveSetStorage<T>,
+    S::Archived: rkyv::CheckBytes<C>,
+    C: ?Sized,
+{
+    #[inline]
+    unsafe fn check_archived(
+        value: *const Self::Archived,
+        context: &mut C,
+    ) -> Result<(), rkyv::bytecheck::ErrorBox<dyn rkyv::bytecheck::Error>> {
+        match rkyv::CheckBytes::check_bytes(value, context) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(rkyv::bytecheck::ErrorBox::new(error)),
+        }
+    }
+}
+
+/// Write presence flags as a bitmap through `borsh`, packing eight flags into
+/// every byte starting from the least significant bit.
+#[cfg(feature = "borsh")]
+pub fn __borsh_write_bitmap<I, W>(flags: I, writer: &mut W) -> borsh::io::Result<()>
+where
+    I: IntoIterator<Item = bool>,
+    W: borsh::io::Write,
+{
+    let mut flags = flags.into_iter().peekable();
+
+    while flags.peek().is_some() {
+        let mut byte = 0u8;
+
+        for (bit, flag) in flags.by_ref().take(8).enumerate() {
+            byte |= u8::from(flag) << bit;
+        }
+
+        borsh::BorshSerialize::serialize(&byte, writer)?;
+    }
+
+    Ok(())
+}
+
+/// Read presence flags written by [`__borsh_write_bitmap`], erroring if any
+/// bit beyond the flags is set.
+#[cfg(feature = "borsh")]
+pub fn __borsh_read_bitmap<R>(flags: &mut [bool], reader: &mut R) -> borsh::io::Result<()>
+where
+    R: borsh::io::Read,
+{
+    for chunk in flags.chunks_mut(8) {
+        let byte = <u8 as borsh::BorshDeserialize>::deserialize_reader(reader)?;
+
+        if u16::from(byte) >> chunk.len() != 0 {
+            return Err(__borsh_invalid_bits());
+        }
+
+        for (bit, flag) in chunk.iter_mut().enumerate() {
+            *flag = byte & (1 << bit) != 0;
+        }
+    }
+
+    Ok(())
+}
+
+/// The error raised when a bitmap or bitset has bits set which don't
+/// correspond to any key.
+#[cfg(feature = "borsh")]
+#[cold]
+pub fn __borsh_invalid_bits() -> borsh::io::Error {
+    borsh::io::Error::new(
+        borsh::io::ErrorKind::InvalidData,
+        "bits set which don't correspond to any key",
+    )
+}
+
+/// Name the schema of a key, qualified by the schema names of its type
+/// parameters in the same way as the collections in `schemars`.
+#[cfg(feature = "schemars")]
+pub fn __schema_name(name: &str, params: &[String]) -> String {
+    let mut name = String::from(name);
+
+    for (n, param) in params.iter().enumerate() {
+        name.push_str(if n == 0 { "_for_" } else { "_and_" });
+        name.push_str(param);
+    }
+
+    name
+}
+
+/// Build the schema of a key, where unit variants are enumerated as strings
+/// and every variant with a field is an externally tagged object.
+#[cfg(feature = "schemars")]
+pub fn __schema_key<const N: usize>(
+    units: &[&str],
+    variants: [(&str, schemars::schema::Schema); N],
+) -> schemars::schema::Schema {
+    use schemars::schema::{
+        InstanceType, ObjectValidation, Schema, SchemaObject, SubschemaValidation,
+    };
+
+    let unit = (!units.is_empty()).then(|| SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        enum_values: Some(units.iter().map(|&unit| unit.into()).collect()),
+        ..SchemaObject::default()
+    });
+
+    // Keys without variants have no values, which is only matched by `false`.
+    if N == 0 {
+        return unit.map_or(Schema::Bool(false), Schema::Object);
+    }
+
+    let mut one_of = std::vec::Vec::with_capacity(N + 1);
+    one_of.extend(unit.map(Schema::Object));
+
+    for (name, schema) in variants {
+        let mut object = ObjectValidation {
+            additional_properties: Some(std::boxed::Box::new(false.into())),
+            ..ObjectValidation::default()
+        };
+
+        object.properties.insert(name.into(), schema);
+        object.required.insert(name.into());
+
+        one_of.push(Schema::Object(SchemaObject {
+            instance_type: Some(InstanceType::Object.into()),
+            object: Some(std::boxed::Box::new(object)),
+            ..SchemaObject::default()
+        }));
+    }
+
+    Schema::Object(SchemaObject {
+        subschemas: Some(std::boxed::Box::new(SubschemaValidation {
+            one_of: Some(one_of),
+            ..SubschemaValidation::default()
+        })),
+        ..SchemaObject::default()
+    })
+}
@@ -0,0 +1,168 @@
+/// Construct a [`Map`][crate::Map] from a list of key-value pairs.
+///
+/// Keys which are listed more than once take the last value they're
+/// associated with.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{map, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second(bool),
+///     Third,
+/// }
+///
+/// let map = map! {
+///     MyKey::First => 1,
+///     MyKey::Second(true) => 2,
+/// };
+///
+/// assert_eq!(map.get(MyKey::First), Some(&1));
+/// assert_eq!(map.get(MyKey::Second(true)), Some(&2));
+/// assert_eq!(map.get(MyKey::Third), None);
+/// ```
+#[macro_export]
+macro_rules! map {
+    ($($key:expr => $value:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut map = $crate::Map::new();
+        $(map.insert($key, $value);)*
+        map
+    }};
+}
+
+/// Construct a [`Set`][crate::Set] from a list of keys.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{set, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second(bool),
+///     Third,
+/// }
+///
+/// let set = set! { MyKey::First, MyKey::Second(false) };
+///
+/// assert!(set.contains(MyKey::First));
+/// assert!(set.contains(MyKey::Second(false)));
+/// assert!(!set.contains(MyKey::Third));
+/// ```
+#[macro_export]
+macro_rules! set {
+    ($($key:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut set = $crate::Set::new();
+        $(set.insert($key);)*
+        set
+    }};
+}
+
+/// Construct a [`TotalMap`][crate::TotalMap] where every inhabitant of the key
+/// must be listed.
+///
+/// Keys are written like in [`map!`], and are also used as patterns to check
+/// that the listing is exhaustive. So they can be unit variants, or variants
+/// whose fields are listed in turn such as `MyKey::Pair(Part::One)`. Leaving
+/// out a key is a compile error naming the missing keys, and listing a key
+/// twice triggers the `unreachable_patterns` lint.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{exhaustive_map, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Level {
+///     Debug,
+///     Info,
+///     Error,
+/// }
+///
+/// let colors = exhaustive_map! {
+///     Level::Debug => "gray",
+///     Level::Info => "white",
+///     Level::Error => "red",
+/// };
+///
+/// assert_eq!(colors.len(), 3);
+/// assert_eq!(colors[Level::Error], "red");
+/// ```
+///
+/// Keys with fields need the `#[key(finite)]` attribute:
+///
+/// ```
+/// use fixed_map::{exhaustive_map, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Part {
+///     One,
+///     Two,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// #[key(finite)]
+/// enum MyKey {
+///     Simple,
+///     Pair(Part),
+/// }
+///
+/// let map = exhaustive_map! {
+///     MyKey::Simple => 0,
+///     MyKey::Pair(Part::One) => 1,
+///     MyKey::Pair(Part::Two) => 2,
+/// };
+///
+/// assert_eq!(map[MyKey::Pair(Part::Two)], 2);
+/// ```
+///
+/// Forgetting a key fails to compile with "non-exhaustive patterns:
+/// `Level::Error` not covered":
+///
+/// ```compile_fail,E0004
+/// use fixed_map::{exhaustive_map, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Level {
+///     Debug,
+///     Info,
+///     Error,
+/// }
+///
+/// let colors = exhaustive_map! {
+///     Level::Debug => "gray",
+///     Level::Info => "white",
+/// };
+/// ```
+#[macro_export]
+macro_rules! exhaustive_map {
+    (@key [$([$($key:tt)+] [$value:expr])*] []) => {{
+        let map = $crate::map! { $($($key)+ => $value),* };
+
+        $crate::macro_support::__exhaustive_map(map, |key| match key {
+            $($($key)+ => {})*
+        })
+    }};
+
+    (@key [$($entries:tt)*] [$($key:tt)+] => $value:expr $(, $($rest:tt)*)?) => {
+        $crate::exhaustive_map!(@key [$($entries)* [$($key)+] [$value]] [] $($($rest)*)?)
+    };
+
+    (@key [$($entries:tt)*] [$($key:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::exhaustive_map!(@key [$($entries)*] [$($key)* $next] $($rest)*)
+    };
+
+    (@key $($rest:tt)*) => {
+        ::core::compile_error!("expected `key => value` entries separated by commas")
+    };
+
+    ($($tt:tt)*) => {
+        $crate::exhaustive_map!(@key [] [] $($tt)*)
+    };
+}
@@ -3,13 +3,40 @@
 mod entry;
 pub use self::entry::Entry;
 
+mod diff;
+pub use self::diff::{Change, Diff, MapPatch};
+
+mod variant;
+pub use self::variant::{Variant, VariantMapStorage, VariantView, VariantViewMut};
+
+#[cfg(feature = "ops")]
+mod ops;
+
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "rayon")]
+pub use self::rayon::{IntoParIter, ParIter, ParIterMut, ParValuesMut};
+
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "rkyv")]
+pub use self::rkyv::{ArchivedIter, ArchivedMap};
+
 pub(crate) mod storage;
+#[cfg(feature = "rayon")]
+pub use self::storage::ParallelMapStorage;
+#[cfg(feature = "rkyv")]
+pub use self::storage::{ArchiveMapStorage, ArchivedMapStorage};
 pub use self::storage::{MapStorage, OccupiedEntry, VacantEntry};
 
 use core::cmp::{Ord, Ordering, PartialOrd};
 use core::fmt;
 use core::hash::{Hash, Hasher};
+use core::ops::{Index, IndexMut};
 
+use crate::raw::RawKeys;
+#[cfg(feature = "borsh")]
+use crate::Finite;
 use crate::Key;
 
 /// The iterator produced by [`Map::iter`].
@@ -30,6 +57,9 @@ pub type ValuesMut<'a, K, V> = <<K as Key>::MapStorage<V> as MapStorage<K, V>>::
 /// The iterator produced by [`Map::into_iter`].
 pub type IntoIter<K, V> = <<K as Key>::MapStorage<V> as MapStorage<K, V>>::IntoIter;
 
+/// The iterator produced by [`Map::zip`].
+pub type Zip<'a, K, V, U> = <<K as Key>::MapStorage<V> as MapStorage<K, V>>::Zip<'a, U>;
+
 /// A fixed map with storage specialized through the [`Key`] trait.
 ///
 /// # Examples
@@ -600,6 +630,123 @@ where
         self.storage.remove(key)
     }
 
+    /// Returns `true` if the map currently contains the key identified by its
+    /// borrowed form.
+    ///
+    /// See [`Key::Ref`] for how keys are borrowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use fixed_map::Map;
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(String::from("a"), 1);
+    /// assert!(map.contains_key_by("a"));
+    /// assert!(!map.contains_key_by("b"));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn contains_key_by(&self, key: K::Ref<'_>) -> bool {
+        self.storage.contains_key_by(key)
+    }
+
+    /// Returns a reference to the value corresponding to the key identified by
+    /// its borrowed form.
+    ///
+    /// See [`Key::Ref`] for how keys are borrowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use fixed_map::Map;
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(String::from("a"), 1);
+    /// assert_eq!(map.get_by("a"), Some(&1));
+    /// assert_eq!(map.get_by("b"), None);
+    /// # }
+    /// ```
+    ///
+    /// Using a composite key with a [borrowed form][borrow]:
+    ///
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Key)]
+    /// #[key(borrow)]
+    /// enum MyKey {
+    ///     Name(String),
+    ///     Anonymous,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(MyKey::Name(String::from("a")), 1);
+    /// map.insert(MyKey::Anonymous, 2);
+    ///
+    /// assert_eq!(map.get_by(MyKeyRef::Name("a")), Some(&1));
+    /// assert_eq!(map.get_by(MyKeyRef::Name("b")), None);
+    /// assert_eq!(map.get_by(MyKeyRef::Anonymous), Some(&2));
+    /// # }
+    /// ```
+    ///
+    /// [borrow]: derive@crate::Key#keyborrow
+    #[inline]
+    pub fn get_by(&self, key: K::Ref<'_>) -> Option<&V> {
+        self.storage.get_by(key)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key
+    /// identified by its borrowed form.
+    ///
+    /// See [`Key::Ref`] for how keys are borrowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use fixed_map::Map;
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(String::from("a"), 1);
+    ///
+    /// if let Some(x) = map.get_mut_by("a") {
+    ///     *x = 2;
+    /// }
+    ///
+    /// assert_eq!(map.get_by("a"), Some(&2));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn get_mut_by(&mut self, key: K::Ref<'_>) -> Option<&mut V> {
+        self.storage.get_mut_by(key)
+    }
+
+    /// Removes the key identified by its borrowed form from the map, returning
+    /// the value at the key if the key was previously in the map.
+    ///
+    /// See [`Key::Ref`] for how keys are borrowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use fixed_map::Map;
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(String::from("a"), 1);
+    /// assert_eq!(map.remove_by("a"), Some(1));
+    /// assert_eq!(map.remove_by("a"), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn remove_by(&mut self, key: K::Ref<'_>) -> Option<V> {
+        self.storage.remove_by(key)
+    }
+
     /// Retains only the elements specified by the predicate.
     ///
     /// In other words, remove all pairs (k, v) for which f(k, &mut v) returns false.
@@ -832,6 +979,253 @@ where
     pub fn entry(&mut self, key: K) -> Entry<'_, K::MapStorage<V>, K, V> {
         K::MapStorage::entry(&mut self.storage, key)
     }
+
+    /// Construct a new map by applying `f` to every entry in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(MyKey::First, 1);
+    /// map.insert(MyKey::Third, 3);
+    ///
+    /// let map = map.map_values(|key, value| format!("{key:?}={value}"));
+    /// assert!(map.values().eq(["First=1", "Third=3"]));
+    /// ```
+    #[inline]
+    pub fn map_values<U, F>(self, f: F) -> Map<K, U>
+    where
+        F: FnMut(K, V) -> U,
+    {
+        Map {
+            storage: self.storage.map_values(f),
+        }
+    }
+
+    /// Combine two maps into one which contains every key in either of
+    /// them. Values present in both maps are combined through `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let mut a = Map::new();
+    /// a.insert(MyKey::First, 1);
+    /// a.insert(MyKey::Second, 2);
+    ///
+    /// let mut b = Map::new();
+    /// b.insert(MyKey::Second, 20);
+    /// b.insert(MyKey::Third, 30);
+    ///
+    /// let map = a.union_with(b, |_, a, b| a + b);
+    /// assert!(map.iter().eq([(MyKey::First, &1), (MyKey::Second, &22), (MyKey::Third, &30)]));
+    /// ```
+    ///
+    /// Using a composite key:
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First(bool),
+    ///     Second,
+    /// }
+    ///
+    /// let mut a = Map::new();
+    /// a.insert(MyKey::First(true), 1);
+    ///
+    /// let mut b = Map::new();
+    /// b.insert(MyKey::First(true), 10);
+    /// b.insert(MyKey::Second, 2);
+    ///
+    /// let map = a.union_with(b, |_, a, b| a.max(b));
+    /// assert!(map.iter().eq([(MyKey::First(true), &10), (MyKey::Second, &2)]));
+    /// ```
+    #[inline]
+    pub fn union_with<F>(self, other: Map<K, V>, mut f: F) -> Map<K, V>
+    where
+        F: FnMut(K, V, V) -> V,
+    {
+        Map {
+            storage: self.storage.merge(other.storage, |key, a, b| match (a, b) {
+                (Some(a), Some(b)) => Some(f(key, a, b)),
+                (a, b) => a.or(b),
+            }),
+        }
+    }
+
+    /// Combine two maps into one which only contains the keys present in both
+    /// of them, with values produced by `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let mut budget = Map::new();
+    /// budget.insert(MyKey::First, 10);
+    /// budget.insert(MyKey::Second, 20);
+    ///
+    /// let mut spent = Map::new();
+    /// spent.insert(MyKey::Second, 5.0);
+    /// spent.insert(MyKey::Third, 1.0);
+    ///
+    /// let left = budget.intersection_with(spent, |_, budget, spent| f64::from(budget) - spent);
+    /// assert!(left.iter().eq([(MyKey::Second, &15.0)]));
+    /// ```
+    #[inline]
+    pub fn intersection_with<U, W, F>(self, other: Map<K, U>, mut f: F) -> Map<K, W>
+    where
+        F: FnMut(K, V, U) -> W,
+    {
+        Map {
+            storage: self
+                .storage
+                .merge(other.storage, |key, a, b| Some(f(key, a?, b?))),
+        }
+    }
+
+    /// An iterator visiting every key present in either map, together with the
+    /// values the two maps hold for it. The iterator element type is `(K,
+    /// Option<&'a V>, Option<&'a U>)`.
+    ///
+    /// Both maps are walked in lockstep in a single pass, so keys are visited
+    /// in the same order as [`Map::iter`] without allocating intermediate
+    /// storage. Only keys with dynamic storage look up their values in the
+    /// other map, since their order is unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let mut a = Map::new();
+    /// a.insert(MyKey::First, 1);
+    /// a.insert(MyKey::Second, 2);
+    ///
+    /// let mut b = Map::new();
+    /// b.insert(MyKey::Second, "two");
+    /// b.insert(MyKey::Third, "three");
+    ///
+    /// assert!(a.zip(&b).eq([
+    ///     (MyKey::First, Some(&1), None),
+    ///     (MyKey::Second, Some(&2), Some(&"two")),
+    ///     (MyKey::Third, None, Some(&"three")),
+    /// ]));
+    /// ```
+    #[inline]
+    pub fn zip<'a, U>(&'a self, other: &'a Map<K, U>) -> Zip<'a, K, V, U> {
+        self.storage.zip(&other.storage)
+    }
+}
+
+impl<K, A, B> Map<K, (A, B)>
+where
+    K: Key,
+{
+    /// Split a map of pairs into two maps with the same keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(MyKey::First, (1, "one"));
+    /// map.insert(MyKey::Second, (2, "two"));
+    ///
+    /// let (numbers, names) = map.unzip();
+    /// assert!(numbers.values().copied().eq([1, 2]));
+    /// assert!(names.values().copied().eq(["one", "two"]));
+    /// ```
+    #[inline]
+    pub fn unzip(self) -> (Map<K, A>, Map<K, B>) {
+        let mut right = K::MapStorage::<B>::empty();
+
+        let left = self.storage.map_values(|key, (a, b)| {
+            right.insert(key, b);
+            a
+        });
+
+        (Map { storage: left }, Map { storage: right })
+    }
+}
+
+impl<K, V> Map<K, V>
+where
+    K: Key,
+    K::MapStorage<V>: RawKeys,
+{
+    /// Get the keys in the map as a raw value.
+    ///
+    /// This is the same value that [`Set::as_raw`] returns for a set holding
+    /// the same keys.
+    ///
+    /// [`Set::as_raw`]: crate::Set::as_raw
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, Key)]
+    /// #[key(bitset, compact)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// assert!(map.as_raw_keys() == 0);
+    /// map.insert(MyKey::First, 1);
+    /// map.insert(MyKey::Third, 3);
+    ///
+    /// let set = Set::from_raw(map.as_raw_keys());
+    /// assert_eq!(set, Set::from([MyKey::First, MyKey::Third]));
+    /// ```
+    #[inline]
+    pub fn as_raw_keys(&self) -> <K::MapStorage<V> as RawKeys>::Value {
+        self.storage.as_raw_keys()
+    }
 }
 
 /// [`Clone`] implementation for a [`Map`].
@@ -1342,6 +1736,116 @@ where
     }
 }
 
+/// [`Index`] implementation for a [`Map`], which returns a reference to the
+/// value corresponding to the key.
+///
+/// The map is indexed by the [borrowed form][Key::Ref] of the key, like
+/// [`Map::get_by`], so keys such as `String` can be looked up with a `&str`.
+///
+/// # Panics
+///
+/// Panics with the [`Debug`][fmt::Debug] representation of the key if it's not
+/// present in the map. Use [`Map::get`] for a non-panicking lookup, or a
+/// [`TotalMap`][crate::TotalMap] where every key is always present.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, Key)]
+/// enum MyKey {
+///     First(bool),
+///     Second,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(MyKey::First(true), 1);
+///
+/// assert_eq!(map[MyKey::First(true)], 1);
+/// ```
+///
+/// Indexing with a missing key:
+///
+/// ```should_panic
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(MyKey::First, 1);
+///
+/// let _ = map[MyKey::Second];
+/// ```
+impl<'a, K, V> Index<K::Ref<'a>> for Map<K, V>
+where
+    K: Key,
+    K::Ref<'a>: fmt::Debug,
+{
+    type Output = V;
+
+    #[inline]
+    fn index(&self, key: K::Ref<'a>) -> &V {
+        match self.storage.get_by(key.clone()) {
+            Some(value) => value,
+            None => missing_key(&key),
+        }
+    }
+}
+
+/// [`IndexMut`] implementation for a [`Map`], which returns a mutable
+/// reference to the value corresponding to the key through its
+/// [borrowed form][Key::Ref].
+///
+/// # Panics
+///
+/// Panics with the [`Debug`][fmt::Debug] representation of the key if it's not
+/// present in the map.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, Key)]
+/// enum MyKey {
+///     First(bool),
+///     Second,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(MyKey::First(true), 1);
+/// map[MyKey::First(true)] += 1;
+///
+/// assert_eq!(map.get(MyKey::First(true)), Some(&2));
+/// ```
+impl<'a, K, V> IndexMut<K::Ref<'a>> for Map<K, V>
+where
+    K: Key,
+    K::Ref<'a>: fmt::Debug,
+{
+    #[inline]
+    fn index_mut(&mut self, key: K::Ref<'a>) -> &mut V {
+        match self.storage.get_mut_by(key.clone()) {
+            Some(value) => value,
+            None => missing_key(&key),
+        }
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn missing_key<K>(key: &K) -> !
+where
+    K: fmt::Debug,
+{
+    panic!("key `{key:?}` is not present in the map")
+}
+
 /// A simple [`FromIterator`] implementation for [`Map`].
 ///
 /// # Example
@@ -1446,3 +1950,223 @@ where
         deserializer.deserialize_map(MapVisitor(core::marker::PhantomData))
     }
 }
+
+/// The map is written as a bitmap of the keys which are present, in the order
+/// of their [`Finite::index`], followed by the value of every present key in
+/// the same order.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Part {
+///     One,
+///     Two,
+///     Three,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Part::One, 1u16);
+/// map.insert(Part::Three, 3u16);
+///
+/// let bytes = borsh::to_vec(&map)?;
+/// assert_eq!(bytes, [0b101, 1, 0, 3, 0]);
+///
+/// let decoded: Map<Part, u16> = borsh::from_slice(&bytes)?;
+/// assert_eq!(decoded, map);
+/// # Ok::<_, borsh::io::Error>(())
+/// ```
+#[cfg(feature = "borsh")]
+impl<K, V> borsh::BorshSerialize for Map<K, V>
+where
+    K: Finite,
+    V: borsh::BorshSerialize,
+{
+    #[inline]
+    fn serialize<W>(&self, writer: &mut W) -> borsh::io::Result<()>
+    where
+        W: borsh::io::Write,
+    {
+        let values = K::array_from_fn(|key| self.get(key));
+        let present = values.as_ref().iter().map(Option::is_some);
+        crate::macro_support::__borsh_write_bitmap(present, writer)?;
+
+        for value in values.into_iter().flatten() {
+            value.serialize(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl<K, V> borsh::BorshDeserialize for Map<K, V>
+where
+    K: Finite,
+    V: borsh::BorshDeserialize,
+{
+    #[inline]
+    fn deserialize_reader<R>(reader: &mut R) -> borsh::io::Result<Self>
+    where
+        R: borsh::io::Read,
+    {
+        let mut present = K::array_from_fn(|_| false);
+        crate::macro_support::__borsh_read_bitmap(present.as_mut(), reader)?;
+
+        let mut map = Map::new();
+
+        for (key, present) in K::array_from_fn(|key| key).into_iter().zip(present) {
+            if present {
+                map.insert(key, V::deserialize_reader(reader)?);
+            }
+        }
+
+        Ok(map)
+    }
+}
+
+/// The map is described as an object. If the key enumerates its values as
+/// strings, like keys using `#[key(schema)]`, every such value is a property
+/// of the object. Keys with fields only contribute their unit variants, since
+/// other variants aren't enumerated as strings. Other properties are only
+/// disallowed if every variant of the key is a property, and are otherwise
+/// described by the schema of the value.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Clone, Copy, Key)]
+/// #[key(schema)]
+/// enum Part {
+///     One,
+///     Two,
+/// }
+///
+/// let schema = schemars::schema_for!(Map<Part, u32>);
+/// let object = schema.schema.object.unwrap();
+///
+/// assert!(object.properties.contains_key("One"));
+/// assert!(object.properties.contains_key("Two"));
+/// ```
+#[cfg(feature = "schemars")]
+impl<K, V> schemars::JsonSchema for Map<K, V>
+where
+    K: Key + schemars::JsonSchema,
+    V: schemars::JsonSchema,
+{
+    #[inline]
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    #[inline]
+    fn schema_name() -> std::string::String {
+        std::format!("Map_of_{}_and_{}", K::schema_name(), V::schema_name())
+    }
+
+    fn json_schema(generator: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        use schemars::schema::{InstanceType, ObjectValidation, Schema, SchemaObject};
+
+        let value = generator.subschema_for::<V>();
+        let mut object = ObjectValidation::default();
+
+        // The key schema is inspected directly, since a reference to it
+        // wouldn't tell us which values it enumerates.
+        let key = K::json_schema(generator);
+
+        let key = match &key {
+            Schema::Object(key) => Some(key),
+            Schema::Bool(..) => None,
+        };
+
+        let one_of = key
+            .and_then(|key| key.subschemas.as_ref())
+            .and_then(|subschemas| subschemas.one_of.as_ref());
+
+        let schemas =
+            key.into_iter().chain(
+                one_of
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|schema| match schema {
+                        Schema::Object(schema) => Some(schema),
+                        Schema::Bool(..) => None,
+                    }),
+            );
+
+        // Set if the key has values which aren't enumerated as strings.
+        let mut open = false;
+
+        for schema in schemas {
+            open |= schema.enum_values.is_none() && schema.subschemas.is_none();
+            let names = schema.enum_values.iter().flatten();
+
+            for name in names.filter_map(|name| name.as_str()) {
+                object.properties.insert(name.into(), value.clone());
+            }
+        }
+
+        object.additional_properties = Some(std::boxed::Box::new(
+            if open || object.properties.is_empty() {
+                value
+            } else {
+                Schema::Bool(false)
+            },
+        ));
+
+        Schema::Object(SchemaObject {
+            instance_type: Some(InstanceType::Object.into()),
+            object: Some(std::boxed::Box::new(object)),
+            ..SchemaObject::default()
+        })
+    }
+}
+
+/// Entries are generated one by one and inserted into the map, so a key which
+/// is generated more than once keeps the last value.
+#[cfg(feature = "arbitrary")]
+impl<'a, K, V> arbitrary::Arbitrary<'a> for Map<K, V>
+where
+    K: Key + arbitrary::Arbitrary<'a>,
+    V: arbitrary::Arbitrary<'a>,
+{
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_iter()?.collect()
+    }
+
+    #[inline]
+    fn arbitrary_take_rest(u: arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_take_rest_iter()?.collect()
+    }
+}
+
+/// Entries are generated as a sequence and inserted into the map, so a key
+/// which is generated more than once keeps the last value.
+#[cfg(feature = "proptest")]
+impl<K, V> proptest::arbitrary::Arbitrary for Map<K, V>
+where
+    K: Key + proptest::arbitrary::Arbitrary,
+    V: proptest::arbitrary::Arbitrary,
+{
+    type Parameters = (K::Parameters, V::Parameters);
+    type Strategy = proptest::strategy::Map<
+        proptest::collection::VecStrategy<(K::Strategy, V::Strategy)>,
+        fn(std::vec::Vec<(K, V)>) -> Self,
+    >;
+
+    #[inline]
+    fn arbitrary_with((key, value): Self::Parameters) -> Self::Strategy {
+        use proptest::arbitrary::any_with;
+        use proptest::strategy::Strategy as _;
+
+        let entries = (any_with::<K>(key), any_with::<V>(value));
+
+        proptest::collection::vec(entries, proptest::collection::SizeRange::default())
+            .prop_map(|entries| entries.into_iter().collect())
+    }
+}
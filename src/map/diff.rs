@@ -0,0 +1,499 @@
+use core::fmt;
+use core::iter;
+
+use crate::map::{MapStorage, Zip};
+use crate::{Key, Map};
+
+/// The iterator produced by [`Map::diff`].
+pub type Diff<'a, K, V> = iter::FilterMap<
+    Zip<'a, K, V, V>,
+    fn((K, Option<&'a V>, Option<&'a V>)) -> Option<Change<K, &'a V>>,
+>;
+
+/// A change to a single key, as produced by [`Map::diff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Change<K, V> {
+    /// The key was added with the given value.
+    Added(K, V),
+    /// The key was removed.
+    Removed(K),
+    /// The value of the key was changed to the given value.
+    Changed(K, V),
+}
+
+impl<K, V> Change<K, V> {
+    /// The key the change applies to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::Change;
+    ///
+    /// assert_eq!(Change::<_, u32>::Removed('a').key(), &'a');
+    /// ```
+    #[inline]
+    pub fn key(&self) -> &K {
+        match self {
+            Change::Added(key, _) | Change::Removed(key) | Change::Changed(key, _) => key,
+        }
+    }
+}
+
+impl<K, V> Change<K, &V> {
+    /// Construct a change which owns its value by cloning it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::Change;
+    ///
+    /// let value = String::from("hello");
+    /// assert_eq!(Change::Added(1, &value).cloned(), Change::Added(1, value.clone()));
+    /// ```
+    #[inline]
+    pub fn cloned(self) -> Change<K, V>
+    where
+        V: Clone,
+    {
+        match self {
+            Change::Added(key, value) => Change::Added(key, value.clone()),
+            Change::Removed(key) => Change::Removed(key),
+            Change::Changed(key, value) => Change::Changed(key, value.clone()),
+        }
+    }
+}
+
+impl<K, V> Map<K, V>
+where
+    K: Key,
+{
+    /// An iterator visiting the changes which turn this map into `other`.
+    ///
+    /// Both maps are walked in lockstep through [`Map::zip`], so changes are
+    /// produced in key order in a single pass. Only dynamic keys stored in a
+    /// `hashbrown` are looked up in the other map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::Change;
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    ///     Fourth,
+    /// }
+    ///
+    /// let mut a = Map::new();
+    /// a.insert(MyKey::First, 1);
+    /// a.insert(MyKey::Second, 2);
+    /// a.insert(MyKey::Fourth, 4);
+    ///
+    /// let mut b = a.clone();
+    /// b.remove(MyKey::First);
+    /// b.insert(MyKey::Second, 20);
+    /// b.insert(MyKey::Third, 3);
+    /// b.remove(MyKey::Fourth);
+    ///
+    /// assert!(a.diff(&b).eq([
+    ///     Change::Removed(MyKey::First),
+    ///     Change::Changed(MyKey::Second, &20),
+    ///     Change::Added(MyKey::Third, &3),
+    ///     Change::Removed(MyKey::Fourth),
+    /// ]));
+    /// ```
+    #[inline]
+    pub fn diff<'a>(&'a self, other: &'a Map<K, V>) -> Diff<'a, K, V>
+    where
+        V: PartialEq,
+    {
+        let change: fn(_) -> _ = |(key, a, b)| match (a, b) {
+            (None, Some(b)) => Some(Change::Added(key, b)),
+            (Some(_), None) => Some(Change::Removed(key)),
+            (Some(a), Some(b)) if a != b => Some(Change::Changed(key, b)),
+            _ => None,
+        };
+
+        self.zip(other).filter_map(change)
+    }
+
+    /// Apply every change in the patch to the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::{Change, MapPatch};
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let mut a = Map::new();
+    /// a.insert(MyKey::First, 1);
+    /// a.insert(MyKey::Second, 2);
+    ///
+    /// let mut b = Map::new();
+    /// b.insert(MyKey::Second, 20);
+    /// b.insert(MyKey::Third, 3);
+    ///
+    /// let patch: MapPatch<_, _> = a.diff(&b).map(Change::cloned).collect();
+    /// a.apply(patch);
+    ///
+    /// assert_eq!(a, b);
+    /// ```
+    #[inline]
+    pub fn apply(&mut self, patch: MapPatch<K, V>) {
+        for (key, value) in patch.changes {
+            match value {
+                Some(value) => {
+                    self.storage.insert(key, value);
+                }
+                None => {
+                    self.storage.remove(key);
+                }
+            }
+        }
+    }
+}
+
+/// A set of changes which can be applied to a [`Map`] with [`Map::apply`].
+///
+/// Only the last change to every key is retained, with added and changed keys
+/// stored as `Some` and removed keys stored as `None`. With the `serde`
+/// feature this is serialized the same way as a `Map<K, Option<V>>`.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::map::{Change, MapPatch};
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Setting {
+///     Volume,
+///     Brightness,
+/// }
+///
+/// let mut patch = MapPatch::new();
+/// patch.push(Change::Added(Setting::Volume, 10));
+/// patch.push(Change::Removed(Setting::Brightness));
+///
+/// let mut config = Map::new();
+/// config.insert(Setting::Brightness, 50);
+/// config.apply(patch);
+///
+/// assert!(config.into_iter().eq([(Setting::Volume, 10)]));
+/// ```
+pub struct MapPatch<K, V>
+where
+    K: Key,
+{
+    changes: Map<K, Option<V>>,
+}
+
+impl<K, V> MapPatch<K, V>
+where
+    K: Key,
+{
+    /// Creates an empty [`MapPatch`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::MapPatch;
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let patch = MapPatch::<MyKey, u32>::new();
+    /// assert!(patch.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> MapPatch<K, V> {
+        MapPatch {
+            changes: Map::new(),
+        }
+    }
+
+    /// Add a change to the patch, replacing any earlier change to the same
+    /// key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::{Change, MapPatch};
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut patch = MapPatch::new();
+    /// patch.push(Change::Added(MyKey::First, 1));
+    /// patch.push(Change::Removed(MyKey::First));
+    ///
+    /// assert_eq!(patch.get(MyKey::First), Some(None));
+    /// assert_eq!(patch.len(), 1);
+    /// ```
+    #[inline]
+    pub fn push(&mut self, change: Change<K, V>) {
+        match change {
+            Change::Added(key, value) | Change::Changed(key, value) => {
+                self.changes.insert(key, Some(value));
+            }
+            Change::Removed(key) => {
+                self.changes.insert(key, None);
+            }
+        }
+    }
+
+    /// Returns the change to the key, where `Some(None)` means that the key
+    /// is removed and `None` means that the key is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::{Change, MapPatch};
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut patch = MapPatch::new();
+    /// patch.push(Change::Changed(MyKey::First, 1));
+    ///
+    /// assert_eq!(patch.get(MyKey::First), Some(Some(&1)));
+    /// assert_eq!(patch.get(MyKey::Second), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, key: K) -> Option<Option<&V>> {
+        self.changes.get(key).map(Option::as_ref)
+    }
+
+    /// Returns the number of keys changed by the patch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::{Change, MapPatch};
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut patch = MapPatch::<_, u32>::new();
+    /// patch.push(Change::Removed(MyKey::Second));
+    ///
+    /// assert_eq!(patch.len(), 1);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns true if the patch doesn't change any key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::{Change, MapPatch};
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut patch = MapPatch::<_, u32>::new();
+    /// assert!(patch.is_empty());
+    ///
+    /// patch.push(Change::Removed(MyKey::Second));
+    /// assert!(!patch.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the changes as a map, where removed keys hold `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::{Change, MapPatch};
+    /// use fixed_map::Key;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut patch = MapPatch::new();
+    /// patch.push(Change::Added(MyKey::First, 1));
+    /// patch.push(Change::Removed(MyKey::Second));
+    ///
+    /// assert!(patch.as_map().iter().eq([(MyKey::First, &Some(1)), (MyKey::Second, &None)]));
+    /// ```
+    #[inline]
+    pub fn as_map(&self) -> &Map<K, Option<V>> {
+        &self.changes
+    }
+}
+
+impl<K, V> Default for MapPatch<K, V>
+where
+    K: Key,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Clone for MapPatch<K, V>
+where
+    K: Key,
+    K::MapStorage<Option<V>>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            changes: self.changes.clone(),
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for MapPatch<K, V>
+where
+    K: Key + fmt::Debug,
+    V: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.changes.fmt(f)
+    }
+}
+
+impl<K, V> PartialEq for MapPatch<K, V>
+where
+    K: Key,
+    K::MapStorage<Option<V>>: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.changes == other.changes
+    }
+}
+
+impl<K, V> Eq for MapPatch<K, V>
+where
+    K: Key,
+    K::MapStorage<Option<V>>: Eq,
+{
+}
+
+impl<K, V> FromIterator<Change<K, V>> for MapPatch<K, V>
+where
+    K: Key,
+{
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Change<K, V>>,
+    {
+        let mut patch = MapPatch::new();
+        patch.extend(iter);
+        patch
+    }
+}
+
+impl<K, V> Extend<Change<K, V>> for MapPatch<K, V>
+where
+    K: Key,
+{
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Change<K, V>>,
+    {
+        for change in iter {
+            self.push(change);
+        }
+    }
+}
+
+/// Construct a [`MapPatch`] from a map where removed keys hold `None`.
+impl<K, V> From<Map<K, Option<V>>> for MapPatch<K, V>
+where
+    K: Key,
+{
+    #[inline]
+    fn from(changes: Map<K, Option<V>>) -> Self {
+        MapPatch { changes }
+    }
+}
+
+/// Convert a [`MapPatch`] into a map where removed keys hold `None`.
+impl<K, V> From<MapPatch<K, V>> for Map<K, Option<V>>
+where
+    K: Key,
+{
+    #[inline]
+    fn from(patch: MapPatch<K, V>) -> Self {
+        patch.changes
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K, V> serde::Serialize for MapPatch<K, V>
+where
+    K: Key + serde::Serialize,
+    V: serde::Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.changes.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> serde::de::Deserialize<'de> for MapPatch<K, V>
+where
+    K: Key + serde::de::Deserialize<'de>,
+    V: serde::Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(MapPatch {
+            changes: Map::deserialize(deserializer)?,
+        })
+    }
+}
@@ -116,7 +116,7 @@ where
 
     /// Ensures a value is in the entry by inserting, if empty, the result of the default function.
     /// This method allows for generating key-derived values for insertion by providing the default
-    /// function a copy of the key that was passed to the `.entry(key)` method call.
+    /// function a clone of the key that was passed to the `.entry(key)` method call.
     ///
     /// # Examples
     ///
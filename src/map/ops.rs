@@ -0,0 +1,478 @@
+//! Element-wise arithmetic for maps, enabled through the `ops` feature.
+//!
+//! A map is treated as a vector indexed by its keys, where a missing key holds
+//! zero. Zero is taken to be [`Default::default`].
+
+use core::iter::Sum;
+use core::mem;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::map::{Map, MapStorage};
+use crate::Key;
+
+impl<K, V> Map<K, V>
+where
+    K: Key,
+{
+    /// Sum every value in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Resource {
+    ///     Wood,
+    ///     Stone,
+    ///     Gold,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// assert_eq!(map.sum(), 0.0);
+    ///
+    /// map.insert(Resource::Wood, 1.5);
+    /// map.insert(Resource::Gold, 2.0);
+    /// assert_eq!(map.sum(), 3.5);
+    /// ```
+    #[inline]
+    pub fn sum(&self) -> V
+    where
+        V: for<'a> Sum<&'a V>,
+    {
+        self.values().sum()
+    }
+
+    /// Calculate the dot product of two maps, where keys missing in either
+    /// map don't contribute to the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Resource {
+    ///     Wood,
+    ///     Stone,
+    ///     Gold,
+    /// }
+    ///
+    /// let mut amount = Map::new();
+    /// amount.insert(Resource::Wood, 10.0);
+    /// amount.insert(Resource::Stone, 4.0);
+    ///
+    /// let mut price = Map::new();
+    /// price.insert(Resource::Wood, 0.5);
+    /// price.insert(Resource::Gold, 100.0);
+    ///
+    /// assert_eq!(amount.dot(&price), 5.0);
+    /// ```
+    #[inline]
+    pub fn dot(&self, other: &Map<K, V>) -> V
+    where
+        V: Clone + Mul<Output = V> + Sum,
+    {
+        self.zip(other)
+            .filter_map(|(_, a, b)| Some(a?.clone() * b?.clone()))
+            .sum()
+    }
+
+    /// Get the entry with the smallest value, or `None` if the map is empty.
+    ///
+    /// If several entries hold the smallest value, the first one is returned.
+    /// Missing keys are not considered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Resource {
+    ///     Wood,
+    ///     Stone,
+    ///     Gold,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// assert_eq!(map.min_by_value(), None);
+    ///
+    /// map.insert(Resource::Wood, 2.0);
+    /// map.insert(Resource::Stone, 1.0);
+    /// map.insert(Resource::Gold, 1.0);
+    /// assert_eq!(map.min_by_value(), Some((Resource::Stone, &1.0)));
+    /// ```
+    #[inline]
+    pub fn min_by_value(&self) -> Option<(K, &V)>
+    where
+        V: PartialOrd,
+    {
+        let mut iter = self.iter();
+        let mut min = iter.next()?;
+
+        for entry in iter {
+            if entry.1 < min.1 {
+                min = entry;
+            }
+        }
+
+        Some(min)
+    }
+
+    /// Get the entry with the largest value, or `None` if the map is empty.
+    ///
+    /// If several entries hold the largest value, the first one is returned.
+    /// Missing keys are not considered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Resource {
+    ///     Wood,
+    ///     Stone,
+    ///     Gold,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// assert_eq!(map.max_by_value(), None);
+    ///
+    /// map.insert(Resource::Wood, 2.0);
+    /// map.insert(Resource::Stone, 1.0);
+    /// map.insert(Resource::Gold, 2.0);
+    /// assert_eq!(map.max_by_value(), Some((Resource::Wood, &2.0)));
+    /// ```
+    #[inline]
+    pub fn max_by_value(&self) -> Option<(K, &V)>
+    where
+        V: PartialOrd,
+    {
+        let mut iter = self.iter();
+        let mut max = iter.next()?;
+
+        for entry in iter {
+            if entry.1 > max.1 {
+                max = entry;
+            }
+        }
+
+        Some(max)
+    }
+}
+
+/// Add two maps element-wise, where a key missing in either map is treated as
+/// zero.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Resource {
+///     Wood,
+///     Stone,
+///     Gold,
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert(Resource::Wood, 1);
+/// a.insert(Resource::Stone, 2);
+///
+/// let mut b = Map::new();
+/// b.insert(Resource::Stone, 3);
+/// b.insert(Resource::Gold, 4);
+///
+/// let c = a + b;
+/// assert!(c.iter().eq([(Resource::Wood, &1), (Resource::Stone, &5), (Resource::Gold, &4)]));
+/// ```
+impl<K, V> Add for Map<K, V>
+where
+    K: Key,
+    V: Add<Output = V>,
+{
+    type Output = Map<K, V>;
+
+    #[inline]
+    fn add(self, rhs: Map<K, V>) -> Self::Output {
+        self.union_with(rhs, |_, a, b| a + b)
+    }
+}
+
+/// Subtract two maps element-wise, where a key missing in either map is
+/// treated as zero.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Resource {
+///     Wood,
+///     Stone,
+///     Gold,
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert(Resource::Wood, 1);
+/// a.insert(Resource::Stone, 2);
+///
+/// let mut b = Map::new();
+/// b.insert(Resource::Stone, 3);
+/// b.insert(Resource::Gold, 4);
+///
+/// let c = a - b;
+/// assert!(c.iter().eq([(Resource::Wood, &1), (Resource::Stone, &-1), (Resource::Gold, &-4)]));
+/// ```
+impl<K, V> Sub for Map<K, V>
+where
+    K: Key,
+    V: Default + Sub<Output = V>,
+{
+    type Output = Map<K, V>;
+
+    #[inline]
+    fn sub(self, rhs: Map<K, V>) -> Self::Output {
+        Map {
+            storage: self.storage.merge(rhs.storage, |_, a, b| match (a, b) {
+                (Some(a), Some(b)) => Some(a - b),
+                (Some(a), None) => Some(a),
+                (None, Some(b)) => Some(V::default() - b),
+                (None, None) => None,
+            }),
+        }
+    }
+}
+
+/// Negate every value in the map.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Resource {
+///     Wood,
+///     Stone,
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert(Resource::Wood, 1.5);
+///
+/// assert!((-a).iter().eq([(Resource::Wood, &-1.5)]));
+/// ```
+impl<K, V> Neg for Map<K, V>
+where
+    K: Key,
+    V: Neg<Output = V>,
+{
+    type Output = Map<K, V>;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        self.map_values(|_, value| -value)
+    }
+}
+
+/// Multiply every value in the map by a scalar.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Resource {
+///     Wood,
+///     Stone,
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert(Resource::Wood, 1.5);
+/// a.insert(Resource::Stone, 2.0);
+///
+/// assert!((a * 2.0).values().eq(&[3.0, 4.0]));
+/// ```
+impl<K, V> Mul<V> for Map<K, V>
+where
+    K: Key,
+    V: Clone + Mul<Output = V>,
+{
+    type Output = Map<K, V>;
+
+    #[inline]
+    fn mul(self, rhs: V) -> Self::Output {
+        self.map_values(|_, value| value * rhs.clone())
+    }
+}
+
+/// Divide every value in the map by a scalar.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Resource {
+///     Wood,
+///     Stone,
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert(Resource::Wood, 3.0);
+/// a.insert(Resource::Stone, 4.0);
+///
+/// assert!((a / 2.0).values().eq(&[1.5, 2.0]));
+/// ```
+impl<K, V> Div<V> for Map<K, V>
+where
+    K: Key,
+    V: Clone + Div<Output = V>,
+{
+    type Output = Map<K, V>;
+
+    #[inline]
+    fn div(self, rhs: V) -> Self::Output {
+        self.map_values(|_, value| value / rhs.clone())
+    }
+}
+
+/// Add another map element-wise in place, where a key missing in either map
+/// is treated as zero.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Resource {
+///     Wood,
+///     Stone,
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert(Resource::Wood, 1);
+///
+/// let mut b = Map::new();
+/// b.insert(Resource::Wood, 2);
+/// b.insert(Resource::Stone, 3);
+///
+/// a += b;
+/// assert!(a.iter().eq([(Resource::Wood, &3), (Resource::Stone, &3)]));
+/// ```
+impl<K, V> AddAssign for Map<K, V>
+where
+    K: Key,
+    V: Add<Output = V>,
+{
+    #[inline]
+    fn add_assign(&mut self, rhs: Map<K, V>) {
+        *self = mem::take(self) + rhs;
+    }
+}
+
+/// Subtract another map element-wise in place, where a key missing in either
+/// map is treated as zero.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Resource {
+///     Wood,
+///     Stone,
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert(Resource::Wood, 1);
+///
+/// let mut b = Map::new();
+/// b.insert(Resource::Wood, 2);
+/// b.insert(Resource::Stone, 3);
+///
+/// a -= b;
+/// assert!(a.iter().eq([(Resource::Wood, &-1), (Resource::Stone, &-3)]));
+/// ```
+impl<K, V> SubAssign for Map<K, V>
+where
+    K: Key,
+    V: Default + Sub<Output = V>,
+{
+    #[inline]
+    fn sub_assign(&mut self, rhs: Map<K, V>) {
+        *self = mem::take(self) - rhs;
+    }
+}
+
+/// Multiply every value in the map by a scalar in place.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Resource {
+///     Wood,
+///     Stone,
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert(Resource::Stone, 3);
+///
+/// a *= 2;
+/// assert!(a.iter().eq([(Resource::Stone, &6)]));
+/// ```
+impl<K, V> MulAssign<V> for Map<K, V>
+where
+    K: Key,
+    V: Clone + MulAssign,
+{
+    #[inline]
+    fn mul_assign(&mut self, rhs: V) {
+        for value in self.values_mut() {
+            *value *= rhs.clone();
+        }
+    }
+}
+
+/// Divide every value in the map by a scalar in place.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Resource {
+///     Wood,
+///     Stone,
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert(Resource::Stone, 3.0);
+///
+/// a /= 2.0;
+/// assert!(a.iter().eq([(Resource::Stone, &1.5)]));
+/// ```
+impl<K, V> DivAssign<V> for Map<K, V>
+where
+    K: Key,
+    V: Clone + DivAssign,
+{
+    #[inline]
+    fn div_assign(&mut self, rhs: V) {
+        for value in self.values_mut() {
+            *value /= rhs.clone();
+        }
+    }
+}
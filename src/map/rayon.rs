@@ -0,0 +1,200 @@
+//! Parallel iteration over maps, enabled through the `rayon` feature.
+
+use ::rayon::iter::IntoParallelIterator;
+
+use crate::map::{Map, ParallelMapStorage};
+use crate::Key;
+
+/// The parallel iterator produced by [`Map::par_iter`].
+pub type ParIter<'a, K, V> = <<K as Key>::MapStorage<V> as ParallelMapStorage<K, V>>::ParIter<'a>;
+
+/// The parallel iterator produced by [`Map::par_iter_mut`].
+pub type ParIterMut<'a, K, V> =
+    <<K as Key>::MapStorage<V> as ParallelMapStorage<K, V>>::ParIterMut<'a>;
+
+/// The parallel iterator produced by [`Map::par_values_mut`].
+pub type ParValuesMut<'a, K, V> =
+    <<K as Key>::MapStorage<V> as ParallelMapStorage<K, V>>::ParValuesMut<'a>;
+
+/// The parallel iterator produced by [`Map::into_par_iter`].
+pub type IntoParIter<K, V> = <<K as Key>::MapStorage<V> as ParallelMapStorage<K, V>>::IntoParIter;
+
+impl<K, V> Map<K, V>
+where
+    K: Key,
+    K::MapStorage<V>: ParallelMapStorage<K, V>,
+{
+    /// A parallel iterator visiting all key-value pairs.
+    ///
+    /// Work is split by slot for keys with a fixed layout, and by bucket for
+    /// dynamic keys, so no intermediate collection is built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use rayon::prelude::*;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum System {
+    ///     Physics,
+    ///     Audio,
+    ///     Render,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(System::Physics, 1);
+    /// map.insert(System::Render, 3);
+    ///
+    /// let sum: i32 = map.par_iter().map(|(_, v)| *v).sum();
+    /// assert_eq!(sum, 4);
+    ///
+    /// let entries: Vec<_> = map.par_iter().collect();
+    /// assert_eq!(entries, vec![(System::Physics, &1), (System::Render, &3)]);
+    /// ```
+    #[inline]
+    pub fn par_iter(&self) -> ParIter<'_, K, V> {
+        self.storage.par_iter()
+    }
+
+    /// A parallel iterator visiting all key-value pairs, with mutable
+    /// references to the values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use rayon::prelude::*;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum System {
+    ///     Physics,
+    ///     Audio,
+    ///     Render,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(System::Physics, 1);
+    /// map.insert(System::Audio, 2);
+    ///
+    /// map.par_iter_mut().for_each(|(system, state)| {
+    ///     if system == System::Audio {
+    ///         *state *= 10;
+    ///     }
+    /// });
+    ///
+    /// assert!(map.iter().eq([(System::Physics, &1), (System::Audio, &20)]));
+    /// ```
+    #[inline]
+    pub fn par_iter_mut(&mut self) -> ParIterMut<'_, K, V> {
+        self.storage.par_iter_mut()
+    }
+
+    /// A parallel iterator visiting all values mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use rayon::prelude::*;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum System {
+    ///     Physics,
+    ///     Audio,
+    ///     Render,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(System::Physics, 1);
+    /// map.insert(System::Render, 3);
+    ///
+    /// map.par_values_mut().for_each(|state| *state += 1);
+    ///
+    /// assert!(map.values().copied().eq([2, 4]));
+    /// ```
+    #[inline]
+    pub fn par_values_mut(&mut self) -> ParValuesMut<'_, K, V> {
+        self.storage.par_values_mut()
+    }
+
+    /// Converts the map into a parallel iterator over its key-value pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use rayon::prelude::*;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Part {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum System {
+    ///     Physics(Part),
+    ///     Audio,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(System::Physics(Part::Two), 1);
+    /// map.insert(System::Audio, 2);
+    ///
+    /// let entries: Vec<_> = map.into_par_iter().collect();
+    /// assert_eq!(entries, vec![(System::Physics(Part::Two), 1), (System::Audio, 2)]);
+    /// ```
+    #[inline]
+    pub fn into_par_iter(self) -> IntoParIter<K, V> {
+        ParallelMapStorage::into_par_iter(self.storage)
+    }
+}
+
+impl<K, V> IntoParallelIterator for Map<K, V>
+where
+    K: Key,
+    K::MapStorage<V>: ParallelMapStorage<K, V>,
+    K: Send,
+    V: Send,
+{
+    type Item = (K, V);
+    type Iter = IntoParIter<K, V>;
+
+    #[inline]
+    fn into_par_iter(self) -> Self::Iter {
+        ParallelMapStorage::into_par_iter(self.storage)
+    }
+}
+
+impl<'a, K, V> IntoParallelIterator for &'a Map<K, V>
+where
+    K: Key,
+    K::MapStorage<V>: ParallelMapStorage<K, V>,
+    K: Send,
+    V: Sync,
+{
+    type Item = (K, &'a V);
+    type Iter = ParIter<'a, K, V>;
+
+    #[inline]
+    fn into_par_iter(self) -> Self::Iter {
+        self.storage.par_iter()
+    }
+}
+
+impl<'a, K, V> IntoParallelIterator for &'a mut Map<K, V>
+where
+    K: Key,
+    K::MapStorage<V>: ParallelMapStorage<K, V>,
+    K: Send,
+    V: Send,
+{
+    type Item = (K, &'a mut V);
+    type Iter = ParIterMut<'a, K, V>;
+
+    #[inline]
+    fn into_par_iter(self) -> Self::Iter {
+        self.storage.par_iter_mut()
+    }
+}
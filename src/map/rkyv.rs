@@ -0,0 +1,168 @@
+//! Zero-copy archiving of maps, enabled through the `rkyv` feature.
+
+use core::fmt;
+
+use ::rkyv::{out_field, Archive, Deserialize, Fallible, Serialize};
+
+use crate::map::{ArchiveMapStorage, ArchivedMapStorage, Map};
+use crate::Key;
+
+/// The iterator produced by [`ArchivedMap::iter`].
+pub type ArchivedIter<'a, K, V> = <ArchivedStorage<K, V> as ArchivedMapStorage<K, V>>::Iter<'a>;
+
+type ArchivedStorage<K, V> = <<K as Key>::MapStorage<V> as ArchiveMapStorage<K, V>>::Archived;
+
+/// The archived form of a [`Map`].
+///
+/// This has the same layout as the storage of the map, so entries are looked
+/// up directly in the archive without deserializing it. For keys deriving
+/// [`Key`][derive@crate::Key] with unit variants, this is an array of archived
+/// optional values with one slot for every variant.
+///
+/// Archives read from untrusted sources, such as memory-mapped files, should be
+/// validated with [`rkyv::check_archived_root`] before they are used. This
+/// requires the `rkyv-validation` feature, which checks that every archived
+/// map storage only marks slots that correspond to a key.
+///
+/// [`rkyv::check_archived_root`]: https://docs.rs/rkyv/0.7/rkyv/validation/validators/fn.check_archived_root.html
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// # #[cfg(feature = "rkyv-validation")] {
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Part {
+///     One,
+///     Two,
+///     Three,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Part::One, 1u32);
+/// map.insert(Part::Three, 3u32);
+///
+/// let bytes = rkyv::to_bytes::<_, 256>(&map).unwrap();
+/// let archived = rkyv::check_archived_root::<Map<Part, u32>>(&bytes).unwrap();
+///
+/// assert_eq!(archived.get(Part::One), Some(&1));
+/// assert_eq!(archived.get(Part::Two), None);
+/// assert_eq!(archived.len(), 2);
+/// # }
+/// ```
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "rkyv-validation",
+    derive(::rkyv::CheckBytes),
+    check_bytes(crate = "::rkyv::bytecheck")
+)]
+pub struct ArchivedMap<K, V>
+where
+    K: Key,
+    V: Archive,
+    K::MapStorage<V>: ArchiveMapStorage<K, V>,
+{
+    storage: ArchivedStorage<K, V>,
+}
+
+impl<K, V> ArchivedMap<K, V>
+where
+    K: Key,
+    V: Archive,
+    K::MapStorage<V>: ArchiveMapStorage<K, V>,
+{
+    /// Returns a reference to the archived value corresponding to the key.
+    #[inline]
+    pub fn get(&self, key: K) -> Option<&V::Archived> {
+        self.storage.get(key)
+    }
+
+    /// Returns `true` if the archived map contains a value for the specified
+    /// key.
+    #[inline]
+    pub fn contains_key(&self, key: K) -> bool {
+        self.storage.get(key).is_some()
+    }
+
+    /// Returns the number of elements in the archived map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Returns true if the archived map contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+
+    /// An iterator visiting all keys and archived values in the order of the
+    /// original map.
+    #[inline]
+    pub fn iter(&self) -> ArchivedIter<'_, K, V> {
+        self.storage.iter()
+    }
+}
+
+impl<K, V> fmt::Debug for ArchivedMap<K, V>
+where
+    K: Key + fmt::Debug,
+    V: Archive,
+    V::Archived: fmt::Debug,
+    K::MapStorage<V>: ArchiveMapStorage<K, V>,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> Archive for Map<K, V>
+where
+    K: Key,
+    V: Archive,
+    K::MapStorage<V>: ArchiveMapStorage<K, V>,
+{
+    type Archived = ArchivedMap<K, V>;
+    type Resolver = <K::MapStorage<V> as ArchiveMapStorage<K, V>>::Resolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (offset, out) = out_field!(out.storage);
+        self.storage.resolve(pos + offset, resolver, out);
+    }
+}
+
+impl<K, V, S> Serialize<S> for Map<K, V>
+where
+    K: Key,
+    V: Serialize<S>,
+    K::MapStorage<V>: ArchiveMapStorage<K, V>,
+    S: Fallible + ?Sized,
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.storage.serialize(serializer)
+    }
+}
+
+impl<K, V, D> Deserialize<Map<K, V>, D> for ArchivedMap<K, V>
+where
+    K: Key,
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
+    K::MapStorage<V>: ArchiveMapStorage<K, V>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Map<K, V>, D::Error> {
+        let mut map = Map::new();
+
+        for (key, value) in self.iter() {
+            map.insert(key, value.deserialize(deserializer)?);
+        }
+
+        Ok(map)
+    }
+}
@@ -3,18 +3,47 @@
 mod boolean;
 pub(crate) use self::boolean::BooleanMapStorage;
 
+mod byte;
+pub(crate) use self::byte::ByteMapStorage;
+
+mod compact;
+pub use self::compact::{Bits, CompactMapStorage};
+
+mod empty;
+pub(crate) use self::empty::EmptyMapStorage;
+
 #[cfg(feature = "hashbrown")]
 mod hashbrown;
 #[cfg(feature = "hashbrown")]
-pub(crate) use self::hashbrown::HashbrownMapStorage;
+pub(crate) use self::hashbrown::{hash_unordered, HashbrownMapStorage};
 
 mod option;
 pub(crate) use self::option::OptionMapStorage;
 
+mod ordering;
+pub(crate) use self::ordering::OrderingMapStorage;
+
+mod product;
+pub(crate) use self::product::{ProductIter, ProductMapStorage};
+
+mod result;
+pub(crate) use self::result::ResultMapStorage;
+
 mod singleton;
 pub(crate) use self::singleton::SingletonMapStorage;
 
+mod zip;
+pub use self::zip::{zip_slots, ZipSlots};
+
+use core::iter::FusedIterator;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelIterator;
+#[cfg(feature = "rkyv")]
+use rkyv::{Archive, Fallible, Serialize};
+
 use crate::map::Entry;
+use crate::Key;
 
 /// The trait defining how storage works.
 ///
@@ -24,36 +53,43 @@ use crate::map::Entry;
 /// - `V` is the value being stored.
 pub trait MapStorage<K, V>: Sized {
     /// Immutable iterator over storage.
-    type Iter<'this>: Iterator<Item = (K, &'this V)>
+    type Iter<'this>: Iterator<Item = (K, &'this V)> + ExactSizeIterator + FusedIterator
     where
         Self: 'this,
         V: 'this;
 
     /// Immutable iterator over keys in storage.
-    type Keys<'this>: Iterator<Item = K>
+    type Keys<'this>: Iterator<Item = K> + ExactSizeIterator + FusedIterator
     where
         Self: 'this;
 
     /// Immutable iterator over values in storage.
-    type Values<'this>: Iterator<Item = &'this V>
+    type Values<'this>: Iterator<Item = &'this V> + ExactSizeIterator + FusedIterator
     where
         Self: 'this,
         V: 'this;
 
     /// Mutable iterator over storage.
-    type IterMut<'this>: Iterator<Item = (K, &'this mut V)>
+    type IterMut<'this>: Iterator<Item = (K, &'this mut V)> + ExactSizeIterator + FusedIterator
     where
         Self: 'this,
         V: 'this;
 
     /// Mutable iterator over values in storage.
-    type ValuesMut<'this>: Iterator<Item = &'this mut V>
+    type ValuesMut<'this>: Iterator<Item = &'this mut V> + ExactSizeIterator + FusedIterator
     where
         Self: 'this,
         V: 'this;
 
     /// Consuming iterator.
-    type IntoIter: Iterator<Item = (K, V)>;
+    type IntoIter: Iterator<Item = (K, V)> + ExactSizeIterator + FusedIterator;
+
+    /// Iterator walking two storages in lockstep.
+    type Zip<'this, U>: Iterator<Item = (K, Option<&'this V>, Option<&'this U>)> + FusedIterator
+    where
+        Self: 'this,
+        V: 'this,
+        U: 'this;
 
     /// An occupied entry.
     type Occupied<'this>: OccupiedEntry<'this, K, V>
@@ -89,6 +125,26 @@ pub trait MapStorage<K, V>: Sized {
     /// This is the storage abstraction for [`Map::remove`][crate::Map::remove].
     fn remove(&mut self, key: K) -> Option<V>;
 
+    /// This is the storage abstraction for [`Map::contains_key_by`][crate::Map::contains_key_by].
+    fn contains_key_by(&self, key: K::Ref<'_>) -> bool
+    where
+        K: Key;
+
+    /// This is the storage abstraction for [`Map::get_by`][crate::Map::get_by].
+    fn get_by(&self, key: K::Ref<'_>) -> Option<&V>
+    where
+        K: Key;
+
+    /// This is the storage abstraction for [`Map::get_mut_by`][crate::Map::get_mut_by].
+    fn get_mut_by(&mut self, key: K::Ref<'_>) -> Option<&mut V>
+    where
+        K: Key;
+
+    /// This is the storage abstraction for [`Map::remove_by`][crate::Map::remove_by].
+    fn remove_by(&mut self, key: K::Ref<'_>) -> Option<V>
+    where
+        K: Key;
+
     /// This is the storage abstraction for [`Map::retain`][crate::Map::retain].
     fn retain<F>(&mut self, f: F)
     where
@@ -117,12 +173,196 @@ pub trait MapStorage<K, V>: Sized {
 
     /// This is the storage abstraction for [`Map::entry`][crate::Map::entry].
     fn entry(&mut self, key: K) -> Entry<'_, Self, K, V>;
+
+    /// This is the storage abstraction for [`Map::zip`][crate::Map::zip].
+    ///
+    /// Storages with a fixed layout walk their slots and the slots of `other`
+    /// in key order, while dynamic keys look up every key in `other`.
+    fn zip<'this, U>(&'this self, other: &'this K::MapStorage<U>) -> Self::Zip<'this, U>
+    where
+        K: Key,
+        U: 'this;
+
+    /// This is the storage abstraction for [`Map::map_values`][crate::Map::map_values].
+    ///
+    /// The default implementation inserts every mapped value into empty
+    /// storage. Storages with a fixed layout override it to map every slot in
+    /// place.
+    #[inline]
+    fn map_values<U, F>(self, mut f: F) -> K::MapStorage<U>
+    where
+        K: Key,
+        F: FnMut(K, V) -> U,
+    {
+        let mut output = K::MapStorage::<U>::empty();
+
+        for (key, value) in MapStorage::into_iter(self) {
+            let value = f(key.clone(), value);
+            output.insert(key, value);
+        }
+
+        output
+    }
+
+    /// Combine two storages key-wise, calling `f` once for every key which is
+    /// present in either of them and storing the value it returns. This is
+    /// the storage abstraction for [`Map::union_with`][crate::Map::union_with]
+    /// and [`Map::intersection_with`][crate::Map::intersection_with].
+    ///
+    /// The default implementation looks up every key of `self` in `other`.
+    /// Storages with a fixed layout override it to walk both storages in
+    /// lockstep.
+    #[inline]
+    fn merge<U, W, F>(self, mut other: K::MapStorage<U>, mut f: F) -> K::MapStorage<W>
+    where
+        K: Key,
+        F: FnMut(K, Option<V>, Option<U>) -> Option<W>,
+    {
+        let mut output = K::MapStorage::<W>::empty();
+
+        for (key, a) in MapStorage::into_iter(self) {
+            let b = other.remove(key.clone());
+
+            if let Some(value) = f(key.clone(), Some(a), b) {
+                output.insert(key, value);
+            }
+        }
+
+        for (key, b) in MapStorage::into_iter(other) {
+            if let Some(value) = f(key.clone(), None, Some(b)) {
+                output.insert(key, value);
+            }
+        }
+
+        output
+    }
+}
+
+/// The trait defining how storage is iterated over in parallel, which is
+/// enabled through the `rayon` feature.
+///
+/// Storages with a fixed layout split their work by slot, while dynamic keys
+/// use the parallel iterators of their backing `hashbrown` map. Tuple keys
+/// don't implement it.
+///
+/// # Type Arguments
+///
+/// - `K` is the key being stored.
+/// - `V` is the value being stored.
+#[cfg(feature = "rayon")]
+pub trait ParallelMapStorage<K, V>: MapStorage<K, V> {
+    /// Immutable parallel iterator over storage.
+    type ParIter<'this>: ParallelIterator<Item = (K, &'this V)>
+    where
+        Self: 'this,
+        V: 'this;
+
+    /// Mutable parallel iterator over storage.
+    type ParIterMut<'this>: ParallelIterator<Item = (K, &'this mut V)>
+    where
+        Self: 'this,
+        V: 'this;
+
+    /// Mutable parallel iterator over values in storage.
+    type ParValuesMut<'this>: ParallelIterator<Item = &'this mut V>
+    where
+        Self: 'this,
+        V: 'this;
+
+    /// Consuming parallel iterator.
+    type IntoParIter: ParallelIterator<Item = (K, V)>;
+
+    /// This is the storage abstraction for [`Map::par_iter`][crate::Map::par_iter].
+    fn par_iter(&self) -> Self::ParIter<'_>;
+
+    /// This is the storage abstraction for [`Map::par_iter_mut`][crate::Map::par_iter_mut].
+    fn par_iter_mut(&mut self) -> Self::ParIterMut<'_>;
+
+    /// This is the storage abstraction for [`Map::par_values_mut`][crate::Map::par_values_mut].
+    fn par_values_mut(&mut self) -> Self::ParValuesMut<'_>;
+
+    /// This is the storage abstraction for [`Map::into_par_iter`][crate::Map::into_par_iter].
+    fn into_par_iter(self) -> Self::IntoParIter;
+}
+
+/// The trait defining how storage is archived through `rkyv`, which is enabled
+/// through the `rkyv` feature.
+///
+/// The archived storage keeps the layout of the storage, so that it can be
+/// accessed directly through [`ArchivedMapStorage`]. Tuple keys and keys with
+/// dynamic storage don't implement it.
+///
+/// # Type Arguments
+///
+/// - `K` is the key being stored.
+/// - `V` is the value being stored.
+#[cfg(feature = "rkyv")]
+pub trait ArchiveMapStorage<K, V>: MapStorage<K, V>
+where
+    V: Archive,
+{
+    /// The archived form of the storage.
+    type Archived: ArchivedMapStorage<K, V>;
+
+    /// The resolver of the archived storage.
+    type Resolver;
+
+    /// Serialize the values of the storage, returning a resolver for it.
+    ///
+    /// # Errors
+    ///
+    /// Errors if any value fails to serialize.
+    fn serialize<S>(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+        V: Serialize<S>;
+
+    /// Create the archived storage at the given position.
+    ///
+    /// # Safety
+    ///
+    /// This has the same requirements as [`Archive::resolve`].
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived);
+}
+
+/// The trait defining how archived storage is accessed, which is enabled
+/// through the `rkyv` feature.
+///
+/// # Type Arguments
+///
+/// - `K` is the key being stored.
+/// - `V` is the value being stored.
+#[cfg(feature = "rkyv")]
+pub trait ArchivedMapStorage<K, V>
+where
+    V: Archive,
+{
+    /// Immutable iterator over archived storage.
+    type Iter<'this>: Iterator<Item = (K, &'this V::Archived)>
+    where
+        Self: 'this,
+        V: 'this;
+
+    /// This is the storage abstraction for [`ArchivedMap::len`][crate::map::ArchivedMap::len].
+    fn len(&self) -> usize;
+
+    /// This is the storage abstraction for [`ArchivedMap::is_empty`][crate::map::ArchivedMap::is_empty].
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// This is the storage abstraction for [`ArchivedMap::get`][crate::map::ArchivedMap::get].
+    fn get(&self, key: K) -> Option<&V::Archived>;
+
+    /// This is the storage abstraction for [`ArchivedMap::iter`][crate::map::ArchivedMap::iter].
+    fn iter(&self) -> Self::Iter<'_>;
 }
 
 /// A view into an occupied entry in a [`Map`][crate::Map]. It is part of the
 /// [`Entry`] enum.
 pub trait OccupiedEntry<'a, K, V> {
-    /// Gets a copy of the key in the entry.
+    /// Gets a clone of the key in the entry.
     ///
     /// # Examples
     ///
@@ -424,7 +664,7 @@ pub trait OccupiedEntry<'a, K, V> {
 /// A view into a vacant entry in a [`Map`][crate::Map].
 /// It is part of the [`Entry`] enum.
 pub trait VacantEntry<'a, K, V> {
-    /// Gets a copy of the key that would be used
+    /// Gets a clone of the key that would be used
     /// when inserting a value through the `VacantEntry`.
     ///
     /// # Examples
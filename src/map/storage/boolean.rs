@@ -2,30 +2,51 @@
 
 #![allow(missing_copy_implementations)]
 
-use core::iter;
-use core::mem;
+use core::array;
+use core::iter::{self, FusedIterator};
 use core::option;
 
+#[cfg(feature = "rayon")]
+use rayon::iter::{
+    IntoParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
+};
+#[cfg(feature = "rkyv")]
+use rkyv::{out_field, Archive, Archived, Fallible, Resolver, Serialize};
+
+use crate::exact_iter::ExactIter;
+use crate::map::storage::{zip_slots, ZipSlots};
+#[cfg(feature = "rayon")]
+use crate::map::ParallelMapStorage;
+#[cfg(feature = "rkyv")]
+use crate::map::{ArchiveMapStorage, ArchivedMapStorage};
 use crate::map::{Entry, MapStorage, OccupiedEntry, VacantEntry};
 use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
 
 const TRUE_BIT: u8 = 0b10;
 const FALSE_BIT: u8 = 0b01;
 
-type Iter<'a, V> = iter::Chain<
-    iter::Map<option::Iter<'a, V>, fn(&'a V) -> (bool, &'a V)>,
-    iter::Map<option::Iter<'a, V>, fn(&'a V) -> (bool, &'a V)>,
+type Iter<'a, V> = ExactIter<
+    iter::Chain<
+        iter::Map<option::Iter<'a, V>, fn(&'a V) -> (bool, &'a V)>,
+        iter::Map<option::Iter<'a, V>, fn(&'a V) -> (bool, &'a V)>,
+    >,
 >;
-type Values<'a, V> = iter::Chain<option::Iter<'a, V>, option::Iter<'a, V>>;
-type IterMut<'a, V> = iter::Chain<
-    iter::Map<option::IterMut<'a, V>, fn(&'a mut V) -> (bool, &'a mut V)>,
-    iter::Map<option::IterMut<'a, V>, fn(&'a mut V) -> (bool, &'a mut V)>,
+type Values<'a, V> = ExactIter<iter::Chain<option::Iter<'a, V>, option::Iter<'a, V>>>;
+type IterMut<'a, V> = ExactIter<
+    iter::Chain<
+        iter::Map<option::IterMut<'a, V>, fn(&'a mut V) -> (bool, &'a mut V)>,
+        iter::Map<option::IterMut<'a, V>, fn(&'a mut V) -> (bool, &'a mut V)>,
+    >,
 >;
-type ValuesMut<'a, V> = iter::Chain<option::IterMut<'a, V>, option::IterMut<'a, V>>;
-type IntoIter<V> = iter::Chain<
-    iter::Map<option::IntoIter<V>, fn(V) -> (bool, V)>,
-    iter::Map<option::IntoIter<V>, fn(V) -> (bool, V)>,
+type ValuesMut<'a, V> = ExactIter<iter::Chain<option::IterMut<'a, V>, option::IterMut<'a, V>>>;
+type IntoIter<V> = ExactIter<
+    iter::Chain<
+        iter::Map<option::IntoIter<V>, fn(V) -> (bool, V)>,
+        iter::Map<option::IntoIter<V>, fn(V) -> (bool, V)>,
+    >,
 >;
+type Zip<'a, V, U> =
+    ZipSlots<'a, array::IntoIter<(bool, Option<&'a V>, Option<&'a U>), 2>, bool, V, U>;
 
 /// [`MapStorage`] for [`bool`] types.
 ///
@@ -138,6 +159,8 @@ impl ExactSizeIterator for Keys {
     }
 }
 
+impl FusedIterator for Keys {}
+
 pub struct Vacant<'a, V> {
     key: bool,
     inner: NoneBucket<'a, V>,
@@ -214,6 +237,11 @@ impl<V> MapStorage<bool, V> for BooleanMapStorage<V> {
     where
         V: 'this;
     type IntoIter = IntoIter<V>;
+    type Zip<'this, U>
+        = Zip<'this, V, U>
+    where
+        V: 'this,
+        U: 'this;
     type Occupied<'this>
         = Occupied<'this, V>
     where
@@ -244,9 +272,9 @@ impl<V> MapStorage<bool, V> for BooleanMapStorage<V> {
     #[inline]
     fn insert(&mut self, key: bool, value: V) -> Option<V> {
         if key {
-            mem::replace(&mut self.t, Some(value))
+            self.t.replace(value)
         } else {
-            mem::replace(&mut self.f, Some(value))
+            self.f.replace(value)
         }
     }
 
@@ -286,6 +314,26 @@ impl<V> MapStorage<bool, V> for BooleanMapStorage<V> {
         }
     }
 
+    #[inline]
+    fn contains_key_by(&self, key: bool) -> bool {
+        self.contains_key(key)
+    }
+
+    #[inline]
+    fn get_by(&self, key: bool) -> Option<&V> {
+        self.get(key)
+    }
+
+    #[inline]
+    fn get_mut_by(&mut self, key: bool) -> Option<&mut V> {
+        self.get_mut(key)
+    }
+
+    #[inline]
+    fn remove_by(&mut self, key: bool) -> Option<V> {
+        self.remove(key)
+    }
+
     #[inline]
     fn retain<F>(&mut self, mut func: F)
     where
@@ -315,7 +363,7 @@ impl<V> MapStorage<bool, V> for BooleanMapStorage<V> {
         let a = self.t.iter().map(map);
         let map: fn(_) -> _ = |v| (false, v);
         let b = self.f.iter().map(map);
-        a.chain(b)
+        ExactIter::new(a.chain(b), self.len())
     }
 
     #[inline]
@@ -328,30 +376,33 @@ impl<V> MapStorage<bool, V> for BooleanMapStorage<V> {
 
     #[inline]
     fn values(&self) -> Self::Values<'_> {
-        self.t.iter().chain(self.f.iter())
+        ExactIter::new(self.t.iter().chain(self.f.iter()), self.len())
     }
 
     #[inline]
     fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let len = self.len();
         let map: fn(_) -> _ = |v| (true, v);
         let a = self.t.iter_mut().map(map);
         let map: fn(_) -> _ = |v| (false, v);
         let b = self.f.iter_mut().map(map);
-        a.chain(b)
+        ExactIter::new(a.chain(b), len)
     }
 
     #[inline]
     fn values_mut(&mut self) -> Self::ValuesMut<'_> {
-        self.t.iter_mut().chain(self.f.iter_mut())
+        let len = self.len();
+        ExactIter::new(self.t.iter_mut().chain(self.f.iter_mut()), len)
     }
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
         let map: fn(_) -> _ = |v| (true, v);
         let a = self.t.into_iter().map(map);
         let map: fn(_) -> _ = |v| (false, v);
         let b = self.f.into_iter().map(map);
-        a.chain(b)
+        ExactIter::new(a.chain(b), len)
     }
 
     #[inline]
@@ -368,4 +419,198 @@ impl<V> MapStorage<bool, V> for BooleanMapStorage<V> {
             }
         }
     }
+
+    #[inline]
+    fn zip<'this, U>(&'this self, other: &'this BooleanMapStorage<U>) -> Self::Zip<'this, U>
+    where
+        U: 'this,
+    {
+        zip_slots([
+            (true, self.t.as_ref(), other.t.as_ref()),
+            (false, self.f.as_ref(), other.f.as_ref()),
+        ])
+    }
+
+    #[inline]
+    fn map_values<U, F>(self, mut func: F) -> BooleanMapStorage<U>
+    where
+        F: FnMut(bool, V) -> U,
+    {
+        BooleanMapStorage {
+            t: self.t.map(|value| func(true, value)),
+            f: self.f.map(|value| func(false, value)),
+        }
+    }
+
+    #[inline]
+    fn merge<U, W, F>(self, other: BooleanMapStorage<U>, mut func: F) -> BooleanMapStorage<W>
+    where
+        F: FnMut(bool, Option<V>, Option<U>) -> Option<W>,
+    {
+        BooleanMapStorage {
+            t: match (self.t, other.t) {
+                (None, None) => None,
+                (a, b) => func(true, a, b),
+            },
+            f: match (self.f, other.f) {
+                (None, None) => None,
+                (a, b) => func(false, a, b),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+type ParIter<'a, V> = rayon::iter::Chain<
+    rayon::iter::Map<rayon::option::Iter<'a, V>, fn(&'a V) -> (bool, &'a V)>,
+    rayon::iter::Map<rayon::option::Iter<'a, V>, fn(&'a V) -> (bool, &'a V)>,
+>;
+#[cfg(feature = "rayon")]
+type ParIterMut<'a, V> = rayon::iter::Chain<
+    rayon::iter::Map<rayon::option::IterMut<'a, V>, fn(&'a mut V) -> (bool, &'a mut V)>,
+    rayon::iter::Map<rayon::option::IterMut<'a, V>, fn(&'a mut V) -> (bool, &'a mut V)>,
+>;
+#[cfg(feature = "rayon")]
+type ParValuesMut<'a, V> =
+    rayon::iter::Chain<rayon::option::IterMut<'a, V>, rayon::option::IterMut<'a, V>>;
+#[cfg(feature = "rayon")]
+type IntoParIter<V> = rayon::iter::Chain<
+    rayon::iter::Map<rayon::option::IntoIter<V>, fn(V) -> (bool, V)>,
+    rayon::iter::Map<rayon::option::IntoIter<V>, fn(V) -> (bool, V)>,
+>;
+
+#[cfg(feature = "rayon")]
+impl<V> ParallelMapStorage<bool, V> for BooleanMapStorage<V>
+where
+    V: Send + Sync,
+{
+    type ParIter<'this>
+        = ParIter<'this, V>
+    where
+        V: 'this;
+    type ParIterMut<'this>
+        = ParIterMut<'this, V>
+    where
+        V: 'this;
+    type ParValuesMut<'this>
+        = ParValuesMut<'this, V>
+    where
+        V: 'this;
+    type IntoParIter = IntoParIter<V>;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let map: fn(_) -> _ = |v| (true, v);
+        let a = self.t.par_iter().map(map);
+        let map: fn(_) -> _ = |v| (false, v);
+        let b = self.f.par_iter().map(map);
+        a.chain(b)
+    }
+
+    #[inline]
+    fn par_iter_mut(&mut self) -> Self::ParIterMut<'_> {
+        let map: fn(_) -> _ = |v| (true, v);
+        let a = self.t.par_iter_mut().map(map);
+        let map: fn(_) -> _ = |v| (false, v);
+        let b = self.f.par_iter_mut().map(map);
+        a.chain(b)
+    }
+
+    #[inline]
+    fn par_values_mut(&mut self) -> Self::ParValuesMut<'_> {
+        self.t.par_iter_mut().chain(self.f.par_iter_mut())
+    }
+
+    #[inline]
+    fn into_par_iter(self) -> Self::IntoParIter {
+        let map: fn(_) -> _ = |v| (true, v);
+        let a = self.t.into_par_iter().map(map);
+        let map: fn(_) -> _ = |v| (false, v);
+        let b = self.f.into_par_iter().map(map);
+        a.chain(b)
+    }
+}
+
+/// The archived form of [`BooleanMapStorage`].
+#[cfg(feature = "rkyv")]
+#[repr(C)]
+#[cfg_attr(
+    feature = "rkyv-validation",
+    derive(::rkyv::CheckBytes),
+    check_bytes(crate = "::rkyv::bytecheck")
+)]
+pub struct ArchivedBooleanMapStorage<V>
+where
+    V: Archive,
+{
+    t: Archived<Option<V>>,
+    f: Archived<Option<V>>,
+}
+
+#[cfg(feature = "rkyv")]
+type ArchivedIter<'a, V> = iter::Chain<
+    iter::Map<option::IntoIter<&'a V>, fn(&'a V) -> (bool, &'a V)>,
+    iter::Map<option::IntoIter<&'a V>, fn(&'a V) -> (bool, &'a V)>,
+>;
+
+#[cfg(feature = "rkyv")]
+impl<V> ArchiveMapStorage<bool, V> for BooleanMapStorage<V>
+where
+    V: Archive,
+{
+    type Archived = ArchivedBooleanMapStorage<V>;
+    type Resolver = (Resolver<Option<V>>, Resolver<Option<V>>);
+
+    #[inline]
+    fn serialize<S>(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+        V: Serialize<S>,
+    {
+        let t = Serialize::serialize(&self.t, serializer)?;
+        let f = Serialize::serialize(&self.f, serializer)?;
+        Ok((t, f))
+    }
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, (t, f): Self::Resolver, out: *mut Self::Archived) {
+        let (offset, field) = out_field!(out.t);
+        self.t.resolve(pos + offset, t, field);
+        let (offset, field) = out_field!(out.f);
+        self.f.resolve(pos + offset, f, field);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<V> ArchivedMapStorage<bool, V> for ArchivedBooleanMapStorage<V>
+where
+    V: Archive,
+{
+    type Iter<'this>
+        = ArchivedIter<'this, V::Archived>
+    where
+        V: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        usize::from(self.t.is_some()) + usize::from(self.f.is_some())
+    }
+
+    #[inline]
+    fn get(&self, key: bool) -> Option<&V::Archived> {
+        if key {
+            self.t.as_ref()
+        } else {
+            self.f.as_ref()
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let map: fn(_) -> _ = |v| (true, v);
+        let a = self.t.as_ref().into_iter().map(map);
+        let map: fn(_) -> _ = |v| (false, v);
+        let b = self.f.as_ref().into_iter().map(map);
+        a.chain(b)
+    }
 }
@@ -0,0 +1,533 @@
+// Iterators are confusing if they impl `Copy`.
+
+#![allow(missing_copy_implementations)]
+
+use core::array;
+use core::iter::{self, FusedIterator};
+use core::marker::PhantomData;
+use core::slice;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::{
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
+    IntoParallelRefMutIterator, ParallelIterator,
+};
+#[cfg(feature = "rkyv")]
+use rkyv::{out_field, Archive, Archived, Fallible, Resolver, Serialize};
+
+use crate::exact_iter::ExactIter;
+use crate::key::Byte;
+#[cfg(feature = "rayon")]
+use crate::map::ParallelMapStorage;
+#[cfg(feature = "rkyv")]
+use crate::map::{ArchiveMapStorage, ArchivedMapStorage};
+use crate::map::{Entry, MapStorage, OccupiedEntry, VacantEntry};
+use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
+
+type Iter<'a, K, V> = ExactIter<
+    iter::FilterMap<
+        iter::Enumerate<slice::Iter<'a, Option<V>>>,
+        fn((usize, &'a Option<V>)) -> Option<(K, &'a V)>,
+    >,
+>;
+type Keys<'a, K, V> = ExactIter<
+    iter::FilterMap<
+        iter::Enumerate<slice::Iter<'a, Option<V>>>,
+        fn((usize, &'a Option<V>)) -> Option<K>,
+    >,
+>;
+type Values<'a, V> = ExactIter<iter::Flatten<slice::Iter<'a, Option<V>>>>;
+type IterMut<'a, K, V> = ExactIter<
+    iter::FilterMap<
+        iter::Enumerate<slice::IterMut<'a, Option<V>>>,
+        fn((usize, &'a mut Option<V>)) -> Option<(K, &'a mut V)>,
+    >,
+>;
+type ValuesMut<'a, V> = ExactIter<iter::Flatten<slice::IterMut<'a, Option<V>>>>;
+type IntoIter<K, V> = ExactIter<
+    iter::FilterMap<
+        iter::Enumerate<array::IntoIter<Option<V>, 256>>,
+        fn((usize, Option<V>)) -> Option<(K, V)>,
+    >,
+>;
+
+/// [`MapStorage`] for byte-sized types like [`u8`] and [`i8`], which stores
+/// every possible key in a dense array of 256 slots.
+///
+/// Keys are iterated over in ascending numerical order.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First(u8),
+///     Second(i8),
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert(MyKey::First(200), 1);
+/// a.insert(MyKey::First(10), 2);
+/// a.insert(MyKey::Second(-5), 3);
+///
+/// assert_eq!(a.get(MyKey::First(10)), Some(&2));
+/// assert_eq!(a.get(MyKey::First(11)), None);
+/// assert_eq!(a.get(MyKey::Second(-5)), Some(&3));
+///
+/// assert!(a.iter().eq([(MyKey::First(10), &2), (MyKey::First(200), &1), (MyKey::Second(-5), &3)]));
+/// assert!(a.values().copied().eq([2, 1, 3]));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ByteMapStorage<K, V> {
+    data: [Option<V>; 256],
+    _marker: PhantomData<K>,
+}
+
+pub struct Vacant<'a, K, V> {
+    key: K,
+    inner: NoneBucket<'a, V>,
+}
+
+pub struct Occupied<'a, K, V> {
+    key: K,
+    inner: SomeBucket<'a, V>,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> for Vacant<'a, K, V>
+where
+    K: Byte,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.key
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        self.inner.insert(value)
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> for Occupied<'a, K, V>
+where
+    K: Byte,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.key
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.inner.as_ref()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.inner.as_mut()
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        self.inner.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.inner.replace(value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        self.inner.take()
+    }
+}
+
+/// See [`ByteMapStorage::zip`].
+///
+/// Walks every slot in ascending order, looking up the same slot in the other
+/// storage.
+pub struct Zip<'a, K, V, U>
+where
+    K: Byte,
+    U: 'a,
+{
+    data: iter::Enumerate<slice::Iter<'a, Option<V>>>,
+    other: &'a K::MapStorage<U>,
+}
+
+impl<'a, K, V, U> Iterator for Zip<'a, K, V, U>
+where
+    K: Byte,
+    U: 'a,
+{
+    type Item = (K, Option<&'a V>, Option<&'a U>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, a) in &mut self.data {
+            let key = K::from_index(index);
+            let a = a.as_ref();
+            let b = self.other.get(key);
+
+            if a.is_some() || b.is_some() {
+                return Some((key, a, b));
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.data.len()))
+    }
+}
+
+impl<'a, K, V, U> FusedIterator for Zip<'a, K, V, U>
+where
+    K: Byte,
+    U: 'a,
+{
+}
+
+impl<K, V> MapStorage<K, V> for ByteMapStorage<K, V>
+where
+    K: Byte,
+{
+    type Iter<'this>
+        = Iter<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type Keys<'this>
+        = Keys<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type Values<'this>
+        = Values<'this, V>
+    where
+        K: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type ValuesMut<'this>
+        = ValuesMut<'this, V>
+    where
+        K: 'this,
+        V: 'this;
+    type IntoIter = IntoIter<K, V>;
+    type Zip<'this, U>
+        = Zip<'this, K, V, U>
+    where
+        K: 'this,
+        V: 'this,
+        U: 'this;
+    type Occupied<'this>
+        = Occupied<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type Vacant<'this>
+        = Vacant<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            data: array::from_fn(|_| None),
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.data.iter().filter(|value| value.is_some()).count()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.data.iter().all(Option::is_none)
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.data[key.index()].replace(value)
+    }
+
+    #[inline]
+    fn contains_key(&self, key: K) -> bool {
+        self.data[key.index()].is_some()
+    }
+
+    #[inline]
+    fn get(&self, key: K) -> Option<&V> {
+        self.data[key.index()].as_ref()
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        self.data[key.index()].as_mut()
+    }
+
+    #[inline]
+    fn remove(&mut self, key: K) -> Option<V> {
+        self.data[key.index()].take()
+    }
+
+    #[inline]
+    fn contains_key_by(&self, key: K) -> bool {
+        self.contains_key(key)
+    }
+
+    #[inline]
+    fn get_by(&self, key: K) -> Option<&V> {
+        self.get(key)
+    }
+
+    #[inline]
+    fn get_mut_by(&mut self, key: K) -> Option<&mut V> {
+        self.get_mut(key)
+    }
+
+    #[inline]
+    fn remove_by(&mut self, key: K) -> Option<V> {
+        self.remove(key)
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        for (index, value) in self.data.iter_mut().enumerate() {
+            if let Some(item) = value {
+                if !func(K::from_index(index), item) {
+                    *value = None;
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.data.fill_with(|| None);
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let map: fn(_) -> _ =
+            |(index, value): (_, &Option<_>)| Some((K::from_index(index), value.as_ref()?));
+        ExactIter::new(self.data.iter().enumerate().filter_map(map), self.len())
+    }
+
+    #[inline]
+    fn keys(&self) -> Self::Keys<'_> {
+        let map: fn(_) -> _ =
+            |(index, value): (_, &Option<_>)| value.is_some().then(|| K::from_index(index));
+        ExactIter::new(self.data.iter().enumerate().filter_map(map), self.len())
+    }
+
+    #[inline]
+    fn values(&self) -> Self::Values<'_> {
+        ExactIter::new(self.data.iter().flatten(), self.len())
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let len = self.len();
+        let map: fn(_) -> _ =
+            |(index, value): (_, &mut Option<_>)| Some((K::from_index(index), value.as_mut()?));
+        ExactIter::new(self.data.iter_mut().enumerate().filter_map(map), len)
+    }
+
+    #[inline]
+    fn values_mut(&mut self) -> Self::ValuesMut<'_> {
+        let len = self.len();
+        ExactIter::new(self.data.iter_mut().flatten(), len)
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        let map: fn(_) -> _ = |(index, value): (_, Option<_>)| Some((K::from_index(index), value?));
+        ExactIter::new(self.data.into_iter().enumerate().filter_map(map), len)
+    }
+
+    #[inline]
+    fn entry(&mut self, key: K) -> Entry<'_, Self, K, V> {
+        match OptionBucket::new(&mut self.data[key.index()]) {
+            OptionBucket::Some(inner) => Entry::Occupied(Occupied { key, inner }),
+            OptionBucket::None(inner) => Entry::Vacant(Vacant { key, inner }),
+        }
+    }
+
+    #[inline]
+    fn zip<'this, U>(&'this self, other: &'this K::MapStorage<U>) -> Self::Zip<'this, U>
+    where
+        U: 'this,
+    {
+        Zip {
+            data: self.data.iter().enumerate(),
+            other,
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+type ParIter<'a, K, V> = rayon::iter::FilterMap<
+    rayon::iter::Enumerate<rayon::slice::Iter<'a, Option<V>>>,
+    fn((usize, &'a Option<V>)) -> Option<(K, &'a V)>,
+>;
+#[cfg(feature = "rayon")]
+type ParIterMut<'a, K, V> = rayon::iter::FilterMap<
+    rayon::iter::Enumerate<rayon::slice::IterMut<'a, Option<V>>>,
+    fn((usize, &'a mut Option<V>)) -> Option<(K, &'a mut V)>,
+>;
+#[cfg(feature = "rayon")]
+type ParValuesMut<'a, V> = rayon::iter::FilterMap<
+    rayon::slice::IterMut<'a, Option<V>>,
+    fn(&'a mut Option<V>) -> Option<&'a mut V>,
+>;
+#[cfg(feature = "rayon")]
+type IntoParIter<K, V> = rayon::iter::FilterMap<
+    rayon::iter::Enumerate<rayon::array::IntoIter<Option<V>, 256>>,
+    fn((usize, Option<V>)) -> Option<(K, V)>,
+>;
+
+#[cfg(feature = "rayon")]
+impl<K, V> ParallelMapStorage<K, V> for ByteMapStorage<K, V>
+where
+    K: Byte + Send + Sync,
+    V: Send + Sync,
+{
+    type ParIter<'this>
+        = ParIter<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type ParIterMut<'this>
+        = ParIterMut<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type ParValuesMut<'this>
+        = ParValuesMut<'this, V>
+    where
+        K: 'this,
+        V: 'this;
+    type IntoParIter = IntoParIter<K, V>;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let map: fn(_) -> _ =
+            |(index, value): (_, &Option<_>)| Some((K::from_index(index), value.as_ref()?));
+        self.data.par_iter().enumerate().filter_map(map)
+    }
+
+    #[inline]
+    fn par_iter_mut(&mut self) -> Self::ParIterMut<'_> {
+        let map: fn(_) -> _ =
+            |(index, value): (_, &mut Option<_>)| Some((K::from_index(index), value.as_mut()?));
+        self.data.par_iter_mut().enumerate().filter_map(map)
+    }
+
+    #[inline]
+    fn par_values_mut(&mut self) -> Self::ParValuesMut<'_> {
+        let map: fn(_) -> _ = Option::as_mut;
+        self.data.par_iter_mut().filter_map(map)
+    }
+
+    #[inline]
+    fn into_par_iter(self) -> Self::IntoParIter {
+        let map: fn(_) -> _ = |(index, value): (_, Option<_>)| Some((K::from_index(index), value?));
+        self.data.into_par_iter().enumerate().filter_map(map)
+    }
+}
+
+/// The archived form of [`ByteMapStorage`], which keeps one slot for every
+/// possible key.
+#[cfg(feature = "rkyv")]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "rkyv-validation",
+    derive(::rkyv::CheckBytes),
+    check_bytes(crate = "::rkyv::bytecheck")
+)]
+pub struct ArchivedByteMapStorage<K, V>
+where
+    V: Archive,
+{
+    data: Archived<[Option<V>; 256]>,
+    _marker: PhantomData<K>,
+}
+
+#[cfg(feature = "rkyv")]
+type ArchivedIter<'a, K, V> = iter::FilterMap<
+    iter::Enumerate<slice::Iter<'a, Archived<Option<V>>>>,
+    fn((usize, &'a Archived<Option<V>>)) -> Option<(K, &'a <V as Archive>::Archived)>,
+>;
+
+#[cfg(feature = "rkyv")]
+impl<K, V> ArchiveMapStorage<K, V> for ByteMapStorage<K, V>
+where
+    K: Byte,
+    V: Archive,
+{
+    type Archived = ArchivedByteMapStorage<K, V>;
+    type Resolver = Resolver<[Option<V>; 256]>;
+
+    #[inline]
+    fn serialize<S>(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+        V: Serialize<S>,
+    {
+        Serialize::serialize(&self.data, serializer)
+    }
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (offset, field) = out_field!(out.data);
+        self.data.resolve(pos + offset, resolver, field);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<K, V> ArchivedMapStorage<K, V> for ArchivedByteMapStorage<K, V>
+where
+    K: Byte,
+    V: Archive,
+{
+    type Iter<'this>
+        = ArchivedIter<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.data.iter().filter(|value| value.is_some()).count()
+    }
+
+    #[inline]
+    fn get(&self, key: K) -> Option<&V::Archived> {
+        self.data[key.index()].as_ref()
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let map: fn(_) -> _ = |(index, value): (_, &Archived<Option<V>>)| {
+            Some((K::from_index(index), value.as_ref()?))
+        };
+        self.data.iter().enumerate().filter_map(map)
+    }
+}
@@ -0,0 +1,1208 @@
+// Iterators are confusing if they impl `Copy`.
+
+#![allow(missing_copy_implementations)]
+#![allow(unsafe_code)]
+
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::iter::{self, FusedIterator};
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+use core::ops::Range;
+use core::slice;
+
+#[cfg(feature = "rayon")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "rkyv")]
+use rkyv::{out_field, Archive, Archived, Deserialize, Fallible, Infallible, Serialize};
+
+#[cfg(feature = "rayon")]
+use rayon::iter::{
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
+    IntoParallelRefMutIterator, ParallelIterator,
+};
+
+use crate::key::Compact;
+#[cfg(feature = "rayon")]
+use crate::map::ParallelMapStorage;
+#[cfg(feature = "rkyv")]
+use crate::map::{ArchiveMapStorage, ArchivedMapStorage};
+use crate::map::{Entry, MapStorage, OccupiedEntry, VacantEntry};
+use crate::raw::RawKeys;
+
+/// An integer used as a bitset of present slots by [`CompactMapStorage`].
+///
+/// Indexes which don't fit in the bitset are never present, and inserting
+/// them panics. The storage relies on this, since a present slot is assumed
+/// to be initialized even if [`Compact`] is implemented incorrectly.
+pub trait Bits: Copy + Eq + Hash {
+    /// The bitset with no slots present.
+    const EMPTY: Self;
+
+    /// The number of present slots.
+    fn len(self) -> usize;
+
+    /// Test if no slots are present.
+    fn is_empty(self) -> bool;
+
+    /// Test if the slot at `index` is present.
+    fn contains(self, index: usize) -> bool;
+
+    /// Mark the slot at `index` as present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` doesn't fit in the bitset.
+    fn insert(&mut self, index: usize);
+
+    /// Mark the slot at `index` as absent.
+    fn remove(&mut self, index: usize);
+
+    /// Remove and return the lowest present slot.
+    fn pop_first(&mut self) -> Option<usize>;
+
+    /// Remove and return the highest present slot.
+    fn pop_last(&mut self) -> Option<usize>;
+}
+
+macro_rules! bits {
+    ($($ty:ty),*) => {
+        $(
+            impl Bits for $ty {
+                const EMPTY: Self = 0;
+
+                #[inline]
+                fn len(self) -> usize {
+                    self.count_ones() as usize
+                }
+
+                #[inline]
+                fn is_empty(self) -> bool {
+                    self == 0
+                }
+
+                #[inline]
+                fn contains(self, index: usize) -> bool {
+                    index < <$ty>::BITS as usize && self & (1 << index) != 0
+                }
+
+                #[inline]
+                fn insert(&mut self, index: usize) {
+                    assert!(
+                        index < <$ty>::BITS as usize,
+                        "index {index} is out of bounds for a bitset of {} bits",
+                        <$ty>::BITS
+                    );
+                    *self |= 1 << index;
+                }
+
+                #[inline]
+                fn remove(&mut self, index: usize) {
+                    if index < <$ty>::BITS as usize {
+                        *self &= !(1 << index);
+                    }
+                }
+
+                #[inline]
+                fn pop_first(&mut self) -> Option<usize> {
+                    if *self == 0 {
+                        return None;
+                    }
+
+                    let index = self.trailing_zeros() as usize;
+                    *self &= *self - 1;
+                    Some(index)
+                }
+
+                #[inline]
+                fn pop_last(&mut self) -> Option<usize> {
+                    if *self == 0 {
+                        return None;
+                    }
+
+                    let index = (<$ty>::BITS - 1 - self.leading_zeros()) as usize;
+                    *self &= !(1 << index);
+                    Some(index)
+                }
+            }
+        )*
+    };
+}
+
+bits!(u8, u16, u32, u64, u128);
+
+type Values<'a, K, V> = iter::Map<Iter<'a, K, V>, fn((K, &'a V)) -> &'a V>;
+type ValuesMut<'a, K, V> = iter::Map<IterMut<'a, K, V>, fn((K, &'a mut V)) -> &'a mut V>;
+
+/// [`MapStorage`] for keys with the `#[key(compact)]` attribute.
+///
+/// Values are stored without the overhead of an [`Option`] by keeping a
+/// bitset of which slots are present next to the uninitialized value slots.
+/// The length is the number of bits set, and iteration only visits present
+/// slots.
+///
+/// Since the storage needs to drop present values, it never implements
+/// [`Copy`].
+///
+/// # Examples
+///
+/// ```
+/// use core::mem::size_of;
+///
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// #[key(compact)]
+/// enum MyKey {
+///     First,
+///     Second,
+///     Third,
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert(MyKey::Third, 1u64);
+/// a.insert(MyKey::First, 2u64);
+///
+/// assert_eq!(a.get(MyKey::First), Some(&2));
+/// assert_eq!(a.get(MyKey::Second), None);
+/// assert_eq!(a.len(), 2);
+///
+/// assert!(a.iter().eq([(MyKey::First, &2), (MyKey::Third, &1)]));
+/// assert_eq!(size_of::<Map<MyKey, u64>>(), size_of::<[u64; 4]>());
+/// ```
+pub struct CompactMapStorage<K, V>
+where
+    K: Compact,
+{
+    present: K::Bits,
+    data: K::Array<MaybeUninit<V>>,
+}
+
+impl<K, V> CompactMapStorage<K, V>
+where
+    K: Compact,
+{
+    #[inline]
+    fn uninit() -> K::Array<MaybeUninit<V>> {
+        K::array_from_fn(|_| MaybeUninit::uninit())
+    }
+
+    #[inline]
+    fn slot(&self, index: usize) -> Option<&V> {
+        if !self.present.contains(index) {
+            return None;
+        }
+
+        // SAFETY: Present slots are initialized.
+        unsafe { Some(self.data.as_ref()[index].assume_init_ref()) }
+    }
+
+    #[inline]
+    fn slot_mut(&mut self, index: usize) -> Option<&mut V> {
+        if !self.present.contains(index) {
+            return None;
+        }
+
+        // SAFETY: Present slots are initialized.
+        unsafe { Some(self.data.as_mut()[index].assume_init_mut()) }
+    }
+
+    #[inline]
+    fn take(&mut self, index: usize) -> Option<V> {
+        if !self.present.contains(index) {
+            return None;
+        }
+
+        self.present.remove(index);
+        // SAFETY: The slot was present, and is no longer marked as present so
+        // it won't be read again.
+        unsafe { Some(self.data.as_ref()[index].assume_init_read()) }
+    }
+}
+
+impl<K, V> Clone for CompactMapStorage<K, V>
+where
+    K: Compact,
+    V: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        let mut storage = Self::empty();
+
+        for (key, value) in self.iter() {
+            storage.insert(key, value.clone());
+        }
+
+        storage
+    }
+}
+
+impl<K, V> Drop for CompactMapStorage<K, V>
+where
+    K: Compact,
+{
+    #[inline]
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<K, V> PartialEq for CompactMapStorage<K, V>
+where
+    K: Compact,
+    V: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.present == other.present && self.values().eq(other.values())
+    }
+}
+
+impl<K, V> Eq for CompactMapStorage<K, V>
+where
+    K: Compact,
+    V: Eq,
+{
+}
+
+impl<K, V> Hash for CompactMapStorage<K, V>
+where
+    K: Compact,
+    V: Hash,
+{
+    #[inline]
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.present.hash(state);
+
+        for value in self.values() {
+            value.hash(state);
+        }
+    }
+}
+
+// Ordering is the same as for storage of optional values, where present slots
+// are compared by index and then by value.
+impl<K, V> PartialOrd for CompactMapStorage<K, V>
+where
+    K: Compact,
+    V: PartialOrd,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let a = self.iter().map(|(key, value)| (key.index(), value));
+        let b = other.iter().map(|(key, value)| (key.index(), value));
+        a.partial_cmp(b)
+    }
+}
+
+impl<K, V> Ord for CompactMapStorage<K, V>
+where
+    K: Compact,
+    V: Ord,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        let a = self.iter().map(|(key, value)| (key.index(), value));
+        let b = other.iter().map(|(key, value)| (key.index(), value));
+        a.cmp(b)
+    }
+}
+
+impl<K, V> RawKeys for CompactMapStorage<K, V>
+where
+    K: Compact,
+{
+    type Value = K::Bits;
+
+    #[inline]
+    fn as_raw_keys(&self) -> K::Bits {
+        self.present
+    }
+}
+
+/// See [`CompactMapStorage::iter`].
+pub struct Iter<'a, K, V>
+where
+    K: Compact,
+{
+    bits: K::Bits,
+    data: &'a [MaybeUninit<V>],
+}
+
+impl<K, V> Clone for Iter<'_, K, V>
+where
+    K: Compact,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            bits: self.bits,
+            data: self.data,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Compact,
+{
+    type Item = (K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.bits.pop_first()?;
+        // SAFETY: Slots in the bitset are initialized.
+        let value = unsafe { self.data[index].assume_init_ref() };
+        Some((K::from_index(index), value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.len();
+        (len, Some(len))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V>
+where
+    K: Compact,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index = self.bits.pop_last()?;
+        // SAFETY: Slots in the bitset are initialized.
+        let value = unsafe { self.data[index].assume_init_ref() };
+        Some((K::from_index(index), value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V>
+where
+    K: Compact,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.bits.len()
+    }
+}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> where K: Compact {}
+
+/// See [`CompactMapStorage::zip`].
+///
+/// Walks every slot in key order, looking up the same key in the other
+/// storage.
+pub struct Zip<'a, K, V, U>
+where
+    K: Compact,
+    U: 'a,
+{
+    this: &'a CompactMapStorage<K, V>,
+    other: &'a K::MapStorage<U>,
+    indexes: Range<usize>,
+}
+
+impl<'a, K, V, U> Iterator for Zip<'a, K, V, U>
+where
+    K: Compact,
+    U: 'a,
+{
+    type Item = (K, Option<&'a V>, Option<&'a U>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for index in &mut self.indexes {
+            let key = K::from_index(index);
+            let a = self.this.slot(index);
+            let b = self.other.get(key.clone());
+
+            if a.is_some() || b.is_some() {
+                return Some((key, a, b));
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.indexes.len()))
+    }
+}
+
+impl<'a, K, V, U> FusedIterator for Zip<'a, K, V, U>
+where
+    K: Compact,
+    U: 'a,
+{
+}
+
+/// See [`CompactMapStorage::keys`].
+pub struct Keys<K>
+where
+    K: Compact,
+{
+    bits: K::Bits,
+    _marker: PhantomData<K>,
+}
+
+impl<K> Clone for Keys<K>
+where
+    K: Compact,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            bits: self.bits,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K> Iterator for Keys<K>
+where
+    K: Compact,
+{
+    type Item = K;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(K::from_index(self.bits.pop_first()?))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.len();
+        (len, Some(len))
+    }
+}
+
+impl<K> DoubleEndedIterator for Keys<K>
+where
+    K: Compact,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        Some(K::from_index(self.bits.pop_last()?))
+    }
+}
+
+impl<K> ExactSizeIterator for Keys<K>
+where
+    K: Compact,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.bits.len()
+    }
+}
+
+impl<K> FusedIterator for Keys<K> where K: Compact {}
+
+/// See [`CompactMapStorage::iter_mut`].
+pub struct IterMut<'a, K, V>
+where
+    K: Compact,
+{
+    bits: K::Bits,
+    // Index of the first slot remaining in `iter`.
+    start: usize,
+    iter: slice::IterMut<'a, MaybeUninit<V>>,
+    _marker: PhantomData<K>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V>
+where
+    K: Compact,
+{
+    type Item = (K, &'a mut V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.bits.pop_first()?;
+        let slot = self.iter.nth(index - self.start)?;
+        self.start = index + 1;
+        // SAFETY: Slots in the bitset are initialized.
+        let value = unsafe { slot.assume_init_mut() };
+        Some((K::from_index(index), value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.len();
+        (len, Some(len))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IterMut<'_, K, V>
+where
+    K: Compact,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index = self.bits.pop_last()?;
+        let end = self.start + self.iter.len();
+        let slot = self.iter.nth_back(end - index - 1)?;
+        // SAFETY: Slots in the bitset are initialized.
+        let value = unsafe { slot.assume_init_mut() };
+        Some((K::from_index(index), value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V>
+where
+    K: Compact,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.bits.len()
+    }
+}
+
+impl<K, V> FusedIterator for IterMut<'_, K, V> where K: Compact {}
+
+/// See [`CompactMapStorage::into_iter`].
+pub struct IntoIter<K, V>
+where
+    K: Compact,
+{
+    bits: K::Bits,
+    data: K::Array<MaybeUninit<V>>,
+}
+
+impl<K, V> Iterator for IntoIter<K, V>
+where
+    K: Compact,
+{
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.bits.pop_first()?;
+        // SAFETY: Slots in the bitset are initialized, and the slot is no
+        // longer in the bitset so it won't be read again.
+        let value = unsafe { self.data.as_ref()[index].assume_init_read() };
+        Some((K::from_index(index), value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.len();
+        (len, Some(len))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoIter<K, V>
+where
+    K: Compact,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index = self.bits.pop_last()?;
+        // SAFETY: Slots in the bitset are initialized, and the slot is no
+        // longer in the bitset so it won't be read again.
+        let value = unsafe { self.data.as_ref()[index].assume_init_read() };
+        Some((K::from_index(index), value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V>
+where
+    K: Compact,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.bits.len()
+    }
+}
+
+impl<K, V> FusedIterator for IntoIter<K, V> where K: Compact {}
+
+impl<K, V> Drop for IntoIter<K, V>
+where
+    K: Compact,
+{
+    #[inline]
+    fn drop(&mut self) {
+        for _ in self {}
+    }
+}
+
+pub struct Vacant<'a, K, V>
+where
+    K: Compact,
+{
+    key: K,
+    present: &'a mut K::Bits,
+    slot: &'a mut MaybeUninit<V>,
+}
+
+pub struct Occupied<'a, K, V>
+where
+    K: Compact,
+{
+    key: K,
+    present: &'a mut K::Bits,
+    slot: &'a mut MaybeUninit<V>,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> for Vacant<'a, K, V>
+where
+    K: Compact,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.key.clone()
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        self.present.insert(self.key.index());
+        self.slot.write(value)
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> for Occupied<'a, K, V>
+where
+    K: Compact,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.key.clone()
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        // SAFETY: Occupied entries are only constructed for present slots.
+        unsafe { self.slot.assume_init_ref() }
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        // SAFETY: Occupied entries are only constructed for present slots.
+        unsafe { self.slot.assume_init_mut() }
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        // SAFETY: Occupied entries are only constructed for present slots.
+        unsafe { self.slot.assume_init_mut() }
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        self.present.remove(self.key.index());
+        // SAFETY: Occupied entries are only constructed for present slots, and
+        // the slot is no longer marked as present so it won't be read again.
+        unsafe { self.slot.assume_init_read() }
+    }
+}
+
+impl<K, V> MapStorage<K, V> for CompactMapStorage<K, V>
+where
+    K: Compact,
+{
+    type Iter<'this>
+        = Iter<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type Keys<'this>
+        = Keys<K>
+    where
+        K: 'this,
+        V: 'this;
+    type Values<'this>
+        = Values<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type ValuesMut<'this>
+        = ValuesMut<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type IntoIter = IntoIter<K, V>;
+    type Zip<'this, U>
+        = Zip<'this, K, V, U>
+    where
+        K: 'this,
+        V: 'this,
+        U: 'this;
+    type Occupied<'this>
+        = Occupied<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type Vacant<'this>
+        = Vacant<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            present: K::Bits::EMPTY,
+            data: Self::uninit(),
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.present.len()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.present.is_empty()
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let index = key.index();
+
+        if let Some(existing) = self.slot_mut(index) {
+            return Some(mem::replace(existing, value));
+        }
+
+        let slot = &mut self.data.as_mut()[index];
+        self.present.insert(index);
+        slot.write(value);
+        None
+    }
+
+    #[inline]
+    fn contains_key(&self, key: K) -> bool {
+        self.present.contains(key.index())
+    }
+
+    #[inline]
+    fn get(&self, key: K) -> Option<&V> {
+        self.slot(key.index())
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        self.slot_mut(key.index())
+    }
+
+    #[inline]
+    fn remove(&mut self, key: K) -> Option<V> {
+        self.take(key.index())
+    }
+
+    #[inline]
+    fn contains_key_by(&self, key: K) -> bool {
+        self.contains_key(key)
+    }
+
+    #[inline]
+    fn get_by(&self, key: K) -> Option<&V> {
+        self.get(key)
+    }
+
+    #[inline]
+    fn get_mut_by(&mut self, key: K) -> Option<&mut V> {
+        self.get_mut(key)
+    }
+
+    #[inline]
+    fn remove_by(&mut self, key: K) -> Option<V> {
+        self.remove(key)
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        let mut bits = self.present;
+
+        while let Some(index) = bits.pop_first() {
+            // SAFETY: Slots in the bitset are initialized.
+            let value = unsafe { self.data.as_mut()[index].assume_init_mut() };
+
+            if !func(K::from_index(index), value) {
+                drop(self.take(index));
+            }
+        }
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        while let Some(index) = self.present.pop_first() {
+            // SAFETY: The slot was present, and is no longer marked as present
+            // so it won't be dropped again.
+            unsafe { self.data.as_mut()[index].assume_init_drop() };
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            bits: self.present,
+            data: self.data.as_ref(),
+        }
+    }
+
+    #[inline]
+    fn keys(&self) -> Self::Keys<'_> {
+        Keys {
+            bits: self.present,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn values(&self) -> Self::Values<'_> {
+        let map: fn(_) -> _ = |(_, value)| value;
+        self.iter().map(map)
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        IterMut {
+            bits: self.present,
+            start: 0,
+            iter: self.data.as_mut().iter_mut(),
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn values_mut(&mut self) -> Self::ValuesMut<'_> {
+        let map: fn(_) -> _ = |(_, value)| value;
+        self.iter_mut().map(map)
+    }
+
+    #[inline]
+    fn into_iter(mut self) -> Self::IntoIter {
+        // Leave the storage empty, so that dropping it doesn't drop any values.
+        IntoIter {
+            bits: mem::replace(&mut self.present, K::Bits::EMPTY),
+            data: mem::replace(&mut self.data, Self::uninit()),
+        }
+    }
+
+    #[inline]
+    fn entry(&mut self, key: K) -> Entry<'_, Self, K, V> {
+        let index = key.index();
+        let present = &mut self.present;
+        let slot = &mut self.data.as_mut()[index];
+
+        if present.contains(index) {
+            Entry::Occupied(Occupied { key, present, slot })
+        } else {
+            Entry::Vacant(Vacant { key, present, slot })
+        }
+    }
+
+    #[inline]
+    fn zip<'this, U>(&'this self, other: &'this K::MapStorage<U>) -> Self::Zip<'this, U>
+    where
+        U: 'this,
+    {
+        Zip {
+            this: self,
+            other,
+            indexes: 0..K::COUNT,
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+type ParIter<'a, K, V> = rayon::iter::FlattenIter<
+    rayon::iter::MapWith<
+        rayon::iter::Enumerate<rayon::slice::Iter<'a, MaybeUninit<V>>>,
+        <K as Compact>::Bits,
+        fn(&mut <K as Compact>::Bits, (usize, &'a MaybeUninit<V>)) -> Option<(K, &'a V)>,
+    >,
+>;
+#[cfg(feature = "rayon")]
+type ParIterMut<'a, K, V> = rayon::iter::FlattenIter<
+    rayon::iter::MapWith<
+        rayon::iter::Enumerate<rayon::slice::IterMut<'a, MaybeUninit<V>>>,
+        <K as Compact>::Bits,
+        fn(&mut <K as Compact>::Bits, (usize, &'a mut MaybeUninit<V>)) -> Option<(K, &'a mut V)>,
+    >,
+>;
+#[cfg(feature = "rayon")]
+type ParValuesMut<'a, K, V> =
+    rayon::iter::Map<ParIterMut<'a, K, V>, fn((K, &'a mut V)) -> &'a mut V>;
+
+/// Parallel iteration splits the work by slot, skipping slots which aren't
+/// present. Consuming iteration first moves the present values out of the
+/// slots in order.
+#[cfg(feature = "rayon")]
+impl<K, V> ParallelMapStorage<K, V> for CompactMapStorage<K, V>
+where
+    K: Compact + Send,
+    K::Bits: Send,
+    V: Send + Sync,
+{
+    type ParIter<'this>
+        = ParIter<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+
+    type ParIterMut<'this>
+        = ParIterMut<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+
+    type ParValuesMut<'this>
+        = ParValuesMut<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+
+    type IntoParIter = rayon::vec::IntoIter<(K, V)>;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let map: fn(&mut K::Bits, _) -> _ = |present, (index, slot): (usize, &MaybeUninit<V>)| {
+            if !present.contains(index) {
+                return None;
+            }
+
+            // SAFETY: Present slots are initialized.
+            unsafe { Some((K::from_index(index), slot.assume_init_ref())) }
+        };
+
+        self.data
+            .as_ref()
+            .par_iter()
+            .enumerate()
+            .map_with(self.present, map)
+            .flatten_iter()
+    }
+
+    #[inline]
+    fn par_iter_mut(&mut self) -> Self::ParIterMut<'_> {
+        let map: fn(&mut K::Bits, _) -> _ =
+            |present, (index, slot): (usize, &mut MaybeUninit<V>)| {
+                if !present.contains(index) {
+                    return None;
+                }
+
+                // SAFETY: Present slots are initialized.
+                unsafe { Some((K::from_index(index), slot.assume_init_mut())) }
+            };
+
+        self.data
+            .as_mut()
+            .par_iter_mut()
+            .enumerate()
+            .map_with(self.present, map)
+            .flatten_iter()
+    }
+
+    #[inline]
+    fn par_values_mut(&mut self) -> Self::ParValuesMut<'_> {
+        let map: fn(_) -> _ = |(_, value)| value;
+        self.par_iter_mut().map(map)
+    }
+
+    #[inline]
+    fn into_par_iter(self) -> Self::IntoParIter {
+        MapStorage::into_iter(self)
+            .collect::<Vec<_>>()
+            .into_par_iter()
+    }
+}
+
+/// The archived form of [`CompactMapStorage`], which keeps the bitset of
+/// present slots next to the archived value slots. Slots which aren't present
+/// are left zeroed.
+#[cfg(feature = "rkyv")]
+#[repr(C)]
+pub struct ArchivedCompactMapStorage<K, V>
+where
+    K: Compact,
+    K::Bits: Archive,
+    V: Archive,
+{
+    present: Archived<K::Bits>,
+    data: K::Array<MaybeUninit<V::Archived>>,
+}
+
+#[cfg(feature = "rkyv")]
+impl<K, V> ArchivedCompactMapStorage<K, V>
+where
+    K: Compact,
+    K::Bits: Archive,
+    Archived<K::Bits>: Deserialize<K::Bits, Infallible>,
+    V: Archive,
+{
+    #[inline]
+    fn present(&self) -> K::Bits {
+        crate::macro_support::__archived_value::<K::Bits>(&self.present)
+    }
+}
+
+/// Validates the bitset of present slots, and the archived value in every
+/// present slot. Slots which aren't present are never read.
+#[cfg(feature = "rkyv-validation")]
+impl<K, V, C> rkyv::CheckBytes<C> for ArchivedCompactMapStorage<K, V>
+where
+    K: Compact,
+    K::Bits: Archive,
+    Archived<K::Bits>: Deserialize<K::Bits, Infallible> + rkyv::CheckBytes<C>,
+    V: Archive,
+    V::Archived: rkyv::CheckBytes<C>,
+    C: ?Sized,
+{
+    type Error = rkyv::bytecheck::StructCheckError;
+
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        use rkyv::bytecheck::{ErrorBox, StructCheckError};
+
+        let present = core::ptr::addr_of!((*value).present);
+
+        <Archived<K::Bits>>::check_bytes(present, context).map_err(|e| StructCheckError {
+            field_name: "present",
+            inner: ErrorBox::new(e),
+        })?;
+
+        let mut present = crate::macro_support::__archived_value::<K::Bits>(&*present);
+        let data = (*core::ptr::addr_of!((*value).data)).as_ref();
+
+        while let Some(index) = present.pop_first() {
+            let Some(slot) = data.get(index) else {
+                return Err(StructCheckError {
+                    field_name: "present",
+                    inner: ErrorBox::new(crate::set::BitsCheckError::new(index)),
+                });
+            };
+
+            V::Archived::check_bytes(slot.as_ptr(), context).map_err(|e| StructCheckError {
+                field_name: "data",
+                inner: ErrorBox::new(e),
+            })?;
+        }
+
+        Ok(&*value)
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<K, V> ArchiveMapStorage<K, V> for CompactMapStorage<K, V>
+where
+    K: Compact,
+    K::Bits: Archive<Resolver = ()>,
+    Archived<K::Bits>: Deserialize<K::Bits, Infallible>,
+    V: Archive,
+{
+    type Archived = ArchivedCompactMapStorage<K, V>;
+    type Resolver = K::Array<Option<V::Resolver>>;
+
+    #[inline]
+    fn serialize<S>(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+        V: Serialize<S>,
+    {
+        let mut error = None;
+
+        let resolvers = K::array_from_fn(|key| {
+            let value = self.slot(key.index())?;
+
+            if error.is_some() {
+                return None;
+            }
+
+            match value.serialize(serializer) {
+                Ok(resolver) => Some(resolver),
+                Err(e) => {
+                    error = Some(e);
+                    None
+                }
+            }
+        });
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(resolvers),
+        }
+    }
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (offset, field) = out_field!(out.present);
+        self.present.resolve(pos + offset, (), field);
+
+        let (offset, field) = out_field!(out.data);
+        // SAFETY: The array only holds uninitialized slots, which are valid
+        // regardless of what the output holds.
+        let slots = (*field).as_mut();
+
+        for (index, (resolver, slot)) in resolver.into_iter().zip(slots).enumerate() {
+            let (Some(resolver), Some(value)) = (resolver, self.slot(index)) else {
+                continue;
+            };
+
+            let slot_offset = slot.as_ptr() as usize - field as usize;
+            value.resolve(pos + offset + slot_offset, resolver, slot.as_mut_ptr());
+        }
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<K, V> ArchivedMapStorage<K, V> for ArchivedCompactMapStorage<K, V>
+where
+    K: Compact,
+    K::Bits: Archive,
+    Archived<K::Bits>: Deserialize<K::Bits, Infallible>,
+    V: Archive,
+{
+    type Iter<'this>
+        = Iter<'this, K, V::Archived>
+    where
+        K: 'this,
+        V: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.present().len()
+    }
+
+    #[inline]
+    fn get(&self, key: K) -> Option<&V::Archived> {
+        let index = key.index();
+
+        if !self.present().contains(index) {
+            return None;
+        }
+
+        // SAFETY: Present slots were resolved when archiving.
+        unsafe { Some(self.data.as_ref()[index].assume_init_ref()) }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            bits: self.present(),
+            data: self.data.as_ref(),
+        }
+    }
+}
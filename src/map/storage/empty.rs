@@ -0,0 +1,365 @@
+use core::convert::Infallible;
+use core::iter;
+use core::marker::PhantomData;
+
+#[cfg(feature = "rkyv")]
+use rkyv::{Archive, Fallible, Serialize};
+
+#[cfg(feature = "rayon")]
+use crate::map::ParallelMapStorage;
+#[cfg(feature = "rkyv")]
+use crate::map::{ArchiveMapStorage, ArchivedMapStorage};
+use crate::map::{Entry, MapStorage, OccupiedEntry, VacantEntry};
+
+/// [`MapStorage`] for types which can't be inhabited (like [`Infallible`]).
+///
+/// # Examples
+///
+/// ```
+/// use core::convert::Infallible;
+///
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First(Infallible),
+///     Second,
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert(MyKey::Second, 1);
+///
+/// assert_eq!(a.get(MyKey::Second), Some(&1));
+/// assert!(a.keys().eq([MyKey::Second]));
+/// ```
+pub struct EmptyMapStorage<V> {
+    _marker: PhantomData<V>,
+}
+
+impl<V> Clone for EmptyMapStorage<V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for EmptyMapStorage<V> {}
+
+impl<V> PartialEq for EmptyMapStorage<V> {
+    #[inline]
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<V> Eq for EmptyMapStorage<V> {}
+
+impl<'a, V> VacantEntry<'a, Infallible, V> for Infallible {
+    #[inline]
+    fn key(&self) -> Infallible {
+        *self
+    }
+
+    #[inline]
+    fn insert(self, _: V) -> &'a mut V {
+        match self {}
+    }
+}
+
+impl<'a, V> OccupiedEntry<'a, Infallible, V> for Infallible {
+    #[inline]
+    fn key(&self) -> Infallible {
+        *self
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        match *self {}
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        match *self {}
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        match self {}
+    }
+
+    #[inline]
+    fn insert(&mut self, _: V) -> V {
+        match *self {}
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        match self {}
+    }
+}
+
+impl<V> MapStorage<Infallible, V> for EmptyMapStorage<V> {
+    type Iter<'this>
+        = iter::Empty<(Infallible, &'this V)>
+    where
+        V: 'this;
+    type Keys<'this>
+        = iter::Empty<Infallible>
+    where
+        V: 'this;
+    type Values<'this>
+        = iter::Empty<&'this V>
+    where
+        V: 'this;
+    type IterMut<'this>
+        = iter::Empty<(Infallible, &'this mut V)>
+    where
+        V: 'this;
+    type ValuesMut<'this>
+        = iter::Empty<&'this mut V>
+    where
+        V: 'this;
+    type IntoIter = iter::Empty<(Infallible, V)>;
+    type Zip<'this, U>
+        = iter::Empty<(Infallible, Option<&'this V>, Option<&'this U>)>
+    where
+        V: 'this,
+        U: 'this;
+    type Occupied<'this>
+        = Infallible
+    where
+        V: 'this;
+    type Vacant<'this>
+        = Infallible
+    where
+        V: 'this;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn insert(&mut self, key: Infallible, _: V) -> Option<V> {
+        match key {}
+    }
+
+    #[inline]
+    fn contains_key(&self, key: Infallible) -> bool {
+        match key {}
+    }
+
+    #[inline]
+    fn get(&self, key: Infallible) -> Option<&V> {
+        match key {}
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: Infallible) -> Option<&mut V> {
+        match key {}
+    }
+
+    #[inline]
+    fn remove(&mut self, key: Infallible) -> Option<V> {
+        match key {}
+    }
+
+    #[inline]
+    fn contains_key_by(&self, key: Infallible) -> bool {
+        self.contains_key(key)
+    }
+
+    #[inline]
+    fn get_by(&self, key: Infallible) -> Option<&V> {
+        self.get(key)
+    }
+
+    #[inline]
+    fn get_mut_by(&mut self, key: Infallible) -> Option<&mut V> {
+        self.get_mut(key)
+    }
+
+    #[inline]
+    fn remove_by(&mut self, key: Infallible) -> Option<V> {
+        self.remove(key)
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, _: F)
+    where
+        F: FnMut(Infallible, &mut V) -> bool,
+    {
+    }
+
+    #[inline]
+    fn clear(&mut self) {}
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        iter::empty()
+    }
+
+    #[inline]
+    fn keys(&self) -> Self::Keys<'_> {
+        iter::empty()
+    }
+
+    #[inline]
+    fn values(&self) -> Self::Values<'_> {
+        iter::empty()
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        iter::empty()
+    }
+
+    #[inline]
+    fn values_mut(&mut self) -> Self::ValuesMut<'_> {
+        iter::empty()
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        iter::empty()
+    }
+
+    #[inline]
+    fn entry(&mut self, key: Infallible) -> Entry<'_, Self, Infallible, V> {
+        match key {}
+    }
+
+    #[inline]
+    fn zip<'this, U>(&'this self, _: &'this EmptyMapStorage<U>) -> Self::Zip<'this, U>
+    where
+        U: 'this,
+    {
+        iter::empty()
+    }
+
+    #[inline]
+    fn map_values<U, F>(self, _: F) -> EmptyMapStorage<U>
+    where
+        F: FnMut(Infallible, V) -> U,
+    {
+        EmptyMapStorage::empty()
+    }
+
+    #[inline]
+    fn merge<U, W, F>(self, _: EmptyMapStorage<U>, _: F) -> EmptyMapStorage<W>
+    where
+        F: FnMut(Infallible, Option<V>, Option<U>) -> Option<W>,
+    {
+        EmptyMapStorage::empty()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<V> ParallelMapStorage<Infallible, V> for EmptyMapStorage<V>
+where
+    V: Send + Sync,
+{
+    type ParIter<'this>
+        = rayon::iter::Empty<(Infallible, &'this V)>
+    where
+        V: 'this;
+    type ParIterMut<'this>
+        = rayon::iter::Empty<(Infallible, &'this mut V)>
+    where
+        V: 'this;
+    type ParValuesMut<'this>
+        = rayon::iter::Empty<&'this mut V>
+    where
+        V: 'this;
+    type IntoParIter = rayon::iter::Empty<(Infallible, V)>;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        rayon::iter::empty()
+    }
+
+    #[inline]
+    fn par_iter_mut(&mut self) -> Self::ParIterMut<'_> {
+        rayon::iter::empty()
+    }
+
+    #[inline]
+    fn par_values_mut(&mut self) -> Self::ParValuesMut<'_> {
+        rayon::iter::empty()
+    }
+
+    #[inline]
+    fn into_par_iter(self) -> Self::IntoParIter {
+        rayon::iter::empty()
+    }
+}
+
+/// The archived form of [`EmptyMapStorage`], which takes up no space.
+#[cfg(feature = "rkyv")]
+#[cfg_attr(
+    feature = "rkyv-validation",
+    derive(::rkyv::CheckBytes),
+    check_bytes(crate = "::rkyv::bytecheck")
+)]
+pub struct ArchivedEmptyMapStorage<V> {
+    _marker: PhantomData<V>,
+}
+
+#[cfg(feature = "rkyv")]
+impl<V> ArchiveMapStorage<Infallible, V> for EmptyMapStorage<V>
+where
+    V: Archive,
+{
+    type Archived = ArchivedEmptyMapStorage<V>;
+    type Resolver = ();
+
+    #[inline]
+    fn serialize<S>(&self, _: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+        V: Serialize<S>,
+    {
+        Ok(())
+    }
+
+    #[inline]
+    unsafe fn resolve(&self, _: usize, (): Self::Resolver, _: *mut Self::Archived) {}
+}
+
+#[cfg(feature = "rkyv")]
+impl<V> ArchivedMapStorage<Infallible, V> for ArchivedEmptyMapStorage<V>
+where
+    V: Archive,
+{
+    type Iter<'this>
+        = iter::Empty<(Infallible, &'this V::Archived)>
+    where
+        V: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn get(&self, key: Infallible) -> Option<&V::Archived> {
+        match key {}
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        iter::empty()
+    }
+}
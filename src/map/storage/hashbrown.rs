@@ -1,6 +1,18 @@
-use core::hash::Hash;
-use core::iter;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::iter::{self, FusedIterator};
 
+use alloc::vec::Vec;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::{
+    IntoParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
+};
+
+use crate::key::HashKey;
+#[cfg(feature = "rayon")]
+use crate::map::ParallelMapStorage;
 use crate::map::{Entry, MapStorage, OccupiedEntry, VacantEntry};
 
 type S = ::hashbrown::hash_map::DefaultHashBuilder;
@@ -66,13 +78,82 @@ where
 {
 }
 
+/// Hashes every entry separately and combines them in an order-independent
+/// manner, since the iteration order of a [`hashbrown::HashMap`] is
+/// unspecified.
+impl<K, V> Hash for HashbrownMapStorage<K, V>
+where
+    K: Hash,
+    V: Hash,
+{
+    #[inline]
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        state.write_usize(self.inner.len());
+        state.write_u64(hash_unordered(&self.inner));
+    }
+}
+
+/// Storages are ordered by comparing their entries sorted by key.
+impl<K, V> PartialOrd for HashbrownMapStorage<K, V>
+where
+    K: Ord + Hash,
+    V: PartialOrd,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        sorted_entries(&self.inner).partial_cmp(&sorted_entries(&other.inner))
+    }
+}
+
+impl<K, V> Ord for HashbrownMapStorage<K, V>
+where
+    K: Ord + Hash,
+    V: Ord,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        sorted_entries(&self.inner).cmp(&sorted_entries(&other.inner))
+    }
+}
+
+/// Combine the hashes of every element with a fixed hasher, so that the result
+/// doesn't depend on iteration order.
+pub(crate) fn hash_unordered<I>(iter: I) -> u64
+where
+    I: IntoIterator,
+    I::Item: Hash,
+{
+    let mut output = 0u64;
+
+    for item in iter {
+        #[allow(deprecated)]
+        let mut hasher = core::hash::SipHasher::new();
+        item.hash(&mut hasher);
+        output = output.wrapping_add(hasher.finish());
+    }
+
+    output
+}
+
+fn sorted_entries<K, V>(map: &::hashbrown::HashMap<K, V>) -> Vec<(&K, &V)>
+where
+    K: Ord,
+{
+    let mut entries = map.iter().collect::<Vec<_>>();
+    entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
 impl<'a, K, V> OccupiedEntry<'a, K, V> for Occupied<'a, K, V>
 where
-    K: Copy,
+    K: Clone,
 {
     #[inline]
     fn key(&self) -> K {
-        *self.key()
+        self.key().clone()
     }
 
     #[inline]
@@ -103,11 +184,11 @@ where
 
 impl<'a, K, V> VacantEntry<'a, K, V> for Vacant<'a, K, V>
 where
-    K: Copy + Hash,
+    K: Clone + Hash,
 {
     #[inline]
     fn key(&self) -> K {
-        *self.key()
+        self.key().clone()
     }
 
     #[inline]
@@ -116,9 +197,56 @@ where
     }
 }
 
+/// See [`HashbrownMapStorage::zip`].
+///
+/// Since the entries of a [`hashbrown::HashMap`] are unordered, every entry of
+/// the first storage looks up its key in the other storage, after which the
+/// entries of the other storage which are missing from the first one follow.
+pub struct Zip<'a, K, V, U>
+where
+    K: HashKey,
+    U: 'a,
+{
+    a: ::hashbrown::hash_map::Iter<'a, K, V>,
+    b: <K::MapStorage<U> as MapStorage<K, U>>::Iter<'a>,
+    this: &'a ::hashbrown::HashMap<K, V>,
+    other: &'a K::MapStorage<U>,
+}
+
+impl<'a, K, V, U> Iterator for Zip<'a, K, V, U>
+where
+    K: HashKey,
+    U: 'a,
+{
+    type Item = (K, Option<&'a V>, Option<&'a U>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((key, a)) = self.a.next() {
+            let b = self.other.get(key.clone());
+            return Some((key.clone(), Some(a), b));
+        }
+
+        loop {
+            let (key, b) = self.b.next()?;
+
+            if !self.this.contains_key(&key) {
+                return Some((key, None, Some(b)));
+            }
+        }
+    }
+}
+
+impl<'a, K, V, U> FusedIterator for Zip<'a, K, V, U>
+where
+    K: HashKey,
+    U: 'a,
+{
+}
+
 impl<K, V> MapStorage<K, V> for HashbrownMapStorage<K, V>
 where
-    K: Copy + Eq + Hash,
+    K: HashKey + Borrow<K::Borrowed>,
 {
     type Iter<'this>
         = iter::Map<
@@ -129,7 +257,7 @@ where
         K: 'this,
         V: 'this;
     type Keys<'this>
-        = iter::Copied<::hashbrown::hash_map::Keys<'this, K, V>>
+        = iter::Cloned<::hashbrown::hash_map::Keys<'this, K, V>>
     where
         K: 'this,
         V: 'this;
@@ -152,6 +280,12 @@ where
         K: 'this,
         V: 'this;
     type IntoIter = ::hashbrown::hash_map::IntoIter<K, V>;
+    type Zip<'this, U>
+        = Zip<'this, K, V, U>
+    where
+        K: 'this,
+        V: 'this,
+        U: 'this;
     type Occupied<'this>
         = Occupied<'this, K, V>
     where
@@ -205,12 +339,32 @@ where
         self.inner.remove(&key)
     }
 
+    #[inline]
+    fn contains_key_by(&self, key: K::Ref<'_>) -> bool {
+        self.inner.contains_key(K::borrowed(&key))
+    }
+
+    #[inline]
+    fn get_by(&self, key: K::Ref<'_>) -> Option<&V> {
+        self.inner.get(K::borrowed(&key))
+    }
+
+    #[inline]
+    fn get_mut_by(&mut self, key: K::Ref<'_>) -> Option<&mut V> {
+        self.inner.get_mut(K::borrowed(&key))
+    }
+
+    #[inline]
+    fn remove_by(&mut self, key: K::Ref<'_>) -> Option<V> {
+        self.inner.remove(K::borrowed(&key))
+    }
+
     #[inline]
     fn retain<F>(&mut self, mut func: F)
     where
         F: FnMut(K, &mut V) -> bool,
     {
-        self.inner.retain(|&k, v| func(k, v));
+        self.inner.retain(|k, v| func(k.clone(), v));
     }
 
     #[inline]
@@ -220,13 +374,13 @@ where
 
     #[inline]
     fn iter(&self) -> Self::Iter<'_> {
-        let map: fn(_) -> _ = |(k, v): (&K, &V)| (*k, v);
+        let map: fn(_) -> _ = |(k, v): (&K, &V)| (k.clone(), v);
         self.inner.iter().map(map)
     }
 
     #[inline]
     fn keys(&self) -> Self::Keys<'_> {
-        self.inner.keys().copied()
+        self.inner.keys().cloned()
     }
 
     #[inline]
@@ -236,7 +390,7 @@ where
 
     #[inline]
     fn iter_mut(&mut self) -> Self::IterMut<'_> {
-        let map: fn(_) -> _ = |(k, v): (&K, &mut V)| (*k, v);
+        let map: fn(_) -> _ = |(k, v): (&K, &mut V)| (k.clone(), v);
         self.inner.iter_mut().map(map)
     }
 
@@ -257,4 +411,69 @@ where
             HashMapEntry::Vacant(entry) => Entry::Vacant(entry),
         }
     }
+
+    #[inline]
+    fn zip<'this, U>(&'this self, other: &'this K::MapStorage<U>) -> Self::Zip<'this, U>
+    where
+        U: 'this,
+    {
+        Zip {
+            a: self.inner.iter(),
+            b: other.iter(),
+            this: &self.inner,
+            other,
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, V> ParallelMapStorage<K, V> for HashbrownMapStorage<K, V>
+where
+    K: HashKey + Borrow<K::Borrowed> + Send + Sync,
+    V: Send + Sync,
+{
+    type ParIter<'this>
+        = rayon::iter::Map<
+        ::hashbrown::hash_map::rayon::ParIter<'this, K, V>,
+        fn((&'this K, &'this V)) -> (K, &'this V),
+    >
+    where
+        K: 'this,
+        V: 'this;
+    type ParIterMut<'this>
+        = rayon::iter::Map<
+        ::hashbrown::hash_map::rayon::ParIterMut<'this, K, V>,
+        fn((&'this K, &'this mut V)) -> (K, &'this mut V),
+    >
+    where
+        K: 'this,
+        V: 'this;
+    type ParValuesMut<'this>
+        = ::hashbrown::hash_map::rayon::ParValuesMut<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type IntoParIter = ::hashbrown::hash_map::rayon::IntoParIter<K, V>;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let map: fn(_) -> _ = |(k, v): (&K, &V)| (k.clone(), v);
+        self.inner.par_iter().map(map)
+    }
+
+    #[inline]
+    fn par_iter_mut(&mut self) -> Self::ParIterMut<'_> {
+        let map: fn(_) -> _ = |(k, v): (&K, &mut V)| (k.clone(), v);
+        self.inner.par_iter_mut().map(map)
+    }
+
+    #[inline]
+    fn par_values_mut(&mut self) -> Self::ParValuesMut<'_> {
+        self.inner.par_values_mut()
+    }
+
+    #[inline]
+    fn into_par_iter(self) -> Self::IntoParIter {
+        self.inner.into_par_iter()
+    }
 }
@@ -1,41 +1,70 @@
 use core::iter;
-use core::mem;
 use core::option;
 
-use crate::map::{Entry, MapStorage, OccupiedEntry, VacantEntry};
+#[cfg(feature = "rayon")]
+use rayon::iter::{
+    IntoParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
+};
+#[cfg(feature = "rkyv")]
+use rkyv::{out_field, Archive, Archived, Fallible, Resolver, Serialize};
+
+use crate::exact_iter::ExactIter;
+#[cfg(feature = "rayon")]
+use crate::map::ParallelMapStorage;
+#[cfg(feature = "rkyv")]
+use crate::map::{ArchiveMapStorage, ArchivedMapStorage};
+use crate::map::{Entry, MapStorage, OccupiedEntry, VacantEntry, VariantMapStorage};
 use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
 use crate::Key;
 
-type Iter<'a, K, V> = iter::Chain<
-    iter::Map<
-        <<K as Key>::MapStorage<V> as MapStorage<K, V>>::Iter<'a>,
-        fn((K, &'a V)) -> (Option<K>, &'a V),
+type Iter<'a, K, V> = ExactIter<
+    iter::Chain<
+        iter::Map<
+            <<K as Key>::MapStorage<V> as MapStorage<K, V>>::Iter<'a>,
+            fn((K, &'a V)) -> (Option<K>, &'a V),
+        >,
+        iter::Map<option::Iter<'a, V>, fn(&'a V) -> (Option<K>, &'a V)>,
+    >,
+>;
+type Keys<'a, K, V> = ExactIter<
+    iter::Chain<
+        iter::Map<<<K as Key>::MapStorage<V> as MapStorage<K, V>>::Keys<'a>, fn(K) -> Option<K>>,
+        option::IntoIter<Option<K>>,
     >,
-    iter::Map<option::Iter<'a, V>, fn(&'a V) -> (Option<K>, &'a V)>,
 >;
-type Keys<'a, K, V> = iter::Chain<
-    iter::Map<<<K as Key>::MapStorage<V> as MapStorage<K, V>>::Keys<'a>, fn(K) -> Option<K>>,
-    option::IntoIter<Option<K>>,
+type Values<'a, K, V> = ExactIter<
+    iter::Chain<<<K as Key>::MapStorage<V> as MapStorage<K, V>>::Values<'a>, option::Iter<'a, V>>,
 >;
-type Values<'a, K, V> =
-    iter::Chain<<<K as Key>::MapStorage<V> as MapStorage<K, V>>::Values<'a>, option::Iter<'a, V>>;
-type IterMut<'a, K, V> = iter::Chain<
-    iter::Map<
-        <<K as Key>::MapStorage<V> as MapStorage<K, V>>::IterMut<'a>,
-        fn((K, &'a mut V)) -> (Option<K>, &'a mut V),
+type IterMut<'a, K, V> = ExactIter<
+    iter::Chain<
+        iter::Map<
+            <<K as Key>::MapStorage<V> as MapStorage<K, V>>::IterMut<'a>,
+            fn((K, &'a mut V)) -> (Option<K>, &'a mut V),
+        >,
+        iter::Map<option::IterMut<'a, V>, fn(&'a mut V) -> (Option<K>, &'a mut V)>,
     >,
-    iter::Map<option::IterMut<'a, V>, fn(&'a mut V) -> (Option<K>, &'a mut V)>,
 >;
-type ValuesMut<'a, K, V> = iter::Chain<
-    <<K as Key>::MapStorage<V> as MapStorage<K, V>>::ValuesMut<'a>,
-    option::IterMut<'a, V>,
+type ValuesMut<'a, K, V> = ExactIter<
+    iter::Chain<
+        <<K as Key>::MapStorage<V> as MapStorage<K, V>>::ValuesMut<'a>,
+        option::IterMut<'a, V>,
+    >,
+>;
+type IntoIter<K, V> = ExactIter<
+    iter::Chain<
+        iter::Map<
+            <<K as Key>::MapStorage<V> as MapStorage<K, V>>::IntoIter,
+            fn((K, V)) -> (Option<K>, V),
+        >,
+        iter::Map<option::IntoIter<V>, fn(V) -> (Option<K>, V)>,
+    >,
 >;
-type IntoIter<K, V> = iter::Chain<
+type Zip<'a, K, V, U> = iter::Chain<
     iter::Map<
-        <<K as Key>::MapStorage<V> as MapStorage<K, V>>::IntoIter,
-        fn((K, V)) -> (Option<K>, V),
+        <<K as Key>::MapStorage<V> as MapStorage<K, V>>::Zip<'a, U>,
+        fn((K, Option<&'a V>, Option<&'a U>)) -> (Option<K>, Option<&'a V>, Option<&'a U>),
     >,
-    iter::Map<option::IntoIter<V>, fn(V) -> (Option<K>, V)>,
+    option::IntoIter<(Option<K>, Option<&'a V>, Option<&'a U>)>,
 >;
 
 /// [`MapStorage`] for [`Option`] types.
@@ -241,6 +270,12 @@ where
         K: 'this,
         V: 'this;
     type IntoIter = IntoIter<K, V>;
+    type Zip<'this, U>
+        = Zip<'this, K, V, U>
+    where
+        K: 'this,
+        V: 'this,
+        U: 'this;
     type Occupied<'this>
         = Occupied<'this, K, V>
     where
@@ -274,7 +309,7 @@ where
     fn insert(&mut self, key: Option<K>, value: V) -> Option<V> {
         match key {
             Some(key) => self.some.insert(key, value),
-            None => mem::replace(&mut self.none, Some(value)),
+            None => self.none.replace(value),
         }
     }
 
@@ -310,6 +345,38 @@ where
         }
     }
 
+    #[inline]
+    fn contains_key_by(&self, key: <Option<K> as Key>::Ref<'_>) -> bool {
+        match key {
+            Some(key) => self.some.contains_key_by(key),
+            None => self.none.is_some(),
+        }
+    }
+
+    #[inline]
+    fn get_by(&self, key: <Option<K> as Key>::Ref<'_>) -> Option<&V> {
+        match key {
+            Some(key) => self.some.get_by(key),
+            None => self.none.as_ref(),
+        }
+    }
+
+    #[inline]
+    fn get_mut_by(&mut self, key: <Option<K> as Key>::Ref<'_>) -> Option<&mut V> {
+        match key {
+            Some(key) => self.some.get_mut_by(key),
+            None => self.none.as_mut(),
+        }
+    }
+
+    #[inline]
+    fn remove_by(&mut self, key: <Option<K> as Key>::Ref<'_>) -> Option<V> {
+        match key {
+            Some(key) => self.some.remove_by(key),
+            None => self.none.take(),
+        }
+    }
+
     #[inline]
     fn retain<F>(&mut self, mut func: F)
     where
@@ -335,44 +402,50 @@ where
         let a = self.some.iter().map(map);
         let map: fn(_) -> _ = |v| (None, v);
         let b = self.none.iter().map(map);
-        a.chain(b)
+        ExactIter::new(a.chain(b), self.len())
     }
 
     #[inline]
     fn keys(&self) -> Self::Keys<'_> {
         let map: fn(_) -> _ = |k| Some(k);
-        self.some
-            .keys()
-            .map(map)
-            .chain(self.none.is_some().then_some(None::<K>))
+        ExactIter::new(
+            self.some
+                .keys()
+                .map(map)
+                .chain(self.none.is_some().then_some(None::<K>)),
+            self.len(),
+        )
     }
 
     #[inline]
     fn values(&self) -> Self::Values<'_> {
-        self.some.values().chain(self.none.iter())
+        ExactIter::new(self.some.values().chain(self.none.iter()), self.len())
     }
 
     #[inline]
     fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let len = self.len();
         let map: fn(_) -> _ = |(k, b)| (Some(k), b);
         let a = self.some.iter_mut().map(map);
         let map: fn(_) -> _ = |v| (None, v);
         let b = self.none.iter_mut().map(map);
-        a.chain(b)
+        ExactIter::new(a.chain(b), len)
     }
 
     #[inline]
     fn values_mut(&mut self) -> Self::ValuesMut<'_> {
-        self.some.values_mut().chain(self.none.iter_mut())
+        let len = self.len();
+        ExactIter::new(self.some.values_mut().chain(self.none.iter_mut()), len)
     }
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
         let map: fn(_) -> _ = |(k, b)| (Some(k), b);
         let a = self.some.into_iter().map(map);
         let map: fn(_) -> _ = |v| (None, v);
         let b = self.none.into_iter().map(map);
-        a.chain(b)
+        ExactIter::new(a.chain(b), len)
     }
 
     #[inline]
@@ -388,4 +461,259 @@ where
             },
         }
     }
+
+    #[inline]
+    fn zip<'this, U>(
+        &'this self,
+        other: &'this <Option<K> as Key>::MapStorage<U>,
+    ) -> Self::Zip<'this, U>
+    where
+        U: 'this,
+    {
+        let map: fn(_) -> _ = |(key, a, b)| (Some(key), a, b);
+        let a = self.some.zip(&other.some).map(map);
+        let (a_none, b_none) = (self.none.as_ref(), other.none.as_ref());
+        let b = (a_none.is_some() || b_none.is_some()).then_some((None, a_none, b_none));
+        a.chain(b)
+    }
+
+    #[inline]
+    fn map_values<U, F>(self, mut f: F) -> <Option<K> as Key>::MapStorage<U>
+    where
+        F: FnMut(Option<K>, V) -> U,
+    {
+        OptionMapStorage {
+            some: self.some.map_values(|key, value| f(Some(key), value)),
+            none: self.none.map(|value| f(None, value)),
+        }
+    }
+
+    #[inline]
+    fn merge<U, W, F>(
+        self,
+        other: <Option<K> as Key>::MapStorage<U>,
+        mut f: F,
+    ) -> <Option<K> as Key>::MapStorage<W>
+    where
+        F: FnMut(Option<K>, Option<V>, Option<U>) -> Option<W>,
+    {
+        OptionMapStorage {
+            some: self.some.merge(other.some, |key, a, b| f(Some(key), a, b)),
+            none: match (self.none, other.none) {
+                (None, None) => None,
+                (a, b) => f(None, a, b),
+            },
+        }
+    }
+}
+
+impl<K, V> VariantMapStorage<Option<K>, V, 1> for OptionMapStorage<K, V>
+where
+    K: Key,
+{
+    type Field = K;
+
+    #[inline]
+    fn variant(&self) -> &K::MapStorage<V> {
+        &self.some
+    }
+
+    #[inline]
+    fn variant_mut(&mut self) -> &mut K::MapStorage<V> {
+        &mut self.some
+    }
+
+    #[inline]
+    fn into_variant(self) -> K::MapStorage<V> {
+        self.some
+    }
+}
+
+#[cfg(feature = "rayon")]
+type ParIter<'a, K, V> = rayon::iter::Chain<
+    rayon::iter::Map<
+        <<K as Key>::MapStorage<V> as ParallelMapStorage<K, V>>::ParIter<'a>,
+        fn((K, &'a V)) -> (Option<K>, &'a V),
+    >,
+    rayon::iter::Map<rayon::option::Iter<'a, V>, fn(&'a V) -> (Option<K>, &'a V)>,
+>;
+#[cfg(feature = "rayon")]
+type ParIterMut<'a, K, V> = rayon::iter::Chain<
+    rayon::iter::Map<
+        <<K as Key>::MapStorage<V> as ParallelMapStorage<K, V>>::ParIterMut<'a>,
+        fn((K, &'a mut V)) -> (Option<K>, &'a mut V),
+    >,
+    rayon::iter::Map<rayon::option::IterMut<'a, V>, fn(&'a mut V) -> (Option<K>, &'a mut V)>,
+>;
+#[cfg(feature = "rayon")]
+type ParValuesMut<'a, K, V> = rayon::iter::Chain<
+    <<K as Key>::MapStorage<V> as ParallelMapStorage<K, V>>::ParValuesMut<'a>,
+    rayon::option::IterMut<'a, V>,
+>;
+#[cfg(feature = "rayon")]
+type IntoParIter<K, V> = rayon::iter::Chain<
+    rayon::iter::Map<
+        <<K as Key>::MapStorage<V> as ParallelMapStorage<K, V>>::IntoParIter,
+        fn((K, V)) -> (Option<K>, V),
+    >,
+    rayon::iter::Map<rayon::option::IntoIter<V>, fn(V) -> (Option<K>, V)>,
+>;
+
+#[cfg(feature = "rayon")]
+impl<K, V> ParallelMapStorage<Option<K>, V> for OptionMapStorage<K, V>
+where
+    K: Key + Send,
+    K::MapStorage<V>: ParallelMapStorage<K, V>,
+    V: Send + Sync,
+{
+    type ParIter<'this>
+        = ParIter<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type ParIterMut<'this>
+        = ParIterMut<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type ParValuesMut<'this>
+        = ParValuesMut<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type IntoParIter = IntoParIter<K, V>;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let map: fn(_) -> _ = |(k, v)| (Some(k), v);
+        let a = self.some.par_iter().map(map);
+        let map: fn(_) -> _ = |v| (None, v);
+        let b = self.none.par_iter().map(map);
+        a.chain(b)
+    }
+
+    #[inline]
+    fn par_iter_mut(&mut self) -> Self::ParIterMut<'_> {
+        let map: fn(_) -> _ = |(k, v)| (Some(k), v);
+        let a = self.some.par_iter_mut().map(map);
+        let map: fn(_) -> _ = |v| (None, v);
+        let b = self.none.par_iter_mut().map(map);
+        a.chain(b)
+    }
+
+    #[inline]
+    fn par_values_mut(&mut self) -> Self::ParValuesMut<'_> {
+        self.some.par_values_mut().chain(self.none.par_iter_mut())
+    }
+
+    #[inline]
+    fn into_par_iter(self) -> Self::IntoParIter {
+        let map: fn(_) -> _ = |(k, v)| (Some(k), v);
+        let a = ParallelMapStorage::into_par_iter(self.some).map(map);
+        let map: fn(_) -> _ = |v| (None, v);
+        let b = self.none.into_par_iter().map(map);
+        a.chain(b)
+    }
+}
+
+/// The archived form of [`OptionMapStorage`].
+#[cfg(feature = "rkyv")]
+#[repr(C)]
+#[cfg_attr(
+    feature = "rkyv-validation",
+    derive(::rkyv::CheckBytes),
+    check_bytes(crate = "::rkyv::bytecheck")
+)]
+pub struct ArchivedOptionMapStorage<K, V>
+where
+    K: Key,
+    K::MapStorage<V>: ArchiveMapStorage<K, V>,
+    V: Archive,
+{
+    some: <K::MapStorage<V> as ArchiveMapStorage<K, V>>::Archived,
+    none: Archived<Option<V>>,
+}
+
+#[cfg(feature = "rkyv")]
+type ArchivedIter<'a, K, V> = iter::Chain<
+    iter::Map<
+        <<<K as Key>::MapStorage<V> as ArchiveMapStorage<K, V>>::Archived as ArchivedMapStorage<
+            K,
+            V,
+        >>::Iter<'a>,
+        fn((K, &'a <V as Archive>::Archived)) -> (Option<K>, &'a <V as Archive>::Archived),
+    >,
+    iter::Map<
+        option::IntoIter<&'a <V as Archive>::Archived>,
+        fn(&'a <V as Archive>::Archived) -> (Option<K>, &'a <V as Archive>::Archived),
+    >,
+>;
+
+#[cfg(feature = "rkyv")]
+impl<K, V> ArchiveMapStorage<Option<K>, V> for OptionMapStorage<K, V>
+where
+    K: Key,
+    K::MapStorage<V>: ArchiveMapStorage<K, V>,
+    V: Archive,
+{
+    type Archived = ArchivedOptionMapStorage<K, V>;
+    type Resolver = (
+        <K::MapStorage<V> as ArchiveMapStorage<K, V>>::Resolver,
+        Resolver<Option<V>>,
+    );
+
+    #[inline]
+    fn serialize<S>(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+        V: Serialize<S>,
+    {
+        let some = self.some.serialize(serializer)?;
+        let none = Serialize::serialize(&self.none, serializer)?;
+        Ok((some, none))
+    }
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, (some, none): Self::Resolver, out: *mut Self::Archived) {
+        let (offset, field) = out_field!(out.some);
+        self.some.resolve(pos + offset, some, field);
+        let (offset, field) = out_field!(out.none);
+        self.none.resolve(pos + offset, none, field);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<K, V> ArchivedMapStorage<Option<K>, V> for ArchivedOptionMapStorage<K, V>
+where
+    K: Key,
+    K::MapStorage<V>: ArchiveMapStorage<K, V>,
+    V: Archive,
+{
+    type Iter<'this>
+        = ArchivedIter<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.some.len() + usize::from(self.none.is_some())
+    }
+
+    #[inline]
+    fn get(&self, key: Option<K>) -> Option<&V::Archived> {
+        match key {
+            Some(key) => self.some.get(key),
+            None => self.none.as_ref(),
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let map: fn(_) -> _ = |(k, v)| (Some(k), v);
+        let a = self.some.iter().map(map);
+        let map: fn(_) -> _ = |v| (None, v);
+        let b = self.none.as_ref().into_iter().map(map);
+        a.chain(b)
+    }
 }
@@ -0,0 +1,528 @@
+// Iterators are confusing if they impl `Copy`.
+
+#![allow(missing_copy_implementations)]
+
+use core::array;
+use core::cmp::Ordering;
+use core::iter;
+use core::slice;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::{
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
+    IntoParallelRefMutIterator, ParallelIterator,
+};
+#[cfg(feature = "rkyv")]
+use rkyv::{out_field, Archive, Archived, Fallible, Resolver, Serialize};
+
+use crate::exact_iter::ExactIter;
+use crate::map::storage::{zip_slots, ZipSlots};
+#[cfg(feature = "rayon")]
+use crate::map::ParallelMapStorage;
+#[cfg(feature = "rkyv")]
+use crate::map::{ArchiveMapStorage, ArchivedMapStorage};
+use crate::map::{Entry, MapStorage, OccupiedEntry, VacantEntry};
+use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
+
+/// Every [`Ordering`] in storage order.
+const ORDERINGS: [Ordering; 3] = [Ordering::Less, Ordering::Equal, Ordering::Greater];
+
+type Iter<'a, V> = ExactIter<
+    iter::FilterMap<
+        iter::Zip<array::IntoIter<Ordering, 3>, slice::Iter<'a, Option<V>>>,
+        fn((Ordering, &'a Option<V>)) -> Option<(Ordering, &'a V)>,
+    >,
+>;
+type Keys<'a, V> = ExactIter<
+    iter::FilterMap<
+        iter::Zip<array::IntoIter<Ordering, 3>, slice::Iter<'a, Option<V>>>,
+        fn((Ordering, &'a Option<V>)) -> Option<Ordering>,
+    >,
+>;
+type Values<'a, V> = ExactIter<iter::Flatten<slice::Iter<'a, Option<V>>>>;
+type IterMut<'a, V> = ExactIter<
+    iter::FilterMap<
+        iter::Zip<array::IntoIter<Ordering, 3>, slice::IterMut<'a, Option<V>>>,
+        fn((Ordering, &'a mut Option<V>)) -> Option<(Ordering, &'a mut V)>,
+    >,
+>;
+type ValuesMut<'a, V> = ExactIter<iter::Flatten<slice::IterMut<'a, Option<V>>>>;
+type IntoIter<V> = ExactIter<
+    iter::FilterMap<
+        iter::Zip<array::IntoIter<Ordering, 3>, array::IntoIter<Option<V>, 3>>,
+        fn((Ordering, Option<V>)) -> Option<(Ordering, V)>,
+    >,
+>;
+type Zip<'a, V, U> =
+    ZipSlots<'a, array::IntoIter<(Ordering, Option<&'a V>, Option<&'a U>), 3>, Ordering, V, U>;
+
+/// [`MapStorage`] for [`Ordering`] types.
+///
+/// # Examples
+///
+/// ```
+/// use core::cmp::Ordering;
+///
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First(Ordering),
+///     Second,
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert(MyKey::First(Ordering::Greater), 1);
+/// a.insert(MyKey::First(Ordering::Less), 2);
+///
+/// assert_eq!(a.get(MyKey::First(Ordering::Less)), Some(&2));
+/// assert_eq!(a.get(MyKey::First(Ordering::Equal)), None);
+/// assert_eq!(a.get(MyKey::First(Ordering::Greater)), Some(&1));
+/// assert_eq!(a.get(MyKey::Second), None);
+///
+/// assert!(a.iter().eq([(MyKey::First(Ordering::Less), &2), (MyKey::First(Ordering::Greater), &1)]));
+/// assert!(a.values().copied().eq([2, 1]));
+/// assert!(a.keys().eq([MyKey::First(Ordering::Less), MyKey::First(Ordering::Greater)]));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct OrderingMapStorage<V> {
+    data: [Option<V>; 3],
+}
+
+pub struct Vacant<'a, V> {
+    key: Ordering,
+    inner: NoneBucket<'a, V>,
+}
+
+pub struct Occupied<'a, V> {
+    key: Ordering,
+    inner: SomeBucket<'a, V>,
+}
+
+impl<'a, V> VacantEntry<'a, Ordering, V> for Vacant<'a, V> {
+    #[inline]
+    fn key(&self) -> Ordering {
+        self.key
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        self.inner.insert(value)
+    }
+}
+
+impl<'a, V> OccupiedEntry<'a, Ordering, V> for Occupied<'a, V> {
+    #[inline]
+    fn key(&self) -> Ordering {
+        self.key
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.inner.as_ref()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.inner.as_mut()
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        self.inner.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.inner.replace(value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        self.inner.take()
+    }
+}
+
+impl<V> MapStorage<Ordering, V> for OrderingMapStorage<V> {
+    type Iter<'this>
+        = Iter<'this, V>
+    where
+        V: 'this;
+    type Keys<'this>
+        = Keys<'this, V>
+    where
+        V: 'this;
+    type Values<'this>
+        = Values<'this, V>
+    where
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, V>
+    where
+        V: 'this;
+    type ValuesMut<'this>
+        = ValuesMut<'this, V>
+    where
+        V: 'this;
+    type IntoIter = IntoIter<V>;
+    type Zip<'this, U>
+        = Zip<'this, V, U>
+    where
+        V: 'this,
+        U: 'this;
+    type Occupied<'this>
+        = Occupied<'this, V>
+    where
+        V: 'this;
+    type Vacant<'this>
+        = Vacant<'this, V>
+    where
+        V: 'this;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            data: [None, None, None],
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.data.iter().filter(|value| value.is_some()).count()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.data.iter().all(Option::is_none)
+    }
+
+    #[inline]
+    fn insert(&mut self, key: Ordering, value: V) -> Option<V> {
+        self.data[index(key)].replace(value)
+    }
+
+    #[inline]
+    fn contains_key(&self, key: Ordering) -> bool {
+        self.data[index(key)].is_some()
+    }
+
+    #[inline]
+    fn get(&self, key: Ordering) -> Option<&V> {
+        self.data[index(key)].as_ref()
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: Ordering) -> Option<&mut V> {
+        self.data[index(key)].as_mut()
+    }
+
+    #[inline]
+    fn remove(&mut self, key: Ordering) -> Option<V> {
+        self.data[index(key)].take()
+    }
+
+    #[inline]
+    fn contains_key_by(&self, key: Ordering) -> bool {
+        self.contains_key(key)
+    }
+
+    #[inline]
+    fn get_by(&self, key: Ordering) -> Option<&V> {
+        self.get(key)
+    }
+
+    #[inline]
+    fn get_mut_by(&mut self, key: Ordering) -> Option<&mut V> {
+        self.get_mut(key)
+    }
+
+    #[inline]
+    fn remove_by(&mut self, key: Ordering) -> Option<V> {
+        self.remove(key)
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(Ordering, &mut V) -> bool,
+    {
+        for (key, value) in ORDERINGS.into_iter().zip(&mut self.data) {
+            if let Some(item) = value {
+                if !func(key, item) {
+                    *value = None;
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.data = [None, None, None];
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let map: fn(_) -> _ = |(k, v): (_, &Option<_>)| Some((k, v.as_ref()?));
+        ExactIter::new(
+            ORDERINGS.into_iter().zip(self.data.iter()).filter_map(map),
+            self.len(),
+        )
+    }
+
+    #[inline]
+    fn keys(&self) -> Self::Keys<'_> {
+        let map: fn(_) -> _ = |(k, v): (_, &Option<_>)| v.is_some().then_some(k);
+        ExactIter::new(
+            ORDERINGS.into_iter().zip(self.data.iter()).filter_map(map),
+            self.len(),
+        )
+    }
+
+    #[inline]
+    fn values(&self) -> Self::Values<'_> {
+        ExactIter::new(self.data.iter().flatten(), self.len())
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let len = self.len();
+        let map: fn(_) -> _ = |(k, v): (_, &mut Option<_>)| Some((k, v.as_mut()?));
+        ExactIter::new(
+            ORDERINGS
+                .into_iter()
+                .zip(self.data.iter_mut())
+                .filter_map(map),
+            len,
+        )
+    }
+
+    #[inline]
+    fn values_mut(&mut self) -> Self::ValuesMut<'_> {
+        let len = self.len();
+        ExactIter::new(self.data.iter_mut().flatten(), len)
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        let map: fn(_) -> _ = |(k, v): (_, Option<_>)| Some((k, v?));
+        ExactIter::new(ORDERINGS.into_iter().zip(self.data).filter_map(map), len)
+    }
+
+    #[inline]
+    fn entry(&mut self, key: Ordering) -> Entry<'_, Self, Ordering, V> {
+        match OptionBucket::new(&mut self.data[index(key)]) {
+            OptionBucket::Some(inner) => Entry::Occupied(Occupied { key, inner }),
+            OptionBucket::None(inner) => Entry::Vacant(Vacant { key, inner }),
+        }
+    }
+
+    #[inline]
+    fn zip<'this, U>(&'this self, other: &'this OrderingMapStorage<U>) -> Self::Zip<'this, U>
+    where
+        U: 'this,
+    {
+        let [a0, a1, a2] = &self.data;
+        let [b0, b1, b2] = &other.data;
+
+        zip_slots([
+            (Ordering::Less, a0.as_ref(), b0.as_ref()),
+            (Ordering::Equal, a1.as_ref(), b1.as_ref()),
+            (Ordering::Greater, a2.as_ref(), b2.as_ref()),
+        ])
+    }
+
+    #[inline]
+    fn map_values<U, F>(self, mut f: F) -> OrderingMapStorage<U>
+    where
+        F: FnMut(Ordering, V) -> U,
+    {
+        let [less, equal, greater] = self.data;
+
+        OrderingMapStorage {
+            data: [
+                less.map(|value| f(Ordering::Less, value)),
+                equal.map(|value| f(Ordering::Equal, value)),
+                greater.map(|value| f(Ordering::Greater, value)),
+            ],
+        }
+    }
+
+    #[inline]
+    fn merge<U, W, F>(self, other: OrderingMapStorage<U>, mut f: F) -> OrderingMapStorage<W>
+    where
+        F: FnMut(Ordering, Option<V>, Option<U>) -> Option<W>,
+    {
+        let [a0, a1, a2] = self.data;
+        let [b0, b1, b2] = other.data;
+
+        let mut merge = |key, a, b| match (a, b) {
+            (None, None) => None,
+            (a, b) => f(key, a, b),
+        };
+
+        OrderingMapStorage {
+            data: [
+                merge(Ordering::Less, a0, b0),
+                merge(Ordering::Equal, a1, b1),
+                merge(Ordering::Greater, a2, b2),
+            ],
+        }
+    }
+}
+
+/// Index of the storage slot for the given ordering.
+#[inline]
+const fn index(key: Ordering) -> usize {
+    match key {
+        Ordering::Less => 0,
+        Ordering::Equal => 1,
+        Ordering::Greater => 2,
+    }
+}
+
+#[cfg(feature = "rayon")]
+type ParIter<'a, V> = rayon::iter::FilterMap<
+    rayon::iter::Zip<rayon::array::IntoIter<Ordering, 3>, rayon::slice::Iter<'a, Option<V>>>,
+    fn((Ordering, &'a Option<V>)) -> Option<(Ordering, &'a V)>,
+>;
+#[cfg(feature = "rayon")]
+type ParIterMut<'a, V> = rayon::iter::FilterMap<
+    rayon::iter::Zip<rayon::array::IntoIter<Ordering, 3>, rayon::slice::IterMut<'a, Option<V>>>,
+    fn((Ordering, &'a mut Option<V>)) -> Option<(Ordering, &'a mut V)>,
+>;
+#[cfg(feature = "rayon")]
+type ParValuesMut<'a, V> = rayon::iter::FilterMap<
+    rayon::slice::IterMut<'a, Option<V>>,
+    fn(&'a mut Option<V>) -> Option<&'a mut V>,
+>;
+#[cfg(feature = "rayon")]
+type IntoParIter<V> = rayon::iter::FilterMap<
+    rayon::iter::Zip<rayon::array::IntoIter<Ordering, 3>, rayon::array::IntoIter<Option<V>, 3>>,
+    fn((Ordering, Option<V>)) -> Option<(Ordering, V)>,
+>;
+
+#[cfg(feature = "rayon")]
+impl<V> ParallelMapStorage<Ordering, V> for OrderingMapStorage<V>
+where
+    V: Send + Sync,
+{
+    type ParIter<'this>
+        = ParIter<'this, V>
+    where
+        V: 'this;
+    type ParIterMut<'this>
+        = ParIterMut<'this, V>
+    where
+        V: 'this;
+    type ParValuesMut<'this>
+        = ParValuesMut<'this, V>
+    where
+        V: 'this;
+    type IntoParIter = IntoParIter<V>;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let map: fn(_) -> _ = |(k, v): (_, &Option<_>)| Some((k, v.as_ref()?));
+        ORDERINGS
+            .into_par_iter()
+            .zip(self.data.par_iter())
+            .filter_map(map)
+    }
+
+    #[inline]
+    fn par_iter_mut(&mut self) -> Self::ParIterMut<'_> {
+        let map: fn(_) -> _ = |(k, v): (_, &mut Option<_>)| Some((k, v.as_mut()?));
+        ORDERINGS
+            .into_par_iter()
+            .zip(self.data.par_iter_mut())
+            .filter_map(map)
+    }
+
+    #[inline]
+    fn par_values_mut(&mut self) -> Self::ParValuesMut<'_> {
+        let map: fn(_) -> _ = Option::as_mut;
+        self.data.par_iter_mut().filter_map(map)
+    }
+
+    #[inline]
+    fn into_par_iter(self) -> Self::IntoParIter {
+        let map: fn(_) -> _ = |(k, v): (_, Option<_>)| Some((k, v?));
+        ORDERINGS
+            .into_par_iter()
+            .zip(self.data.into_par_iter())
+            .filter_map(map)
+    }
+}
+
+/// The archived form of [`OrderingMapStorage`].
+#[cfg(feature = "rkyv")]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "rkyv-validation",
+    derive(::rkyv::CheckBytes),
+    check_bytes(crate = "::rkyv::bytecheck")
+)]
+pub struct ArchivedOrderingMapStorage<V>
+where
+    V: Archive,
+{
+    data: Archived<[Option<V>; 3]>,
+}
+
+#[cfg(feature = "rkyv")]
+type ArchivedIter<'a, V> = iter::FilterMap<
+    iter::Zip<array::IntoIter<Ordering, 3>, slice::Iter<'a, Archived<Option<V>>>>,
+    fn((Ordering, &'a Archived<Option<V>>)) -> Option<(Ordering, &'a <V as Archive>::Archived)>,
+>;
+
+#[cfg(feature = "rkyv")]
+impl<V> ArchiveMapStorage<Ordering, V> for OrderingMapStorage<V>
+where
+    V: Archive,
+{
+    type Archived = ArchivedOrderingMapStorage<V>;
+    type Resolver = Resolver<[Option<V>; 3]>;
+
+    #[inline]
+    fn serialize<S>(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+        V: Serialize<S>,
+    {
+        Serialize::serialize(&self.data, serializer)
+    }
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (offset, field) = out_field!(out.data);
+        self.data.resolve(pos + offset, resolver, field);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<V> ArchivedMapStorage<Ordering, V> for ArchivedOrderingMapStorage<V>
+where
+    V: Archive,
+{
+    type Iter<'this>
+        = ArchivedIter<'this, V>
+    where
+        V: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.data.iter().filter(|value| value.is_some()).count()
+    }
+
+    #[inline]
+    fn get(&self, key: Ordering) -> Option<&V::Archived> {
+        self.data[index(key)].as_ref()
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let map: fn(_) -> _ = |(k, v): (_, &Archived<Option<V>>)| Some((k, v.as_ref()?));
+        ORDERINGS.into_iter().zip(self.data.iter()).filter_map(map)
+    }
+}
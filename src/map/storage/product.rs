@@ -0,0 +1,671 @@
+use core::iter::{self, FusedIterator};
+
+use crate::exact_iter::ExactIter;
+use crate::key::Tuple;
+use crate::map::{Entry, MapStorage, OccupiedEntry, VacantEntry};
+use crate::Key;
+
+type Head<K> = <K as Tuple>::Head;
+type Tail<K> = <K as Tuple>::Tail;
+type Inner<K, V> = <Tail<K> as Key>::MapStorage<V>;
+type Outer<K, V> = <Head<K> as Key>::MapStorage<Inner<K, V>>;
+type OuterZip<'a, K, V, U> =
+    <Outer<K, V> as MapStorage<Head<K>, Inner<K, V>>>::Zip<'a, Inner<K, U>>;
+
+type Iter<'a, K, V> = ExactIter<
+    ProductIter<
+        Head<K>,
+        &'a Inner<K, V>,
+        <Outer<K, V> as MapStorage<Head<K>, Inner<K, V>>>::Iter<'a>,
+        <Inner<K, V> as MapStorage<Tail<K>, V>>::Iter<'a>,
+        (K, &'a V),
+    >,
+>;
+type Keys<'a, K, V> = ExactIter<
+    ProductIter<
+        Head<K>,
+        &'a Inner<K, V>,
+        <Outer<K, V> as MapStorage<Head<K>, Inner<K, V>>>::Iter<'a>,
+        <Inner<K, V> as MapStorage<Tail<K>, V>>::Keys<'a>,
+        K,
+    >,
+>;
+type Values<'a, K, V> = ExactIter<
+    iter::FlatMap<
+        <Outer<K, V> as MapStorage<Head<K>, Inner<K, V>>>::Values<'a>,
+        <Inner<K, V> as MapStorage<Tail<K>, V>>::Values<'a>,
+        fn(&'a Inner<K, V>) -> <Inner<K, V> as MapStorage<Tail<K>, V>>::Values<'a>,
+    >,
+>;
+type IterMut<'a, K, V> = ExactIter<
+    ProductIter<
+        Head<K>,
+        &'a mut Inner<K, V>,
+        <Outer<K, V> as MapStorage<Head<K>, Inner<K, V>>>::IterMut<'a>,
+        <Inner<K, V> as MapStorage<Tail<K>, V>>::IterMut<'a>,
+        (K, &'a mut V),
+    >,
+>;
+type ValuesMut<'a, K, V> = ExactIter<
+    iter::FlatMap<
+        <Outer<K, V> as MapStorage<Head<K>, Inner<K, V>>>::ValuesMut<'a>,
+        <Inner<K, V> as MapStorage<Tail<K>, V>>::ValuesMut<'a>,
+        fn(&'a mut Inner<K, V>) -> <Inner<K, V> as MapStorage<Tail<K>, V>>::ValuesMut<'a>,
+    >,
+>;
+type IntoIter<K, V> = ExactIter<
+    ProductIter<
+        Head<K>,
+        Inner<K, V>,
+        <Outer<K, V> as MapStorage<Head<K>, Inner<K, V>>>::IntoIter,
+        <Inner<K, V> as MapStorage<Tail<K>, V>>::IntoIter,
+        (K, V),
+    >,
+>;
+
+/// [`MapStorage`] for tuple types.
+///
+/// The first element of the tuple indexes into storage for the remaining
+/// elements, so the storage for `(A, B)` is the storage of `A` holding the
+/// storage of `B`.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Part {
+///     A,
+///     B,
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert((Part::A, true), 1);
+/// a.insert((Part::B, false), 2);
+///
+/// assert_eq!(a.get((Part::A, true)), Some(&1));
+/// assert_eq!(a.get((Part::A, false)), None);
+/// assert_eq!(a.get((Part::B, false)), Some(&2));
+///
+/// assert!(a.iter().eq([((Part::A, true), &1), ((Part::B, false), &2)]));
+/// assert!(a.values().copied().eq([1, 2]));
+/// assert!(a.keys().eq([(Part::A, true), (Part::B, false)]));
+/// ```
+pub struct ProductMapStorage<K, V>
+where
+    K: Tuple,
+{
+    inner: Outer<K, V>,
+}
+
+impl<K, V> Clone for ProductMapStorage<K, V>
+where
+    K: Tuple,
+    Outer<K, V>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K, V> Copy for ProductMapStorage<K, V>
+where
+    K: Tuple,
+    Outer<K, V>: Copy,
+{
+}
+
+// Removing a value through an entry might leave empty inner storage behind, so
+// equality is determined by the stored entries rather than the storage itself.
+impl<K, V> PartialEq for ProductMapStorage<K, V>
+where
+    K: Tuple,
+    V: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<K, V> Eq for ProductMapStorage<K, V>
+where
+    K: Tuple,
+    V: Eq,
+{
+}
+
+/// Iterator over a product of storages, which joins the key of every outer
+/// entry with the items produced by its inner storage.
+pub struct ProductIter<H, X, I, J, T>
+where
+    J: Iterator,
+{
+    outer: I,
+    inner: fn(X) -> J,
+    join: fn(H, J::Item) -> T,
+    front: Option<(H, J)>,
+    back: Option<(H, J)>,
+}
+
+impl<H, X, I, J, T> ProductIter<H, X, I, J, T>
+where
+    J: Iterator,
+{
+    #[inline]
+    pub(crate) fn new(outer: I, inner: fn(X) -> J, join: fn(H, J::Item) -> T) -> Self {
+        Self {
+            outer,
+            inner,
+            join,
+            front: None,
+            back: None,
+        }
+    }
+}
+
+impl<H, X, I, J, T> Clone for ProductIter<H, X, I, J, T>
+where
+    H: Clone,
+    I: Clone,
+    J: Clone + Iterator,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            outer: self.outer.clone(),
+            inner: self.inner,
+            join: self.join,
+            front: self.front.clone(),
+            back: self.back.clone(),
+        }
+    }
+}
+
+impl<H, X, I, J, T> Iterator for ProductIter<H, X, I, J, T>
+where
+    H: Clone,
+    I: Iterator<Item = (H, X)>,
+    J: Iterator,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((head, inner)) = &mut self.front {
+                if let Some(item) = inner.next() {
+                    return Some((self.join)(head.clone(), item));
+                }
+
+                self.front = None;
+            }
+
+            let Some((head, inner)) = self.outer.next() else {
+                let (head, inner) = self.back.as_mut()?;
+
+                let Some(item) = inner.next() else {
+                    self.back = None;
+                    return None;
+                };
+
+                return Some((self.join)(head.clone(), item));
+            };
+
+            self.front = Some((head, (self.inner)(inner)));
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (front_lower, front_upper) = self
+            .front
+            .as_ref()
+            .map_or((0, Some(0)), |(_, inner)| inner.size_hint());
+        let (back_lower, back_upper) = self
+            .back
+            .as_ref()
+            .map_or((0, Some(0)), |(_, inner)| inner.size_hint());
+
+        let lower = front_lower.saturating_add(back_lower);
+
+        match (self.outer.size_hint(), front_upper, back_upper) {
+            ((0, Some(0)), Some(front), Some(back)) => (lower, front.checked_add(back)),
+            _ => (lower, None),
+        }
+    }
+}
+
+impl<H, X, I, J, T> DoubleEndedIterator for ProductIter<H, X, I, J, T>
+where
+    H: Clone,
+    I: DoubleEndedIterator<Item = (H, X)>,
+    J: DoubleEndedIterator,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((head, inner)) = &mut self.back {
+                if let Some(item) = inner.next_back() {
+                    return Some((self.join)(head.clone(), item));
+                }
+
+                self.back = None;
+            }
+
+            let Some((head, inner)) = self.outer.next_back() else {
+                let (head, inner) = self.front.as_mut()?;
+
+                let Some(item) = inner.next_back() else {
+                    self.front = None;
+                    return None;
+                };
+
+                return Some((self.join)(head.clone(), item));
+            };
+
+            self.back = Some((head, (self.inner)(inner)));
+        }
+    }
+}
+
+/// Lockstep iterator over two products of storages, which walks the outer
+/// storages in lockstep and then the inner storages of every key present in
+/// either of them.
+pub struct ProductZip<'a, K, V, U>
+where
+    K: Tuple + 'a,
+    V: 'a,
+    U: 'a,
+{
+    outer: OuterZip<'a, K, V, U>,
+    inner: Option<(Head<K>, InnerZip<'a, K, V, U>)>,
+}
+
+enum InnerZip<'a, K, V, U>
+where
+    K: Tuple + 'a,
+    V: 'a,
+    U: 'a,
+{
+    Both(<Inner<K, V> as MapStorage<Tail<K>, V>>::Zip<'a, U>),
+    Left(<Inner<K, V> as MapStorage<Tail<K>, V>>::Iter<'a>),
+    Right(<Inner<K, U> as MapStorage<Tail<K>, U>>::Iter<'a>),
+}
+
+impl<'a, K, V, U> Iterator for ProductZip<'a, K, V, U>
+where
+    K: Tuple + 'a,
+    V: 'a,
+    U: 'a,
+{
+    type Item = (K, Option<&'a V>, Option<&'a U>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((head, inner)) = &mut self.inner {
+                let item = match inner {
+                    InnerZip::Both(zip) => zip.next(),
+                    InnerZip::Left(iter) => iter.next().map(|(tail, a)| (tail, Some(a), None)),
+                    InnerZip::Right(iter) => iter.next().map(|(tail, b)| (tail, None, Some(b))),
+                };
+
+                if let Some((tail, a, b)) = item {
+                    return Some((K::join(head.clone(), tail), a, b));
+                }
+
+                self.inner = None;
+            }
+
+            let (head, a, b) = self.outer.next()?;
+
+            let inner = match (a, b) {
+                (Some(a), Some(b)) => InnerZip::Both(a.zip(b)),
+                (Some(a), None) => InnerZip::Left(a.iter()),
+                (None, Some(b)) => InnerZip::Right(b.iter()),
+                (None, None) => continue,
+            };
+
+            self.inner = Some((head, inner));
+        }
+    }
+}
+
+impl<'a, K, V, U> FusedIterator for ProductZip<'a, K, V, U>
+where
+    K: Tuple + 'a,
+    V: 'a,
+    U: 'a,
+{
+}
+
+pub enum Vacant<'a, K: 'a, V: 'a>
+where
+    K: Tuple,
+{
+    Outer(
+        Tail<K>,
+        <Outer<K, V> as MapStorage<Head<K>, Inner<K, V>>>::Vacant<'a>,
+    ),
+    Inner(Head<K>, <Inner<K, V> as MapStorage<Tail<K>, V>>::Vacant<'a>),
+}
+
+pub struct Occupied<'a, K: 'a, V: 'a>
+where
+    K: Tuple,
+{
+    head: Head<K>,
+    inner: <Inner<K, V> as MapStorage<Tail<K>, V>>::Occupied<'a>,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> for Vacant<'a, K, V>
+where
+    K: Tuple,
+{
+    #[inline]
+    fn key(&self) -> K {
+        match self {
+            Vacant::Outer(tail, entry) => K::join(entry.key(), tail.clone()),
+            Vacant::Inner(head, entry) => K::join(head.clone(), entry.key()),
+        }
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        match self {
+            Vacant::Outer(tail, entry) => match entry.insert(Inner::<K, V>::empty()).entry(tail) {
+                Entry::Occupied(mut entry) => {
+                    entry.insert(value);
+                    entry.into_mut()
+                }
+                Entry::Vacant(entry) => entry.insert(value),
+            },
+            Vacant::Inner(_, entry) => entry.insert(value),
+        }
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> for Occupied<'a, K, V>
+where
+    K: Tuple,
+{
+    #[inline]
+    fn key(&self) -> K {
+        K::join(self.head.clone(), self.inner.key())
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.inner.get()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.inner.get_mut()
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        self.inner.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.inner.insert(value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        self.inner.remove()
+    }
+}
+
+impl<K, V> MapStorage<K, V> for ProductMapStorage<K, V>
+where
+    K: Tuple,
+{
+    type Iter<'this>
+        = Iter<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type Keys<'this>
+        = Keys<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type Values<'this>
+        = Values<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type ValuesMut<'this>
+        = ValuesMut<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type IntoIter = IntoIter<K, V>;
+    type Zip<'this, U>
+        = ProductZip<'this, K, V, U>
+    where
+        K: 'this,
+        V: 'this,
+        U: 'this;
+    type Occupied<'this>
+        = Occupied<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type Vacant<'this>
+        = Vacant<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            inner: Outer::<K, V>::empty(),
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.values().map(MapStorage::len).sum()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.inner.values().all(MapStorage::is_empty)
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (head, tail) = key.split();
+
+        match self.inner.entry(head) {
+            Entry::Occupied(entry) => entry.into_mut().insert(tail, value),
+            Entry::Vacant(entry) => entry.insert(Inner::<K, V>::empty()).insert(tail, value),
+        }
+    }
+
+    #[inline]
+    fn contains_key(&self, key: K) -> bool {
+        let (head, tail) = key.split();
+
+        self.inner
+            .get(head)
+            .is_some_and(|inner| inner.contains_key(tail))
+    }
+
+    #[inline]
+    fn get(&self, key: K) -> Option<&V> {
+        let (head, tail) = key.split();
+        self.inner.get(head)?.get(tail)
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        let (head, tail) = key.split();
+        self.inner.get_mut(head)?.get_mut(tail)
+    }
+
+    #[inline]
+    fn remove(&mut self, key: K) -> Option<V> {
+        let (head, tail) = key.split();
+        let inner = self.inner.get_mut(head.clone())?;
+        let value = inner.remove(tail);
+
+        if inner.is_empty() {
+            self.inner.remove(head);
+        }
+
+        value
+    }
+
+    #[inline]
+    fn contains_key_by(&self, key: K::Ref<'_>) -> bool {
+        let (head, tail) = K::split_ref(key);
+
+        self.inner
+            .get_by(head)
+            .is_some_and(|inner| inner.contains_key_by(tail))
+    }
+
+    #[inline]
+    fn get_by(&self, key: K::Ref<'_>) -> Option<&V> {
+        let (head, tail) = K::split_ref(key);
+        self.inner.get_by(head)?.get_by(tail)
+    }
+
+    #[inline]
+    fn get_mut_by(&mut self, key: K::Ref<'_>) -> Option<&mut V> {
+        let (head, tail) = K::split_ref(key);
+        self.inner.get_mut_by(head)?.get_mut_by(tail)
+    }
+
+    #[inline]
+    fn remove_by(&mut self, key: K::Ref<'_>) -> Option<V> {
+        let (head, tail) = K::split_ref(key);
+        let inner = self.inner.get_mut_by(head.clone())?;
+        let value = inner.remove_by(tail);
+
+        if inner.is_empty() {
+            self.inner.remove_by(head);
+        }
+
+        value
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        self.inner.retain(|head, inner| {
+            inner.retain(|tail, value| func(K::join(head.clone(), tail), value));
+            !inner.is_empty()
+        });
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        ExactIter::new(
+            ProductIter::new(
+                self.inner.iter(),
+                MapStorage::iter,
+                |head, (tail, value)| (K::join(head, tail), value),
+            ),
+            self.len(),
+        )
+    }
+
+    #[inline]
+    fn keys(&self) -> Self::Keys<'_> {
+        ExactIter::new(
+            ProductIter::new(self.inner.iter(), MapStorage::keys, K::join),
+            self.len(),
+        )
+    }
+
+    #[inline]
+    fn values(&self) -> Self::Values<'_> {
+        let map: fn(_) -> _ = MapStorage::values;
+        ExactIter::new(self.inner.values().flat_map(map), self.len())
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let len = self.len();
+        ExactIter::new(
+            ProductIter::new(
+                self.inner.iter_mut(),
+                MapStorage::iter_mut,
+                |head, (tail, value)| (K::join(head, tail), value),
+            ),
+            len,
+        )
+    }
+
+    #[inline]
+    fn values_mut(&mut self) -> Self::ValuesMut<'_> {
+        let len = self.len();
+        let map: fn(_) -> _ = MapStorage::values_mut;
+        ExactIter::new(self.inner.values_mut().flat_map(map), len)
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        ExactIter::new(
+            ProductIter::new(
+                self.inner.into_iter(),
+                MapStorage::into_iter,
+                |head, (tail, value)| (K::join(head, tail), value),
+            ),
+            len,
+        )
+    }
+
+    #[inline]
+    fn entry(&mut self, key: K) -> Entry<'_, Self, K, V> {
+        let (head, tail) = key.split();
+
+        match self.inner.entry(head.clone()) {
+            Entry::Occupied(entry) => match entry.into_mut().entry(tail) {
+                Entry::Occupied(inner) => Entry::Occupied(Occupied { head, inner }),
+                Entry::Vacant(entry) => Entry::Vacant(Vacant::Inner(head, entry)),
+            },
+            Entry::Vacant(entry) => Entry::Vacant(Vacant::Outer(tail, entry)),
+        }
+    }
+
+    #[inline]
+    fn zip<'this, U>(&'this self, other: &'this K::MapStorage<U>) -> Self::Zip<'this, U>
+    where
+        U: 'this,
+    {
+        ProductZip {
+            outer: self.inner.zip(&K::product(other).inner),
+            inner: None,
+        }
+    }
+}
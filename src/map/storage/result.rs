@@ -0,0 +1,740 @@
+use core::iter;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelIterator;
+#[cfg(feature = "rkyv")]
+use rkyv::{out_field, Archive, Fallible, Serialize};
+
+use crate::exact_iter::ExactIter;
+#[cfg(feature = "rayon")]
+use crate::map::ParallelMapStorage;
+#[cfg(feature = "rkyv")]
+use crate::map::{ArchiveMapStorage, ArchivedMapStorage};
+use crate::map::{Entry, MapStorage, OccupiedEntry, VacantEntry};
+use crate::Key;
+
+type Iter<'a, T, E, V> = ExactIter<
+    iter::Chain<
+        iter::Map<
+            <<T as Key>::MapStorage<V> as MapStorage<T, V>>::Iter<'a>,
+            fn((T, &'a V)) -> (Result<T, E>, &'a V),
+        >,
+        iter::Map<
+            <<E as Key>::MapStorage<V> as MapStorage<E, V>>::Iter<'a>,
+            fn((E, &'a V)) -> (Result<T, E>, &'a V),
+        >,
+    >,
+>;
+type Keys<'a, T, E, V> = ExactIter<
+    iter::Chain<
+        iter::Map<<<T as Key>::MapStorage<V> as MapStorage<T, V>>::Keys<'a>, fn(T) -> Result<T, E>>,
+        iter::Map<<<E as Key>::MapStorage<V> as MapStorage<E, V>>::Keys<'a>, fn(E) -> Result<T, E>>,
+    >,
+>;
+type Values<'a, T, E, V> = ExactIter<
+    iter::Chain<
+        <<T as Key>::MapStorage<V> as MapStorage<T, V>>::Values<'a>,
+        <<E as Key>::MapStorage<V> as MapStorage<E, V>>::Values<'a>,
+    >,
+>;
+type IterMut<'a, T, E, V> = ExactIter<
+    iter::Chain<
+        iter::Map<
+            <<T as Key>::MapStorage<V> as MapStorage<T, V>>::IterMut<'a>,
+            fn((T, &'a mut V)) -> (Result<T, E>, &'a mut V),
+        >,
+        iter::Map<
+            <<E as Key>::MapStorage<V> as MapStorage<E, V>>::IterMut<'a>,
+            fn((E, &'a mut V)) -> (Result<T, E>, &'a mut V),
+        >,
+    >,
+>;
+type ValuesMut<'a, T, E, V> = ExactIter<
+    iter::Chain<
+        <<T as Key>::MapStorage<V> as MapStorage<T, V>>::ValuesMut<'a>,
+        <<E as Key>::MapStorage<V> as MapStorage<E, V>>::ValuesMut<'a>,
+    >,
+>;
+type IntoIter<T, E, V> = ExactIter<
+    iter::Chain<
+        iter::Map<
+            <<T as Key>::MapStorage<V> as MapStorage<T, V>>::IntoIter,
+            fn((T, V)) -> (Result<T, E>, V),
+        >,
+        iter::Map<
+            <<E as Key>::MapStorage<V> as MapStorage<E, V>>::IntoIter,
+            fn((E, V)) -> (Result<T, E>, V),
+        >,
+    >,
+>;
+type Zip<'a, T, E, V, U> = iter::Chain<
+    iter::Map<
+        <<T as Key>::MapStorage<V> as MapStorage<T, V>>::Zip<'a, U>,
+        fn((T, Option<&'a V>, Option<&'a U>)) -> (Result<T, E>, Option<&'a V>, Option<&'a U>),
+    >,
+    iter::Map<
+        <<E as Key>::MapStorage<V> as MapStorage<E, V>>::Zip<'a, U>,
+        fn((E, Option<&'a V>, Option<&'a U>)) -> (Result<T, E>, Option<&'a V>, Option<&'a U>),
+    >,
+>;
+
+/// [`MapStorage`] for [`Result`] types.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Part {
+///     A,
+///     B,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First(Result<Part, bool>),
+///     Second,
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert(MyKey::First(Err(false)), 1);
+/// a.insert(MyKey::First(Ok(Part::A)), 2);
+///
+/// assert_eq!(a.get(MyKey::First(Ok(Part::A))), Some(&2));
+/// assert_eq!(a.get(MyKey::First(Ok(Part::B))), None);
+/// assert_eq!(a.get(MyKey::First(Err(false))), Some(&1));
+/// assert_eq!(a.get(MyKey::Second), None);
+///
+/// assert!(a.iter().eq([(MyKey::First(Ok(Part::A)), &2), (MyKey::First(Err(false)), &1)]));
+/// assert!(a.values().copied().eq([2, 1]));
+/// assert!(a.keys().eq([MyKey::First(Ok(Part::A)), MyKey::First(Err(false))]));
+/// ```
+pub struct ResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+{
+    ok: T::MapStorage<V>,
+    err: E::MapStorage<V>,
+}
+
+impl<T, E, V> Clone for ResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+    T::MapStorage<V>: Clone,
+    E::MapStorage<V>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            ok: self.ok.clone(),
+            err: self.err.clone(),
+        }
+    }
+}
+
+impl<T, E, V> Copy for ResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+    T::MapStorage<V>: Copy,
+    E::MapStorage<V>: Copy,
+{
+}
+
+impl<T, E, V> PartialEq for ResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+    T::MapStorage<V>: PartialEq,
+    E::MapStorage<V>: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ok == other.ok && self.err == other.err
+    }
+}
+
+impl<T, E, V> Eq for ResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+    T::MapStorage<V>: Eq,
+    E::MapStorage<V>: Eq,
+{
+}
+
+pub enum Vacant<'a, T: 'a, E: 'a, V: 'a>
+where
+    T: Key,
+    E: Key,
+{
+    Ok(<T::MapStorage<V> as MapStorage<T, V>>::Vacant<'a>),
+    Err(<E::MapStorage<V> as MapStorage<E, V>>::Vacant<'a>),
+}
+
+pub enum Occupied<'a, T: 'a, E: 'a, V: 'a>
+where
+    T: Key,
+    E: Key,
+{
+    Ok(<T::MapStorage<V> as MapStorage<T, V>>::Occupied<'a>),
+    Err(<E::MapStorage<V> as MapStorage<E, V>>::Occupied<'a>),
+}
+
+impl<'a, T, E, V> VacantEntry<'a, Result<T, E>, V> for Vacant<'a, T, E, V>
+where
+    T: Key,
+    E: Key,
+{
+    #[inline]
+    fn key(&self) -> Result<T, E> {
+        match self {
+            Vacant::Ok(entry) => Ok(entry.key()),
+            Vacant::Err(entry) => Err(entry.key()),
+        }
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        match self {
+            Vacant::Ok(entry) => entry.insert(value),
+            Vacant::Err(entry) => entry.insert(value),
+        }
+    }
+}
+
+impl<'a, T, E, V> OccupiedEntry<'a, Result<T, E>, V> for Occupied<'a, T, E, V>
+where
+    T: Key,
+    E: Key,
+{
+    #[inline]
+    fn key(&self) -> Result<T, E> {
+        match self {
+            Occupied::Ok(entry) => Ok(entry.key()),
+            Occupied::Err(entry) => Err(entry.key()),
+        }
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        match self {
+            Occupied::Ok(entry) => entry.get(),
+            Occupied::Err(entry) => entry.get(),
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        match self {
+            Occupied::Ok(entry) => entry.get_mut(),
+            Occupied::Err(entry) => entry.get_mut(),
+        }
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        match self {
+            Occupied::Ok(entry) => entry.into_mut(),
+            Occupied::Err(entry) => entry.into_mut(),
+        }
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        match self {
+            Occupied::Ok(entry) => entry.insert(value),
+            Occupied::Err(entry) => entry.insert(value),
+        }
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        match self {
+            Occupied::Ok(entry) => entry.remove(),
+            Occupied::Err(entry) => entry.remove(),
+        }
+    }
+}
+
+impl<T, E, V> MapStorage<Result<T, E>, V> for ResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+{
+    type Iter<'this>
+        = Iter<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+    type Keys<'this>
+        = Keys<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+    type Values<'this>
+        = Values<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+    type ValuesMut<'this>
+        = ValuesMut<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+    type IntoIter = IntoIter<T, E, V>;
+    type Zip<'this, U>
+        = Zip<'this, T, E, V, U>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this,
+        U: 'this;
+    type Occupied<'this>
+        = Occupied<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+    type Vacant<'this>
+        = Vacant<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            ok: T::MapStorage::empty(),
+            err: E::MapStorage::empty(),
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.ok.len() + self.err.len()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.ok.is_empty() && self.err.is_empty()
+    }
+
+    #[inline]
+    fn insert(&mut self, key: Result<T, E>, value: V) -> Option<V> {
+        match key {
+            Ok(key) => self.ok.insert(key, value),
+            Err(key) => self.err.insert(key, value),
+        }
+    }
+
+    #[inline]
+    fn contains_key(&self, key: Result<T, E>) -> bool {
+        match key {
+            Ok(key) => self.ok.contains_key(key),
+            Err(key) => self.err.contains_key(key),
+        }
+    }
+
+    #[inline]
+    fn get(&self, key: Result<T, E>) -> Option<&V> {
+        match key {
+            Ok(key) => self.ok.get(key),
+            Err(key) => self.err.get(key),
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: Result<T, E>) -> Option<&mut V> {
+        match key {
+            Ok(key) => self.ok.get_mut(key),
+            Err(key) => self.err.get_mut(key),
+        }
+    }
+
+    #[inline]
+    fn remove(&mut self, key: Result<T, E>) -> Option<V> {
+        match key {
+            Ok(key) => self.ok.remove(key),
+            Err(key) => self.err.remove(key),
+        }
+    }
+
+    #[inline]
+    fn contains_key_by(&self, key: <Result<T, E> as Key>::Ref<'_>) -> bool {
+        match key {
+            Ok(key) => self.ok.contains_key_by(key),
+            Err(key) => self.err.contains_key_by(key),
+        }
+    }
+
+    #[inline]
+    fn get_by(&self, key: <Result<T, E> as Key>::Ref<'_>) -> Option<&V> {
+        match key {
+            Ok(key) => self.ok.get_by(key),
+            Err(key) => self.err.get_by(key),
+        }
+    }
+
+    #[inline]
+    fn get_mut_by(&mut self, key: <Result<T, E> as Key>::Ref<'_>) -> Option<&mut V> {
+        match key {
+            Ok(key) => self.ok.get_mut_by(key),
+            Err(key) => self.err.get_mut_by(key),
+        }
+    }
+
+    #[inline]
+    fn remove_by(&mut self, key: <Result<T, E> as Key>::Ref<'_>) -> Option<V> {
+        match key {
+            Ok(key) => self.ok.remove_by(key),
+            Err(key) => self.err.remove_by(key),
+        }
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(Result<T, E>, &mut V) -> bool,
+    {
+        self.ok.retain(|k, v| func(Ok(k), v));
+        self.err.retain(|k, v| func(Err(k), v));
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.ok.clear();
+        self.err.clear();
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let map: fn(_) -> _ = |(k, v)| (Ok(k), v);
+        let a = self.ok.iter().map(map);
+        let map: fn(_) -> _ = |(k, v)| (Err(k), v);
+        let b = self.err.iter().map(map);
+        ExactIter::new(a.chain(b), self.len())
+    }
+
+    #[inline]
+    fn keys(&self) -> Self::Keys<'_> {
+        let map: fn(_) -> _ = Ok;
+        let a = self.ok.keys().map(map);
+        let map: fn(_) -> _ = Err;
+        let b = self.err.keys().map(map);
+        ExactIter::new(a.chain(b), self.len())
+    }
+
+    #[inline]
+    fn values(&self) -> Self::Values<'_> {
+        ExactIter::new(self.ok.values().chain(self.err.values()), self.len())
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let len = self.len();
+        let map: fn(_) -> _ = |(k, v)| (Ok(k), v);
+        let a = self.ok.iter_mut().map(map);
+        let map: fn(_) -> _ = |(k, v)| (Err(k), v);
+        let b = self.err.iter_mut().map(map);
+        ExactIter::new(a.chain(b), len)
+    }
+
+    #[inline]
+    fn values_mut(&mut self) -> Self::ValuesMut<'_> {
+        let len = self.len();
+        ExactIter::new(self.ok.values_mut().chain(self.err.values_mut()), len)
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        let map: fn(_) -> _ = |(k, v)| (Ok(k), v);
+        let a = self.ok.into_iter().map(map);
+        let map: fn(_) -> _ = |(k, v)| (Err(k), v);
+        let b = self.err.into_iter().map(map);
+        ExactIter::new(a.chain(b), len)
+    }
+
+    #[inline]
+    fn entry(&mut self, key: Result<T, E>) -> Entry<'_, Self, Result<T, E>, V> {
+        match key {
+            Ok(key) => match self.ok.entry(key) {
+                Entry::Occupied(entry) => Entry::Occupied(Occupied::Ok(entry)),
+                Entry::Vacant(entry) => Entry::Vacant(Vacant::Ok(entry)),
+            },
+            Err(key) => match self.err.entry(key) {
+                Entry::Occupied(entry) => Entry::Occupied(Occupied::Err(entry)),
+                Entry::Vacant(entry) => Entry::Vacant(Vacant::Err(entry)),
+            },
+        }
+    }
+
+    #[inline]
+    fn zip<'this, U>(
+        &'this self,
+        other: &'this <Result<T, E> as Key>::MapStorage<U>,
+    ) -> Self::Zip<'this, U>
+    where
+        U: 'this,
+    {
+        let map: fn(_) -> _ = |(key, a, b)| (Ok(key), a, b);
+        let a = self.ok.zip(&other.ok).map(map);
+        let map: fn(_) -> _ = |(key, a, b)| (Err(key), a, b);
+        let b = self.err.zip(&other.err).map(map);
+        a.chain(b)
+    }
+
+    #[inline]
+    fn map_values<U, F>(self, mut f: F) -> <Result<T, E> as Key>::MapStorage<U>
+    where
+        F: FnMut(Result<T, E>, V) -> U,
+    {
+        ResultMapStorage {
+            ok: self.ok.map_values(|key, value| f(Ok(key), value)),
+            err: self.err.map_values(|key, value| f(Err(key), value)),
+        }
+    }
+
+    #[inline]
+    fn merge<U, W, F>(
+        self,
+        other: <Result<T, E> as Key>::MapStorage<U>,
+        mut f: F,
+    ) -> <Result<T, E> as Key>::MapStorage<W>
+    where
+        F: FnMut(Result<T, E>, Option<V>, Option<U>) -> Option<W>,
+    {
+        ResultMapStorage {
+            ok: self.ok.merge(other.ok, |key, a, b| f(Ok(key), a, b)),
+            err: self.err.merge(other.err, |key, a, b| f(Err(key), a, b)),
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+type ParIter<'a, T, E, V> = rayon::iter::Chain<
+    rayon::iter::Map<
+        <<T as Key>::MapStorage<V> as ParallelMapStorage<T, V>>::ParIter<'a>,
+        fn((T, &'a V)) -> (Result<T, E>, &'a V),
+    >,
+    rayon::iter::Map<
+        <<E as Key>::MapStorage<V> as ParallelMapStorage<E, V>>::ParIter<'a>,
+        fn((E, &'a V)) -> (Result<T, E>, &'a V),
+    >,
+>;
+#[cfg(feature = "rayon")]
+type ParIterMut<'a, T, E, V> = rayon::iter::Chain<
+    rayon::iter::Map<
+        <<T as Key>::MapStorage<V> as ParallelMapStorage<T, V>>::ParIterMut<'a>,
+        fn((T, &'a mut V)) -> (Result<T, E>, &'a mut V),
+    >,
+    rayon::iter::Map<
+        <<E as Key>::MapStorage<V> as ParallelMapStorage<E, V>>::ParIterMut<'a>,
+        fn((E, &'a mut V)) -> (Result<T, E>, &'a mut V),
+    >,
+>;
+#[cfg(feature = "rayon")]
+type ParValuesMut<'a, T, E, V> = rayon::iter::Chain<
+    <<T as Key>::MapStorage<V> as ParallelMapStorage<T, V>>::ParValuesMut<'a>,
+    <<E as Key>::MapStorage<V> as ParallelMapStorage<E, V>>::ParValuesMut<'a>,
+>;
+#[cfg(feature = "rayon")]
+type IntoParIter<T, E, V> = rayon::iter::Chain<
+    rayon::iter::Map<
+        <<T as Key>::MapStorage<V> as ParallelMapStorage<T, V>>::IntoParIter,
+        fn((T, V)) -> (Result<T, E>, V),
+    >,
+    rayon::iter::Map<
+        <<E as Key>::MapStorage<V> as ParallelMapStorage<E, V>>::IntoParIter,
+        fn((E, V)) -> (Result<T, E>, V),
+    >,
+>;
+
+#[cfg(feature = "rayon")]
+impl<T, E, V> ParallelMapStorage<Result<T, E>, V> for ResultMapStorage<T, E, V>
+where
+    T: Key + Send,
+    E: Key + Send,
+    T::MapStorage<V>: ParallelMapStorage<T, V>,
+    E::MapStorage<V>: ParallelMapStorage<E, V>,
+    V: Send + Sync,
+{
+    type ParIter<'this>
+        = ParIter<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+    type ParIterMut<'this>
+        = ParIterMut<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+    type ParValuesMut<'this>
+        = ParValuesMut<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+    type IntoParIter = IntoParIter<T, E, V>;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let map: fn(_) -> _ = |(k, v)| (Ok(k), v);
+        let a = self.ok.par_iter().map(map);
+        let map: fn(_) -> _ = |(k, v)| (Err(k), v);
+        let b = self.err.par_iter().map(map);
+        a.chain(b)
+    }
+
+    #[inline]
+    fn par_iter_mut(&mut self) -> Self::ParIterMut<'_> {
+        let map: fn(_) -> _ = |(k, v)| (Ok(k), v);
+        let a = self.ok.par_iter_mut().map(map);
+        let map: fn(_) -> _ = |(k, v)| (Err(k), v);
+        let b = self.err.par_iter_mut().map(map);
+        a.chain(b)
+    }
+
+    #[inline]
+    fn par_values_mut(&mut self) -> Self::ParValuesMut<'_> {
+        self.ok.par_values_mut().chain(self.err.par_values_mut())
+    }
+
+    #[inline]
+    fn into_par_iter(self) -> Self::IntoParIter {
+        let map: fn(_) -> _ = |(k, v)| (Ok(k), v);
+        let a = ParallelMapStorage::into_par_iter(self.ok).map(map);
+        let map: fn(_) -> _ = |(k, v)| (Err(k), v);
+        let b = ParallelMapStorage::into_par_iter(self.err).map(map);
+        a.chain(b)
+    }
+}
+
+/// The archived form of [`ResultMapStorage`].
+#[cfg(feature = "rkyv")]
+#[repr(C)]
+#[cfg_attr(
+    feature = "rkyv-validation",
+    derive(::rkyv::CheckBytes),
+    check_bytes(crate = "::rkyv::bytecheck")
+)]
+pub struct ArchivedResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+    T::MapStorage<V>: ArchiveMapStorage<T, V>,
+    E::MapStorage<V>: ArchiveMapStorage<E, V>,
+    V: Archive,
+{
+    ok: <T::MapStorage<V> as ArchiveMapStorage<T, V>>::Archived,
+    err: <E::MapStorage<V> as ArchiveMapStorage<E, V>>::Archived,
+}
+
+#[cfg(feature = "rkyv")]
+type ArchivedIter<'a, T, E, V> = iter::Chain<
+    iter::Map<
+        <<<T as Key>::MapStorage<V> as ArchiveMapStorage<T, V>>::Archived as ArchivedMapStorage<
+            T,
+            V,
+        >>::Iter<'a>,
+        fn((T, &'a <V as Archive>::Archived)) -> (Result<T, E>, &'a <V as Archive>::Archived),
+    >,
+    iter::Map<
+        <<<E as Key>::MapStorage<V> as ArchiveMapStorage<E, V>>::Archived as ArchivedMapStorage<
+            E,
+            V,
+        >>::Iter<'a>,
+        fn((E, &'a <V as Archive>::Archived)) -> (Result<T, E>, &'a <V as Archive>::Archived),
+    >,
+>;
+
+#[cfg(feature = "rkyv")]
+impl<T, E, V> ArchiveMapStorage<Result<T, E>, V> for ResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+    T::MapStorage<V>: ArchiveMapStorage<T, V>,
+    E::MapStorage<V>: ArchiveMapStorage<E, V>,
+    V: Archive,
+{
+    type Archived = ArchivedResultMapStorage<T, E, V>;
+    type Resolver = (
+        <T::MapStorage<V> as ArchiveMapStorage<T, V>>::Resolver,
+        <E::MapStorage<V> as ArchiveMapStorage<E, V>>::Resolver,
+    );
+
+    #[inline]
+    fn serialize<S>(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+        V: Serialize<S>,
+    {
+        let ok = self.ok.serialize(serializer)?;
+        let err = self.err.serialize(serializer)?;
+        Ok((ok, err))
+    }
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, (ok, err): Self::Resolver, out: *mut Self::Archived) {
+        let (offset, field) = out_field!(out.ok);
+        self.ok.resolve(pos + offset, ok, field);
+        let (offset, field) = out_field!(out.err);
+        self.err.resolve(pos + offset, err, field);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<T, E, V> ArchivedMapStorage<Result<T, E>, V> for ArchivedResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+    T::MapStorage<V>: ArchiveMapStorage<T, V>,
+    E::MapStorage<V>: ArchiveMapStorage<E, V>,
+    V: Archive,
+{
+    type Iter<'this>
+        = ArchivedIter<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.ok.len() + self.err.len()
+    }
+
+    #[inline]
+    fn get(&self, key: Result<T, E>) -> Option<&V::Archived> {
+        match key {
+            Ok(key) => self.ok.get(key),
+            Err(key) => self.err.get(key),
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let map: fn(_) -> _ = |(k, v)| (Ok(k), v);
+        let a = self.ok.iter().map(map);
+        let map: fn(_) -> _ = |(k, v)| (Err(k), v);
+        let b = self.err.iter().map(map);
+        a.chain(b)
+    }
+}
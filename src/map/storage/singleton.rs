@@ -1,7 +1,17 @@
-use core::mem;
+#[cfg(feature = "rayon")]
+use rayon::iter::{
+    IntoParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
+};
+#[cfg(feature = "rkyv")]
+use rkyv::{out_field, Archive, Archived, Fallible, Resolver, Serialize};
 
+#[cfg(feature = "rayon")]
+use crate::map::ParallelMapStorage;
+#[cfg(feature = "rkyv")]
+use crate::map::{ArchiveMapStorage, ArchivedMapStorage};
 use crate::map::{Entry, MapStorage};
 use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
+use crate::Key;
 
 /// [`MapStorage`] type that can only inhabit a single value (like `()`).
 #[repr(transparent)]
@@ -47,6 +57,11 @@ where
     where
         V: 'this;
     type IntoIter = core::option::IntoIter<(K, V)>;
+    type Zip<'this, U>
+        = core::option::IntoIter<(K, Option<&'this V>, Option<&'this U>)>
+    where
+        V: 'this,
+        U: 'this;
     type Occupied<'this>
         = SomeBucket<'this, V>
     where
@@ -75,7 +90,7 @@ where
 
     #[inline]
     fn insert(&mut self, _: K, value: V) -> Option<V> {
-        mem::replace(&mut self.inner, Some(value))
+        self.inner.replace(value)
     }
 
     #[inline]
@@ -98,6 +113,38 @@ where
         self.inner.take()
     }
 
+    #[inline]
+    fn contains_key_by(&self, _: K::Ref<'_>) -> bool
+    where
+        K: Key,
+    {
+        self.inner.is_some()
+    }
+
+    #[inline]
+    fn get_by(&self, _: K::Ref<'_>) -> Option<&V>
+    where
+        K: Key,
+    {
+        self.inner.as_ref()
+    }
+
+    #[inline]
+    fn get_mut_by(&mut self, _: K::Ref<'_>) -> Option<&mut V>
+    where
+        K: Key,
+    {
+        self.inner.as_mut()
+    }
+
+    #[inline]
+    fn remove_by(&mut self, _: K::Ref<'_>) -> Option<V>
+    where
+        K: Key,
+    {
+        self.inner.take()
+    }
+
     #[inline]
     fn retain<F>(&mut self, mut func: F)
     where
@@ -152,4 +199,132 @@ where
             OptionBucket::None(none) => Entry::Vacant(none),
         }
     }
+
+    #[inline]
+    fn zip<'this, U>(&'this self, other: &'this K::MapStorage<U>) -> Self::Zip<'this, U>
+    where
+        K: Key,
+        U: 'this,
+    {
+        let a = self.inner.as_ref();
+        let b = other.get(K::default());
+        (a.is_some() || b.is_some())
+            .then(|| (K::default(), a, b))
+            .into_iter()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, V> ParallelMapStorage<K, V> for SingletonMapStorage<V>
+where
+    K: Default + Send,
+    V: Send + Sync,
+{
+    type ParIter<'this>
+        = rayon::iter::Map<rayon::option::Iter<'this, V>, fn(&'this V) -> (K, &'this V)>
+    where
+        V: 'this;
+    type ParIterMut<'this>
+        = rayon::iter::Map<rayon::option::IterMut<'this, V>, fn(&'this mut V) -> (K, &'this mut V)>
+    where
+        V: 'this;
+    type ParValuesMut<'this>
+        = rayon::option::IterMut<'this, V>
+    where
+        V: 'this;
+    type IntoParIter = rayon::iter::Map<rayon::option::IntoIter<V>, fn(V) -> (K, V)>;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let map: fn(_) -> _ = |v| (K::default(), v);
+        self.inner.par_iter().map(map)
+    }
+
+    #[inline]
+    fn par_iter_mut(&mut self) -> Self::ParIterMut<'_> {
+        let map: fn(_) -> _ = |v| (K::default(), v);
+        self.inner.par_iter_mut().map(map)
+    }
+
+    #[inline]
+    fn par_values_mut(&mut self) -> Self::ParValuesMut<'_> {
+        self.inner.par_iter_mut()
+    }
+
+    #[inline]
+    fn into_par_iter(self) -> Self::IntoParIter {
+        let map: fn(_) -> _ = |v| (K::default(), v);
+        self.inner.into_par_iter().map(map)
+    }
+}
+
+/// The archived form of [`SingletonMapStorage`].
+#[cfg(feature = "rkyv")]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "rkyv-validation",
+    derive(::rkyv::CheckBytes),
+    check_bytes(crate = "::rkyv::bytecheck")
+)]
+pub struct ArchivedSingletonMapStorage<V>
+where
+    V: Archive,
+{
+    inner: Archived<Option<V>>,
+}
+
+#[cfg(feature = "rkyv")]
+impl<K, V> ArchiveMapStorage<K, V> for SingletonMapStorage<V>
+where
+    K: Default,
+    V: Archive,
+{
+    type Archived = ArchivedSingletonMapStorage<V>;
+    type Resolver = Resolver<Option<V>>;
+
+    #[inline]
+    fn serialize<S>(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+        V: Serialize<S>,
+    {
+        Serialize::serialize(&self.inner, serializer)
+    }
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (offset, field) = out_field!(out.inner);
+        self.inner.resolve(pos + offset, resolver, field);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<K, V> ArchivedMapStorage<K, V> for ArchivedSingletonMapStorage<V>
+where
+    K: Default,
+    V: Archive,
+{
+    type Iter<'this>
+        = core::iter::Map<
+        core::option::IntoIter<&'this V::Archived>,
+        fn(&'this V::Archived) -> (K, &'this V::Archived),
+    >
+    where
+        V: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        usize::from(self.inner.is_some())
+    }
+
+    #[inline]
+    fn get(&self, _: K) -> Option<&V::Archived> {
+        self.inner.as_ref()
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let map: fn(_) -> _ = |v| (K::default(), v);
+        self.inner.as_ref().into_iter().map(map)
+    }
 }
@@ -0,0 +1,16 @@
+use core::iter;
+
+/// Lockstep iterator over the slots of two storages with a fixed layout, which
+/// skips the slots that are vacant in both of them.
+pub type ZipSlots<'a, I, K, V, U> = iter::Filter<I, fn(&(K, Option<&'a V>, Option<&'a U>)) -> bool>;
+
+/// Construct a [`ZipSlots`] iterator over pairs of slots given in key order.
+#[inline]
+pub fn zip_slots<'a, I, K, V, U>(slots: I) -> ZipSlots<'a, I::IntoIter, K, V, U>
+where
+    I: IntoIterator<Item = (K, Option<&'a V>, Option<&'a U>)>,
+{
+    let occupied: fn(&_) -> _ =
+        |(_, a, b): &(K, Option<&V>, Option<&U>)| a.is_some() || b.is_some();
+    slots.into_iter().filter(occupied)
+}
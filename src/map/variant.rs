@@ -0,0 +1,479 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::map::{Iter, IterMut, Keys, MapStorage, Values, ValuesMut};
+use crate::{Key, Map};
+
+/// Selects a single variant with a field of the key `K`, by the index of the
+/// variant in its declaration.
+///
+/// The [`Key` derive][derive@crate::Key] generates an associated constant
+/// holding the selector of every variant with a field, named after the variant
+/// in upper snake case. So the selector of `MyKey::Composite` is
+/// `MyKey::COMPOSITE`. The selector of the `Some` variant of [`Option`] is
+/// [`Variant::SOME`].
+///
+/// Selectors are used with [`Map::variant`], [`Map::variant_mut`] and
+/// [`Map::into_variant`], which resolve the storage of the variant at compile
+/// time.
+pub struct Variant<K, const INDEX: usize> {
+    _marker: PhantomData<fn() -> K>,
+}
+
+impl<K, const INDEX: usize> Variant<K, INDEX> {
+    /// Construct the selector of the variant at `INDEX`.
+    ///
+    /// Selecting a variant without a field, or an index past the last variant,
+    /// fails to compile once the selector is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::Variant;
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     Simple,
+    ///     Composite(bool),
+    /// }
+    ///
+    /// let map = Map::<MyKey, u32>::new();
+    /// assert!(map.variant(Variant::<MyKey, 1>::new()).is_empty());
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use fixed_map::map::Variant;
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     Simple,
+    ///     Composite(bool),
+    /// }
+    ///
+    /// let map = Map::<MyKey, u32>::new();
+    /// map.variant(Variant::<MyKey, 0>::new());
+    /// ```
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K> Variant<Option<K>, 1> {
+    /// The selector of the `Some` variant of [`Option`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::Variant;
+    /// use fixed_map::Map;
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Some(true), 1);
+    /// map.insert(None, 2);
+    ///
+    /// assert!(map.variant(Variant::SOME).iter().eq([(true, &1)]));
+    /// ```
+    pub const SOME: Self = Self::new();
+}
+
+impl<K, const INDEX: usize> Clone for Variant<K, INDEX> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, const INDEX: usize> Copy for Variant<K, INDEX> {}
+
+impl<K, const INDEX: usize> Default for Variant<K, INDEX> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, const INDEX: usize> fmt::Debug for Variant<K, INDEX> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Variant").field(&INDEX).finish()
+    }
+}
+
+/// Map storage which holds the entries under the variant at `INDEX` of its
+/// key in a nested storage for the field type.
+///
+/// This is implemented by the [`Key` derive][derive@crate::Key] for every
+/// variant with a field, and for the `Some` variant of [`Option`]. It's used
+/// by [`Map::variant`], [`Map::variant_mut`] and [`Map::into_variant`].
+pub trait VariantMapStorage<K, V, const INDEX: usize> {
+    /// The type of the field of the variant.
+    type Field: Key;
+
+    /// Returns the storage of the variant.
+    fn variant(&self) -> &<Self::Field as Key>::MapStorage<V>;
+
+    /// Returns the storage of the variant mutably.
+    fn variant_mut(&mut self) -> &mut <Self::Field as Key>::MapStorage<V>;
+
+    /// Converts into the storage of the variant, dropping every entry under
+    /// other variants.
+    fn into_variant(self) -> <Self::Field as Key>::MapStorage<V>;
+}
+
+impl<K, V> Map<K, V>
+where
+    K: Key,
+{
+    /// Returns a view of the entries under a single variant of the key, which
+    /// is selected by passing its [`Variant`] selector.
+    ///
+    /// The view only accesses the storage of that variant, without scanning
+    /// the rest of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Part {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     Simple,
+    ///     Composite(Part),
+    ///     Number(u8),
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(MyKey::Simple, 1);
+    /// map.insert(MyKey::Composite(Part::Two), 2);
+    /// map.insert(MyKey::Number(3), 3);
+    ///
+    /// let composite = map.variant(MyKey::COMPOSITE);
+    /// assert_eq!(composite.len(), 1);
+    /// assert!(composite.iter().eq([(Part::Two, &2)]));
+    /// ```
+    #[inline]
+    pub fn variant<T, const INDEX: usize>(
+        &self,
+        variant: Variant<K, INDEX>,
+    ) -> VariantView<'_, T, V>
+    where
+        K::MapStorage<V>: VariantMapStorage<K, V, INDEX, Field = T>,
+        T: Key,
+    {
+        let _ = variant;
+
+        VariantView {
+            storage: self.storage.variant(),
+        }
+    }
+
+    /// Returns a mutable view of the entries under a single variant of the
+    /// key, which is selected by passing its [`Variant`] selector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Part {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     Simple,
+    ///     Composite(Part),
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(MyKey::Simple, 1);
+    /// map.insert(MyKey::Composite(Part::One), 2);
+    /// map.insert(MyKey::Composite(Part::Two), 3);
+    ///
+    /// let mut composite = map.variant_mut(MyKey::COMPOSITE);
+    /// composite.retain(|_, value| *value > 2);
+    /// composite.insert(Part::One, 4);
+    ///
+    /// assert_eq!(map.get(MyKey::Composite(Part::One)), Some(&4));
+    ///
+    /// map.variant_mut(MyKey::COMPOSITE).clear();
+    /// assert!(map.into_iter().eq([(MyKey::Simple, 1)]));
+    /// ```
+    #[inline]
+    pub fn variant_mut<T, const INDEX: usize>(
+        &mut self,
+        variant: Variant<K, INDEX>,
+    ) -> VariantViewMut<'_, T, V>
+    where
+        K::MapStorage<V>: VariantMapStorage<K, V, INDEX, Field = T>,
+        T: Key,
+    {
+        let _ = variant;
+
+        VariantViewMut {
+            storage: self.storage.variant_mut(),
+        }
+    }
+
+    /// Converts into a map of the entries under a single variant of the key,
+    /// which is selected by passing its [`Variant`] selector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     Simple,
+    ///     Number(u8),
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(MyKey::Simple, 1);
+    /// map.insert(MyKey::Number(2), 2);
+    ///
+    /// let numbers: Map<u8, u32> = map.into_variant(MyKey::NUMBER);
+    /// assert_eq!(numbers.get(2), Some(&2));
+    /// assert_eq!(numbers.len(), 1);
+    /// ```
+    #[inline]
+    pub fn into_variant<T, const INDEX: usize>(self, variant: Variant<K, INDEX>) -> Map<T, V>
+    where
+        K::MapStorage<V>: VariantMapStorage<K, V, INDEX, Field = T>,
+        T: Key,
+    {
+        let _ = variant;
+
+        Map {
+            storage: self.storage.into_variant(),
+        }
+    }
+}
+
+/// A view of the entries under a single variant of a key, returned by
+/// [`Map::variant`].
+pub struct VariantView<'a, T, V>
+where
+    T: Key,
+{
+    storage: &'a T::MapStorage<V>,
+}
+
+impl<'a, T, V> VariantView<'a, T, V>
+where
+    T: Key,
+{
+    /// Returns a reference to the value corresponding to the field of the
+    /// variant.
+    #[inline]
+    pub fn get(&self, key: T) -> Option<&'a V> {
+        self.storage.get(key)
+    }
+
+    /// Returns `true` if the variant holds a value for the field.
+    #[inline]
+    pub fn contains_key(&self, key: T) -> bool {
+        self.storage.contains_key(key)
+    }
+
+    /// Returns the number of entries under the variant.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Returns true if there are no entries under the variant.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+
+    /// An iterator visiting the fields and values of the entries under the
+    /// variant.
+    #[inline]
+    pub fn iter(&self) -> Iter<'a, T, V> {
+        self.storage.iter()
+    }
+
+    /// An iterator visiting the fields of the entries under the variant.
+    #[inline]
+    pub fn keys(&self) -> Keys<'a, T, V> {
+        self.storage.keys()
+    }
+
+    /// An iterator visiting the values of the entries under the variant.
+    #[inline]
+    pub fn values(&self) -> Values<'a, T, V> {
+        self.storage.values()
+    }
+}
+
+impl<T, V> Clone for VariantView<'_, T, V>
+where
+    T: Key,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, V> Copy for VariantView<'_, T, V> where T: Key {}
+
+impl<T, V> fmt::Debug for VariantView<'_, T, V>
+where
+    T: Key + fmt::Debug,
+    V: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T, V> IntoIterator for VariantView<'a, T, V>
+where
+    T: Key,
+    V: 'a,
+{
+    type Item = (T, &'a V);
+    type IntoIter = Iter<'a, T, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.storage.iter()
+    }
+}
+
+/// A mutable view of the entries under a single variant of a key, returned
+/// by [`Map::variant_mut`].
+pub struct VariantViewMut<'a, T, V>
+where
+    T: Key,
+{
+    storage: &'a mut T::MapStorage<V>,
+}
+
+impl<T, V> VariantViewMut<'_, T, V>
+where
+    T: Key,
+{
+    /// Returns a reference to the value corresponding to the field of the
+    /// variant.
+    #[inline]
+    pub fn get(&self, key: T) -> Option<&V> {
+        self.storage.get(key)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the field of
+    /// the variant.
+    #[inline]
+    pub fn get_mut(&mut self, key: T) -> Option<&mut V> {
+        self.storage.get_mut(key)
+    }
+
+    /// Returns `true` if the variant holds a value for the field.
+    #[inline]
+    pub fn contains_key(&self, key: T) -> bool {
+        self.storage.contains_key(key)
+    }
+
+    /// Inserts a value for the field of the variant, returning the previous
+    /// value if one was present.
+    #[inline]
+    pub fn insert(&mut self, key: T, value: V) -> Option<V> {
+        self.storage.insert(key, value)
+    }
+
+    /// Removes the value for the field of the variant, returning it if it was
+    /// present.
+    #[inline]
+    pub fn remove(&mut self, key: T) -> Option<V> {
+        self.storage.remove(key)
+    }
+
+    /// Retains only the entries under the variant specified by the predicate.
+    #[inline]
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(T, &mut V) -> bool,
+    {
+        self.storage.retain(f);
+    }
+
+    /// Removes every entry under the variant.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.storage.clear();
+    }
+
+    /// Returns the number of entries under the variant.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Returns true if there are no entries under the variant.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+
+    /// An iterator visiting the fields and values of the entries under the
+    /// variant.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T, V> {
+        self.storage.iter()
+    }
+
+    /// An iterator visiting the fields and mutable values of the entries
+    /// under the variant.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, V> {
+        self.storage.iter_mut()
+    }
+
+    /// An iterator visiting the fields of the entries under the variant.
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, T, V> {
+        self.storage.keys()
+    }
+
+    /// An iterator visiting the values of the entries under the variant.
+    #[inline]
+    pub fn values(&self) -> Values<'_, T, V> {
+        self.storage.values()
+    }
+
+    /// An iterator visiting the mutable values of the entries under the
+    /// variant.
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, T, V> {
+        self.storage.values_mut()
+    }
+}
+
+impl<T, V> fmt::Debug for VariantViewMut<'_, T, V>
+where
+    T: Key + fmt::Debug,
+    V: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
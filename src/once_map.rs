@@ -0,0 +1,816 @@
+//! Contains the lazily initialized [`OnceMap`] and [`OnceCellMap`]
+//! implementations.
+
+use core::cell::OnceCell;
+use core::fmt;
+use core::iter;
+use core::slice;
+
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+use crate::total_map::Keys;
+use crate::{Finite, Map};
+
+/// The iterator produced by [`OnceMap::iter`].
+#[cfg(feature = "std")]
+pub type Iter<'a, K, V> = iter::FilterMap<
+    iter::Zip<Keys<K>, slice::Iter<'a, OnceLock<V>>>,
+    fn((K, &'a OnceLock<V>)) -> Option<(K, &'a V)>,
+>;
+
+/// The iterator produced by [`OnceMap::into_iter`].
+#[cfg(feature = "std")]
+pub type IntoIter<K, V> = iter::FilterMap<
+    iter::Zip<Keys<K>, <<K as Finite>::Array<OnceLock<V>> as IntoIterator>::IntoIter>,
+    fn((K, OnceLock<V>)) -> Option<(K, V)>,
+>;
+
+/// The iterator produced by [`OnceCellMap::iter`].
+pub type CellIter<'a, K, V> = iter::FilterMap<
+    iter::Zip<Keys<K>, slice::Iter<'a, OnceCell<V>>>,
+    fn((K, &'a OnceCell<V>)) -> Option<(K, &'a V)>,
+>;
+
+/// The iterator produced by [`OnceCellMap::into_iter`].
+pub type CellIntoIter<K, V> = iter::FilterMap<
+    iter::Zip<Keys<K>, <<K as Finite>::Array<OnceCell<V>> as IntoIterator>::IntoIter>,
+    fn((K, OnceCell<V>)) -> Option<(K, V)>,
+>;
+
+/// A thread-safe map where the value of every inhabitant of a [`Finite`] key
+/// is lazily initialized at most once, stored in a [`OnceLock`].
+///
+/// Initializing one key never blocks access to another, since there is no
+/// lock guarding the map as a whole.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+///
+/// use fixed_map::{Key, OnceMap};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Pipeline {
+///     Opaque,
+///     Transparent,
+///     Shadow,
+/// }
+///
+/// fn compile(pipeline: Pipeline) -> String {
+///     format!("compiled {pipeline:?}")
+/// }
+///
+/// let shaders = OnceMap::new();
+///
+/// thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| shaders.get_or_init(Pipeline::Opaque, || compile(Pipeline::Opaque)));
+///     }
+/// });
+///
+/// assert_eq!(shaders.get(Pipeline::Opaque).map(String::as_str), Some("compiled Opaque"));
+/// assert!(shaders.get(Pipeline::Shadow).is_none());
+/// assert_eq!(shaders.iter().count(), 1);
+/// ```
+#[cfg(feature = "std")]
+pub struct OnceMap<K, V>
+where
+    K: Finite,
+{
+    slots: K::Array<OnceLock<V>>,
+}
+
+#[cfg(feature = "std")]
+impl<K, V> OnceMap<K, V>
+where
+    K: Finite,
+{
+    /// Creates an empty [`OnceMap`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = OnceMap::<MyKey, u32>::new();
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> OnceMap<K, V> {
+        OnceMap {
+            slots: K::array_from_fn(|_| OnceLock::new()),
+        }
+    }
+
+    /// Returns a reference to the value of the key, or `None` if it hasn't
+    /// been initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = OnceMap::new();
+    /// map.get_or_init(MyKey::First, || 1);
+    ///
+    /// assert_eq!(map.get(MyKey::First), Some(&1));
+    /// assert_eq!(map.get(MyKey::Second), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, key: K) -> Option<&V> {
+        self.slot(key).get()
+    }
+
+    /// Returns a mutable reference to the value of the key, or `None` if it
+    /// hasn't been initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = OnceMap::new();
+    /// map.get_or_init(MyKey::First, || 1);
+    ///
+    /// if let Some(value) = map.get_mut(MyKey::First) {
+    ///     *value += 1;
+    /// }
+    ///
+    /// assert_eq!(map.get(MyKey::First), Some(&2));
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        self.slots.as_mut()[key.index()].get_mut()
+    }
+
+    /// Returns the value of the key, initializing it with `f` if it hasn't
+    /// been initialized yet.
+    ///
+    /// If several threads initialize the same key concurrently, only one `f`
+    /// is called and the others block until it has completed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = OnceMap::new();
+    /// assert_eq!(map.get_or_init(MyKey::First, || 1), &1);
+    /// assert_eq!(map.get_or_init(MyKey::First, || 2), &1);
+    /// ```
+    #[inline]
+    pub fn get_or_init<F>(&self, key: K, f: F) -> &V
+    where
+        F: FnOnce() -> V,
+    {
+        self.slot(key).get_or_init(f)
+    }
+
+    /// Initializes the value of the key, returning the value back as an
+    /// error if it was already initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = OnceMap::new();
+    /// assert_eq!(map.set(MyKey::First, 1), Ok(()));
+    /// assert_eq!(map.set(MyKey::First, 2), Err(2));
+    /// ```
+    #[inline]
+    pub fn set(&self, key: K, value: V) -> Result<(), V> {
+        self.slot(key).set(value)
+    }
+
+    /// Takes the value of the key out of the map, leaving it uninitialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = OnceMap::new();
+    /// map.get_or_init(MyKey::First, || 1);
+    ///
+    /// assert_eq!(map.take(MyKey::First), Some(1));
+    /// assert_eq!(map.get_or_init(MyKey::First, || 2), &2);
+    /// ```
+    #[inline]
+    pub fn take(&mut self, key: K) -> Option<V> {
+        self.slots.as_mut()[key.index()].take()
+    }
+
+    /// Returns the number of initialized values in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = OnceMap::new();
+    /// map.get_or_init(MyKey::Second, || 2);
+    ///
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns true if no value in the map has been initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = OnceMap::new();
+    /// assert!(map.is_empty());
+    ///
+    /// map.get_or_init(MyKey::Second, || 2);
+    /// assert!(!map.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// An iterator visiting the initialized key-value pairs in index order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let map = OnceMap::new();
+    /// map.get_or_init(MyKey::Third, || 3);
+    /// map.get_or_init(MyKey::First, || 1);
+    ///
+    /// assert!(map.iter().eq([(MyKey::First, &1), (MyKey::Third, &3)]));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        K::array_from_fn(|key| key)
+            .into_iter()
+            .zip(self.slots.as_ref())
+            .filter_map(|(key, slot)| Some((key, slot.get()?)))
+    }
+
+    #[inline]
+    fn slot(&self, key: K) -> &OnceLock<V> {
+        &self.slots.as_ref()[key.index()]
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V> Default for OnceMap<K, V>
+where
+    K: Finite,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The [`Debug`][fmt::Debug] implementation for a [`OnceMap`], which only
+/// includes initialized values.
+#[cfg(feature = "std")]
+impl<K, V> fmt::Debug for OnceMap<K, V>
+where
+    K: Finite + fmt::Debug,
+    V: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, K, V> IntoIterator for &'a OnceMap<K, V>
+where
+    K: Finite,
+{
+    type Item = (K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Produce an owning iterator visiting the initialized key-value pairs of the
+/// [`OnceMap`] in index order.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, OnceMap};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let map = OnceMap::new();
+/// map.get_or_init(MyKey::Second, || 2);
+///
+/// assert!(map.into_iter().eq([(MyKey::Second, 2)]));
+/// ```
+#[cfg(feature = "std")]
+impl<K, V> IntoIterator for OnceMap<K, V>
+where
+    K: Finite,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        K::array_from_fn(|key| key)
+            .into_iter()
+            .zip(self.slots)
+            .filter_map(|(key, slot)| Some((key, slot.into_inner()?)))
+    }
+}
+
+/// Construct a [`OnceMap`] where the values present in a [`Map`] are
+/// initialized.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map, OnceMap};
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(MyKey::First, 1);
+///
+/// let map = OnceMap::from(map);
+/// assert_eq!(map.get_or_init(MyKey::First, || 2), &1);
+/// ```
+#[cfg(feature = "std")]
+impl<K, V> From<Map<K, V>> for OnceMap<K, V>
+where
+    K: Finite,
+{
+    #[inline]
+    fn from(mut map: Map<K, V>) -> Self {
+        OnceMap {
+            slots: K::array_from_fn(|key| match map.remove(key) {
+                Some(value) => OnceLock::from(value),
+                None => OnceLock::new(),
+            }),
+        }
+    }
+}
+
+/// Convert a [`OnceMap`] into a [`Map`] holding its initialized values.
+#[cfg(feature = "std")]
+impl<K, V> From<OnceMap<K, V>> for Map<K, V>
+where
+    K: Finite,
+{
+    #[inline]
+    fn from(map: OnceMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+/// A single-threaded map where the value of every inhabitant of a [`Finite`]
+/// key is lazily initialized at most once, stored in a [`OnceCell`].
+///
+/// This is the counterpart to [`OnceMap`] which is available without the
+/// `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, OnceCellMap};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Pipeline {
+///     Opaque,
+///     Transparent,
+///     Shadow,
+/// }
+///
+/// let shaders = OnceCellMap::new();
+///
+/// let opaque: &u32 = shaders.get_or_init(Pipeline::Opaque, || 1);
+/// let shadow = shaders.get_or_init(Pipeline::Shadow, || 3);
+/// assert_eq!(opaque + shadow, 4);
+///
+/// assert!(shaders.iter().eq([(Pipeline::Opaque, &1), (Pipeline::Shadow, &3)]));
+/// ```
+pub struct OnceCellMap<K, V>
+where
+    K: Finite,
+{
+    slots: K::Array<OnceCell<V>>,
+}
+
+impl<K, V> OnceCellMap<K, V>
+where
+    K: Finite,
+{
+    /// Creates an empty [`OnceCellMap`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceCellMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = OnceCellMap::<MyKey, u32>::new();
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> OnceCellMap<K, V> {
+        OnceCellMap {
+            slots: K::array_from_fn(|_| OnceCell::new()),
+        }
+    }
+
+    /// Returns a reference to the value of the key, or `None` if it hasn't
+    /// been initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceCellMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = OnceCellMap::new();
+    /// map.get_or_init(MyKey::First, || 1);
+    ///
+    /// assert_eq!(map.get(MyKey::First), Some(&1));
+    /// assert_eq!(map.get(MyKey::Second), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, key: K) -> Option<&V> {
+        self.slot(key).get()
+    }
+
+    /// Returns a mutable reference to the value of the key, or `None` if it
+    /// hasn't been initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceCellMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = OnceCellMap::new();
+    /// map.get_or_init(MyKey::First, || 1);
+    ///
+    /// if let Some(value) = map.get_mut(MyKey::First) {
+    ///     *value += 1;
+    /// }
+    ///
+    /// assert_eq!(map.get(MyKey::First), Some(&2));
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        self.slots.as_mut()[key.index()].get_mut()
+    }
+
+    /// Returns the value of the key, initializing it with `f` if it hasn't
+    /// been initialized yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` reentrantly initializes the same key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceCellMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = OnceCellMap::new();
+    /// assert_eq!(map.get_or_init(MyKey::First, || 1), &1);
+    /// assert_eq!(map.get_or_init(MyKey::First, || 2), &1);
+    /// ```
+    #[inline]
+    pub fn get_or_init<F>(&self, key: K, f: F) -> &V
+    where
+        F: FnOnce() -> V,
+    {
+        self.slot(key).get_or_init(f)
+    }
+
+    /// Initializes the value of the key, returning the value back as an
+    /// error if it was already initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceCellMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = OnceCellMap::new();
+    /// assert_eq!(map.set(MyKey::First, 1), Ok(()));
+    /// assert_eq!(map.set(MyKey::First, 2), Err(2));
+    /// ```
+    #[inline]
+    pub fn set(&self, key: K, value: V) -> Result<(), V> {
+        self.slot(key).set(value)
+    }
+
+    /// Takes the value of the key out of the map, leaving it uninitialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceCellMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = OnceCellMap::new();
+    /// map.get_or_init(MyKey::First, || 1);
+    ///
+    /// assert_eq!(map.take(MyKey::First), Some(1));
+    /// assert_eq!(map.get_or_init(MyKey::First, || 2), &2);
+    /// ```
+    #[inline]
+    pub fn take(&mut self, key: K) -> Option<V> {
+        self.slots.as_mut()[key.index()].take()
+    }
+
+    /// Returns the number of initialized values in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceCellMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = OnceCellMap::new();
+    /// map.get_or_init(MyKey::Second, || 2);
+    ///
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns true if no value in the map has been initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceCellMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = OnceCellMap::new();
+    /// assert!(map.is_empty());
+    ///
+    /// map.get_or_init(MyKey::Second, || 2);
+    /// assert!(!map.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// An iterator visiting the initialized key-value pairs in index order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, OnceCellMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let map = OnceCellMap::new();
+    /// map.get_or_init(MyKey::Third, || 3);
+    /// map.get_or_init(MyKey::First, || 1);
+    ///
+    /// assert!(map.iter().eq([(MyKey::First, &1), (MyKey::Third, &3)]));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> CellIter<'_, K, V> {
+        K::array_from_fn(|key| key)
+            .into_iter()
+            .zip(self.slots.as_ref())
+            .filter_map(|(key, slot)| Some((key, slot.get()?)))
+    }
+
+    #[inline]
+    fn slot(&self, key: K) -> &OnceCell<V> {
+        &self.slots.as_ref()[key.index()]
+    }
+}
+
+impl<K, V> Default for OnceCellMap<K, V>
+where
+    K: Finite,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The [`Debug`][fmt::Debug] implementation for a [`OnceCellMap`], which only
+/// includes initialized values.
+impl<K, V> fmt::Debug for OnceCellMap<K, V>
+where
+    K: Finite + fmt::Debug,
+    V: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a OnceCellMap<K, V>
+where
+    K: Finite,
+{
+    type Item = (K, &'a V);
+    type IntoIter = CellIter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Produce an owning iterator visiting the initialized key-value pairs of the
+/// [`OnceCellMap`] in index order.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, OnceCellMap};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let map = OnceCellMap::new();
+/// map.get_or_init(MyKey::Second, || 2);
+///
+/// assert!(map.into_iter().eq([(MyKey::Second, 2)]));
+/// ```
+impl<K, V> IntoIterator for OnceCellMap<K, V>
+where
+    K: Finite,
+{
+    type Item = (K, V);
+    type IntoIter = CellIntoIter<K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        K::array_from_fn(|key| key)
+            .into_iter()
+            .zip(self.slots)
+            .filter_map(|(key, slot)| Some((key, slot.into_inner()?)))
+    }
+}
+
+/// Construct a [`OnceCellMap`] where the values present in a [`Map`] are
+/// initialized.
+impl<K, V> From<Map<K, V>> for OnceCellMap<K, V>
+where
+    K: Finite,
+{
+    #[inline]
+    fn from(mut map: Map<K, V>) -> Self {
+        OnceCellMap {
+            slots: K::array_from_fn(|key| match map.remove(key) {
+                Some(value) => OnceCell::from(value),
+                None => OnceCell::new(),
+            }),
+        }
+    }
+}
+
+/// Convert a [`OnceCellMap`] into a [`Map`] holding its initialized values.
+impl<K, V> From<OnceCellMap<K, V>> for Map<K, V>
+where
+    K: Finite,
+{
+    #[inline]
+    fn from(map: OnceCellMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
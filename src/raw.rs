@@ -20,3 +20,19 @@ pub trait RawStorage: Sized {
     /// Build storage from raw storage.
     fn from_raw(raw: Self::Value) -> Self;
 }
+
+/// Trait implemented for map storage which can expose the keys it contains as
+/// a raw value.
+///
+/// This is implemented for [`MapStorage`] when the `#[key(compact)]` attribute
+/// is present. The raw value is the same as the one [`RawStorage`] provides
+/// for a set of the same keys with the `#[key(bitset)]` attribute.
+///
+/// [`MapStorage`]: crate::map::MapStorage
+pub trait RawKeys {
+    /// The backing raw value.
+    type Value;
+
+    /// Get the raw value of the keys in storage.
+    fn as_raw_keys(&self) -> Self::Value;
+}
@@ -7,8 +7,24 @@ use core::hash::{Hash, Hasher};
 pub mod intersection;
 pub mod storage;
 
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "rayon")]
+pub use self::rayon::ParIter;
+
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "rkyv-validation")]
+pub use self::rkyv::BitsCheckError;
+#[cfg(feature = "rkyv")]
+pub use self::rkyv::{ArchivedIter, ArchivedSet};
+
 pub use self::intersection::Intersection;
+#[cfg(feature = "rayon")]
+pub use self::storage::ParallelSetStorage;
 pub use self::storage::SetStorage;
+#[cfg(feature = "rkyv")]
+pub use self::storage::{ArchiveSetStorage, ArchivedSetStorage};
 
 use crate::raw::RawStorage;
 use crate::Key;
@@ -259,6 +275,50 @@ where
         self.storage.remove(value)
     }
 
+    /// Returns `true` if the set contains the value identified by its borrowed
+    /// form.
+    ///
+    /// See [`Key::Ref`] for how values are borrowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use fixed_map::Set;
+    ///
+    /// let mut set = Set::new();
+    /// set.insert(String::from("a"));
+    /// assert!(set.contains_by("a"));
+    /// assert!(!set.contains_by("b"));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn contains_by(&self, value: T::Ref<'_>) -> bool {
+        self.storage.contains_by(value)
+    }
+
+    /// Removes the value identified by its borrowed form from the set. Returns
+    /// `true` if the value was present in the set.
+    ///
+    /// See [`Key::Ref`] for how values are borrowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use fixed_map::Set;
+    ///
+    /// let mut set = Set::new();
+    /// set.insert(String::from("a"));
+    /// assert!(set.remove_by("a"));
+    /// assert!(!set.remove_by("a"));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn remove_by(&mut self, value: T::Ref<'_>) -> bool {
+        self.storage.remove_by(value)
+    }
+
     /// Retains only the elements specified by the predicate.
     ///
     /// In other words, remove all elements e for which f(e) returns false.
@@ -431,15 +491,9 @@ where
     #[inline]
     pub fn intersection<'a>(&'a self, other: &'a Set<T>) -> Intersection<'a, T> {
         if self.len() <= other.len() {
-            Intersection {
-                iter: self.iter(),
-                other,
-            }
+            Intersection::new(self.iter(), other)
         } else {
-            Intersection {
-                iter: other.iter(),
-                other: self,
-            }
+            Intersection::new(other.iter(), self)
         }
     }
 }
@@ -1064,6 +1118,157 @@ where
     }
 }
 
+/// The set is written through its storage. Keys using `#[key(bitset)]` are
+/// written as their raw integer, while other keys with unit variants are
+/// written as a bitmap of the values which are present.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Set};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// #[key(bitset)]
+/// enum Part {
+///     One,
+///     Two,
+///     Three,
+/// }
+///
+/// let mut set = Set::new();
+/// set.insert(Part::One);
+/// set.insert(Part::Three);
+///
+/// let bytes = borsh::to_vec(&set)?;
+/// assert_eq!(bytes, [0b101]);
+///
+/// let decoded: Set<Part> = borsh::from_slice(&bytes)?;
+/// assert_eq!(decoded, set);
+/// # Ok::<_, borsh::io::Error>(())
+/// ```
+#[cfg(feature = "borsh")]
+impl<T> borsh::BorshSerialize for Set<T>
+where
+    T: Key,
+    T::SetStorage: borsh::BorshSerialize,
+{
+    #[inline]
+    fn serialize<W>(&self, writer: &mut W) -> borsh::io::Result<()>
+    where
+        W: borsh::io::Write,
+    {
+        self.storage.serialize(writer)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl<T> borsh::BorshDeserialize for Set<T>
+where
+    T: Key,
+    T::SetStorage: borsh::BorshDeserialize,
+{
+    #[inline]
+    fn deserialize_reader<R>(reader: &mut R) -> borsh::io::Result<Self>
+    where
+        R: borsh::io::Read,
+    {
+        Ok(Self {
+            storage: T::SetStorage::deserialize_reader(reader)?,
+        })
+    }
+}
+
+/// The set is described as an array of unique values of the key.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Set};
+///
+/// #[derive(Clone, Copy, Key)]
+/// #[key(schema)]
+/// enum Part {
+///     One,
+///     Two,
+/// }
+///
+/// let schema = schemars::schema_for!(Set<Part>);
+/// let array = schema.schema.array.unwrap();
+///
+/// assert_eq!(array.unique_items, Some(true));
+/// ```
+#[cfg(feature = "schemars")]
+impl<T> schemars::JsonSchema for Set<T>
+where
+    T: Key + schemars::JsonSchema,
+{
+    #[inline]
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    #[inline]
+    fn schema_name() -> std::string::String {
+        std::format!("Set_of_{}", T::schema_name())
+    }
+
+    fn json_schema(generator: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        use schemars::schema::{ArrayValidation, InstanceType, Schema, SchemaObject};
+
+        Schema::Object(SchemaObject {
+            instance_type: Some(InstanceType::Array.into()),
+            array: Some(std::boxed::Box::new(ArrayValidation {
+                items: Some(generator.subschema_for::<T>().into()),
+                unique_items: Some(true),
+                ..ArrayValidation::default()
+            })),
+            ..SchemaObject::default()
+        })
+    }
+}
+
+/// Values are generated one by one and inserted into the set, so values may be
+/// generated more than once.
+#[cfg(feature = "arbitrary")]
+impl<'a, T> arbitrary::Arbitrary<'a> for Set<T>
+where
+    T: Key + arbitrary::Arbitrary<'a>,
+{
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_iter()?.collect()
+    }
+
+    #[inline]
+    fn arbitrary_take_rest(u: arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_take_rest_iter()?.collect()
+    }
+}
+
+/// Values are generated as a sequence and inserted into the set, so values
+/// may be generated more than once.
+#[cfg(feature = "proptest")]
+impl<T> proptest::arbitrary::Arbitrary for Set<T>
+where
+    T: Key + proptest::arbitrary::Arbitrary,
+{
+    type Parameters = T::Parameters;
+    type Strategy = proptest::strategy::Map<
+        proptest::collection::VecStrategy<T::Strategy>,
+        fn(std::vec::Vec<T>) -> Self,
+    >;
+
+    #[inline]
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy as _;
+
+        let values = proptest::arbitrary::any_with::<T>(args);
+
+        proptest::collection::vec(values, proptest::collection::SizeRange::default())
+            .prop_map(|values| values.into_iter().collect())
+    }
+}
+
 impl<T, const N: usize> From<[T; N]> for Set<T>
 where
     T: Key,
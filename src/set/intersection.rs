@@ -1,6 +1,7 @@
 //! Module that defines the [`Intersection`] for [`Set`].
 
 use core::fmt;
+use core::iter::FusedIterator;
 
 use super::{Iter, Key, Set};
 
@@ -9,6 +10,9 @@ use super::{Iter, Key, Set};
 /// This `struct` is created by the [`intersection`] method on [`Set`]. See its
 /// documentation for more.
 ///
+/// The number of elements in the intersection isn't known up front, so unlike
+/// the other set iterators this doesn't implement [`ExactSizeIterator`].
+///
 /// [`intersection`]: Set::intersection
 ///
 /// # Examples
@@ -33,9 +37,19 @@ use super::{Iter, Key, Set};
               without modifying either input set"]
 pub struct Intersection<'a, T: 'a + Key> {
     // iterator of the first set
-    pub(super) iter: Iter<'a, T>,
+    iter: Iter<'a, T>,
     // the second set
-    pub(super) other: &'a Set<T>,
+    other: &'a Set<T>,
+}
+
+impl<'a, T> Intersection<'a, T>
+where
+    T: Key,
+{
+    #[inline]
+    pub(super) fn new(iter: Iter<'a, T>, other: &'a Set<T>) -> Self {
+        Self { iter, other }
+    }
 }
 
 impl<T: Key> Clone for Intersection<'_, T> {
@@ -59,7 +73,7 @@ where
         loop {
             let elt = self.iter.next()?;
 
-            if self.other.contains(elt) {
+            if self.other.contains(elt.clone()) {
                 return Some(elt);
             }
         }
@@ -78,7 +92,7 @@ where
         F: FnMut(B, Self::Item) -> B,
     {
         self.iter.fold(init, |acc, elt| {
-            if self.other.contains(elt) {
+            if self.other.contains(elt.clone()) {
                 f(acc, elt)
             } else {
                 acc
@@ -87,6 +101,8 @@ where
     }
 }
 
+impl<T> FusedIterator for Intersection<'_, T> where T: Key {}
+
 impl<T> fmt::Debug for Intersection<'_, T>
 where
     T: fmt::Debug + Key,
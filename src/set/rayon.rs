@@ -0,0 +1,57 @@
+//! Parallel iteration over sets, enabled through the `rayon` feature.
+
+use ::rayon::iter::IntoParallelIterator;
+
+use crate::set::{ParallelSetStorage, Set};
+use crate::Key;
+
+/// The parallel iterator produced by [`Set::par_iter`].
+pub type ParIter<'a, T> = <<T as Key>::SetStorage as ParallelSetStorage<T>>::ParIter<'a>;
+
+impl<T> Set<T>
+where
+    T: Key,
+    T::SetStorage: ParallelSetStorage<T>,
+{
+    /// A parallel iterator visiting all values in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    /// use rayon::prelude::*;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum System {
+    ///     Physics,
+    ///     Audio,
+    ///     Render,
+    /// }
+    ///
+    /// let mut set = Set::new();
+    /// set.insert(System::Physics);
+    /// set.insert(System::Render);
+    ///
+    /// let systems: Vec<_> = set.par_iter().collect();
+    /// assert_eq!(systems, vec![System::Physics, System::Render]);
+    /// ```
+    #[inline]
+    pub fn par_iter(&self) -> ParIter<'_, T> {
+        self.storage.par_iter()
+    }
+}
+
+impl<'a, T> IntoParallelIterator for &'a Set<T>
+where
+    T: Key,
+    T::SetStorage: ParallelSetStorage<T>,
+    T: Send,
+{
+    type Item = T;
+    type Iter = ParIter<'a, T>;
+
+    #[inline]
+    fn into_par_iter(self) -> Self::Iter {
+        self.storage.par_iter()
+    }
+}
@@ -0,0 +1,184 @@
+//! Zero-copy archiving of sets, enabled through the `rkyv` feature.
+
+use core::fmt;
+
+use ::rkyv::{out_field, Archive, Deserialize, Fallible, Serialize};
+
+use crate::set::{ArchiveSetStorage, ArchivedSetStorage, Set};
+use crate::Key;
+
+/// The iterator produced by [`ArchivedSet::iter`].
+pub type ArchivedIter<'a, T> = <ArchivedStorage<T> as ArchivedSetStorage<T>>::Iter<'a>;
+
+type ArchivedStorage<T> = <<T as Key>::SetStorage as ArchiveSetStorage<T>>::Archived;
+
+/// The archived form of a [`Set`].
+///
+/// This has the same layout as the storage of the set, so values are looked up
+/// directly in the archive without deserializing it. For keys using
+/// `#[key(bitset)]`, this is the archived raw integer of the bitset.
+///
+/// Archives read from untrusted sources, such as memory-mapped files, should be
+/// validated with [`rkyv::check_archived_root`] before they are used. This
+/// requires the `rkyv-validation` feature, which checks that every archived
+/// set storage only marks slots that correspond to a key.
+///
+/// [`rkyv::check_archived_root`]: https://docs.rs/rkyv/0.7/rkyv/validation/validators/fn.check_archived_root.html
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Set};
+///
+/// # #[cfg(feature = "rkyv-validation")] {
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// #[key(bitset)]
+/// enum Part {
+///     One,
+///     Two,
+///     Three,
+/// }
+///
+/// let mut set = Set::new();
+/// set.insert(Part::One);
+/// set.insert(Part::Three);
+///
+/// let bytes = rkyv::to_bytes::<_, 256>(&set).unwrap();
+/// let archived = rkyv::check_archived_root::<Set<Part>>(&bytes).unwrap();
+///
+/// assert!(archived.contains(Part::One));
+/// assert!(!archived.contains(Part::Two));
+/// assert!(archived.iter().eq([Part::One, Part::Three]));
+/// # }
+/// ```
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "rkyv-validation",
+    derive(::rkyv::CheckBytes),
+    check_bytes(crate = "::rkyv::bytecheck")
+)]
+pub struct ArchivedSet<T>
+where
+    T: Key,
+    T::SetStorage: ArchiveSetStorage<T>,
+{
+    storage: ArchivedStorage<T>,
+}
+
+impl<T> ArchivedSet<T>
+where
+    T: Key,
+    T::SetStorage: ArchiveSetStorage<T>,
+{
+    /// Returns `true` if the archived set contains the value.
+    #[inline]
+    pub fn contains(&self, value: T) -> bool {
+        self.storage.contains(value)
+    }
+
+    /// Returns the number of elements in the archived set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Returns true if the archived set contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+
+    /// An iterator visiting all values in the order of the original set.
+    #[inline]
+    pub fn iter(&self) -> ArchivedIter<'_, T> {
+        self.storage.iter()
+    }
+}
+
+impl<T> fmt::Debug for ArchivedSet<T>
+where
+    T: Key + fmt::Debug,
+    T::SetStorage: ArchiveSetStorage<T>,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Archive for Set<T>
+where
+    T: Key,
+    T::SetStorage: ArchiveSetStorage<T>,
+{
+    type Archived = ArchivedSet<T>;
+    type Resolver = <T::SetStorage as ArchiveSetStorage<T>>::Resolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (offset, out) = out_field!(out.storage);
+        self.storage.resolve(pos + offset, resolver, out);
+    }
+}
+
+impl<T, S> Serialize<S> for Set<T>
+where
+    T: Key,
+    T::SetStorage: ArchiveSetStorage<T>,
+    S: Fallible + ?Sized,
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.storage.serialize(serializer)
+    }
+}
+
+impl<T, D> Deserialize<Set<T>, D> for ArchivedSet<T>
+where
+    T: Key,
+    T::SetStorage: ArchiveSetStorage<T>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<Set<T>, D::Error> {
+        Ok(self.iter().collect())
+    }
+}
+
+/// The error produced when validating an archived bitset which has a bit set
+/// that doesn't correspond to any key, which is enabled through the
+/// `rkyv-validation` feature.
+#[cfg(feature = "rkyv-validation")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitsCheckError {
+    index: usize,
+}
+
+#[cfg(feature = "rkyv-validation")]
+impl BitsCheckError {
+    #[inline]
+    pub(crate) fn new(index: usize) -> Self {
+        Self { index }
+    }
+
+    /// The index of a bit which is set but doesn't correspond to any key.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+#[cfg(feature = "rkyv-validation")]
+impl fmt::Display for BitsCheckError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bit {} is set in archived bitset but doesn't correspond to a key",
+            self.index
+        )
+    }
+}
+
+#[cfg(feature = "rkyv-validation")]
+impl std::error::Error for BitsCheckError {}
@@ -6,6 +6,12 @@ pub use self::singleton::SingletonSetStorage;
 mod boolean;
 pub use self::boolean::BooleanSetStorage;
 
+mod byte;
+pub use self::byte::ByteSetStorage;
+
+mod empty;
+pub use self::empty::EmptySetStorage;
+
 #[cfg(feature = "hashbrown")]
 mod hashbrown;
 #[cfg(feature = "hashbrown")]
@@ -14,6 +20,24 @@ pub use self::hashbrown::HashbrownSetStorage;
 mod option;
 pub use self::option::OptionSetStorage;
 
+mod ordering;
+pub use self::ordering::OrderingSetStorage;
+
+mod product;
+pub use self::product::ProductSetStorage;
+
+mod result;
+pub use self::result::ResultSetStorage;
+
+use core::iter::FusedIterator;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelIterator;
+#[cfg(feature = "rkyv")]
+use rkyv::Fallible;
+
+use crate::Key;
+
 /// The trait defining how storage works for [`Set`][crate::Set].
 ///
 /// # Type Arguments
@@ -21,12 +45,12 @@ pub use self::option::OptionSetStorage;
 /// - `T` is the key being stored.
 pub trait SetStorage<T>: Sized {
     /// Immutable iterator over storage.
-    type Iter<'this>: Iterator<Item = T> + Clone
+    type Iter<'this>: Iterator<Item = T> + ExactSizeIterator + FusedIterator + Clone
     where
         Self: 'this;
 
     /// Owning iterator over the storage.
-    type IntoIter: Iterator<Item = T>;
+    type IntoIter: Iterator<Item = T> + ExactSizeIterator + FusedIterator;
 
     /// Construct empty storage.
     fn empty() -> Self;
@@ -46,6 +70,16 @@ pub trait SetStorage<T>: Sized {
     /// This is the storage abstraction for [`Set::remove`][crate::Set::remove].
     fn remove(&mut self, value: T) -> bool;
 
+    /// This is the storage abstraction for [`Set::contains_by`][crate::Set::contains_by].
+    fn contains_by(&self, value: T::Ref<'_>) -> bool
+    where
+        T: Key;
+
+    /// This is the storage abstraction for [`Set::remove_by`][crate::Set::remove_by].
+    fn remove_by(&mut self, value: T::Ref<'_>) -> bool
+    where
+        T: Key;
+
     /// This is the storage abstraction for [`Set::retain`][crate::Set::retain].
     fn retain<F>(&mut self, f: F)
     where
@@ -60,3 +94,87 @@ pub trait SetStorage<T>: Sized {
     /// This is the storage abstraction for [`Set::into_iter`][crate::Set::into_iter].
     fn into_iter(self) -> Self::IntoIter;
 }
+
+/// The trait defining how storage for [`Set`][crate::Set] is iterated over in
+/// parallel, which is enabled through the `rayon` feature.
+///
+/// Keys using `#[key(bitset)]` and tuple keys don't implement it.
+///
+/// # Type Arguments
+///
+/// - `T` is the key being stored.
+#[cfg(feature = "rayon")]
+pub trait ParallelSetStorage<T>: SetStorage<T> {
+    /// Immutable parallel iterator over storage.
+    type ParIter<'this>: ParallelIterator<Item = T>
+    where
+        Self: 'this;
+
+    /// This is the storage abstraction for [`Set::par_iter`][crate::Set::par_iter].
+    fn par_iter(&self) -> Self::ParIter<'_>;
+}
+
+/// The trait defining how storage for [`Set`][crate::Set] is archived through
+/// `rkyv`, which is enabled through the `rkyv` feature.
+///
+/// The archived storage keeps the layout of the storage, so that it can be
+/// accessed directly through [`ArchivedSetStorage`]. Keys using
+/// `#[key(bitset)]` are archived as their raw integer. Tuple keys and keys with
+/// dynamic storage don't implement it.
+///
+/// # Type Arguments
+///
+/// - `T` is the key being stored.
+#[cfg(feature = "rkyv")]
+pub trait ArchiveSetStorage<T>: SetStorage<T> {
+    /// The archived form of the storage.
+    type Archived: ArchivedSetStorage<T>;
+
+    /// The resolver of the archived storage.
+    type Resolver;
+
+    /// Serialize the storage, returning a resolver for it.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the storage fails to serialize.
+    fn serialize<S>(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized;
+
+    /// Create the archived storage at the given position.
+    ///
+    /// # Safety
+    ///
+    /// This has the same requirements as [`Archive::resolve`][rkyv::Archive::resolve].
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived);
+}
+
+/// The trait defining how archived storage for [`Set`][crate::Set] is
+/// accessed, which is enabled through the `rkyv` feature.
+///
+/// # Type Arguments
+///
+/// - `T` is the key being stored.
+#[cfg(feature = "rkyv")]
+pub trait ArchivedSetStorage<T> {
+    /// Immutable iterator over archived storage.
+    type Iter<'this>: Iterator<Item = T>
+    where
+        Self: 'this;
+
+    /// This is the storage abstraction for [`ArchivedSet::len`][crate::set::ArchivedSet::len].
+    fn len(&self) -> usize;
+
+    /// This is the storage abstraction for [`ArchivedSet::is_empty`][crate::set::ArchivedSet::is_empty].
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// This is the storage abstraction for [`ArchivedSet::contains`][crate::set::ArchivedSet::contains].
+    fn contains(&self, value: T) -> bool;
+
+    /// This is the storage abstraction for [`ArchivedSet::iter`][crate::set::ArchivedSet::iter].
+    fn iter(&self) -> Self::Iter<'_>;
+}
@@ -1,9 +1,21 @@
 // Iterators are confusing if they impl `Copy`.
 #![allow(missing_copy_implementations)]
 
+use core::iter::FusedIterator;
 use core::mem;
 
+#[cfg(feature = "borsh")]
+use borsh::{io, BorshDeserialize, BorshSerialize};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+#[cfg(feature = "rkyv")]
+use rkyv::{out_field, Archive, Archived, Fallible, Resolver, Serialize};
+
+#[cfg(feature = "rayon")]
+use crate::set::ParallelSetStorage;
 use crate::set::SetStorage;
+#[cfg(feature = "rkyv")]
+use crate::set::{ArchiveSetStorage, ArchivedSetStorage};
 
 const TRUE_BIT: u8 = 0b10;
 const FALSE_BIT: u8 = 0b01;
@@ -115,6 +127,8 @@ impl ExactSizeIterator for Iter {
     }
 }
 
+impl FusedIterator for Iter {}
+
 impl SetStorage<bool> for BooleanSetStorage {
     type Iter<'this> = Iter;
     type IntoIter = Iter;
@@ -138,7 +152,7 @@ impl SetStorage<bool> for BooleanSetStorage {
     #[inline]
     fn insert(&mut self, value: bool) -> bool {
         let update = self.bits | to_bits(value);
-        test(mem::replace(&mut self.bits, update), value)
+        !test(mem::replace(&mut self.bits, update), value)
     }
 
     #[inline]
@@ -148,9 +162,18 @@ impl SetStorage<bool> for BooleanSetStorage {
 
     #[inline]
     fn remove(&mut self, value: bool) -> bool {
-        let value = to_bits(value);
-        let update = self.bits & !value;
-        mem::replace(&mut self.bits, update) != 0
+        let update = self.bits & !to_bits(value);
+        test(mem::replace(&mut self.bits, update), value)
+    }
+
+    #[inline]
+    fn contains_by(&self, value: bool) -> bool {
+        self.contains(value)
+    }
+
+    #[inline]
+    fn remove_by(&mut self, value: bool) -> bool {
+        self.remove(value)
     }
 
     #[inline]
@@ -196,3 +219,109 @@ const fn to_bits(value: bool) -> u8 {
         FALSE_BIT
     }
 }
+
+#[cfg(feature = "rayon")]
+impl ParallelSetStorage<bool> for BooleanSetStorage {
+    type ParIter<'this> =
+        rayon::iter::Chain<rayon::option::IntoIter<bool>, rayon::option::IntoIter<bool>>;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let a = test(self.bits, true).then_some(true).into_par_iter();
+        let b = test(self.bits, false).then_some(false).into_par_iter();
+        a.chain(b)
+    }
+}
+
+/// The archived form of [`BooleanSetStorage`].
+#[cfg(feature = "rkyv")]
+#[repr(transparent)]
+pub struct ArchivedBooleanSetStorage {
+    bits: Archived<u8>,
+}
+
+#[cfg(feature = "rkyv-validation")]
+impl<C> rkyv::CheckBytes<C> for ArchivedBooleanSetStorage
+where
+    C: ?Sized,
+{
+    type Error = crate::set::BitsCheckError;
+
+    #[inline]
+    unsafe fn check_bytes<'a>(value: *const Self, _: &mut C) -> Result<&'a Self, Self::Error> {
+        let bits = *core::ptr::addr_of!((*value).bits);
+        crate::macro_support::__check_bits(u128::from(bits), 2)?;
+        Ok(&*value)
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl ArchiveSetStorage<bool> for BooleanSetStorage {
+    type Archived = ArchivedBooleanSetStorage;
+    type Resolver = Resolver<u8>;
+
+    #[inline]
+    fn serialize<S>(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+    {
+        Serialize::serialize(&self.bits, serializer)
+    }
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (offset, field) = out_field!(out.bits);
+        self.bits.resolve(pos + offset, resolver, field);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl ArchivedSetStorage<bool> for ArchivedBooleanSetStorage {
+    type Iter<'this> = Iter;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    #[inline]
+    fn contains(&self, value: bool) -> bool {
+        let bit = if value { TRUE_BIT } else { FALSE_BIT };
+        self.bits & bit != 0
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter { bits: self.bits }
+    }
+}
+
+/// The storage is written as its raw bits, with `false` in the least
+/// significant bit.
+#[cfg(feature = "borsh")]
+impl BorshSerialize for BooleanSetStorage {
+    #[inline]
+    fn serialize<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        BorshSerialize::serialize(&self.bits, writer)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl BorshDeserialize for BooleanSetStorage {
+    #[inline]
+    fn deserialize_reader<R>(reader: &mut R) -> io::Result<Self>
+    where
+        R: io::Read,
+    {
+        let bits = u8::deserialize_reader(reader)?;
+
+        if bits & !(TRUE_BIT | FALSE_BIT) != 0 {
+            return Err(crate::macro_support::__borsh_invalid_bits());
+        }
+
+        Ok(Self { bits })
+    }
+}
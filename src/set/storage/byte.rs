@@ -0,0 +1,332 @@
+// Iterators are confusing if they impl `Copy`.
+#![allow(missing_copy_implementations)]
+
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::mem;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+#[cfg(feature = "rkyv")]
+use rkyv::{out_field, Archive, Archived, Fallible, Resolver, Serialize};
+
+use crate::key::Byte;
+#[cfg(feature = "rkyv")]
+use crate::macro_support::__archived_value;
+#[cfg(feature = "rayon")]
+use crate::set::ParallelSetStorage;
+use crate::set::SetStorage;
+#[cfg(feature = "rkyv")]
+use crate::set::{ArchiveSetStorage, ArchivedSetStorage};
+
+/// [`SetStorage`] for byte-sized types like [`u8`] and [`i8`], which stores
+/// every possible value in a 256-bit bitset.
+///
+/// Values are iterated over in ascending numerical order.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Set};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First(u8),
+///     Second(i8),
+/// }
+///
+/// let mut a = Set::new();
+/// a.insert(MyKey::First(200));
+/// a.insert(MyKey::First(10));
+/// a.insert(MyKey::Second(-5));
+///
+/// assert!(a.contains(MyKey::First(10)));
+/// assert!(!a.contains(MyKey::First(11)));
+/// assert!(a.contains(MyKey::Second(-5)));
+///
+/// assert!(a.iter().eq([MyKey::First(10), MyKey::First(200), MyKey::Second(-5)]));
+/// assert!(a.iter().rev().eq([MyKey::Second(-5), MyKey::First(200), MyKey::First(10)]));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ByteSetStorage<K> {
+    bits: [u128; 2],
+    _marker: PhantomData<K>,
+}
+
+/// See [`ByteSetStorage::iter`].
+pub struct Iter<K> {
+    bits: [u128; 2],
+    _marker: PhantomData<K>,
+}
+
+impl<K> Clone for Iter<K> {
+    #[inline]
+    fn clone(&self) -> Iter<K> {
+        Iter {
+            bits: self.bits,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K> Iterator for Iter<K>
+where
+    K: Byte,
+{
+    type Item = K;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for (n, word) in self.bits.iter_mut().enumerate() {
+            if *word != 0 {
+                let index = word.trailing_zeros();
+                *word &= !(1 << index);
+                return Some(K::from_index(n * 128 + index as usize));
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<K> DoubleEndedIterator for Iter<K>
+where
+    K: Byte,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        for (n, word) in self.bits.iter_mut().enumerate().rev() {
+            if *word != 0 {
+                let index = u128::BITS - 1 - word.leading_zeros();
+                *word &= !(1 << index);
+                return Some(K::from_index(n * 128 + index as usize));
+            }
+        }
+
+        None
+    }
+}
+
+impl<K> ExactSizeIterator for Iter<K>
+where
+    K: Byte,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        count(self.bits)
+    }
+}
+
+impl<K> FusedIterator for Iter<K> where K: Byte {}
+
+impl<K> SetStorage<K> for ByteSetStorage<K>
+where
+    K: Byte,
+{
+    type Iter<'this>
+        = Iter<K>
+    where
+        K: 'this;
+    type IntoIter = Iter<K>;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            bits: [0; 2],
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        count(self.bits)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.bits == [0; 2]
+    }
+
+    #[inline]
+    fn insert(&mut self, value: K) -> bool {
+        let (word, mask) = to_bits(value);
+        let update = self.bits[word] | mask;
+        mem::replace(&mut self.bits[word], update) & mask == 0
+    }
+
+    #[inline]
+    fn contains(&self, value: K) -> bool {
+        let (word, mask) = to_bits(value);
+        self.bits[word] & mask != 0
+    }
+
+    #[inline]
+    fn remove(&mut self, value: K) -> bool {
+        let (word, mask) = to_bits(value);
+        let update = self.bits[word] & !mask;
+        mem::replace(&mut self.bits[word], update) & mask != 0
+    }
+
+    #[inline]
+    fn contains_by(&self, value: K) -> bool {
+        self.contains(value)
+    }
+
+    #[inline]
+    fn remove_by(&mut self, value: K) -> bool {
+        self.remove(value)
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(K) -> bool,
+    {
+        for value in self.iter() {
+            if !f(value) {
+                let (word, mask) = to_bits(value);
+                self.bits[word] &= !mask;
+            }
+        }
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.bits = [0; 2];
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            bits: self.bits,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter {
+            bits: self.bits,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Number of values stored in the given bitset.
+#[inline]
+const fn count([low, high]: [u128; 2]) -> usize {
+    (low.count_ones() + high.count_ones()) as usize
+}
+
+/// The word index and bit mask of the given value.
+#[inline]
+fn to_bits<K>(value: K) -> (usize, u128)
+where
+    K: Byte,
+{
+    let index = value.index();
+    (index / 128, 1 << (index % 128))
+}
+
+#[cfg(feature = "rayon")]
+impl<K> ParallelSetStorage<K> for ByteSetStorage<K>
+where
+    K: Byte + Send,
+{
+    // Each half of the bitset is iterated over by its own task.
+    type ParIter<'this>
+        = rayon::iter::FlatMapIter<rayon::array::IntoIter<[u128; 2], 2>, fn([u128; 2]) -> Iter<K>>
+    where
+        K: 'this;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let [low, high] = self.bits;
+        let map: fn(_) -> _ = |bits| Iter {
+            bits,
+            _marker: PhantomData,
+        };
+        [[low, 0], [0, high]].into_par_iter().flat_map_iter(map)
+    }
+}
+
+/// The archived form of [`ByteSetStorage`].
+#[cfg(feature = "rkyv")]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "rkyv-validation",
+    derive(::rkyv::CheckBytes),
+    check_bytes(crate = "::rkyv::bytecheck")
+)]
+pub struct ArchivedByteSetStorage<K> {
+    bits: Archived<[u128; 2]>,
+    _marker: PhantomData<K>,
+}
+
+#[cfg(feature = "rkyv")]
+impl<K> ArchivedByteSetStorage<K> {
+    #[inline]
+    fn bits(&self) -> [u128; 2] {
+        let [low, high] = &self.bits;
+        [__archived_value(low), __archived_value(high)]
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<K> ArchiveSetStorage<K> for ByteSetStorage<K>
+where
+    K: Byte,
+{
+    type Archived = ArchivedByteSetStorage<K>;
+    type Resolver = Resolver<[u128; 2]>;
+
+    #[inline]
+    fn serialize<S>(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+    {
+        Serialize::serialize(&self.bits, serializer)
+    }
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (offset, field) = out_field!(out.bits);
+        self.bits.resolve(pos + offset, resolver, field);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<K> ArchivedSetStorage<K> for ArchivedByteSetStorage<K>
+where
+    K: Byte,
+{
+    type Iter<'this>
+        = Iter<K>
+    where
+        K: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        count(self.bits())
+    }
+
+    #[inline]
+    fn contains(&self, value: K) -> bool {
+        let (word, mask) = to_bits(value);
+        self.bits()[word] & mask != 0
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            bits: self.bits(),
+            _marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use core::convert::Infallible;
+use core::iter;
+
+#[cfg(feature = "borsh")]
+use borsh::{io, BorshDeserialize, BorshSerialize};
+#[cfg(feature = "rkyv")]
+use rkyv::Fallible;
+
+#[cfg(feature = "rayon")]
+use crate::set::ParallelSetStorage;
+use crate::set::SetStorage;
+#[cfg(feature = "rkyv")]
+use crate::set::{ArchiveSetStorage, ArchivedSetStorage};
+
+/// [`SetStorage`] for types which can't be inhabited (like [`Infallible`]).
+///
+/// # Examples
+///
+/// ```
+/// use core::convert::Infallible;
+///
+/// use fixed_map::{Key, Set};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First(Infallible),
+///     Second,
+/// }
+///
+/// let mut a = Set::new();
+/// a.insert(MyKey::Second);
+///
+/// assert!(a.contains(MyKey::Second));
+/// assert!(a.iter().eq([MyKey::Second]));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EmptySetStorage;
+
+impl SetStorage<Infallible> for EmptySetStorage {
+    type Iter<'this> = iter::Empty<Infallible>;
+    type IntoIter = iter::Empty<Infallible>;
+
+    #[inline]
+    fn empty() -> Self {
+        Self
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn insert(&mut self, value: Infallible) -> bool {
+        match value {}
+    }
+
+    #[inline]
+    fn contains(&self, value: Infallible) -> bool {
+        match value {}
+    }
+
+    #[inline]
+    fn remove(&mut self, value: Infallible) -> bool {
+        match value {}
+    }
+
+    #[inline]
+    fn contains_by(&self, value: Infallible) -> bool {
+        self.contains(value)
+    }
+
+    #[inline]
+    fn remove_by(&mut self, value: Infallible) -> bool {
+        self.remove(value)
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, _: F)
+    where
+        F: FnMut(Infallible) -> bool,
+    {
+    }
+
+    #[inline]
+    fn clear(&mut self) {}
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        iter::empty()
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        iter::empty()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl ParallelSetStorage<Infallible> for EmptySetStorage {
+    type ParIter<'this> = rayon::iter::Empty<Infallible>;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        rayon::iter::empty()
+    }
+}
+
+/// The archived form of [`EmptySetStorage`], which takes up no space.
+#[cfg(feature = "rkyv")]
+#[cfg_attr(
+    feature = "rkyv-validation",
+    derive(::rkyv::CheckBytes),
+    check_bytes(crate = "::rkyv::bytecheck")
+)]
+pub struct ArchivedEmptySetStorage;
+
+#[cfg(feature = "rkyv")]
+impl ArchiveSetStorage<Infallible> for EmptySetStorage {
+    type Archived = ArchivedEmptySetStorage;
+    type Resolver = ();
+
+    #[inline]
+    fn serialize<S>(&self, _: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+    {
+        Ok(())
+    }
+
+    #[inline]
+    unsafe fn resolve(&self, _: usize, (): Self::Resolver, _: *mut Self::Archived) {}
+}
+
+#[cfg(feature = "rkyv")]
+impl ArchivedSetStorage<Infallible> for ArchivedEmptySetStorage {
+    type Iter<'this> = iter::Empty<Infallible>;
+
+    #[inline]
+    fn len(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn contains(&self, value: Infallible) -> bool {
+        match value {}
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        iter::empty()
+    }
+}
+
+/// Nothing is written, since the storage can never hold a value.
+#[cfg(feature = "borsh")]
+impl BorshSerialize for EmptySetStorage {
+    #[inline]
+    fn serialize<W>(&self, _: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl BorshDeserialize for EmptySetStorage {
+    #[inline]
+    fn deserialize_reader<R>(_: &mut R) -> io::Result<Self>
+    where
+        R: io::Read,
+    {
+        Ok(Self)
+    }
+}
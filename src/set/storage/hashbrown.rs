@@ -1,6 +1,17 @@
-use core::hash::Hash;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
 use core::iter;
 
+use alloc::vec::Vec;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::key::HashKey;
+use crate::map::storage::hash_unordered;
+#[cfg(feature = "rayon")]
+use crate::set::ParallelSetStorage;
 use crate::set::SetStorage;
 
 /// [`SetStorage`] for dynamically stored types, using [`hashbrown::HashSet`].
@@ -54,12 +65,59 @@ where
 
 impl<T> Eq for HashbrownSetStorage<T> where T: Eq + Hash {}
 
+/// Hashes every element separately and combines them in an order-independent
+/// manner, since the iteration order of a [`hashbrown::HashSet`] is
+/// unspecified.
+impl<T> Hash for HashbrownSetStorage<T>
+where
+    T: Hash,
+{
+    #[inline]
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        state.write_usize(self.inner.len());
+        state.write_u64(hash_unordered(&self.inner));
+    }
+}
+
+/// Storages are ordered by comparing their sorted elements.
+impl<T> PartialOrd for HashbrownSetStorage<T>
+where
+    T: Ord + Hash,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for HashbrownSetStorage<T>
+where
+    T: Ord + Hash,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        sorted(&self.inner).cmp(&sorted(&other.inner))
+    }
+}
+
+fn sorted<T>(set: &::hashbrown::HashSet<T>) -> Vec<&T>
+where
+    T: Ord,
+{
+    let mut elements = set.iter().collect::<Vec<_>>();
+    elements.sort_unstable();
+    elements
+}
+
 impl<T> SetStorage<T> for HashbrownSetStorage<T>
 where
-    T: Copy + Eq + Hash,
+    T: HashKey + Borrow<T::Borrowed>,
 {
     type Iter<'this>
-        = iter::Copied<::hashbrown::hash_set::Iter<'this, T>>
+        = iter::Cloned<::hashbrown::hash_set::Iter<'this, T>>
     where
         T: 'this;
     type IntoIter = ::hashbrown::hash_set::IntoIter<T>;
@@ -96,12 +154,22 @@ where
         self.inner.remove(&value)
     }
 
+    #[inline]
+    fn contains_by(&self, value: T::Ref<'_>) -> bool {
+        self.inner.contains(T::borrowed(&value))
+    }
+
+    #[inline]
+    fn remove_by(&mut self, value: T::Ref<'_>) -> bool {
+        self.inner.remove(T::borrowed(&value))
+    }
+
     #[inline]
     fn retain<F>(&mut self, mut func: F)
     where
         F: FnMut(T) -> bool,
     {
-        self.inner.retain(|&value| func(value));
+        self.inner.retain(|value| func(value.clone()));
     }
 
     #[inline]
@@ -111,7 +179,7 @@ where
 
     #[inline]
     fn iter(&self) -> Self::Iter<'_> {
-        self.inner.iter().copied()
+        self.inner.iter().cloned()
     }
 
     #[inline]
@@ -119,3 +187,19 @@ where
         self.inner.into_iter()
     }
 }
+
+#[cfg(feature = "rayon")]
+impl<T> ParallelSetStorage<T> for HashbrownSetStorage<T>
+where
+    T: HashKey + Borrow<T::Borrowed> + Send + Sync,
+{
+    type ParIter<'this>
+        = rayon::iter::Cloned<::hashbrown::hash_set::rayon::ParIter<'this, T>>
+    where
+        T: 'this;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        self.inner.par_iter().cloned()
+    }
+}
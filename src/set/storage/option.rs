@@ -2,16 +2,30 @@ use core::iter;
 use core::mem;
 use core::option;
 
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+#[cfg(feature = "rkyv")]
+use rkyv::{out_field, Archive, Archived, Fallible, Resolver, Serialize};
+
+use crate::exact_iter::ExactIter;
+#[cfg(feature = "rayon")]
+use crate::set::ParallelSetStorage;
 use crate::set::SetStorage;
+#[cfg(feature = "rkyv")]
+use crate::set::{ArchiveSetStorage, ArchivedSetStorage};
 use crate::Key;
 
-type Iter<'a, T> = iter::Chain<
-    iter::Map<<<T as Key>::SetStorage as SetStorage<T>>::Iter<'a>, fn(T) -> Option<T>>,
-    option::IntoIter<Option<T>>,
+type Iter<'a, T> = ExactIter<
+    iter::Chain<
+        iter::Map<<<T as Key>::SetStorage as SetStorage<T>>::Iter<'a>, fn(T) -> Option<T>>,
+        option::IntoIter<Option<T>>,
+    >,
 >;
-type IntoIter<T> = iter::Chain<
-    iter::Map<<<T as Key>::SetStorage as SetStorage<T>>::IntoIter, fn(T) -> Option<T>>,
-    option::IntoIter<Option<T>>,
+type IntoIter<T> = ExactIter<
+    iter::Chain<
+        iter::Map<<<T as Key>::SetStorage as SetStorage<T>>::IntoIter, fn(T) -> Option<T>>,
+        option::IntoIter<Option<T>>,
+    >,
 >;
 
 /// [`SetStorage`] for [`Option`] types.
@@ -118,14 +132,14 @@ where
 
     #[inline]
     fn is_empty(&self) -> bool {
-        self.some.is_empty() && self.none
+        self.some.is_empty() && !self.none
     }
 
     #[inline]
     fn insert(&mut self, value: Option<T>) -> bool {
         match value {
             Some(value) => self.some.insert(value),
-            None => mem::replace(&mut self.none, true),
+            None => !mem::replace(&mut self.none, true),
         }
     }
 
@@ -145,6 +159,22 @@ where
         }
     }
 
+    #[inline]
+    fn contains_by(&self, value: <Option<T> as Key>::Ref<'_>) -> bool {
+        match value {
+            Some(key) => self.some.contains_by(key),
+            None => self.none,
+        }
+    }
+
+    #[inline]
+    fn remove_by(&mut self, value: <Option<T> as Key>::Ref<'_>) -> bool {
+        match value {
+            Some(key) => self.some.remove_by(key),
+            None => mem::replace(&mut self.none, false),
+        }
+    }
+
     #[inline]
     fn retain<F>(&mut self, mut func: F)
     where
@@ -166,18 +196,140 @@ where
     #[inline]
     fn iter(&self) -> Self::Iter<'_> {
         let map: fn(_) -> _ = Some;
-        self.some
-            .iter()
-            .map(map)
-            .chain(self.none.then_some(None::<T>))
+        ExactIter::new(
+            self.some
+                .iter()
+                .map(map)
+                .chain(self.none.then_some(None::<T>)),
+            self.len(),
+        )
     }
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        let map: fn(_) -> _ = Some;
+        ExactIter::new(
+            self.some
+                .into_iter()
+                .map(map)
+                .chain(self.none.then_some(None::<T>)),
+            len,
+        )
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T> ParallelSetStorage<Option<T>> for OptionSetStorage<T>
+where
+    T: Key + Send,
+    T::SetStorage: ParallelSetStorage<T>,
+{
+    type ParIter<'this>
+        = rayon::iter::Chain<
+        rayon::iter::Map<
+            <T::SetStorage as ParallelSetStorage<T>>::ParIter<'this>,
+            fn(T) -> Option<T>,
+        >,
+        rayon::option::IntoIter<Option<T>>,
+    >
+    where
+        T: 'this;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let map: fn(_) -> _ = Some;
+        let a = self.some.par_iter().map(map);
+        let b = self.none.then_some(None::<T>).into_par_iter();
+        a.chain(b)
+    }
+}
+
+/// The archived form of [`OptionSetStorage`].
+#[cfg(feature = "rkyv")]
+#[repr(C)]
+#[cfg_attr(
+    feature = "rkyv-validation",
+    derive(::rkyv::CheckBytes),
+    check_bytes(crate = "::rkyv::bytecheck")
+)]
+pub struct ArchivedOptionSetStorage<T>
+where
+    T: Key,
+    T::SetStorage: ArchiveSetStorage<T>,
+{
+    some: <T::SetStorage as ArchiveSetStorage<T>>::Archived,
+    none: Archived<bool>,
+}
+
+#[cfg(feature = "rkyv")]
+impl<T> ArchiveSetStorage<Option<T>> for OptionSetStorage<T>
+where
+    T: Key,
+    T::SetStorage: ArchiveSetStorage<T>,
+{
+    type Archived = ArchivedOptionSetStorage<T>;
+    type Resolver = (
+        <T::SetStorage as ArchiveSetStorage<T>>::Resolver,
+        Resolver<bool>,
+    );
+
+    #[inline]
+    fn serialize<S>(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+    {
+        let some = self.some.serialize(serializer)?;
+        Serialize::serialize(&self.none, serializer)?;
+        Ok((some, ()))
+    }
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, (some, none): Self::Resolver, out: *mut Self::Archived) {
+        let (offset, field) = out_field!(out.some);
+        self.some.resolve(pos + offset, some, field);
+        let (offset, field) = out_field!(out.none);
+        self.none.resolve(pos + offset, none, field);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<T> ArchivedSetStorage<Option<T>> for ArchivedOptionSetStorage<T>
+where
+    T: Key,
+    T::SetStorage: ArchiveSetStorage<T>,
+{
+    type Iter<'this>
+        = iter::Chain<
+        iter::Map<
+            <<T::SetStorage as ArchiveSetStorage<T>>::Archived as ArchivedSetStorage<T>>::Iter<
+                'this,
+            >,
+            fn(T) -> Option<T>,
+        >,
+        option::IntoIter<Option<T>>,
+    >
+    where
+        T: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.some.len() + usize::from(self.none)
+    }
+
+    #[inline]
+    fn contains(&self, value: Option<T>) -> bool {
+        match value {
+            Some(value) => self.some.contains(value),
+            None => self.none,
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
         let map: fn(_) -> _ = Some;
-        self.some
-            .into_iter()
-            .map(map)
-            .chain(self.none.then_some(None::<T>))
+        let a = self.some.iter().map(map);
+        let b = self.none.then_some(None::<T>).into_iter();
+        a.chain(b)
     }
 }
@@ -0,0 +1,302 @@
+// Iterators are confusing if they impl `Copy`.
+#![allow(missing_copy_implementations)]
+
+use core::cmp::Ordering;
+use core::iter::FusedIterator;
+use core::mem;
+
+#[cfg(feature = "borsh")]
+use borsh::{io, BorshDeserialize, BorshSerialize};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+#[cfg(feature = "rkyv")]
+use rkyv::{out_field, Archive, Archived, Fallible, Resolver, Serialize};
+
+#[cfg(feature = "rayon")]
+use crate::set::ParallelSetStorage;
+use crate::set::SetStorage;
+#[cfg(feature = "rkyv")]
+use crate::set::{ArchiveSetStorage, ArchivedSetStorage};
+
+/// Every [`Ordering`] in storage order.
+const ORDERINGS: [Ordering; 3] = [Ordering::Less, Ordering::Equal, Ordering::Greater];
+
+/// [`SetStorage`] for [`Ordering`] types.
+///
+/// # Examples
+///
+/// ```
+/// use core::cmp::Ordering;
+///
+/// use fixed_map::{Key, Set};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First(Ordering),
+///     Second,
+/// }
+///
+/// let mut a = Set::new();
+/// a.insert(MyKey::First(Ordering::Greater));
+/// a.insert(MyKey::First(Ordering::Less));
+///
+/// assert!(a.contains(MyKey::First(Ordering::Less)));
+/// assert!(!a.contains(MyKey::First(Ordering::Equal)));
+/// assert!(!a.contains(MyKey::Second));
+///
+/// assert!(a.iter().eq([MyKey::First(Ordering::Less), MyKey::First(Ordering::Greater)]));
+/// assert!(a.iter().rev().eq([MyKey::First(Ordering::Greater), MyKey::First(Ordering::Less)]));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct OrderingSetStorage {
+    bits: u8,
+}
+
+/// See [`OrderingSetStorage::iter`].
+pub struct Iter {
+    bits: u8,
+}
+
+impl Clone for Iter {
+    #[inline]
+    fn clone(&self) -> Iter {
+        Iter { bits: self.bits }
+    }
+}
+
+impl Iterator for Iter {
+    type Item = Ordering;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bits == 0 {
+            return None;
+        }
+
+        let index = self.bits.trailing_zeros();
+        self.bits &= !(1 << index);
+        Some(ORDERINGS[index as usize])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Iter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.bits == 0 {
+            return None;
+        }
+
+        let index = u8::BITS - 1 - self.bits.leading_zeros();
+        self.bits &= !(1 << index);
+        Some(ORDERINGS[index as usize])
+    }
+}
+
+impl ExactSizeIterator for Iter {
+    #[inline]
+    fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+}
+
+impl FusedIterator for Iter {}
+
+impl SetStorage<Ordering> for OrderingSetStorage {
+    type Iter<'this> = Iter;
+    type IntoIter = Iter;
+
+    #[inline]
+    fn empty() -> Self {
+        Self { bits: 0 }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    #[inline]
+    fn insert(&mut self, value: Ordering) -> bool {
+        let mask = to_bits(value);
+        let update = self.bits | mask;
+        mem::replace(&mut self.bits, update) & mask == 0
+    }
+
+    #[inline]
+    fn contains(&self, value: Ordering) -> bool {
+        self.bits & to_bits(value) != 0
+    }
+
+    #[inline]
+    fn remove(&mut self, value: Ordering) -> bool {
+        let mask = to_bits(value);
+        let update = self.bits & !mask;
+        mem::replace(&mut self.bits, update) & mask != 0
+    }
+
+    #[inline]
+    fn contains_by(&self, value: Ordering) -> bool {
+        self.contains(value)
+    }
+
+    #[inline]
+    fn remove_by(&mut self, value: Ordering) -> bool {
+        self.remove(value)
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Ordering) -> bool,
+    {
+        for value in ORDERINGS {
+            if self.contains(value) && !f(value) {
+                self.bits &= !to_bits(value);
+            }
+        }
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.bits = 0;
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter { bits: self.bits }
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter { bits: self.bits }
+    }
+}
+
+#[inline]
+const fn to_bits(value: Ordering) -> u8 {
+    match value {
+        Ordering::Less => 0b001,
+        Ordering::Equal => 0b010,
+        Ordering::Greater => 0b100,
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl ParallelSetStorage<Ordering> for OrderingSetStorage {
+    type ParIter<'this> = rayon::iter::FilterMap<
+        rayon::iter::Zip<rayon::array::IntoIter<Ordering, 3>, rayon::array::IntoIter<u8, 3>>,
+        fn((Ordering, u8)) -> Option<Ordering>,
+    >;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let map: fn(_) -> _ = |(k, bits)| (bits & to_bits(k) != 0).then_some(k);
+        ORDERINGS
+            .into_par_iter()
+            .zip([self.bits; 3].into_par_iter())
+            .filter_map(map)
+    }
+}
+
+/// The archived form of [`OrderingSetStorage`].
+#[cfg(feature = "rkyv")]
+#[repr(transparent)]
+pub struct ArchivedOrderingSetStorage {
+    bits: Archived<u8>,
+}
+
+#[cfg(feature = "rkyv-validation")]
+impl<C> rkyv::CheckBytes<C> for ArchivedOrderingSetStorage
+where
+    C: ?Sized,
+{
+    type Error = crate::set::BitsCheckError;
+
+    #[inline]
+    unsafe fn check_bytes<'a>(value: *const Self, _: &mut C) -> Result<&'a Self, Self::Error> {
+        let bits = *core::ptr::addr_of!((*value).bits);
+        crate::macro_support::__check_bits(u128::from(bits), 3)?;
+        Ok(&*value)
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl ArchiveSetStorage<Ordering> for OrderingSetStorage {
+    type Archived = ArchivedOrderingSetStorage;
+    type Resolver = Resolver<u8>;
+
+    #[inline]
+    fn serialize<S>(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+    {
+        Serialize::serialize(&self.bits, serializer)
+    }
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (offset, field) = out_field!(out.bits);
+        self.bits.resolve(pos + offset, resolver, field);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl ArchivedSetStorage<Ordering> for ArchivedOrderingSetStorage {
+    type Iter<'this> = Iter;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    #[inline]
+    fn contains(&self, value: Ordering) -> bool {
+        self.bits & to_bits(value) != 0
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter { bits: self.bits }
+    }
+}
+
+/// The storage is written as its raw bits, with [`Ordering::Less`] in the
+/// least significant bit.
+#[cfg(feature = "borsh")]
+impl BorshSerialize for OrderingSetStorage {
+    #[inline]
+    fn serialize<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        BorshSerialize::serialize(&self.bits, writer)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl BorshDeserialize for OrderingSetStorage {
+    #[inline]
+    fn deserialize_reader<R>(reader: &mut R) -> io::Result<Self>
+    where
+        R: io::Read,
+    {
+        let bits = u8::deserialize_reader(reader)?;
+
+        if bits & !0b111 != 0 {
+            return Err(crate::macro_support::__borsh_invalid_bits());
+        }
+
+        Ok(Self { bits })
+    }
+}
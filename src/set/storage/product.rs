@@ -0,0 +1,301 @@
+use crate::exact_iter::ExactIter;
+use crate::key::Tuple;
+use crate::map::storage::ProductIter;
+use crate::map::{Entry, MapStorage, OccupiedEntry, VacantEntry};
+use crate::set::SetStorage;
+use crate::Key;
+
+type Head<K> = <K as Tuple>::Head;
+type Tail<K> = <K as Tuple>::Tail;
+type Inner<K> = <Tail<K> as Key>::SetStorage;
+type Outer<K> = <Head<K> as Key>::MapStorage<Inner<K>>;
+
+type Iter<'a, K> = ExactIter<
+    ProductIter<
+        Head<K>,
+        &'a Inner<K>,
+        Heads<'a, K>,
+        <Inner<K> as SetStorage<Tail<K>>>::Iter<'a>,
+        K,
+    >,
+>;
+type IntoIter<K> = ExactIter<
+    ProductIter<
+        Head<K>,
+        Inner<K>,
+        <Outer<K> as MapStorage<Head<K>, Inner<K>>>::IntoIter,
+        <Inner<K> as SetStorage<Tail<K>>>::IntoIter,
+        K,
+    >,
+>;
+
+/// [`SetStorage`] for tuple types.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Set};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Part {
+///     A,
+///     B,
+/// }
+///
+/// let mut a = Set::new();
+/// a.insert((Part::A, true));
+/// a.insert((Part::B, false));
+///
+/// assert!(a.contains((Part::A, true)));
+/// assert!(!a.contains((Part::A, false)));
+/// assert!(a.contains((Part::B, false)));
+///
+/// assert!(a.iter().eq([(Part::A, true), (Part::B, false)]));
+/// ```
+pub struct ProductSetStorage<K>
+where
+    K: Tuple,
+{
+    // Set of heads which have a non-empty inner set, which are kept separately
+    // since map storage iterators can't be cloned.
+    heads: <Head<K> as Key>::SetStorage,
+    inner: Outer<K>,
+}
+
+impl<K> Clone for ProductSetStorage<K>
+where
+    K: Tuple,
+    <Head<K> as Key>::SetStorage: Clone,
+    Outer<K>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            heads: self.heads.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K> Copy for ProductSetStorage<K>
+where
+    K: Tuple,
+    <Head<K> as Key>::SetStorage: Copy,
+    Outer<K>: Copy,
+{
+}
+
+impl<K> PartialEq for ProductSetStorage<K>
+where
+    K: Tuple,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|value| other.contains(value))
+    }
+}
+
+impl<K> Eq for ProductSetStorage<K> where K: Tuple {}
+
+/// Iterator over every head in a [`ProductSetStorage`] together with its
+/// inner set.
+pub struct Heads<'a, K: 'a>
+where
+    K: Tuple,
+{
+    heads: <<Head<K> as Key>::SetStorage as SetStorage<Head<K>>>::Iter<'a>,
+    inner: &'a Outer<K>,
+}
+
+impl<K> Clone for Heads<'_, K>
+where
+    K: Tuple,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            heads: self.heads.clone(),
+            inner: self.inner,
+        }
+    }
+}
+
+impl<'a, K: 'a> Iterator for Heads<'a, K>
+where
+    K: Tuple,
+{
+    type Item = (Head<K>, &'a Inner<K>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let head = self.heads.next()?;
+
+            if let Some(inner) = self.inner.get(head.clone()) {
+                return Some((head, inner));
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.heads.size_hint().1)
+    }
+}
+
+impl<'a, K: 'a> DoubleEndedIterator for Heads<'a, K>
+where
+    K: Tuple,
+    <<Head<K> as Key>::SetStorage as SetStorage<Head<K>>>::Iter<'a>: DoubleEndedIterator,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let head = self.heads.next_back()?;
+
+            if let Some(inner) = self.inner.get(head.clone()) {
+                return Some((head, inner));
+            }
+        }
+    }
+}
+
+impl<K> SetStorage<K> for ProductSetStorage<K>
+where
+    K: Tuple,
+{
+    type Iter<'this>
+        = Iter<'this, K>
+    where
+        K: 'this;
+    type IntoIter = IntoIter<K>;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            heads: SetStorage::empty(),
+            inner: MapStorage::empty(),
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.values().map(SetStorage::len).sum()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: K) -> bool {
+        let (head, tail) = value.split();
+
+        let inner = match self.inner.entry(head.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                self.heads.insert(head);
+                entry.insert(Inner::<K>::empty())
+            }
+        };
+
+        inner.insert(tail)
+    }
+
+    #[inline]
+    fn contains(&self, value: K) -> bool {
+        let (head, tail) = value.split();
+
+        self.inner
+            .get(head)
+            .is_some_and(|inner| inner.contains(tail))
+    }
+
+    #[inline]
+    fn remove(&mut self, value: K) -> bool {
+        let (head, tail) = value.split();
+
+        let Some(inner) = self.inner.get_mut(head.clone()) else {
+            return false;
+        };
+
+        let removed = inner.remove(tail);
+
+        if inner.is_empty() {
+            self.inner.remove(head.clone());
+            self.heads.remove(head);
+        }
+
+        removed
+    }
+
+    #[inline]
+    fn contains_by(&self, value: K::Ref<'_>) -> bool {
+        let (head, tail) = K::split_ref(value);
+
+        self.inner
+            .get_by(head)
+            .is_some_and(|inner| inner.contains_by(tail))
+    }
+
+    #[inline]
+    fn remove_by(&mut self, value: K::Ref<'_>) -> bool {
+        let (head, tail) = K::split_ref(value);
+
+        let Some(inner) = self.inner.get_mut_by(head.clone()) else {
+            return false;
+        };
+
+        let removed = inner.remove_by(tail);
+
+        if inner.is_empty() {
+            self.inner.remove_by(head.clone());
+            self.heads.remove_by(head);
+        }
+
+        removed
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(K) -> bool,
+    {
+        self.inner.retain(|head, inner| {
+            inner.retain(|tail| func(K::join(head.clone(), tail)));
+            !inner.is_empty()
+        });
+
+        let inner = &self.inner;
+        self.heads.retain(|head| inner.contains_key(head));
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.heads.clear();
+        self.inner.clear();
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let heads = Heads {
+            heads: self.heads.iter(),
+            inner: &self.inner,
+        };
+
+        ExactIter::new(
+            ProductIter::new(heads, SetStorage::iter, K::join),
+            self.len(),
+        )
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        ExactIter::new(
+            ProductIter::new(self.inner.into_iter(), SetStorage::into_iter, K::join),
+            len,
+        )
+    }
+}
@@ -0,0 +1,353 @@
+use core::iter;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelIterator;
+#[cfg(feature = "rkyv")]
+use rkyv::{out_field, Fallible};
+
+use crate::exact_iter::ExactIter;
+#[cfg(feature = "rayon")]
+use crate::set::ParallelSetStorage;
+use crate::set::SetStorage;
+#[cfg(feature = "rkyv")]
+use crate::set::{ArchiveSetStorage, ArchivedSetStorage};
+use crate::Key;
+
+type Iter<'a, T, E> = ExactIter<
+    iter::Chain<
+        iter::Map<<<T as Key>::SetStorage as SetStorage<T>>::Iter<'a>, fn(T) -> Result<T, E>>,
+        iter::Map<<<E as Key>::SetStorage as SetStorage<E>>::Iter<'a>, fn(E) -> Result<T, E>>,
+    >,
+>;
+type IntoIter<T, E> = ExactIter<
+    iter::Chain<
+        iter::Map<<<T as Key>::SetStorage as SetStorage<T>>::IntoIter, fn(T) -> Result<T, E>>,
+        iter::Map<<<E as Key>::SetStorage as SetStorage<E>>::IntoIter, fn(E) -> Result<T, E>>,
+    >,
+>;
+
+/// [`SetStorage`] for [`Result`] types.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Set};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Part {
+///     A,
+///     B,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First(Result<Part, bool>),
+///     Second,
+/// }
+///
+/// let mut a = Set::new();
+/// a.insert(MyKey::First(Err(false)));
+/// a.insert(MyKey::First(Ok(Part::A)));
+///
+/// assert!(a.contains(MyKey::First(Ok(Part::A))));
+/// assert!(!a.contains(MyKey::First(Ok(Part::B))));
+/// assert!(a.contains(MyKey::First(Err(false))));
+/// assert!(!a.contains(MyKey::Second));
+///
+/// assert!(a.iter().eq([MyKey::First(Ok(Part::A)), MyKey::First(Err(false))]));
+/// ```
+pub struct ResultSetStorage<T, E>
+where
+    T: Key,
+    E: Key,
+{
+    ok: T::SetStorage,
+    err: E::SetStorage,
+}
+
+impl<T, E> Clone for ResultSetStorage<T, E>
+where
+    T: Key,
+    E: Key,
+    T::SetStorage: Clone,
+    E::SetStorage: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            ok: self.ok.clone(),
+            err: self.err.clone(),
+        }
+    }
+}
+
+impl<T, E> Copy for ResultSetStorage<T, E>
+where
+    T: Key,
+    E: Key,
+    T::SetStorage: Copy,
+    E::SetStorage: Copy,
+{
+}
+
+impl<T, E> PartialEq for ResultSetStorage<T, E>
+where
+    T: Key,
+    E: Key,
+    T::SetStorage: PartialEq,
+    E::SetStorage: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ok == other.ok && self.err == other.err
+    }
+}
+
+impl<T, E> Eq for ResultSetStorage<T, E>
+where
+    T: Key,
+    E: Key,
+    T::SetStorage: Eq,
+    E::SetStorage: Eq,
+{
+}
+
+impl<T, E> SetStorage<Result<T, E>> for ResultSetStorage<T, E>
+where
+    T: Key,
+    E: Key,
+{
+    type Iter<'this>
+        = Iter<'this, T, E>
+    where
+        T: 'this,
+        E: 'this;
+    type IntoIter = IntoIter<T, E>;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            ok: T::SetStorage::empty(),
+            err: E::SetStorage::empty(),
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.ok.len() + self.err.len()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.ok.is_empty() && self.err.is_empty()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: Result<T, E>) -> bool {
+        match value {
+            Ok(value) => self.ok.insert(value),
+            Err(value) => self.err.insert(value),
+        }
+    }
+
+    #[inline]
+    fn contains(&self, value: Result<T, E>) -> bool {
+        match value {
+            Ok(value) => self.ok.contains(value),
+            Err(value) => self.err.contains(value),
+        }
+    }
+
+    #[inline]
+    fn remove(&mut self, value: Result<T, E>) -> bool {
+        match value {
+            Ok(value) => self.ok.remove(value),
+            Err(value) => self.err.remove(value),
+        }
+    }
+
+    #[inline]
+    fn contains_by(&self, value: <Result<T, E> as Key>::Ref<'_>) -> bool {
+        match value {
+            Ok(value) => self.ok.contains_by(value),
+            Err(value) => self.err.contains_by(value),
+        }
+    }
+
+    #[inline]
+    fn remove_by(&mut self, value: <Result<T, E> as Key>::Ref<'_>) -> bool {
+        match value {
+            Ok(value) => self.ok.remove_by(value),
+            Err(value) => self.err.remove_by(value),
+        }
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(Result<T, E>) -> bool,
+    {
+        self.ok.retain(|value| func(Ok(value)));
+        self.err.retain(|value| func(Err(value)));
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.ok.clear();
+        self.err.clear();
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let map: fn(_) -> _ = Ok;
+        let a = self.ok.iter().map(map);
+        let map: fn(_) -> _ = Err;
+        let b = self.err.iter().map(map);
+        ExactIter::new(a.chain(b), self.len())
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        let map: fn(_) -> _ = Ok;
+        let a = self.ok.into_iter().map(map);
+        let map: fn(_) -> _ = Err;
+        let b = self.err.into_iter().map(map);
+        ExactIter::new(a.chain(b), len)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T, E> ParallelSetStorage<Result<T, E>> for ResultSetStorage<T, E>
+where
+    T: Key + Send,
+    E: Key + Send,
+    T::SetStorage: ParallelSetStorage<T>,
+    E::SetStorage: ParallelSetStorage<E>,
+{
+    type ParIter<'this>
+        = rayon::iter::Chain<
+        rayon::iter::Map<
+            <T::SetStorage as ParallelSetStorage<T>>::ParIter<'this>,
+            fn(T) -> Result<T, E>,
+        >,
+        rayon::iter::Map<
+            <E::SetStorage as ParallelSetStorage<E>>::ParIter<'this>,
+            fn(E) -> Result<T, E>,
+        >,
+    >
+    where
+        T: 'this,
+        E: 'this;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let map: fn(_) -> _ = Ok;
+        let a = self.ok.par_iter().map(map);
+        let map: fn(_) -> _ = Err;
+        let b = self.err.par_iter().map(map);
+        a.chain(b)
+    }
+}
+
+/// The archived form of [`ResultSetStorage`].
+#[cfg(feature = "rkyv")]
+#[repr(C)]
+#[cfg_attr(
+    feature = "rkyv-validation",
+    derive(::rkyv::CheckBytes),
+    check_bytes(crate = "::rkyv::bytecheck")
+)]
+pub struct ArchivedResultSetStorage<T, E>
+where
+    T: Key,
+    E: Key,
+    T::SetStorage: ArchiveSetStorage<T>,
+    E::SetStorage: ArchiveSetStorage<E>,
+{
+    ok: <T::SetStorage as ArchiveSetStorage<T>>::Archived,
+    err: <E::SetStorage as ArchiveSetStorage<E>>::Archived,
+}
+
+#[cfg(feature = "rkyv")]
+impl<T, E> ArchiveSetStorage<Result<T, E>> for ResultSetStorage<T, E>
+where
+    T: Key,
+    E: Key,
+    T::SetStorage: ArchiveSetStorage<T>,
+    E::SetStorage: ArchiveSetStorage<E>,
+{
+    type Archived = ArchivedResultSetStorage<T, E>;
+    type Resolver = (
+        <T::SetStorage as ArchiveSetStorage<T>>::Resolver,
+        <E::SetStorage as ArchiveSetStorage<E>>::Resolver,
+    );
+
+    #[inline]
+    fn serialize<S>(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+    {
+        let ok = self.ok.serialize(serializer)?;
+        let err = self.err.serialize(serializer)?;
+        Ok((ok, err))
+    }
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, (ok, err): Self::Resolver, out: *mut Self::Archived) {
+        let (offset, field) = out_field!(out.ok);
+        self.ok.resolve(pos + offset, ok, field);
+        let (offset, field) = out_field!(out.err);
+        self.err.resolve(pos + offset, err, field);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<T, E> ArchivedSetStorage<Result<T, E>> for ArchivedResultSetStorage<T, E>
+where
+    T: Key,
+    E: Key,
+    T::SetStorage: ArchiveSetStorage<T>,
+    E::SetStorage: ArchiveSetStorage<E>,
+{
+    type Iter<'this>
+        = iter::Chain<
+        iter::Map<
+            <<T::SetStorage as ArchiveSetStorage<T>>::Archived as ArchivedSetStorage<T>>::Iter<
+                'this,
+            >,
+            fn(T) -> Result<T, E>,
+        >,
+        iter::Map<
+            <<E::SetStorage as ArchiveSetStorage<E>>::Archived as ArchivedSetStorage<E>>::Iter<
+                'this,
+            >,
+            fn(E) -> Result<T, E>,
+        >,
+    >
+    where
+        T: 'this,
+        E: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.ok.len() + self.err.len()
+    }
+
+    #[inline]
+    fn contains(&self, value: Result<T, E>) -> bool {
+        match value {
+            Ok(value) => self.ok.contains(value),
+            Err(value) => self.err.contains(value),
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let map: fn(_) -> _ = Ok;
+        let a = self.ok.iter().map(map);
+        let map: fn(_) -> _ = Err;
+        let b = self.err.iter().map(map);
+        a.chain(b)
+    }
+}
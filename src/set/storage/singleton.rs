@@ -1,6 +1,18 @@
 use core::mem;
 
+#[cfg(feature = "borsh")]
+use borsh::{io, BorshDeserialize, BorshSerialize};
+#[cfg(feature = "rayon")]
+use rayon::iter::IntoParallelIterator;
+#[cfg(feature = "rkyv")]
+use rkyv::{out_field, Archive, Archived, Fallible, Resolver, Serialize};
+
+#[cfg(feature = "rayon")]
+use crate::set::ParallelSetStorage;
 use crate::set::SetStorage;
+#[cfg(feature = "rkyv")]
+use crate::set::{ArchiveSetStorage, ArchivedSetStorage};
+use crate::Key;
 
 /// [`SetStorage`]  types that can only inhabit a single value (like `()`).
 #[repr(transparent)]
@@ -46,6 +58,22 @@ where
         mem::replace(&mut self.is_set, false)
     }
 
+    #[inline]
+    fn contains_by(&self, _: T::Ref<'_>) -> bool
+    where
+        T: Key,
+    {
+        self.is_set
+    }
+
+    #[inline]
+    fn remove_by(&mut self, _: T::Ref<'_>) -> bool
+    where
+        T: Key,
+    {
+        mem::replace(&mut self.is_set, false)
+    }
+
     #[inline]
     fn retain<F>(&mut self, mut func: F)
     where
@@ -69,3 +97,97 @@ where
         self.is_set.then_some(T::default()).into_iter()
     }
 }
+
+#[cfg(feature = "rayon")]
+impl<T> ParallelSetStorage<T> for SingletonSetStorage
+where
+    T: Default + Clone + Send,
+{
+    type ParIter<'this> = rayon::option::IntoIter<T>;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        self.is_set.then_some(T::default()).into_par_iter()
+    }
+}
+
+/// The archived form of [`SingletonSetStorage`].
+#[cfg(feature = "rkyv")]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "rkyv-validation",
+    derive(::rkyv::CheckBytes),
+    check_bytes(crate = "::rkyv::bytecheck")
+)]
+pub struct ArchivedSingletonSetStorage {
+    is_set: Archived<bool>,
+}
+
+#[cfg(feature = "rkyv")]
+impl<T> ArchiveSetStorage<T> for SingletonSetStorage
+where
+    T: Default + Clone,
+{
+    type Archived = ArchivedSingletonSetStorage;
+    type Resolver = Resolver<bool>;
+
+    #[inline]
+    fn serialize<S>(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+    {
+        Serialize::serialize(&self.is_set, serializer)
+    }
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (offset, field) = out_field!(out.is_set);
+        self.is_set.resolve(pos + offset, resolver, field);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<T> ArchivedSetStorage<T> for ArchivedSingletonSetStorage
+where
+    T: Default,
+{
+    type Iter<'this> = core::option::IntoIter<T>;
+
+    #[inline]
+    fn len(&self) -> usize {
+        usize::from(self.is_set)
+    }
+
+    #[inline]
+    fn contains(&self, _: T) -> bool {
+        self.is_set
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        self.is_set.then(T::default).into_iter()
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl BorshSerialize for SingletonSetStorage {
+    #[inline]
+    fn serialize<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        BorshSerialize::serialize(&self.is_set, writer)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl BorshDeserialize for SingletonSetStorage {
+    #[inline]
+    fn deserialize_reader<R>(reader: &mut R) -> io::Result<Self>
+    where
+        R: io::Read,
+    {
+        let is_set = bool::deserialize_reader(reader)?;
+        Ok(Self { is_set })
+    }
+}
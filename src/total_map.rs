@@ -0,0 +1,551 @@
+//! Contains the fixed [`TotalMap`] implementation.
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter;
+use core::ops::{Index, IndexMut};
+use core::slice;
+
+use crate::{Finite, Map};
+
+/// The iterator produced by [`TotalMap::keys`].
+pub type Keys<K> = <<K as Finite>::Array<K> as IntoIterator>::IntoIter;
+
+/// The iterator produced by [`TotalMap::iter`].
+pub type Iter<'a, K, V> = iter::Zip<Keys<K>, slice::Iter<'a, V>>;
+
+/// The iterator produced by [`TotalMap::values`].
+pub type Values<'a, V> = slice::Iter<'a, V>;
+
+/// The iterator produced by [`TotalMap::iter_mut`].
+pub type IterMut<'a, K, V> = iter::Zip<Keys<K>, slice::IterMut<'a, V>>;
+
+/// The iterator produced by [`TotalMap::values_mut`].
+pub type ValuesMut<'a, V> = slice::IterMut<'a, V>;
+
+/// The iterator produced by [`TotalMap::into_iter`].
+pub type IntoIter<K, V> = iter::Zip<Keys<K>, <<K as Finite>::Array<V> as IntoIterator>::IntoIter>;
+
+/// A fixed map which holds a value for every inhabitant of a [`Finite`] key.
+///
+/// Since every key is always present, lookups and indexing can't fail.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, TotalMap};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     North,
+///     South,
+///     East,
+///     West,
+/// }
+///
+/// let mut map = TotalMap::<MyKey, u32>::default();
+/// map[MyKey::North] += 1;
+/// *map.get_mut(MyKey::West) = 4;
+///
+/// assert_eq!(map[MyKey::North], 1);
+/// assert_eq!(*map.get(MyKey::South), 0);
+/// assert!(map.values().copied().eq([1, 0, 0, 4]));
+/// ```
+pub struct TotalMap<K, V>
+where
+    K: Finite,
+{
+    data: K::Array<V>,
+}
+
+impl<K, V> TotalMap<K, V>
+where
+    K: Finite,
+{
+    /// Creates a [`TotalMap`] where the value of every key is produced by
+    /// calling `f` with the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = TotalMap::from_fn(|key| key == MyKey::Second);
+    /// assert!(!map[MyKey::First]);
+    /// assert!(map[MyKey::Second]);
+    /// ```
+    #[inline]
+    pub fn from_fn<F>(f: F) -> TotalMap<K, V>
+    where
+        F: FnMut(K) -> V,
+    {
+        TotalMap {
+            data: K::array_from_fn(f),
+        }
+    }
+
+    /// Returns the number of elements in the map, which is the number of
+    /// inhabitants of the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = TotalMap::<MyKey, u32>::default();
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.as_ref().len()
+    }
+
+    /// Returns true if the key has no inhabitants, so the map can't contain
+    /// any elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    /// }
+    ///
+    /// let map = TotalMap::<MyKey, u32>::default();
+    /// assert!(!map.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.as_ref().is_empty()
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = TotalMap::<MyKey, u32>::from_fn(|key| key as u32);
+    /// assert_eq!(*map.get(MyKey::Second), 1);
+    /// ```
+    #[inline]
+    pub fn get(&self, key: K) -> &V {
+        &self.data.as_ref()[key.index()]
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TotalMap::<MyKey, u32>::default();
+    /// *map.get_mut(MyKey::Second) = 2;
+    /// assert_eq!(*map.get(MyKey::Second), 2);
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, key: K) -> &mut V {
+        &mut self.data.as_mut()[key.index()]
+    }
+
+    /// Replaces the value corresponding to the key, returning the old value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TotalMap::<MyKey, u32>::default();
+    /// assert_eq!(map.insert(MyKey::First, 1), 0);
+    /// assert_eq!(map.insert(MyKey::First, 2), 1);
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> V {
+        core::mem::replace(self.get_mut(key), value)
+    }
+
+    /// An iterator visiting all key-value pairs in index order. The iterator
+    /// element type is `(K, &'a V)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = TotalMap::<MyKey, u32>::from_fn(|key| key as u32);
+    /// assert!(map.iter().eq([(MyKey::First, &0), (MyKey::Second, &1)]));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.keys().zip(self.data.as_ref())
+    }
+
+    /// An iterator visiting all keys in index order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = TotalMap::<MyKey, u32>::default();
+    /// assert!(map.keys().eq([MyKey::First, MyKey::Second]));
+    /// ```
+    #[inline]
+    pub fn keys(&self) -> Keys<K> {
+        K::array_from_fn(|key| key).into_iter()
+    }
+
+    /// An iterator visiting all values in index order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = TotalMap::<MyKey, u32>::from_fn(|key| key as u32 + 1);
+    /// assert!(map.values().copied().eq([1, 2]));
+    /// ```
+    #[inline]
+    pub fn values(&self) -> Values<'_, V> {
+        self.data.as_ref().iter()
+    }
+
+    /// An iterator visiting all key-value pairs in index order, with mutable
+    /// references to the values. The iterator element type is `(K, &'a mut
+    /// V)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TotalMap::<MyKey, u32>::default();
+    ///
+    /// for (key, value) in map.iter_mut() {
+    ///     *value = key as u32 * 10;
+    /// }
+    ///
+    /// assert!(map.values().copied().eq([0, 10]));
+    /// ```
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.keys().zip(self.data.as_mut())
+    }
+
+    /// An iterator visiting all values mutably in index order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TotalMap::<MyKey, u32>::default();
+    ///
+    /// for value in map.values_mut() {
+    ///     *value += 2;
+    /// }
+    ///
+    /// assert!(map.values().copied().eq([2, 2]));
+    /// ```
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, V> {
+        self.data.as_mut().iter_mut()
+    }
+}
+
+/// The [`Default`] implementation for a [`TotalMap`] produces a map where every
+/// value is [`Default::default`].
+impl<K, V> Default for TotalMap<K, V>
+where
+    K: Finite,
+    V: Default,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::from_fn(|_| V::default())
+    }
+}
+
+impl<K, V> Clone for TotalMap<K, V>
+where
+    K: Finite,
+    V: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        let data = self.data.as_ref();
+        Self::from_fn(|key| data[key.index()].clone())
+    }
+}
+
+/// The [`Debug`][fmt::Debug] implementation for a [`TotalMap`].
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, TotalMap};
+///
+/// #[derive(Debug, Clone, Copy, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let map = TotalMap::<MyKey, u32>::default();
+/// assert_eq!(format!("{:?}", map), "{First: 0, Second: 0}");
+/// ```
+impl<K, V> fmt::Debug for TotalMap<K, V>
+where
+    K: Finite + fmt::Debug,
+    V: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> PartialEq for TotalMap<K, V>
+where
+    K: Finite,
+    V: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.data.as_ref() == other.data.as_ref()
+    }
+}
+
+impl<K, V> Eq for TotalMap<K, V>
+where
+    K: Finite,
+    V: Eq,
+{
+}
+
+impl<K, V> Hash for TotalMap<K, V>
+where
+    K: Finite,
+    V: Hash,
+{
+    #[inline]
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.data.as_ref().hash(state);
+    }
+}
+
+/// Total maps are ordered by their values in key order.
+impl<K, V> PartialOrd for TotalMap<K, V>
+where
+    K: Finite,
+    V: PartialOrd,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.data.as_ref().partial_cmp(other.data.as_ref())
+    }
+}
+
+impl<K, V> Ord for TotalMap<K, V>
+where
+    K: Finite,
+    V: Ord,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.data.as_ref().cmp(other.data.as_ref())
+    }
+}
+
+/// [`Index`] implementation for a [`TotalMap`], which can't fail since every
+/// key is present.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, TotalMap};
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let mut map = TotalMap::<MyKey, u32>::default();
+/// map[MyKey::Second] = 2;
+///
+/// assert_eq!(map[MyKey::First], 0);
+/// assert_eq!(map[MyKey::Second], 2);
+/// ```
+impl<K, V> Index<K> for TotalMap<K, V>
+where
+    K: Finite,
+{
+    type Output = V;
+
+    #[inline]
+    fn index(&self, key: K) -> &V {
+        self.get(key)
+    }
+}
+
+impl<K, V> IndexMut<K> for TotalMap<K, V>
+where
+    K: Finite,
+{
+    #[inline]
+    fn index_mut(&mut self, key: K) -> &mut V {
+        self.get_mut(key)
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a TotalMap<K, V>
+where
+    K: Finite,
+{
+    type Item = (K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut TotalMap<K, V>
+where
+    K: Finite,
+{
+    type Item = (K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Produce an owning iterator visiting all key-value pairs of the
+/// [`TotalMap`] in index order.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, TotalMap};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let map = TotalMap::<MyKey, u32>::from_fn(|key| key as u32);
+/// assert!(map.into_iter().eq([(MyKey::First, 0), (MyKey::Second, 1)]));
+/// ```
+impl<K, V> IntoIterator for TotalMap<K, V>
+where
+    K: Finite,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        K::array_from_fn(|key| key).into_iter().zip(self.data)
+    }
+}
+
+/// Convert a [`TotalMap`] into a [`Map`] where every key is present.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map, TotalMap};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let map = Map::from(TotalMap::<MyKey, u32>::from_fn(|key| key as u32));
+/// assert_eq!(map.get(MyKey::Second), Some(&1));
+/// assert_eq!(map.len(), 2);
+/// ```
+impl<K, V> From<TotalMap<K, V>> for Map<K, V>
+where
+    K: Finite,
+{
+    #[inline]
+    fn from(map: TotalMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
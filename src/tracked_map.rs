@@ -0,0 +1,930 @@
+//! Contains the fixed [`TrackedMap`] implementation.
+
+use core::fmt;
+use core::mem;
+
+use crate::map::{Iter, Keys, MapStorage, Values};
+use crate::{map, Key, Map, Set};
+
+/// A [`Map`] which records the keys that have been modified in a [`Set`].
+///
+/// Modifications are recorded by [`insert`][TrackedMap::insert],
+/// [`remove`][TrackedMap::remove], [`get_mut`][TrackedMap::get_mut],
+/// [`entry`][TrackedMap::entry], [`retain`][TrackedMap::retain] and
+/// [`clear`][TrackedMap::clear], and are drained with
+/// [`take_dirty`][TrackedMap::take_dirty]. Keys using the `#[key(bitset)]`
+/// attribute record modifications in a single integer.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Set, TrackedMap};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// #[key(bitset)]
+/// enum Light {
+///     Ambient,
+///     Sun,
+///     Torch,
+/// }
+///
+/// let mut lights = TrackedMap::new();
+/// lights.insert(Light::Ambient, 0.2);
+/// lights.insert(Light::Sun, 1.0);
+/// assert_eq!(lights.take_dirty(), Set::from([Light::Ambient, Light::Sun]));
+///
+/// if let Some(sun) = lights.get_mut(Light::Sun) {
+///     *sun *= 0.5;
+/// }
+///
+/// assert_eq!(lights.take_dirty(), Set::from([Light::Sun]));
+/// assert!(lights.take_dirty().is_empty());
+/// ```
+pub struct TrackedMap<K, V>
+where
+    K: Key,
+{
+    map: Map<K, V>,
+    dirty: Set<K>,
+}
+
+impl<K, V> TrackedMap<K, V>
+where
+    K: Key,
+{
+    /// Creates an empty [`TrackedMap`] with no modified keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TrackedMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let map = TrackedMap::<MyKey, u32>::new();
+    /// assert!(map.is_empty());
+    /// assert!(map.dirty().is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> TrackedMap<K, V> {
+        TrackedMap {
+            map: Map::new(),
+            dirty: Set::new(),
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TrackedMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// map.insert(MyKey::First, 42);
+    ///
+    /// assert_eq!(map.get(MyKey::First), Some(&42));
+    /// assert_eq!(map.get(MyKey::Second), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, key: K) -> Option<&V> {
+        self.map.get(key)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TrackedMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// map.insert(MyKey::First, 42);
+    ///
+    /// assert!(map.contains_key(MyKey::First));
+    /// assert!(!map.contains_key(MyKey::Second));
+    /// ```
+    #[inline]
+    pub fn contains_key(&self, key: K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Returns the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TrackedMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// map.insert(MyKey::First, 42);
+    ///
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the map contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TrackedMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// assert!(map.is_empty());
+    ///
+    /// map.insert(MyKey::First, 42);
+    /// assert!(!map.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TrackedMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// map.insert(MyKey::Second, 2);
+    ///
+    /// assert!(map.iter().eq([(MyKey::Second, &2)]));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.map.iter()
+    }
+
+    /// An iterator visiting all keys in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TrackedMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// map.insert(MyKey::Second, 2);
+    ///
+    /// assert!(map.keys().eq([MyKey::Second]));
+    /// ```
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        self.map.keys()
+    }
+
+    /// An iterator visiting all values in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TrackedMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// map.insert(MyKey::Second, 2);
+    ///
+    /// assert!(map.values().copied().eq([2]));
+    /// ```
+    #[inline]
+    pub fn values(&self) -> Values<'_, K, V> {
+        self.map.values()
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// The key is recorded as modified if it is present, whether or not the
+    /// value is actually changed through the reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set, TrackedMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// map.insert(MyKey::First, 1);
+    /// map.take_dirty();
+    ///
+    /// *map.get_mut(MyKey::First).unwrap() += 1;
+    /// assert!(map.get_mut(MyKey::Second).is_none());
+    ///
+    /// assert_eq!(map.take_dirty(), Set::from([MyKey::First]));
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        let value = self.map.get_mut(key.clone())?;
+        self.dirty.insert(key);
+        Some(value)
+    }
+
+    /// Inserts a key-value pair into the map, recording the key as modified
+    /// and returning the previous value if one was present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set, TrackedMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// assert_eq!(map.insert(MyKey::First, 1), None);
+    /// assert_eq!(map.insert(MyKey::First, 2), Some(1));
+    ///
+    /// assert_eq!(map.take_dirty(), Set::from([MyKey::First]));
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.dirty.insert(key.clone());
+        self.map.insert(key, value)
+    }
+
+    /// Removes a key from the map, returning the value at the key if it was
+    /// present.
+    ///
+    /// The key is only recorded as modified if it was present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set, TrackedMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// map.insert(MyKey::First, 1);
+    /// map.take_dirty();
+    ///
+    /// assert_eq!(map.remove(MyKey::First), Some(1));
+    /// assert_eq!(map.remove(MyKey::Second), None);
+    ///
+    /// assert_eq!(map.take_dirty(), Set::from([MyKey::First]));
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, key: K) -> Option<V> {
+        let value = self.map.remove(key.clone())?;
+        self.dirty.insert(key);
+        Some(value)
+    }
+
+    /// Gets the given key's corresponding [`Entry`] in the map for in-place
+    /// manipulation.
+    ///
+    /// The key is recorded as modified once a value is inserted or removed
+    /// through the entry, or when a mutable reference to its value is handed
+    /// out through [`OccupiedEntry`]. Looking at an entry without changing it
+    /// leaves the key unrecorded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set, TrackedMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// *map.entry(MyKey::Second).or_insert(0) += 1;
+    ///
+    /// assert_eq!(map.get(MyKey::Second), Some(&1));
+    /// assert_eq!(map.take_dirty(), Set::from([MyKey::Second]));
+    ///
+    /// map.entry(MyKey::Second).or_insert(0);
+    /// assert!(map.dirty().is_empty());
+    /// ```
+    #[inline]
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        let dirty = &mut self.dirty;
+
+        match self.map.entry(key) {
+            map::Entry::Occupied(entry) => Entry::Occupied(OccupiedEntry { entry, dirty }),
+            map::Entry::Vacant(entry) => Entry::Vacant(VacantEntry { entry, dirty }),
+        }
+    }
+
+    /// Retains only the elements specified by the predicate, recording every
+    /// removed key as modified.
+    ///
+    /// The predicate receives a mutable reference to every value, but only
+    /// removed keys are recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set, TrackedMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// map.insert(MyKey::First, 1);
+    /// map.insert(MyKey::Second, 2);
+    /// map.take_dirty();
+    ///
+    /// map.retain(|_, value| *value > 1);
+    ///
+    /// assert_eq!(map.take_dirty(), Set::from([MyKey::First]));
+    /// ```
+    #[inline]
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        let dirty = &mut self.dirty;
+
+        self.map.retain(|key, value| {
+            if f(key.clone(), value) {
+                true
+            } else {
+                dirty.insert(key);
+                false
+            }
+        });
+    }
+
+    /// Clears the map, recording every removed key as modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set, TrackedMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// map.insert(MyKey::Second, 2);
+    /// map.take_dirty();
+    ///
+    /// map.clear();
+    ///
+    /// assert!(map.is_empty());
+    /// assert_eq!(map.take_dirty(), Set::from([MyKey::Second]));
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.retain(|_, _| false);
+    }
+
+    /// Returns `true` if the key has been modified since the last call to
+    /// [`take_dirty`][TrackedMap::take_dirty].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TrackedMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// map.insert(MyKey::First, 1);
+    ///
+    /// assert!(map.is_dirty(MyKey::First));
+    /// assert!(!map.is_dirty(MyKey::Second));
+    /// ```
+    #[inline]
+    pub fn is_dirty(&self, key: K) -> bool {
+        self.dirty.contains(key)
+    }
+
+    /// Returns the keys which have been modified since the last call to
+    /// [`take_dirty`][TrackedMap::take_dirty].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set, TrackedMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// map.insert(MyKey::Second, 2);
+    ///
+    /// assert_eq!(map.dirty(), &Set::from([MyKey::Second]));
+    /// ```
+    #[inline]
+    pub fn dirty(&self) -> &Set<K> {
+        &self.dirty
+    }
+
+    /// Returns the keys which have been modified, and stops recording them as
+    /// modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set, TrackedMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// map.insert(MyKey::Second, 2);
+    ///
+    /// assert_eq!(map.take_dirty(), Set::from([MyKey::Second]));
+    /// assert!(map.take_dirty().is_empty());
+    /// ```
+    #[inline]
+    pub fn take_dirty(&mut self) -> Set<K> {
+        mem::take(&mut self.dirty)
+    }
+
+    /// Returns a reference to the underlying [`Map`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TrackedMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// map.insert(MyKey::First, 1);
+    ///
+    /// assert_eq!(map.as_map().get(MyKey::First), Some(&1));
+    /// ```
+    #[inline]
+    pub fn as_map(&self) -> &Map<K, V> {
+        &self.map
+    }
+}
+
+impl<K, V> Default for TrackedMap<K, V>
+where
+    K: Key,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Clone for TrackedMap<K, V>
+where
+    K: Key,
+    K::MapStorage<V>: Clone,
+    K::SetStorage: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+            dirty: self.dirty.clone(),
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for TrackedMap<K, V>
+where
+    K: Key + fmt::Debug,
+    V: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrackedMap")
+            .field("map", &self.map)
+            .field("dirty", &self.dirty)
+            .finish()
+    }
+}
+
+/// Construct a [`TrackedMap`] from a [`Map`], where no key is recorded as
+/// modified.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map, TrackedMap};
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(MyKey::First, 1);
+///
+/// let map = TrackedMap::from(map);
+/// assert_eq!(map.get(MyKey::First), Some(&1));
+/// assert!(map.dirty().is_empty());
+/// ```
+impl<K, V> From<Map<K, V>> for TrackedMap<K, V>
+where
+    K: Key,
+{
+    #[inline]
+    fn from(map: Map<K, V>) -> Self {
+        TrackedMap {
+            map,
+            dirty: Set::new(),
+        }
+    }
+}
+
+/// Convert a [`TrackedMap`] into its underlying [`Map`], discarding the
+/// recorded modifications.
+impl<K, V> From<TrackedMap<K, V>> for Map<K, V>
+where
+    K: Key,
+{
+    #[inline]
+    fn from(map: TrackedMap<K, V>) -> Self {
+        map.map
+    }
+}
+
+/// A view into a single entry in a [`TrackedMap`], which may either be vacant
+/// or occupied.
+///
+/// This enum is constructed from the [`entry`][TrackedMap::entry] method on
+/// [`TrackedMap`]. Its variants implement the [`OccupiedEntry`] and
+/// [`VacantEntry`] traits, which record the key as modified once the entry is
+/// changed.
+///
+/// [`OccupiedEntry`]: crate::map::OccupiedEntry
+/// [`VacantEntry`]: crate::map::VacantEntry
+pub enum Entry<'a, K, V>
+where
+    K: Key,
+    V: 'a,
+{
+    /// An occupied entry.
+    Occupied(OccupiedEntry<'a, K, V>),
+    /// A vacant entry.
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: Key,
+    V: 'a,
+{
+    /// Ensures a value is in the entry by inserting the default if empty,
+    /// and returns a mutable reference to the value in the entry.
+    ///
+    /// Only inserting the default records the key as modified, so changes
+    /// made to an existing value through the returned reference aren't
+    /// recorded. Use [`and_modify`][Entry::and_modify] for those.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set, TrackedMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    ///
+    /// assert_eq!(*map.entry(MyKey::First).or_insert(3), 3);
+    /// assert_eq!(map.take_dirty(), Set::from([MyKey::First]));
+    ///
+    /// assert_eq!(*map.entry(MyKey::First).or_insert(4), 3);
+    /// assert!(map.dirty().is_empty());
+    /// ```
+    #[inline]
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => map::OccupiedEntry::into_mut(entry.entry),
+            Entry::Vacant(entry) => map::VacantEntry::insert(entry, default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default
+    /// function if empty, and returns a mutable reference to the value in the
+    /// entry.
+    ///
+    /// Like [`or_insert`][Entry::or_insert], only inserting records the key
+    /// as modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TrackedMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    ///
+    /// map.entry(MyKey::First).or_insert_with(|| 3);
+    /// assert_eq!(map.get(MyKey::First), Some(&3));
+    /// assert!(map.is_dirty(MyKey::First));
+    /// ```
+    #[inline]
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => map::OccupiedEntry::into_mut(entry.entry),
+            Entry::Vacant(entry) => map::VacantEntry::insert(entry, default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result of
+    /// the default function called with the key, and returns a mutable
+    /// reference to the value in the entry.
+    ///
+    /// Like [`or_insert`][Entry::or_insert], only inserting records the key
+    /// as modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TrackedMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    ///
+    /// map.entry(MyKey::Second).or_insert_with_key(|key| key as u32);
+    /// assert_eq!(map.get(MyKey::Second), Some(&1));
+    /// ```
+    #[inline]
+    pub fn or_insert_with_key<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce(K) -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => map::OccupiedEntry::into_mut(entry.entry),
+            Entry::Vacant(entry) => {
+                let value = default(map::VacantEntry::key(&entry));
+                map::VacantEntry::insert(entry, value)
+            }
+        }
+    }
+
+    /// Returns a copy of this entry's key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TrackedMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::<MyKey, u32>::new();
+    /// assert_eq!(map.entry(MyKey::First).key(), MyKey::First);
+    /// assert!(map.dirty().is_empty());
+    /// ```
+    #[inline]
+    pub fn key(&self) -> K {
+        match self {
+            Entry::Occupied(entry) => map::OccupiedEntry::key(entry),
+            Entry::Vacant(entry) => map::VacantEntry::key(entry),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map, recording the key as modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set, TrackedMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::new();
+    /// map.insert(MyKey::First, 42);
+    /// map.take_dirty();
+    ///
+    /// map.entry(MyKey::First).and_modify(|e| *e += 1).or_insert(0);
+    /// assert_eq!(map.get(MyKey::First), Some(&43));
+    /// assert_eq!(map.take_dirty(), Set::from([MyKey::First]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(map::OccupiedEntry::get_mut(&mut entry));
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the default value if
+    /// empty, and returns a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TrackedMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = TrackedMap::<MyKey, u32>::new();
+    ///
+    /// map.entry(MyKey::First).or_default();
+    /// assert_eq!(map.get(MyKey::First), Some(&0));
+    /// ```
+    #[inline]
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+}
+
+/// A view into an occupied entry in a [`TrackedMap`]. It is part of the
+/// [`Entry`] enum.
+///
+/// The key is recorded as modified when the value is mutably borrowed,
+/// replaced or removed through the entry.
+pub struct OccupiedEntry<'a, K, V>
+where
+    K: Key,
+    V: 'a,
+{
+    entry: <K::MapStorage<V> as MapStorage<K, V>>::Occupied<'a>,
+    dirty: &'a mut Set<K>,
+}
+
+impl<'a, K, V> map::OccupiedEntry<'a, K, V> for OccupiedEntry<'a, K, V>
+where
+    K: Key,
+    V: 'a,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.entry.key()
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.entry.get()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.dirty.insert(self.entry.key());
+        self.entry.get_mut()
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        self.dirty.insert(self.entry.key());
+        self.entry.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.dirty.insert(self.entry.key());
+        self.entry.insert(value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        self.dirty.insert(self.entry.key());
+        self.entry.remove()
+    }
+}
+
+/// A view into a vacant entry in a [`TrackedMap`]. It is part of the
+/// [`Entry`] enum.
+///
+/// The key is recorded as modified when a value is inserted.
+pub struct VacantEntry<'a, K, V>
+where
+    K: Key,
+    V: 'a,
+{
+    entry: <K::MapStorage<V> as MapStorage<K, V>>::Vacant<'a>,
+    dirty: &'a mut Set<K>,
+}
+
+impl<'a, K, V> map::VacantEntry<'a, K, V> for VacantEntry<'a, K, V>
+where
+    K: Key,
+    V: 'a,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.entry.key()
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        self.dirty.insert(self.entry.key());
+        self.entry.insert(value)
+    }
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(arbitrary)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(arbitrary)]
+enum Event {
+    Start,
+    Part(Part),
+    Flag(bool),
+    Maybe(Option<Part>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(arbitrary)]
+enum Scoped<T> {
+    Global,
+    Local(T),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(arbitrary, finite)]
+enum Slot {
+    Start,
+    Part(Part),
+    Flag(bool),
+}
+
+fn bytes() -> Vec<u8> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+
+    (0..1024)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 32) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn unit_keys() {
+    let bytes = bytes();
+    let mut u = Unstructured::new(&bytes);
+    let mut seen = Set::new();
+
+    while !u.is_empty() {
+        seen.insert(Part::arbitrary(&mut u).unwrap());
+    }
+
+    assert!(seen.iter().eq([Part::One, Part::Two, Part::Three]));
+}
+
+#[test]
+fn composite_keys() {
+    let bytes = bytes();
+    let mut u = Unstructured::new(&bytes);
+    let mut seen = Set::new();
+
+    while !u.is_empty() {
+        seen.insert(Event::arbitrary(&mut u).unwrap());
+    }
+
+    assert!(seen.contains(Event::Start));
+    assert!(seen.contains(Event::Flag(true)));
+    assert!(seen.contains(Event::Flag(false)));
+    assert!(seen.contains(Event::Maybe(None)));
+    assert!(seen.iter().any(|event| matches!(event, Event::Part(..))));
+}
+
+#[test]
+fn finite_keys() {
+    let bytes = bytes();
+    let mut u = Unstructured::new(&bytes);
+    let mut counts = Map::<Slot, usize>::new();
+
+    while !u.is_empty() {
+        *counts.entry(Slot::arbitrary(&mut u).unwrap()).or_default() += 1;
+    }
+
+    assert_eq!(counts.len(), 6);
+
+    for (key, &count) in &counts {
+        assert!((100..250).contains(&count), "{key:?} picked {count} times");
+    }
+}
+
+#[test]
+fn generic_keys() {
+    let bytes = bytes();
+    let mut u = Unstructured::new(&bytes);
+    let mut seen = Set::new();
+
+    while !u.is_empty() {
+        seen.insert(Scoped::<Part>::arbitrary(&mut u).unwrap());
+    }
+
+    assert_eq!(seen.len(), 4);
+}
+
+#[test]
+fn empty_input() {
+    let mut u = Unstructured::new(&[]);
+    assert_eq!(Part::arbitrary(&mut u).unwrap(), Part::One);
+
+    let mut u = Unstructured::new(&[]);
+    let map = Map::<Event, u32>::arbitrary(&mut u).unwrap();
+    assert!(map.is_empty());
+}
+
+#[test]
+fn maps_and_sets() {
+    let bytes = bytes();
+
+    let map = Map::<Event, u32>::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+    assert!(!map.is_empty());
+    assert_eq!(map.len(), map.iter().count());
+
+    for (key, value) in &map {
+        assert_eq!(map.get(key), Some(value));
+    }
+
+    let set = Set::<Scoped<Part>>::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+    assert!(!set.is_empty());
+    assert!(set.len() <= 4);
+    assert_eq!(set.len(), set.iter().count());
+}
@@ -0,0 +1,91 @@
+use std::sync::atomic::Ordering;
+use std::thread;
+
+use fixed_map::{AtomicCounters, AtomicSet, Key, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(bitset)]
+enum Flag {
+    One,
+    Two,
+    Three,
+    Four,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Event {
+    Start,
+    Stop,
+}
+
+#[test]
+fn set() {
+    let set = AtomicSet::new();
+    assert!(set.insert(Flag::One, Ordering::Relaxed));
+    assert!(set.insert(Flag::Three, Ordering::Relaxed));
+    assert!(!set.insert(Flag::Three, Ordering::Relaxed));
+
+    assert!(set.contains(Flag::Three, Ordering::Relaxed));
+    assert!(!set.contains(Flag::Two, Ordering::Relaxed));
+
+    assert!(set.remove(Flag::One, Ordering::Relaxed));
+    assert!(!set.remove(Flag::One, Ordering::Relaxed));
+
+    let old = set.fetch_union(Set::from([Flag::Two, Flag::Four]), Ordering::Relaxed);
+    assert_eq!(old, Set::from([Flag::Three]));
+
+    let old = set.fetch_intersection(Set::from([Flag::Two, Flag::Three]), Ordering::Relaxed);
+    assert_eq!(old, Set::from([Flag::Two, Flag::Three, Flag::Four]));
+    assert_eq!(
+        set.load(Ordering::Relaxed),
+        Set::from([Flag::Two, Flag::Three])
+    );
+
+    assert_eq!(format!("{set:?}"), "{Two, Three}");
+    assert_eq!(
+        AtomicSet::<Flag>::from_raw(0b0110).into_inner(),
+        set.into_inner()
+    );
+}
+
+#[test]
+fn set_threads() {
+    let set = AtomicSet::new();
+
+    thread::scope(|s| {
+        for flag in [Flag::One, Flag::Two, Flag::Three, Flag::Four] {
+            let set = &set;
+            s.spawn(move || set.insert(flag, Ordering::Relaxed));
+        }
+    });
+
+    assert_eq!(set.load(Ordering::Relaxed).len(), 4);
+}
+
+#[test]
+fn counters() {
+    let counters = AtomicCounters::new();
+
+    thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(|| {
+                for _ in 0..100 {
+                    counters.fetch_add(Event::Start, 1, Ordering::Relaxed);
+                }
+
+                counters.fetch_add(Event::Stop, 1, Ordering::Relaxed);
+            });
+        }
+    });
+
+    assert_eq!(counters.load(Event::Start, Ordering::Relaxed), 800);
+    assert_eq!(counters.fetch_sub(Event::Stop, 3, Ordering::Relaxed), 8);
+    assert_eq!(format!("{counters:?}"), "{Start: 800, Stop: 5}");
+
+    let taken = counters.take(Ordering::Relaxed);
+    assert!(taken.values().copied().eq([800, 5]));
+    assert!(counters
+        .snapshot(Ordering::Relaxed)
+        .values()
+        .all(|&count| count == 0));
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "hashbrown")]
+
+use std::rc::Rc;
+
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, PartialEq, Key)]
+#[key(borrow)]
+enum MyKey {
+    Name(String),
+    Scoped(Option<Box<str>>),
+    Composite(Part),
+    Anonymous,
+}
+
+#[derive(Debug, Clone, PartialEq, Key)]
+#[key(borrow)]
+enum Scoped<T> {
+    Global,
+    Local(T),
+}
+
+#[test]
+fn map() {
+    let mut map = Map::new();
+    map.insert(MyKey::Name(String::from("a")), 1);
+    map.insert(MyKey::Scoped(Some("b".into())), 2);
+    map.insert(MyKey::Scoped(None), 3);
+    map.insert(MyKey::Composite(Part::Two), 4);
+
+    assert!(map.contains_key_by(MyKeyRef::Name("a")));
+    assert!(!map.contains_key_by(MyKeyRef::Name("b")));
+    assert!(!map.contains_key_by(MyKeyRef::Anonymous));
+
+    assert_eq!(map.get_by(MyKeyRef::Name("a")), Some(&1));
+    assert_eq!(map.get_by(MyKeyRef::Scoped(Some("b"))), Some(&2));
+    assert_eq!(map.get_by(MyKeyRef::Scoped(None)), Some(&3));
+    assert_eq!(map.get_by(MyKeyRef::Composite(Part::One)), None);
+    assert_eq!(map.get_by(MyKeyRef::Composite(Part::Two)), Some(&4));
+
+    *map.get_mut_by(MyKeyRef::Name("a")).unwrap() += 10;
+    assert_eq!(map.get(MyKey::Name(String::from("a"))), Some(&11));
+
+    assert_eq!(map.remove_by(MyKeyRef::Scoped(Some("b"))), Some(2));
+    assert_eq!(map.remove_by(MyKeyRef::Scoped(Some("b"))), None);
+    assert_eq!(map.len(), 3);
+
+    *map.entry(MyKey::Anonymous).or_default() += 5;
+    *map.entry(MyKey::Name(String::from("c"))).or_default() += 6;
+    assert_eq!(map.get_by(MyKeyRef::Anonymous), Some(&5));
+    assert_eq!(map.get_by(MyKeyRef::Name("c")), Some(&6));
+}
+
+#[test]
+fn set() {
+    let mut set = Set::new();
+    set.insert(MyKey::Name(String::from("a")));
+    set.insert(MyKey::Anonymous);
+
+    assert!(set.contains_by(MyKeyRef::Name("a")));
+    assert!(!set.contains_by(MyKeyRef::Name("b")));
+    assert!(set.contains_by(MyKeyRef::Anonymous));
+
+    assert!(set.remove_by(MyKeyRef::Name("a")));
+    assert!(!set.remove_by(MyKeyRef::Name("a")));
+    assert!(set.remove_by(MyKeyRef::Anonymous));
+    assert!(set.is_empty());
+}
+
+#[test]
+fn strings() {
+    let mut map = Map::new();
+    map.insert(Rc::<str>::from("a"), 1);
+    assert_eq!(map.get_by("a"), Some(&1));
+    assert_eq!(map.remove_by("a"), Some(1));
+
+    let mut map = Map::new();
+    map.insert((String::from("a"), Part::One), 1);
+    assert_eq!(map.get_by(("a", Part::One)), Some(&1));
+    assert_eq!(map.get_by(("a", Part::Two)), None);
+
+    let mut set = Set::new();
+    set.insert(Ok::<_, Part>(String::from("a")));
+    assert!(set.contains_by(Ok("a")));
+    assert!(!set.contains_by(Err(Part::One)));
+}
+
+#[test]
+fn generic() {
+    let mut map = Map::new();
+    map.insert(Scoped::Local(String::from("a")), 1);
+    map.insert(Scoped::Global, 2);
+
+    assert_eq!(map.get_by(ScopedRef::Local("a")), Some(&1));
+    assert_eq!(map.get_by(ScopedRef::Local("b")), None);
+    assert_eq!(map.get_by(ScopedRef::Global), Some(&2));
+}
@@ -0,0 +1,213 @@
+#![cfg(all(feature = "borsh", feature = "std"))]
+
+use core::cmp::Ordering;
+
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(compact)]
+enum Compact {
+    First,
+    Second,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(bitset)]
+enum Bits {
+    First,
+    Second,
+    Third,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(bitset)]
+enum Wide {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+}
+
+// Uses the names of the writer and reader parameters in the generated storage.
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(bitset)]
+enum Width<const W: usize, const R: usize> {
+    Small,
+    Large,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Many {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+}
+
+#[test]
+fn map() {
+    let mut map: Map<Part, u32> = Map::new();
+    map.insert(Part::One, 1);
+    map.insert(Part::Three, 3);
+
+    let bytes = borsh::to_vec(&map).unwrap();
+    assert_eq!(bytes, [0b101, 1, 0, 0, 0, 3, 0, 0, 0]);
+
+    let decoded: Map<Part, u32> = borsh::from_slice(&bytes).unwrap();
+    assert_eq!(decoded, map);
+
+    let empty = borsh::to_vec(&Map::<Part, u32>::new()).unwrap();
+    assert_eq!(empty, [0]);
+    assert!(borsh::from_slice::<Map<Part, u32>>(&empty)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn compact_map() {
+    let mut map: Map<Compact, String> = Map::new();
+    map.insert(Compact::Second, String::from("second"));
+
+    let bytes = borsh::to_vec(&map).unwrap();
+    assert_eq!(&bytes[..5], &[0b10, 6, 0, 0, 0]);
+
+    let decoded: Map<Compact, String> = borsh::from_slice(&bytes).unwrap();
+    assert_eq!(decoded, map);
+}
+
+#[test]
+fn generic_names() {
+    let mut set: Set<Width<1, 2>> = Set::new();
+    set.insert(Width::Large);
+
+    let bytes = borsh::to_vec(&set).unwrap();
+    assert_eq!(bytes, [0b10]);
+    assert_eq!(borsh::from_slice::<Set<Width<1, 2>>>(&bytes).unwrap(), set);
+}
+
+#[test]
+fn wide_bitmap() {
+    let mut map: Map<Many, u8> = Map::new();
+    map.insert(Many::A, 1);
+    map.insert(Many::I, 9);
+
+    let bytes = borsh::to_vec(&map).unwrap();
+    assert_eq!(bytes, [0b1, 0b1, 1, 9]);
+    assert_eq!(borsh::from_slice::<Map<Many, u8>>(&bytes).unwrap(), map);
+
+    let mut set: Set<Many> = Set::new();
+    set.insert(Many::B);
+    set.insert(Many::I);
+
+    let bytes = borsh::to_vec(&set).unwrap();
+    assert_eq!(bytes, [0b10, 0b1]);
+    assert_eq!(borsh::from_slice::<Set<Many>>(&bytes).unwrap(), set);
+}
+
+#[test]
+fn builtin_maps() {
+    let mut map: Map<bool, u8> = Map::new();
+    map.insert(true, 1);
+    let bytes = borsh::to_vec(&map).unwrap();
+    assert_eq!(bytes, [0b10, 1]);
+    assert_eq!(borsh::from_slice::<Map<bool, u8>>(&bytes).unwrap(), map);
+
+    let mut map: Map<Ordering, u8> = Map::new();
+    map.insert(Ordering::Less, 1);
+    map.insert(Ordering::Greater, 3);
+    let bytes = borsh::to_vec(&map).unwrap();
+    assert_eq!(bytes, [0b101, 1, 3]);
+    assert_eq!(borsh::from_slice::<Map<Ordering, u8>>(&bytes).unwrap(), map);
+
+    let mut map: Map<(), u8> = Map::new();
+    map.insert((), 7);
+    let bytes = borsh::to_vec(&map).unwrap();
+    assert_eq!(bytes, [0b1, 7]);
+    assert_eq!(borsh::from_slice::<Map<(), u8>>(&bytes).unwrap(), map);
+}
+
+#[test]
+fn set() {
+    let mut set: Set<Part> = Set::new();
+    set.insert(Part::Two);
+    set.insert(Part::Three);
+
+    let bytes = borsh::to_vec(&set).unwrap();
+    assert_eq!(bytes, [0b110]);
+    assert_eq!(borsh::from_slice::<Set<Part>>(&bytes).unwrap(), set);
+}
+
+#[test]
+fn bitset() {
+    let mut set: Set<Bits> = Set::new();
+    set.insert(Bits::First);
+    set.insert(Bits::Third);
+
+    let bytes = borsh::to_vec(&set).unwrap();
+    assert_eq!(bytes, [0b101]);
+    assert_eq!(borsh::from_slice::<Set<Bits>>(&bytes).unwrap(), set);
+
+    let mut set: Set<Wide> = Set::new();
+    set.insert(Wide::B);
+    set.insert(Wide::I);
+
+    let bytes = borsh::to_vec(&set).unwrap();
+    assert_eq!(bytes, 0b1_0000_0010u16.to_le_bytes());
+    assert_eq!(borsh::from_slice::<Set<Wide>>(&bytes).unwrap(), set);
+}
+
+#[test]
+fn builtin_sets() {
+    let mut set: Set<bool> = Set::new();
+    set.insert(false);
+    let bytes = borsh::to_vec(&set).unwrap();
+    assert_eq!(bytes, [0b01]);
+    assert_eq!(borsh::from_slice::<Set<bool>>(&bytes).unwrap(), set);
+
+    let mut set: Set<Ordering> = Set::new();
+    set.insert(Ordering::Equal);
+    set.insert(Ordering::Greater);
+    let bytes = borsh::to_vec(&set).unwrap();
+    assert_eq!(bytes, [0b110]);
+    assert_eq!(borsh::from_slice::<Set<Ordering>>(&bytes).unwrap(), set);
+
+    let mut set: Set<()> = Set::new();
+    set.insert(());
+    let bytes = borsh::to_vec(&set).unwrap();
+    assert_eq!(bytes, [1]);
+    assert_eq!(borsh::from_slice::<Set<()>>(&bytes).unwrap(), set);
+}
+
+#[test]
+fn invalid_bits() {
+    assert!(borsh::from_slice::<Map<Part, u8>>(&[0b1000]).is_err());
+    assert!(borsh::from_slice::<Map<Many, u8>>(&[0, 0b10]).is_err());
+    assert!(borsh::from_slice::<Set<Part>>(&[0b1000]).is_err());
+    assert!(borsh::from_slice::<Set<Bits>>(&[0b1000]).is_err());
+    assert!(borsh::from_slice::<Set<Wide>>(&0b10_0000_0000u16.to_le_bytes()).is_err());
+    assert!(borsh::from_slice::<Set<bool>>(&[0b100]).is_err());
+    assert!(borsh::from_slice::<Set<Ordering>>(&[0b1000]).is_err());
+}
+
+#[test]
+fn truncated() {
+    assert!(borsh::from_slice::<Map<Part, u32>>(&[0b1, 1, 0]).is_err());
+    assert!(borsh::from_slice::<Set<Wide>>(&[0]).is_err());
+}
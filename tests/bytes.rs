@@ -0,0 +1,89 @@
+use fixed_map::{Map, Set};
+
+#[test]
+fn map() {
+    let mut map: Map<u8, u32> = Map::new();
+
+    assert_eq!(map.insert(255, 1), None);
+    assert_eq!(map.insert(0, 2), None);
+    assert_eq!(map.insert(128, 3), None);
+    assert_eq!(map.insert(255, 4), Some(1));
+
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get(255), Some(&4));
+    assert_eq!(map.get(1), None);
+
+    assert!(map.iter().eq([(0, &2), (128, &3), (255, &4)]));
+    assert!(map.keys().rev().eq([255, 128, 0]));
+
+    // Dense storage is `Copy` when its values are.
+    let copy = map;
+    assert_eq!(copy, map);
+
+    map.retain(|key, _| key < 200);
+    assert!(map.into_iter().eq([(0, 2), (128, 3)]));
+}
+
+#[test]
+fn map_signed() {
+    let mut map: Map<i8, u32> = Map::new();
+
+    map.insert(i8::MAX, 1);
+    map.insert(0, 2);
+    map.insert(i8::MIN, 3);
+    map.insert(-1, 4);
+
+    assert!(map.keys().eq([i8::MIN, -1, 0, i8::MAX]));
+    assert_eq!(map.remove(-1), Some(4));
+    assert_eq!(map.remove(-1), None);
+
+    *map.entry(5).or_default() += 10;
+    assert_eq!(map.get(5), Some(&10));
+}
+
+#[test]
+fn set() {
+    let mut set: Set<u8> = Set::new();
+
+    assert!(set.insert(127));
+    assert!(set.insert(128));
+    assert!(set.insert(255));
+    assert!(set.insert(0));
+    assert!(!set.insert(0));
+
+    assert_eq!(set.len(), 4);
+    assert!(set.contains(128));
+    assert!(!set.contains(129));
+
+    assert!(set.iter().eq([0, 127, 128, 255]));
+    assert!(set.iter().rev().eq([255, 128, 127, 0]));
+    assert_eq!(set.iter().len(), 4);
+
+    set.retain(|value| value % 2 == 0);
+    assert!(set.iter().eq([0, 128]));
+
+    assert!(set.remove(128));
+    assert!(!set.remove(128));
+    assert!(set.remove(0));
+    assert!(set.is_empty());
+}
+
+#[test]
+fn set_signed() {
+    let set: Set<i8> = [5, -5, i8::MIN, i8::MAX, 0].into_iter().collect();
+    assert!(set.iter().eq([i8::MIN, -5, 0, 5, i8::MAX]));
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn hashed() {
+    use fixed_map::Hashed;
+
+    let mut map = Map::new();
+    map.insert(Hashed(200u8), 1);
+    map.insert(Hashed(10u8), 2);
+
+    assert_eq!(map.get(Hashed(200)), Some(&1));
+    assert_eq!(map.len(), 2);
+    assert!(core::mem::size_of_val(&map) < core::mem::size_of::<Map<u8, u32>>());
+}
@@ -0,0 +1,191 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(compact)]
+enum Slot {
+    First,
+    Second,
+    Third,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum MyKey {
+    Simple,
+    Composite(Part),
+    Flag(Option<bool>),
+}
+
+#[cfg(feature = "hashbrown")]
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Dynamic {
+    Number(u32),
+    Other,
+}
+
+#[test]
+fn unit() {
+    let a = Map::from_iter([(Part::One, 1), (Part::Two, 2)]);
+    let b = Map::from_iter([(Part::Two, 20), (Part::Three, 30)]);
+
+    assert!(a
+        .map_values(|key, value| key as u32 * 10 + value)
+        .values()
+        .eq(&[1, 12]));
+
+    assert!(a.zip(&b).eq([
+        (Part::One, Some(&1), None),
+        (Part::Two, Some(&2), Some(&20)),
+        (Part::Three, None, Some(&30)),
+    ]));
+
+    let union = a.union_with(b, |_, a, b| a + b);
+    assert!(union
+        .iter()
+        .eq([(Part::One, &1), (Part::Two, &22), (Part::Three, &30)]));
+
+    let intersection = a.intersection_with(b, |key, a, b| (key, a * b));
+    assert!(intersection.values().eq([&(Part::Two, 40)]));
+
+    let (left, right) = Map::from_iter([(Part::One, (1, 'a')), (Part::Three, (3, 'c'))]).unzip();
+    assert!(left.iter().eq([(Part::One, &1), (Part::Three, &3)]));
+    assert!(right.iter().eq([(Part::One, &'a'), (Part::Three, &'c')]));
+}
+
+#[test]
+fn composite() {
+    let a = Map::from_iter([
+        (MyKey::Simple, 1),
+        (MyKey::Composite(Part::One), 2),
+        (MyKey::Flag(None), 3),
+        (MyKey::Flag(Some(true)), 4),
+    ]);
+
+    let b = Map::from_iter([
+        (MyKey::Composite(Part::One), 20),
+        (MyKey::Composite(Part::Two), 30),
+        (MyKey::Flag(Some(true)), 40),
+        (MyKey::Flag(Some(false)), 50),
+    ]);
+
+    let union = a.union_with(b, |_, a, b| a + b);
+    assert_eq!(union.len(), 6);
+    assert_eq!(union.get(MyKey::Simple), Some(&1));
+    assert_eq!(union.get(MyKey::Composite(Part::One)), Some(&22));
+    assert_eq!(union.get(MyKey::Composite(Part::Two)), Some(&30));
+    assert_eq!(union.get(MyKey::Flag(None)), Some(&3));
+    assert_eq!(union.get(MyKey::Flag(Some(true))), Some(&44));
+    assert_eq!(union.get(MyKey::Flag(Some(false))), Some(&50));
+
+    let intersection = a.intersection_with(b, |_, a, b| b - a);
+    assert!(intersection.iter().eq([
+        (MyKey::Composite(Part::One), &18),
+        (MyKey::Flag(Some(true)), &36)
+    ]));
+
+    assert!(a.zip(&b).eq([
+        (MyKey::Simple, Some(&1), None),
+        (MyKey::Composite(Part::One), Some(&2), Some(&20)),
+        (MyKey::Composite(Part::Two), None, Some(&30)),
+        (MyKey::Flag(Some(true)), Some(&4), Some(&40)),
+        (MyKey::Flag(Some(false)), None, Some(&50)),
+        (MyKey::Flag(None), Some(&3), None),
+    ]));
+
+    let (left, right) = a.map_values(|_, value| (value, value * 2)).unzip();
+    assert_eq!(left, a);
+    assert!(right.iter().all(|(key, value)| *value == a[key] * 2));
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn dynamic() {
+    let a = Map::from_iter([(Dynamic::Number(1), 1), (Dynamic::Number(2), 2)]);
+    let b = Map::from_iter([(Dynamic::Number(2), 20), (Dynamic::Number(3), 30)]);
+
+    let union = a.clone().union_with(b.clone(), |_, a, b| a + b);
+    assert_eq!(
+        union,
+        Map::from_iter([
+            (Dynamic::Number(1), 1),
+            (Dynamic::Number(2), 22),
+            (Dynamic::Number(3), 30)
+        ])
+    );
+
+    let intersection = a.clone().intersection_with(b.clone(), |_, a, b| a * b);
+    assert_eq!(intersection, Map::from_iter([(Dynamic::Number(2), 40)]));
+
+    let mut zipped = a.zip(&b).collect::<Vec<_>>();
+    zipped.sort_by_key(|(key, _, _)| match key {
+        Dynamic::Number(n) => *n,
+        Dynamic::Other => 0,
+    });
+
+    assert_eq!(
+        zipped,
+        [
+            (Dynamic::Number(1), Some(&1), None),
+            (Dynamic::Number(2), Some(&2), Some(&20)),
+            (Dynamic::Number(3), None, Some(&30)),
+        ]
+    );
+}
+
+#[test]
+fn tuples() {
+    let a = Map::from_iter([((Part::One, true), 1), ((Part::Two, false), 2)]);
+    let b = Map::from_iter([((Part::One, true), 10), ((Part::Three, true), 30)]);
+
+    let union = a.union_with(b, |_, a, b| a + b);
+    assert!(union.iter().eq([
+        ((Part::One, true), &11),
+        ((Part::Two, false), &2),
+        ((Part::Three, true), &30),
+    ]));
+
+    assert!(a.zip(&b).eq([
+        ((Part::One, true), Some(&1), Some(&10)),
+        ((Part::Two, false), Some(&2), None),
+        ((Part::Three, true), None, Some(&30)),
+    ]));
+
+    let doubled = a.map_values(|_, value| value * 2);
+    assert!(doubled.values().copied().eq([2, 4]));
+}
+
+#[test]
+fn zip_key_order() {
+    let a = Map::from_iter([(200u8, 'a'), (3, 'b')]);
+    let b = Map::from_iter([(100u8, 'c'), (3, 'd')]);
+
+    assert!(a.zip(&b).eq([
+        (3, Some(&'b'), Some(&'d')),
+        (100, None, Some(&'c')),
+        (200, Some(&'a'), None),
+    ]));
+
+    let a = Map::from_iter([(Slot::Third, 3)]);
+    let b = Map::from_iter([(Slot::First, 10), (Slot::Third, 30)]);
+
+    assert!(a.zip(&b).eq([
+        (Slot::First, None, Some(&10)),
+        (Slot::Third, Some(&3), Some(&30)),
+    ]));
+
+    let a = Map::from_iter([(Ok::<bool, Part>(false), 1), (Err(Part::Two), 2)]);
+    let b = Map::from_iter([(Ok(true), 10), (Err(Part::One), 20)]);
+
+    assert!(a.zip(&b).eq([
+        (Ok(true), None, Some(&10)),
+        (Ok(false), Some(&1), None),
+        (Err(Part::One), None, Some(&20)),
+        (Err(Part::Two), Some(&2), None),
+    ]));
+}
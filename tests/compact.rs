@@ -0,0 +1,224 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use fixed_map::map::{Entry, OccupiedEntry, VacantEntry};
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(bitset, compact)]
+enum Part {
+    One,
+    Two,
+    Three,
+    Four,
+}
+
+/// Counts the number of times it has been dropped.
+#[derive(Clone)]
+struct Counted(Rc<Cell<usize>>);
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn map() {
+    let mut map = Map::new();
+    assert!(map.is_empty());
+    assert_eq!(map.insert(Part::Three, 3), None);
+    assert_eq!(map.insert(Part::One, 1), None);
+    assert_eq!(map.insert(Part::One, 10), Some(1));
+    assert_eq!(map.len(), 2);
+
+    assert_eq!(map.get(Part::One), Some(&10));
+    assert_eq!(map.get(Part::Two), None);
+    *map.get_mut(Part::Three).unwrap() += 30;
+
+    assert!(map.iter().eq([(Part::One, &10), (Part::Three, &33)]));
+    assert!(map.iter().rev().eq([(Part::Three, &33), (Part::One, &10)]));
+    assert!(map.keys().eq([Part::One, Part::Three]));
+
+    for (key, value) in map.iter_mut().rev() {
+        *value += key as i32;
+    }
+
+    assert!(map.values().copied().eq([10, 35]));
+    assert_eq!(map.remove(Part::One), Some(10));
+    assert_eq!(map.remove(Part::One), None);
+    assert!(map.into_iter().eq([(Part::Three, 35)]));
+}
+
+#[test]
+fn entry() {
+    let mut map = Map::new();
+
+    match map.entry(Part::Two) {
+        Entry::Vacant(entry) => {
+            assert_eq!(entry.key(), Part::Two);
+            *entry.insert(2) += 1;
+        }
+        Entry::Occupied(..) => unreachable!(),
+    }
+
+    match map.entry(Part::Two) {
+        Entry::Occupied(mut entry) => {
+            assert_eq!(entry.insert(20), 3);
+            assert_eq!(entry.remove(), 20);
+        }
+        Entry::Vacant(..) => unreachable!(),
+    }
+
+    assert!(map.is_empty());
+    *map.entry(Part::Four).or_default() += 4;
+    assert_eq!(map.get(Part::Four), Some(&4));
+}
+
+#[test]
+fn raw_keys() {
+    let mut map = Map::new();
+    assert_eq!(map.as_raw_keys(), 0);
+
+    map.insert(Part::Two, 2);
+    map.insert(Part::Four, 4);
+
+    assert_eq!(map.as_raw_keys(), 0b1010);
+    assert_eq!(
+        Set::<Part>::from_raw(map.as_raw_keys()),
+        Set::from([Part::Two, Part::Four])
+    );
+}
+
+#[test]
+fn traits() {
+    let mut a = Map::new();
+    a.insert(Part::One, 1);
+    a.insert(Part::Three, 3);
+
+    let mut b = a.clone();
+    assert_eq!(a, b);
+
+    b.insert(Part::Two, 2);
+    assert_ne!(a, b);
+    assert!(a > b);
+
+    b.remove(Part::Two);
+    b.insert(Part::Three, 4);
+    assert!(a < b);
+}
+
+#[test]
+fn drops() {
+    let drops = Rc::new(Cell::new(0));
+    let value = Counted(drops.clone());
+
+    let mut map = Map::new();
+    map.insert(Part::One, value.clone());
+    map.insert(Part::Two, value.clone());
+    map.insert(Part::Three, value.clone());
+    map.insert(Part::Four, value.clone());
+
+    map.insert(Part::One, value.clone());
+    assert_eq!(drops.get(), 1);
+
+    map.retain(|key, _| key != Part::Two);
+    assert_eq!(drops.get(), 2);
+
+    let mut iter = map.clone().into_iter();
+    drop(iter.next());
+    assert_eq!(drops.get(), 3);
+    drop(iter);
+    assert_eq!(drops.get(), 5);
+
+    drop(map);
+    assert_eq!(drops.get(), 8);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(compact)]
+enum Wide {
+    V0,
+    V1,
+    V2,
+    V3,
+    V4,
+    V5,
+    V6,
+    V7,
+    V8,
+    V9,
+    V10,
+    V11,
+    V12,
+    V13,
+    V14,
+    V15,
+    V16,
+    V17,
+    V18,
+    V19,
+    V20,
+    V21,
+    V22,
+    V23,
+    V24,
+    V25,
+    V26,
+    V27,
+    V28,
+    V29,
+    V30,
+    V31,
+    V32,
+    V33,
+    V34,
+    V35,
+    V36,
+    V37,
+    V38,
+    V39,
+    V40,
+    V41,
+    V42,
+    V43,
+    V44,
+    V45,
+    V46,
+    V47,
+    V48,
+    V49,
+    V50,
+    V51,
+    V52,
+    V53,
+    V54,
+    V55,
+    V56,
+    V57,
+    V58,
+    V59,
+    V60,
+    V61,
+    V62,
+    V63,
+}
+
+#[test]
+fn bits_boundary() {
+    assert_eq!(core::mem::size_of::<Map<Wide, ()>>(), 8);
+
+    let mut map = Map::new();
+    map.insert(Wide::V63, 63);
+    map.insert(Wide::V0, 0);
+
+    assert_eq!(map.get(Wide::V63), Some(&63));
+    assert_eq!(map.get(Wide::V31), None);
+    assert_eq!(map.as_raw_keys(), 1 << 63 | 1);
+    assert!(map.iter().eq([(Wide::V0, &0), (Wide::V63, &63)]));
+    assert!(map.iter().rev().eq([(Wide::V63, &63), (Wide::V0, &0)]));
+
+    assert_eq!(map.remove(Wide::V63), Some(63));
+    assert_eq!(map.get(Wide::V63), None);
+    assert_eq!(map.len(), 1);
+}
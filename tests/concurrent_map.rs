@@ -0,0 +1,122 @@
+#![cfg(feature = "std")]
+
+use std::thread;
+
+use fixed_map::{ConcurrentMap, Finite, Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(finite)]
+enum Slot {
+    Idle,
+    Busy(Part),
+    Flag(bool),
+}
+
+#[test]
+fn concurrent_map() {
+    let map = ConcurrentMap::new();
+    assert_eq!(map.insert(Part::One, 1), None);
+    assert_eq!(map.insert(Part::One, 10), Some(1));
+    assert_eq!(map.insert(Part::Three, 3), None);
+
+    assert_eq!(*map.get(Part::One).unwrap(), 10);
+    assert!(map.get(Part::Two).is_none());
+    assert!(map.get_mut(Part::Two).is_none());
+    *map.get_mut(Part::Three).unwrap() += 30;
+
+    assert_eq!(map.update(Part::Two, |value| value.replace(2)), None);
+    assert_eq!(format!("{map:?}"), "{One: 10, Two: 2, Three: 33}");
+
+    assert_eq!(map.remove(Part::One), Some(10));
+    assert!(!map.contains_key(Part::One));
+
+    let snapshot = map.snapshot();
+    assert!(snapshot.iter().eq([(Part::Two, &2), (Part::Three, &33)]));
+    assert_eq!(Map::from(map), snapshot);
+}
+
+#[test]
+fn composite() {
+    assert_eq!(Slot::COUNT, 6);
+    assert_eq!(Slot::Busy(Part::Three).index(), 3);
+    assert_eq!(Slot::Flag(true).index(), 5);
+
+    assert_eq!(
+        Slot::array_from_fn(|key| key),
+        [
+            Slot::Idle,
+            Slot::Busy(Part::One),
+            Slot::Busy(Part::Two),
+            Slot::Busy(Part::Three),
+            Slot::Flag(false),
+            Slot::Flag(true),
+        ]
+    );
+
+    let map = ConcurrentMap::new();
+    map.insert(Slot::Flag(true), 1);
+    map.insert(Slot::Busy(Part::Two), 2);
+    map.update(Slot::Idle, |value| *value.get_or_insert(0) += 3);
+
+    assert_eq!(*map.get(Slot::Busy(Part::Two)).unwrap(), 2);
+    assert!(map.get(Slot::Busy(Part::One)).is_none());
+
+    assert!(map.snapshot().into_iter().eq([
+        (Slot::Idle, 3),
+        (Slot::Busy(Part::Two), 2),
+        (Slot::Flag(true), 1),
+    ]));
+}
+
+#[test]
+fn poisoned() {
+    let map = ConcurrentMap::new();
+    map.insert(Part::One, 1);
+
+    let result = thread::scope(|s| {
+        s.spawn(|| {
+            map.update(Part::One, |value| {
+                *value = Some(2);
+                panic!("poison the slot");
+            })
+        })
+        .join()
+    });
+
+    assert!(result.is_err());
+    // The slot is still used, and holds what the panicking thread left behind.
+    assert_eq!(*map.get(Part::One).unwrap(), 2);
+}
+
+#[test]
+fn threads() {
+    let map = ConcurrentMap::new();
+
+    thread::scope(|s| {
+        for part in [Part::One, Part::Two, Part::Three] {
+            for _ in 0..4 {
+                let map = &map;
+
+                s.spawn(move || {
+                    for _ in 0..100 {
+                        map.update(part, |value| *value.get_or_insert(0) += 1);
+                    }
+                });
+            }
+        }
+
+        s.spawn(|| {
+            let snapshot = map.snapshot();
+            assert!(snapshot.values().all(|&count| count <= 400));
+        });
+    });
+
+    assert!(map.snapshot().values().copied().eq([400, 400, 400]));
+}
@@ -0,0 +1,67 @@
+use fixed_map::{Counter, Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Event {
+    Start,
+    Part(Part),
+    Byte(u8),
+}
+
+#[test]
+fn counter() {
+    let mut counter = Counter::new();
+    assert_eq!(counter.most_common(), None);
+
+    counter.add(Event::Start);
+    counter.add_n(Event::Part(Part::Two), 3);
+    counter.add_n(Event::Byte(7), 2);
+    counter.add_n(Event::Byte(8), 0);
+
+    assert_eq!(counter.len(), 3);
+    assert_eq!(counter.total(), 6);
+    assert_eq!(counter.count(Event::Part(Part::Two)), 3);
+    assert_eq!(counter.count(Event::Byte(8)), 0);
+    assert_eq!(counter.most_common(), Some((Event::Part(Part::Two), 3)));
+
+    assert!(counter.remove(Event::Start));
+    assert!(!counter.remove(Event::Start));
+    assert!(!counter.contains(Event::Start));
+    assert_eq!(counter.len(), 2);
+
+    let map = Map::from(counter);
+    assert_eq!(map.get(Event::Byte(7)), Some(&2));
+}
+
+#[test]
+fn multiset() {
+    let a = Counter::from_iter([Part::One, Part::One, Part::Two]);
+    let b = Counter::from_iter([Part::One, Part::Three, Part::Three]);
+
+    assert!(a
+        .union(b)
+        .into_iter()
+        .eq([(Part::One, 2), (Part::Two, 1), (Part::Three, 2)]));
+    assert!(a.intersection(b).into_iter().eq([(Part::One, 1)]));
+
+    let mut c = a;
+    c.extend([Part::One, Part::Three, Part::Three]);
+    assert_eq!(c.total(), 6);
+    assert_eq!(
+        c,
+        Counter::from_iter([
+            Part::One,
+            Part::Three,
+            Part::Two,
+            Part::One,
+            Part::Three,
+            Part::One
+        ])
+    );
+}
@@ -0,0 +1,72 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use fixed_map::{DefaultMap, Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+fn hash<T>(value: &T) -> u64
+where
+    T: Hash,
+{
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn default_map() {
+    let mut map = DefaultMap::<Part, u32>::new();
+    assert!(map.values().all(|value| *value == 0));
+
+    *map.get_mut(Part::One) += 1;
+    map[Part::Three] += 3;
+    assert_eq!(map.insert(Part::Three, 30), 3);
+
+    assert_eq!(*map.get(Part::One), 1);
+    assert_eq!(map[Part::Two], 0);
+    assert!(map
+        .iter()
+        .eq([(Part::One, &1), (Part::Two, &0), (Part::Three, &30)]));
+
+    assert_eq!(map.remove(Part::One), 1);
+    assert_eq!(map.remove(Part::One), 0);
+    assert_eq!(format!("{:?}", map), "{Three: 30}");
+
+    map.clear();
+    assert_eq!(map, DefaultMap::new());
+}
+
+#[test]
+fn defaults_are_absent() {
+    let mut a = DefaultMap::<Part, u32>::new();
+    let b = DefaultMap::<Part, u32>::new();
+
+    a[Part::Two] = 2;
+    assert_ne!(a, b);
+
+    a[Part::Two] = 0;
+    assert_eq!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+}
+
+#[test]
+fn convert() {
+    let mut map = DefaultMap::<Part, u32>::new();
+    map[Part::One] = 0;
+    map[Part::Two] = 2;
+
+    let map = Map::from(map);
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.get(Part::Two), Some(&2));
+
+    let map = DefaultMap::from(map);
+    assert!(map
+        .into_iter()
+        .eq([(Part::One, 0), (Part::Two, 2), (Part::Three, 0)]));
+}
@@ -0,0 +1,115 @@
+use fixed_map::map::{Change, MapPatch};
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum MyKey {
+    Simple,
+    Composite(Part),
+    Flag(Option<bool>),
+}
+
+#[cfg(feature = "hashbrown")]
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Dynamic {
+    Number(u32),
+    Other,
+}
+
+fn map<K, V, const N: usize>(entries: [(K, V); N]) -> Map<K, V>
+where
+    K: Key,
+{
+    entries.into_iter().collect()
+}
+
+#[test]
+fn unit() {
+    let mut a = map([(Part::One, 1), (Part::Two, 2)]);
+    let b = map([(Part::Two, 2), (Part::Three, 3)]);
+
+    assert!(a.diff(&a).next().is_none());
+    assert!(a
+        .diff(&b)
+        .eq([Change::Removed(Part::One), Change::Added(Part::Three, &3)]));
+
+    let patch: MapPatch<_, _> = a.diff(&b).map(Change::cloned).collect();
+    assert_eq!(patch.len(), 2);
+
+    a.apply(patch);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn composite() {
+    let mut a = map([
+        (MyKey::Simple, 1),
+        (MyKey::Composite(Part::Two), 2),
+        (MyKey::Flag(None), 3),
+    ]);
+
+    let b = map([
+        (MyKey::Composite(Part::Two), 20),
+        (MyKey::Flag(None), 3),
+        (MyKey::Flag(Some(true)), 4),
+    ]);
+
+    assert!(a.diff(&b).eq([
+        Change::Removed(MyKey::Simple),
+        Change::Changed(MyKey::Composite(Part::Two), &20),
+        Change::Added(MyKey::Flag(Some(true)), &4),
+    ]));
+
+    a.apply(a.diff(&b).map(Change::cloned).collect());
+    assert_eq!(a, b);
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn dynamic() {
+    let mut a = map([(Dynamic::Number(1), 1), (Dynamic::Number(2), 2)]);
+    let b = map([(Dynamic::Number(2), 20), (Dynamic::Other, 3)]);
+
+    let mut patch = MapPatch::new();
+    patch.extend(a.diff(&b).map(Change::cloned));
+
+    assert_eq!(patch.get(Dynamic::Number(1)), Some(None));
+    assert_eq!(patch.get(Dynamic::Number(2)), Some(Some(&20)));
+    assert_eq!(patch.get(Dynamic::Other), Some(Some(&3)));
+
+    a.apply(patch);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn key_order() {
+    let a = map([
+        (MyKey::Composite(Part::One), 1),
+        (MyKey::Composite(Part::Three), 3),
+        (MyKey::Flag(Some(false)), 5),
+        (MyKey::Flag(None), 6),
+    ]);
+
+    let b = map([
+        (MyKey::Simple, 0),
+        (MyKey::Composite(Part::Two), 2),
+        (MyKey::Composite(Part::Three), 30),
+        (MyKey::Flag(Some(true)), 4),
+        (MyKey::Flag(None), 6),
+    ]);
+
+    assert!(a.diff(&b).eq([
+        Change::Added(MyKey::Simple, &0),
+        Change::Removed(MyKey::Composite(Part::One)),
+        Change::Added(MyKey::Composite(Part::Two), &2),
+        Change::Changed(MyKey::Composite(Part::Three), &30),
+        Change::Added(MyKey::Flag(Some(true)), &4),
+        Change::Removed(MyKey::Flag(Some(false))),
+    ]));
+}
@@ -0,0 +1,74 @@
+use core::iter::FusedIterator;
+
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum MyKey {
+    Simple,
+    Composite(Part),
+    Tuple((Option<bool>, u8)),
+}
+
+fn assert_exact<I>(iter: I, expected: usize)
+where
+    I: ExactSizeIterator + FusedIterator,
+{
+    assert_eq!(iter.len(), expected);
+    assert_eq!(iter.size_hint(), (expected, Some(expected)));
+    assert_eq!(iter.count(), expected);
+}
+
+#[test]
+fn map() {
+    let mut map = Map::new();
+    map.insert(MyKey::Simple, 1);
+    map.insert(MyKey::Composite(Part::Two), 2);
+    map.insert(MyKey::Tuple((None, 7)), 3);
+    map.insert(MyKey::Tuple((Some(true), 200)), 4);
+
+    assert_exact(map.iter(), 4);
+    assert_exact(map.keys(), 4);
+    assert_exact(map.values(), 4);
+    assert_exact(map.iter_mut(), 4);
+    assert_exact(map.values_mut(), 4);
+
+    let mut iter = map.into_iter();
+    assert_eq!(iter.len(), 4);
+    iter.next();
+    iter.next_back();
+    assert_eq!(iter.len(), 2);
+    assert_exact(iter, 2);
+
+    let mut map = Map::new();
+    map.insert(Part::One, 1);
+    map.insert(Part::Three, 3);
+    assert_exact(map.iter(), 2);
+    assert_exact(map.into_iter(), 2);
+}
+
+#[test]
+fn set() {
+    let mut set = Set::new();
+    set.insert(MyKey::Simple);
+    set.insert(MyKey::Composite(Part::One));
+    set.insert(MyKey::Tuple((Some(false), 3)));
+
+    assert_exact(set.iter(), 3);
+    assert_exact(set.into_iter(), 3);
+
+    let a = Set::from([Part::One, Part::Two]);
+    let b = Set::from([Part::Two, Part::Three]);
+    assert_exact(a.iter(), 2);
+
+    // The intersection is filtered lazily, so only an upper bound is known.
+    let intersection = a.intersection(&b);
+    assert_eq!(intersection.size_hint(), (0, Some(2)));
+    assert_eq!(intersection.count(), 1);
+}
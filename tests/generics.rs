@@ -0,0 +1,171 @@
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+enum Scoped<T> {
+    Global,
+    Local(T),
+}
+
+#[test]
+fn generic_map() {
+    let mut map: Map<Scoped<Part>, u32> = Map::new();
+
+    map.insert(Scoped::Global, 1);
+    map.insert(Scoped::Local(Part::Two), 2);
+
+    assert_eq!(map.get(Scoped::Global), Some(&1));
+    assert_eq!(map.get(Scoped::Local(Part::One)), None);
+    assert_eq!(map.get(Scoped::Local(Part::Two)), Some(&2));
+
+    *map.entry(Scoped::Local(Part::One)).or_default() += 3;
+
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        [
+            (Scoped::Global, &1),
+            (Scoped::Local(Part::One), &3),
+            (Scoped::Local(Part::Two), &2)
+        ]
+    );
+
+    assert_eq!(
+        map.iter().rev().map(|(k, _)| k).collect::<Vec<_>>(),
+        [
+            Scoped::Local(Part::Two),
+            Scoped::Local(Part::One),
+            Scoped::Global
+        ]
+    );
+}
+
+#[test]
+fn generic_set() {
+    let mut set: Set<Scoped<Option<Part>>> = Set::new();
+
+    set.insert(Scoped::Local(None));
+    set.insert(Scoped::Local(Some(Part::Two)));
+
+    assert!(!set.contains(Scoped::Global));
+    assert!(set.contains(Scoped::Local(None)));
+    assert!(set.contains(Scoped::Local(Some(Part::Two))));
+
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.iter().rev().count(), 2);
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn lifetime_key() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+    enum Name<'a> {
+        Anonymous,
+        Named(&'a str),
+    }
+
+    let name = String::from("bob");
+
+    let mut map = Map::new();
+    map.insert(Name::Anonymous, 1);
+    map.insert(Name::Named(&name), 2);
+
+    assert_eq!(map.get(Name::Anonymous), Some(&1));
+    assert_eq!(map.get(Name::Named("bob")), Some(&2));
+    assert_eq!(map.get(Name::Named("alice")), None);
+}
+
+#[test]
+fn generic_names_in_combinators() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+    enum Pair<U, W> {
+        Left(U),
+        Right(W),
+    }
+
+    let mut a: Map<Pair<Part, bool>, u32> = Map::new();
+    a.insert(Pair::Left(Part::One), 1);
+    a.insert(Pair::Right(true), 2);
+
+    let mut b = Map::new();
+    b.insert(Pair::Right(true), 'b');
+    b.insert(Pair::Right(false), 'c');
+
+    assert!(a.zip(&b).eq([
+        (Pair::Left(Part::One), Some(&1), None),
+        (Pair::Right(true), Some(&2), Some(&'b')),
+        (Pair::Right(false), None, Some(&'c')),
+    ]));
+
+    let merged = a
+        .map_values(|_, value| value * 10)
+        .union_with(b.map_values(|_, value| value as u32), |_, a, b| a + b);
+
+    assert_eq!(merged.get(Pair::Left(Part::One)), Some(&10));
+    assert_eq!(merged.get(Pair::Right(true)), Some(&(20 + 'b' as u32)));
+    assert_eq!(merged.get(Pair::Right(false)), Some(&('c' as u32)));
+}
+
+#[test]
+fn generated_names() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+    enum Names<V, F, H> {
+        Value(V),
+        Func(F),
+        Hasher(H),
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+    enum Flags<const V: usize> {
+        First,
+        Second,
+    }
+
+    let mut map: Map<Names<Part, bool, Part>, u32> = Map::new();
+    map.insert(Names::Value(Part::One), 1);
+    map.insert(Names::Hasher(Part::Two), 2);
+    map.retain(|key, _| key != Names::Value(Part::One));
+    assert!(map.iter().eq([(Names::Hasher(Part::Two), &2)]));
+
+    let mut map: Map<Flags<1>, u32> = Map::new();
+    map.insert(Flags::Second, 2);
+    assert!(map.keys().eq([Flags::Second]));
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn generated_lifetimes() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+    enum This<'this> {
+        Anonymous,
+        Named(&'this str),
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+    enum Bounds<'trivial_bounds> {
+        Anonymous,
+        Named(&'trivial_bounds str),
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+    enum Arbitrary<'arbitrary> {
+        Anonymous,
+        Named(&'arbitrary str),
+    }
+
+    let mut map = Map::new();
+    map.insert(This::Named("bob"), 1);
+    assert_eq!(map.get(This::Named("bob")), Some(&1));
+
+    let mut map = Map::new();
+    map.insert(Bounds::Named("bob"), 2);
+    assert_eq!(map.get(Bounds::Anonymous), None);
+
+    let mut map = Map::new();
+    map.insert(Arbitrary::Anonymous, 3);
+    assert!(map.keys().eq([Arbitrary::Anonymous]));
+}
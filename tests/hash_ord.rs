@@ -0,0 +1,94 @@
+#![cfg(feature = "hashbrown")]
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum MyKey {
+    Simple,
+    Composite(Part),
+    String(&'static str),
+    Number(u32),
+}
+
+fn hash<T>(value: &T) -> u64
+where
+    T: Hash,
+{
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn map_hash() {
+    let mut a = Map::new();
+    let mut b = Map::new();
+
+    for n in 0..32 {
+        a.insert(MyKey::Number(n), n);
+        b.insert(MyKey::Number(31 - n), 31 - n);
+    }
+
+    a.insert(MyKey::String("a"), 1);
+    b.insert(MyKey::String("a"), 1);
+
+    assert_eq!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+
+    b.insert(MyKey::Number(0), 1);
+    assert_ne!(hash(&a), hash(&b));
+
+    let mut set = HashSet::new();
+    set.insert(a.clone());
+    set.insert(a.clone());
+    set.insert(b);
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn map_ord() {
+    let mut a = Map::new();
+    a.insert(MyKey::Number(1), 1);
+    a.insert(MyKey::Number(3), 3);
+
+    let mut b = Map::new();
+    b.insert(MyKey::Number(3), 3);
+    b.insert(MyKey::Number(2), 2);
+
+    assert!(a < b);
+
+    let mut c = Map::new();
+    c.insert(MyKey::Simple, 1);
+
+    let mut d = Map::new();
+    d.insert(MyKey::Composite(Part::Two), 1);
+
+    // Composite storages are compared variant by variant in declaration
+    // order, where a variant without entries orders first.
+    let mut list = vec![b.clone(), d.clone(), a.clone(), c.clone()];
+    list.sort();
+    assert_eq!(list, [a, b, d, c]);
+}
+
+#[test]
+fn set_hash_ord() {
+    let a = Set::from([MyKey::String("a"), MyKey::String("b"), MyKey::Number(1)]);
+    let b = Set::from([MyKey::Number(1), MyKey::String("b"), MyKey::String("a")]);
+
+    assert_eq!(hash(&a), hash(&b));
+    assert_eq!(a.cmp(&b), std::cmp::Ordering::Equal);
+
+    let c = Set::from([MyKey::String("a"), MyKey::String("c")]);
+    assert!(a < c);
+    assert!(Set::from([MyKey::Simple]) > c);
+}
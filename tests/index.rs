@@ -0,0 +1,82 @@
+use core::cmp::Ordering;
+
+use fixed_map::{Key, Map, TotalMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum MyKey {
+    Simple,
+    Composite(Part),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Empty {}
+
+#[test]
+fn map() {
+    let mut map = Map::new();
+    map.insert(MyKey::Simple, 1);
+    map.insert(MyKey::Composite(Part::Two), 2);
+
+    map[MyKey::Composite(Part::Two)] += 10;
+
+    assert_eq!(map[MyKey::Simple], 1);
+    assert_eq!(map[MyKey::Composite(Part::Two)], 12);
+}
+
+#[test]
+#[should_panic(expected = "key `Composite(One)` is not present in the map")]
+fn map_missing() {
+    let mut map = Map::new();
+    map.insert(MyKey::Composite(Part::Two), 2);
+    map[MyKey::Composite(Part::One)] += 1;
+}
+
+#[test]
+fn total_map() {
+    let mut map = TotalMap::<Part, u32>::default();
+    map[Part::Two] += 2;
+
+    assert_eq!(map[Part::One], 0);
+    assert_eq!(map[Part::Two], 2);
+    assert_eq!(map.insert(Part::One, 1), 0);
+
+    let map = Map::from(map);
+    assert_eq!(map.get(Part::One), Some(&1));
+    assert_eq!(map.get(Part::Two), Some(&2));
+}
+
+#[test]
+fn total_map_builtin() {
+    let map = TotalMap::from_fn(|value: bool| u32::from(value));
+    assert_eq!(map[true], 1);
+    assert!(map.iter().eq([(false, &0), (true, &1)]));
+
+    let map = TotalMap::from_fn(|ordering: Ordering| ordering as i32);
+    assert!(map.values().copied().eq([-1, 0, 1]));
+
+    let map = TotalMap::from_fn(|()| "unit");
+    assert_eq!(map[()], "unit");
+}
+
+#[test]
+fn total_map_empty() {
+    let map = TotalMap::<Empty, u32>::default();
+    assert!(map.is_empty());
+    assert_eq!(map.iter().count(), 0);
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn map_borrowed() {
+    let mut map = Map::new();
+    map.insert(String::from("a"), 1);
+
+    map["a"] += 1;
+    assert_eq!(map["a"], 2);
+}
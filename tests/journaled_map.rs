@@ -0,0 +1,123 @@
+#![cfg(feature = "std")]
+
+use std::rc::Rc;
+
+use fixed_map::journaled_map::Entry;
+use fixed_map::map::OccupiedEntry;
+use fixed_map::{JournaledMap, Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Property {
+    Name,
+    Part(Part),
+    Flag(Option<bool>),
+}
+
+#[test]
+fn journaled_map() {
+    let mut map = JournaledMap::from(Map::from_iter([(Property::Name, 1)]));
+    let start = map.checkpoint();
+
+    map.insert(Property::Part(Part::Two), 2);
+    *map.get_mut(Property::Name).unwrap() += 10;
+
+    let middle = map.checkpoint();
+
+    match map.entry(Property::Name) {
+        Entry::Occupied(entry) => {
+            entry.remove();
+        }
+        Entry::Vacant(..) => unreachable!(),
+    }
+
+    map.remove(Property::Part(Part::Two));
+    map.insert(Property::Flag(None), 3);
+    assert!(map.iter().eq([(Property::Flag(None), &3)]));
+
+    assert!(map.rollback_to(middle));
+    assert_eq!(map.get(Property::Name), Some(&11));
+    assert_eq!(map.get(Property::Part(Part::Two)), Some(&2));
+    assert!(!map.contains_key(Property::Flag(None)));
+
+    assert!(map.rollback_to(start));
+    assert!(map.iter().eq([(Property::Name, &1)]));
+}
+
+#[test]
+fn commit() {
+    let mut map = JournaledMap::new();
+    let start = map.checkpoint();
+
+    map.insert(Part::One, 1);
+    map.commit();
+
+    let after = map.checkpoint();
+    map.insert(Part::Three, 3);
+
+    assert!(!map.rollback_to(start));
+    assert_eq!(map.len(), 2);
+
+    assert!(map.rollback_to(after));
+    assert!(Map::from(map).into_iter().eq([(Part::One, 1)]));
+}
+
+#[test]
+fn invalidated_checkpoint() {
+    let mut map = JournaledMap::new();
+
+    let first = map.checkpoint();
+    map.insert(Part::One, 1);
+    let second = map.checkpoint();
+    map.insert(Part::One, 2);
+
+    assert!(map.rollback_to(first));
+    map.insert(Part::Two, 5);
+    map.insert(Part::Three, 6);
+
+    // The journal has grown past `second` again, but with other records.
+    assert!(!map.rollback_to(second));
+    assert!(map.iter().eq([(Part::Two, &5), (Part::Three, &6)]));
+
+    assert!(map.rollback_to(first));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn lazy_entry() {
+    let mut map = JournaledMap::new();
+    map.insert(Part::One, Rc::new(1));
+    map.commit();
+
+    let start = map.checkpoint();
+
+    match map.entry(Part::One) {
+        Entry::Occupied(entry) => assert_eq!(**entry.get(), 1),
+        Entry::Vacant(..) => unreachable!(),
+    }
+
+    let _ = map.entry(Part::Two).key();
+
+    // Looking at entries doesn't clone their values into the journal.
+    assert_eq!(Rc::strong_count(map.get(Part::One).unwrap()), 1);
+    assert_eq!(map.checkpoint(), start);
+
+    match map.entry(Part::One) {
+        Entry::Occupied(mut entry) => {
+            entry.insert(Rc::new(2));
+            entry.insert(Rc::new(3));
+        }
+        Entry::Vacant(..) => unreachable!(),
+    }
+
+    map.entry(Part::Two).or_insert_with(|| Rc::new(4));
+
+    assert!(map.rollback_to(start));
+    assert!(map.iter().eq([(Part::One, &Rc::new(1))]));
+}
@@ -0,0 +1,72 @@
+use fixed_map::{exhaustive_map, map, set, Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum MyKey {
+    Simple,
+    Composite(Part),
+}
+
+#[test]
+fn map() {
+    let empty: Map<Part, u32> = map! {};
+    assert!(empty.is_empty());
+
+    let map = map! {
+        MyKey::Simple => 1,
+        MyKey::Composite(Part::Two) => 2,
+        MyKey::Simple => 10,
+    };
+
+    assert!(map
+        .into_iter()
+        .eq([(MyKey::Simple, 10), (MyKey::Composite(Part::Two), 2)]));
+}
+
+#[test]
+fn set() {
+    let empty: Set<Part> = set! {};
+    assert!(empty.is_empty());
+
+    let set = set![MyKey::Composite(Part::Three), MyKey::Simple];
+    assert!(set
+        .iter()
+        .eq([MyKey::Simple, MyKey::Composite(Part::Three)]));
+}
+
+#[test]
+fn exhaustive() {
+    let map = exhaustive_map! {
+        Part::Three => "three",
+        Part::One => "one",
+        Part::Two => "two",
+    };
+
+    assert!(map.values().eq(&["one", "two", "three"]));
+}
+
+#[test]
+fn exhaustive_composite() {
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    #[key(finite)]
+    enum Finite {
+        Simple,
+        Composite(Part),
+    }
+
+    let map = exhaustive_map! {
+        Finite::Composite(Part::Two) => 2,
+        Finite::Simple => 0,
+        Finite::Composite(Part::One) => 1,
+        Finite::Composite(Part::Three) => 3,
+    };
+
+    assert!(map.values().copied().eq([0, 1, 2, 3]));
+    assert_eq!(map[Finite::Composite(Part::Three)], 3);
+}
@@ -0,0 +1,71 @@
+use std::cell::Cell;
+
+use fixed_map::{Key, Map, OnceCellMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn once_map() {
+    use std::thread;
+
+    use fixed_map::OnceMap;
+
+    let calls = std::sync::atomic::AtomicUsize::new(0);
+    let map = OnceMap::new();
+
+    thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(|| {
+                let value = map.get_or_init(Part::Two, || {
+                    calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    2
+                });
+
+                assert_eq!(*value, 2);
+            });
+        }
+    });
+
+    assert_eq!(calls.into_inner(), 1);
+    assert_eq!(map.set(Part::Two, 20), Err(20));
+    assert_eq!(map.set(Part::Three, 3), Ok(()));
+    assert_eq!(map.len(), 2);
+    assert_eq!(format!("{map:?}"), "{Two: 2, Three: 3}");
+
+    let mut map = map;
+    assert_eq!(map.take(Part::Two), Some(2));
+    assert!(map.iter().eq([(Part::Three, &3)]));
+    assert_eq!(Map::from(map).get(Part::Three), Some(&3));
+}
+
+#[test]
+fn once_cell_map() {
+    let calls = Cell::new(0);
+    let map = OnceCellMap::new();
+
+    for _ in 0..3 {
+        map.get_or_init(Part::One, || {
+            calls.set(calls.get() + 1);
+            1
+        });
+    }
+
+    assert_eq!(calls.get(), 1);
+    assert_eq!(map.get(Part::Three), None);
+
+    let mut values = Map::new();
+    values.insert(Part::Three, 3);
+
+    let mut map = OnceCellMap::from(values);
+    assert_eq!(map.get_or_init(Part::Three, || 30), &3);
+    *map.get_mut(Part::Three).unwrap() += 30;
+    map.get_or_init(Part::One, || 1);
+
+    assert!(map.into_iter().eq([(Part::One, 1), (Part::Three, 33)]));
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "ops")]
+
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Resource {
+    Wood,
+    Stone,
+    Gold,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Stat {
+    Total,
+    Resource(Resource),
+}
+
+#[test]
+fn arithmetic() {
+    let a = Map::from_iter([(Resource::Wood, 1.0), (Resource::Stone, 2.0)]);
+    let b = Map::from_iter([(Resource::Stone, 0.5), (Resource::Gold, 4.0)]);
+
+    assert_eq!(
+        a + b,
+        Map::from_iter([
+            (Resource::Wood, 1.0),
+            (Resource::Stone, 2.5),
+            (Resource::Gold, 4.0)
+        ])
+    );
+    assert_eq!(
+        a - b,
+        Map::from_iter([
+            (Resource::Wood, 1.0),
+            (Resource::Stone, 1.5),
+            (Resource::Gold, -4.0)
+        ])
+    );
+    assert_eq!(
+        -a,
+        Map::from_iter([(Resource::Wood, -1.0), (Resource::Stone, -2.0)])
+    );
+    assert_eq!(
+        a * 3.0,
+        Map::from_iter([(Resource::Wood, 3.0), (Resource::Stone, 6.0)])
+    );
+    assert_eq!(
+        a / 2.0,
+        Map::from_iter([(Resource::Wood, 0.5), (Resource::Stone, 1.0)])
+    );
+
+    let mut c = a;
+    c += b;
+    c -= a;
+    assert_eq!(
+        c,
+        Map::from_iter([
+            (Resource::Wood, 0.0),
+            (Resource::Stone, 0.5),
+            (Resource::Gold, 4.0)
+        ])
+    );
+
+    c *= 2.0;
+    c /= 4.0;
+    assert_eq!(
+        c,
+        Map::from_iter([
+            (Resource::Wood, 0.0),
+            (Resource::Stone, 0.25),
+            (Resource::Gold, 2.0)
+        ])
+    );
+}
+
+#[test]
+fn reductions() {
+    let a = Map::from_iter([
+        (Resource::Wood, 3),
+        (Resource::Stone, 1),
+        (Resource::Gold, 3),
+    ]);
+    let b = Map::from_iter([(Resource::Wood, 2), (Resource::Gold, 5)]);
+
+    assert_eq!(a.sum(), 7);
+    assert_eq!(a.dot(&b), 21);
+    assert_eq!(a.min_by_value(), Some((Resource::Stone, &1)));
+    assert_eq!(a.max_by_value(), Some((Resource::Wood, &3)));
+    assert_eq!(Map::<Resource, u32>::new().sum(), 0);
+    assert_eq!(Map::<Resource, u32>::new().max_by_value(), None);
+}
+
+#[test]
+fn composite() {
+    let a = Map::from_iter([(Stat::Total, 10), (Stat::Resource(Resource::Wood), 4)]);
+    let b = Map::from_iter([
+        (Stat::Resource(Resource::Wood), 1),
+        (Stat::Resource(Resource::Gold), 5),
+    ]);
+
+    assert_eq!(
+        a + b,
+        Map::from_iter([
+            (Stat::Total, 10),
+            (Stat::Resource(Resource::Wood), 5),
+            (Stat::Resource(Resource::Gold), 5),
+        ])
+    );
+    assert_eq!(a.dot(&b), 4);
+}
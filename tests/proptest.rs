@@ -0,0 +1,103 @@
+#![cfg(feature = "proptest")]
+
+use fixed_map::{Key, Map, Set};
+use proptest::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(arbitrary)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(arbitrary)]
+enum Event {
+    Start,
+    Part(Part),
+    Flag(bool),
+    Maybe(Option<Part>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(arbitrary)]
+enum Scoped<T> {
+    Global,
+    Local(T),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(arbitrary, finite)]
+enum Slot {
+    Start,
+    Part(Part),
+    Flag(bool),
+}
+
+proptest! {
+    #[test]
+    fn keys_round_trip(event in any::<Event>()) {
+        let mut map = Map::new();
+        map.insert(event, 1);
+        prop_assert_eq!(map.get(event), Some(&1));
+        prop_assert!(map.keys().eq([event]));
+    }
+
+    #[test]
+    fn maps_match_entries(map in any::<Map<Event, u32>>()) {
+        prop_assert!(map.len() <= 10);
+        prop_assert_eq!(map.len(), map.iter().count());
+
+        for (key, value) in &map {
+            prop_assert_eq!(map.get(key), Some(value));
+        }
+    }
+
+    #[test]
+    fn sets_match_values(set in any::<Set<Scoped<Part>>>()) {
+        prop_assert!(set.len() <= 4);
+
+        for value in &set {
+            prop_assert!(set.contains(value));
+        }
+    }
+}
+
+#[test]
+fn uniform_unit_keys() {
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+
+    let mut runner = TestRunner::deterministic();
+    let strategy = any::<Part>();
+    let mut seen = Set::new();
+
+    for _ in 0..64 {
+        seen.insert(strategy.new_tree(&mut runner).unwrap().current());
+    }
+
+    assert!(seen.iter().eq([Part::One, Part::Two, Part::Three]));
+}
+
+#[test]
+fn weighted_finite_keys() {
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+
+    let mut runner = TestRunner::deterministic();
+    let strategy = any::<Slot>();
+    let mut counts = Map::<Slot, usize>::new();
+
+    for _ in 0..600 {
+        *counts
+            .entry(strategy.new_tree(&mut runner).unwrap().current())
+            .or_default() += 1;
+    }
+
+    assert_eq!(counts.len(), 6);
+
+    for (key, &count) in &counts {
+        assert!((50..150).contains(&count), "{key:?} picked {count} times");
+    }
+}
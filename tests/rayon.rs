@@ -0,0 +1,209 @@
+#![cfg(feature = "rayon")]
+
+use core::cmp::Ordering;
+use core::convert::Infallible;
+
+use fixed_map::{Key, Map, Set};
+use rayon::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(compact)]
+enum Compact {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Event {
+    Start,
+    Part(Part),
+    Flag(bool),
+    Maybe(Option<Part>),
+    Outcome(Result<bool, Ordering>),
+    Unit(()),
+    Byte(u8),
+    Never(Infallible),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Scoped<T> {
+    Global,
+    Local(T),
+}
+
+#[test]
+fn simple() {
+    let mut map: Map<Part, i32> = Map::new();
+    map.insert(Part::One, 1);
+    map.insert(Part::Three, 3);
+
+    let entries: Vec<_> = map.par_iter().collect();
+    assert_eq!(entries, [(Part::One, &1), (Part::Three, &3)]);
+
+    map.par_iter_mut().for_each(|(key, value)| {
+        if key == Part::Three {
+            *value *= 10;
+        }
+    });
+
+    map.par_values_mut().for_each(|value| *value += 1);
+    assert!(map.iter().eq([(Part::One, &2), (Part::Three, &31)]));
+
+    let sum: i32 = (&map).into_par_iter().map(|(_, value)| *value).sum();
+    assert_eq!(sum, 33);
+
+    let entries: Vec<_> = map.into_par_iter().collect();
+    assert_eq!(entries, [(Part::One, 2), (Part::Three, 31)]);
+}
+
+#[test]
+fn compact() {
+    let mut map: Map<Compact, String> = Map::new();
+    map.insert(Compact::One, String::from("one"));
+    map.insert(Compact::Three, String::from("three"));
+
+    let entries: Vec<_> = map
+        .par_iter()
+        .map(|(key, value)| (key, value.len()))
+        .collect();
+    assert_eq!(entries, [(Compact::One, 3), (Compact::Three, 5)]);
+
+    map.par_iter_mut().for_each(|(key, value)| {
+        if key == Compact::Three {
+            value.push('!');
+        }
+    });
+
+    map.par_values_mut().for_each(|value| value.insert(0, '_'));
+
+    let entries: Vec<_> = map.into_par_iter().collect();
+    assert_eq!(
+        entries,
+        [
+            (Compact::One, String::from("_one")),
+            (Compact::Three, String::from("_three!"))
+        ]
+    );
+}
+
+#[test]
+fn composite() {
+    let keys = [
+        Event::Start,
+        Event::Part(Part::Two),
+        Event::Flag(true),
+        Event::Flag(false),
+        Event::Maybe(Some(Part::One)),
+        Event::Maybe(None),
+        Event::Outcome(Ok(false)),
+        Event::Outcome(Err(Ordering::Greater)),
+        Event::Unit(()),
+        Event::Byte(42),
+    ];
+
+    let mut map = Map::new();
+
+    for (value, key) in keys.into_iter().enumerate() {
+        map.insert(key, value);
+    }
+
+    let entries: Vec<_> = map.par_iter().collect();
+    assert_eq!(entries, map.iter().collect::<Vec<_>>());
+
+    map.par_iter_mut().for_each(|(_, value)| *value *= 2);
+    map.par_values_mut().for_each(|value| *value += 1);
+
+    let expected: Vec<_> = map.iter().map(|(key, value)| (key, *value)).collect();
+    assert_eq!(expected.len(), keys.len());
+    assert!(expected.iter().all(|(_, value)| value % 2 == 1));
+
+    let entries: Vec<_> = map.into_par_iter().collect();
+    assert_eq!(entries, expected);
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn hashbrown() {
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Dynamic {
+        Number(u32),
+        Other,
+    }
+
+    let mut map = Map::new();
+
+    for n in 0..100 {
+        map.insert(Dynamic::Number(n), n);
+    }
+
+    map.insert(Dynamic::Other, 1000);
+
+    map.par_values_mut().for_each(|value| *value += 1);
+    assert_eq!(map.par_iter().map(|(_, value)| *value).sum::<u32>(), 6051);
+
+    map.par_iter_mut().for_each(|(key, value)| {
+        if let Dynamic::Number(n) = key {
+            *value = n;
+        }
+    });
+
+    let mut entries: Vec<_> = map.into_par_iter().map(|(_, value)| value).collect();
+    entries.sort_unstable();
+    assert_eq!(entries.len(), 101);
+    assert_eq!(entries.last(), Some(&1001));
+
+    let mut set = Set::new();
+    set.insert(Dynamic::Number(1));
+    set.insert(Dynamic::Number(2));
+    assert_eq!(set.par_iter().count(), 2);
+}
+
+#[test]
+fn set() {
+    let mut set = Set::new();
+    set.insert(Part::One);
+    set.insert(Part::Three);
+
+    let parts: Vec<_> = set.par_iter().collect();
+    assert_eq!(parts, [Part::One, Part::Three]);
+
+    let mut set = Set::new();
+    set.insert(Event::Part(Part::Two));
+    set.insert(Event::Flag(false));
+    set.insert(Event::Maybe(None));
+    set.insert(Event::Outcome(Err(Ordering::Less)));
+    set.insert(Event::Unit(()));
+    set.insert(Event::Byte(200));
+    set.insert(Event::Byte(3));
+    set.insert(Event::Start);
+
+    let events: Vec<_> = (&set).into_par_iter().collect();
+    assert_eq!(events, set.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn generic() {
+    let mut map: Map<Scoped<Part>, u32> = Map::new();
+    map.insert(Scoped::Global, 1);
+    map.insert(Scoped::Local(Part::Two), 2);
+
+    map.par_values_mut().for_each(|value| *value *= 3);
+
+    let entries: Vec<_> = map.par_iter().collect();
+    assert_eq!(
+        entries,
+        [(Scoped::Global, &3), (Scoped::Local(Part::Two), &6)]
+    );
+
+    let set: Set<Scoped<Part>> = map.keys().collect();
+    let keys: Vec<_> = set.par_iter().collect();
+    assert_eq!(keys, [Scoped::Global, Scoped::Local(Part::Two)]);
+}
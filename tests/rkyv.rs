@@ -0,0 +1,369 @@
+#![cfg(all(feature = "rkyv", feature = "std"))]
+
+use core::cmp::Ordering;
+use core::convert::Infallible;
+
+use fixed_map::map::{ArchiveMapStorage, ArchivedMap};
+use fixed_map::set::{ArchiveSetStorage, ArchivedSet};
+use fixed_map::{Key, Map, Set};
+use rkyv::{AlignedVec, Archive, Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(bitset)]
+enum Bits {
+    First,
+    Second,
+    Third,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(compact)]
+enum Compact {
+    First,
+    Second,
+    Third,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Event {
+    Start,
+    Part(Part),
+    Flag(bool),
+    Maybe(Option<Part>),
+    Outcome(Result<bool, Ordering>),
+    Unit(()),
+    Byte(u8),
+    Never(Infallible),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Scoped<T> {
+    Global,
+    Local(T),
+}
+
+// Uses the name of the serializer parameter in the generated storage.
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Named<S> {
+    Global,
+    Local(S),
+}
+
+fn to_bytes<T>(value: &T) -> AlignedVec
+where
+    T: Serialize<rkyv::ser::serializers::AllocSerializer<256>>,
+{
+    rkyv::to_bytes::<_, 256>(value).unwrap()
+}
+
+fn map_root<K, V>(bytes: &[u8]) -> &ArchivedMap<K, V>
+where
+    K: Key,
+    V: Archive,
+    K::MapStorage<V>: ArchiveMapStorage<K, V>,
+{
+    // SAFETY: The bytes were produced by serializing a map of the same type.
+    unsafe { rkyv::archived_root::<Map<K, V>>(bytes) }
+}
+
+fn set_root<T>(bytes: &[u8]) -> &ArchivedSet<T>
+where
+    T: Key,
+    T::SetStorage: ArchiveSetStorage<T>,
+{
+    // SAFETY: The bytes were produced by serializing a set of the same type.
+    unsafe { rkyv::archived_root::<Set<T>>(bytes) }
+}
+
+#[test]
+fn simple() {
+    let mut map: Map<Part, u32> = Map::new();
+    map.insert(Part::One, 1);
+    map.insert(Part::Three, 3);
+
+    let bytes = to_bytes(&map);
+    let archived = map_root::<Part, u32>(&bytes);
+
+    assert_eq!(archived.len(), 2);
+    assert!(!archived.is_empty());
+    assert_eq!(archived.get(Part::One), Some(&1));
+    assert_eq!(archived.get(Part::Two), None);
+    assert!(archived.contains_key(Part::Three));
+    assert!(archived.iter().eq([(Part::One, &1), (Part::Three, &3)]));
+    assert_eq!(format!("{archived:?}"), "{One: 1, Three: 3}");
+
+    let mut set: Set<Part> = Set::new();
+    set.insert(Part::Two);
+
+    let bytes = to_bytes(&set);
+    let archived = set_root::<Part>(&bytes);
+
+    assert_eq!(archived.len(), 1);
+    assert!(archived.contains(Part::Two));
+    assert!(!archived.contains(Part::One));
+    assert!(archived.iter().eq([Part::Two]));
+}
+
+#[test]
+fn fixed_layout() {
+    // Every variant has a slot, so the archive has the same size regardless of
+    // how many entries are present.
+    let empty = to_bytes(&Map::<Part, u32>::new());
+
+    let mut map: Map<Part, u32> = Map::new();
+    map.insert(Part::One, 1);
+    map.insert(Part::Two, 2);
+    map.insert(Part::Three, 3);
+    let full = to_bytes(&map);
+
+    assert_eq!(empty.len(), full.len());
+    assert_eq!(
+        core::mem::size_of::<ArchivedMap<Part, u32>>(),
+        core::mem::size_of::<[rkyv::option::ArchivedOption<u32>; 3]>()
+    );
+    assert!(map_root::<Part, u32>(&empty).is_empty());
+}
+
+#[test]
+fn bitset() {
+    let mut set: Set<Bits> = Set::new();
+    set.insert(Bits::First);
+    set.insert(Bits::Third);
+
+    let bytes = to_bytes(&set);
+    assert_eq!(&bytes[..], &[0b101]);
+
+    let archived = set_root::<Bits>(&bytes);
+    assert_eq!(archived.len(), 2);
+    assert!(archived.contains(Bits::First));
+    assert!(!archived.contains(Bits::Second));
+    assert!(archived.iter().eq([Bits::First, Bits::Third]));
+    assert_eq!(format!("{archived:?}"), "{First, Third}");
+}
+
+#[test]
+fn compact() {
+    let mut map: Map<Compact, String> = Map::new();
+    map.insert(Compact::Third, String::from("three"));
+    map.insert(Compact::First, String::from("one"));
+
+    let bytes = to_bytes(&map);
+    let archived = map_root::<Compact, String>(&bytes);
+
+    assert_eq!(archived.len(), 2);
+    assert_eq!(archived.get(Compact::First).unwrap(), "one");
+    assert_eq!(archived.get(Compact::Second), None);
+    assert_eq!(archived.get(Compact::Third).unwrap(), "three");
+    assert!(archived
+        .iter()
+        .map(|(key, value)| (key, value.as_str()))
+        .eq([(Compact::First, "one"), (Compact::Third, "three")]));
+
+    let deserialized: Map<Compact, String> = archived.deserialize(&mut rkyv::Infallible).unwrap();
+    assert_eq!(deserialized, map);
+
+    let empty = to_bytes(&Map::<Compact, u32>::new());
+    assert!(map_root::<Compact, u32>(&empty).is_empty());
+}
+
+#[test]
+fn composite() {
+    let mut map: Map<Event, u16> = Map::new();
+    map.insert(Event::Start, 1);
+    map.insert(Event::Part(Part::Two), 2);
+    map.insert(Event::Flag(false), 3);
+    map.insert(Event::Maybe(None), 4);
+    map.insert(Event::Outcome(Err(Ordering::Less)), 5);
+    map.insert(Event::Unit(()), 6);
+    map.insert(Event::Byte(200), 7);
+
+    let bytes = to_bytes(&map);
+    let archived = map_root::<Event, u16>(&bytes);
+
+    assert_eq!(archived.len(), 7);
+    assert_eq!(archived.get(Event::Part(Part::Two)), Some(&2));
+    assert_eq!(archived.get(Event::Part(Part::One)), None);
+    assert_eq!(archived.get(Event::Flag(false)), Some(&3));
+    assert_eq!(archived.get(Event::Outcome(Err(Ordering::Less))), Some(&5));
+    assert_eq!(archived.get(Event::Byte(200)), Some(&7));
+    assert_eq!(archived.get(Event::Byte(100)), None);
+    assert!(archived
+        .iter()
+        .map(|(key, value)| (key, *value))
+        .eq(map.iter().map(|(key, value)| (key, *value))));
+
+    let mut set: Set<Event> = Set::new();
+    set.insert(Event::Maybe(Some(Part::Three)));
+    set.insert(Event::Outcome(Ok(true)));
+    set.insert(Event::Start);
+    set.insert(Event::Byte(255));
+
+    let bytes = to_bytes(&set);
+    let archived = set_root::<Event>(&bytes);
+
+    assert_eq!(archived.len(), 4);
+    assert!(archived.contains(Event::Outcome(Ok(true))));
+    assert!(archived.contains(Event::Byte(255)));
+    assert!(!archived.contains(Event::Byte(0)));
+    assert!(!archived.contains(Event::Outcome(Ok(false))));
+    assert!(archived.iter().eq(set.iter()));
+}
+
+#[test]
+fn generic() {
+    let mut map: Map<Scoped<Part>, u32> = Map::new();
+    map.insert(Scoped::Global, 1);
+    map.insert(Scoped::Local(Part::Three), 3);
+
+    let bytes = to_bytes(&map);
+    let archived = map_root::<Scoped<Part>, u32>(&bytes);
+
+    assert_eq!(archived.get(Scoped::Local(Part::Three)), Some(&3));
+    assert_eq!(archived.get(Scoped::Local(Part::One)), None);
+    assert!(archived
+        .iter()
+        .eq([(Scoped::Global, &1), (Scoped::Local(Part::Three), &3)]));
+
+    let mut map: Map<Named<bool>, u32> = Map::new();
+    map.insert(Named::Local(false), 2);
+
+    let bytes = to_bytes(&map);
+    let archived = map_root::<Named<bool>, u32>(&bytes);
+    assert!(archived.iter().eq([(Named::Local(false), &2)]));
+
+    let mut set: Set<Scoped<bool>> = Set::new();
+    set.insert(Scoped::Local(true));
+
+    let bytes = to_bytes(&set);
+    let archived = set_root::<Scoped<bool>>(&bytes);
+    assert!(archived.iter().eq([Scoped::Local(true)]));
+}
+
+#[test]
+fn deserialize() {
+    let mut map: Map<Event, String> = Map::new();
+    map.insert(Event::Start, String::from("start"));
+    map.insert(Event::Byte(42), String::from("byte"));
+    map.insert(Event::Maybe(Some(Part::One)), String::from("one"));
+
+    let bytes = to_bytes(&map);
+    let archived = map_root::<Event, String>(&bytes);
+    assert_eq!(
+        archived
+            .get(Event::Maybe(Some(Part::One)))
+            .map(|s| s.as_str()),
+        Some("one")
+    );
+
+    let deserialized: Map<Event, String> = archived.deserialize(&mut rkyv::Infallible).unwrap();
+    assert_eq!(deserialized, map);
+
+    let mut set: Set<Bits> = Set::new();
+    set.insert(Bits::Second);
+
+    let bytes = to_bytes(&set);
+    let deserialized: Set<Bits> = set_root::<Bits>(&bytes)
+        .deserialize(&mut rkyv::Infallible)
+        .unwrap();
+    assert_eq!(deserialized, set);
+}
+
+#[cfg(feature = "rkyv-validation")]
+#[test]
+fn validation() {
+    let mut map: Map<Part, u32> = Map::new();
+    map.insert(Part::One, 1);
+    map.insert(Part::Three, 3);
+
+    let bytes = to_bytes(&map);
+    let archived = rkyv::check_archived_root::<Map<Part, u32>>(&bytes).unwrap();
+    assert!(archived.iter().eq([(Part::One, &1), (Part::Three, &3)]));
+
+    let mut map: Map<Event, String> = Map::new();
+    map.insert(Event::Start, String::from("start"));
+    map.insert(Event::Maybe(Some(Part::One)), String::from("one"));
+    map.insert(
+        Event::Outcome(Err(Ordering::Greater)),
+        String::from("greater"),
+    );
+    map.insert(Event::Byte(42), String::from("byte"));
+
+    let bytes = to_bytes(&map);
+    let archived = rkyv::check_archived_root::<Map<Event, String>>(&bytes).unwrap();
+    assert!(archived
+        .iter()
+        .map(|(key, value)| (key, value.as_str()))
+        .eq(map.iter().map(|(key, value)| (key, value.as_str()))));
+
+    let mut map: Map<Compact, String> = Map::new();
+    map.insert(Compact::Second, String::from("two"));
+
+    let bytes = to_bytes(&map);
+    let archived = rkyv::check_archived_root::<Map<Compact, String>>(&bytes).unwrap();
+    assert!(archived
+        .iter()
+        .map(|(key, value)| (key, value.as_str()))
+        .eq([(Compact::Second, "two")]));
+
+    let mut set: Set<Event> = Set::new();
+    set.insert(Event::Flag(true));
+    set.insert(Event::Maybe(None));
+    set.insert(Event::Byte(7));
+
+    let bytes = to_bytes(&set);
+    let archived = rkyv::check_archived_root::<Set<Event>>(&bytes).unwrap();
+    assert!(archived.iter().eq(set.iter()));
+
+    let mut set: Set<Scoped<Bits>> = Set::new();
+    set.insert(Scoped::Local(Bits::Second));
+
+    let bytes = to_bytes(&set);
+    let archived = rkyv::check_archived_root::<Set<Scoped<Bits>>>(&bytes).unwrap();
+    assert!(archived.iter().eq([Scoped::Local(Bits::Second)]));
+}
+
+#[cfg(feature = "rkyv-validation")]
+#[test]
+fn validation_rejects_invalid() {
+    // The tag of an archived option is either zero or one.
+    let mut map: Map<Part, u32> = Map::new();
+    map.insert(Part::One, 1);
+
+    let mut bytes = to_bytes(&map);
+    bytes[0] = 2;
+    assert!(rkyv::check_archived_root::<Map<Part, u32>>(&bytes).is_err());
+
+    // A bitset only has bits for each variant of the key.
+    let mut set: Set<Bits> = Set::new();
+    set.insert(Bits::First);
+
+    let mut bytes = to_bytes(&set);
+    assert!(rkyv::check_archived_root::<Set<Bits>>(&bytes).is_ok());
+    bytes[0] |= 0b1000;
+    assert!(rkyv::check_archived_root::<Set<Bits>>(&bytes).is_err());
+
+    // Compact storage must not mark slots beyond the end of its values.
+    let mut map: Map<Compact, u32> = Map::new();
+    map.insert(Compact::First, 1);
+
+    let mut bytes = to_bytes(&map);
+    assert!(rkyv::check_archived_root::<Map<Compact, u32>>(&bytes).is_ok());
+    bytes[0] |= 0b1000;
+    assert!(rkyv::check_archived_root::<Map<Compact, u32>>(&bytes).is_err());
+
+    // Boolean sets only have bits for `false` and `true`.
+    let mut set: Set<bool> = Set::new();
+    set.insert(true);
+
+    let mut bytes = to_bytes(&set);
+    assert!(rkyv::check_archived_root::<Set<bool>>(&bytes).is_ok());
+    bytes[0] |= 0b100;
+    assert!(rkyv::check_archived_root::<Set<bool>>(&bytes).is_err());
+}
@@ -0,0 +1,165 @@
+#![cfg(feature = "schemars")]
+
+use fixed_map::{Key, Map, Set};
+use schemars::{schema_for, JsonSchema};
+use serde_json::json;
+
+#[derive(Clone, Copy, Key)]
+#[key(schema)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Clone, Copy, Key)]
+#[key(schema)]
+enum Composite {
+    First,
+    Second(Part),
+    Third(bool),
+}
+
+#[derive(Clone, Copy, Key)]
+#[key(schema)]
+enum Scoped<T> {
+    Global,
+    Local(T),
+}
+
+#[derive(Clone, Copy, Key)]
+#[key(schema)]
+enum Empty {}
+
+#[test]
+fn unit_key() {
+    let schema = schema_for!(Part);
+
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Part",
+            "type": "string",
+            "enum": ["One", "Two"],
+        })
+    );
+}
+
+#[test]
+fn composite_key() {
+    let schema = schema_for!(Composite);
+
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Composite",
+            "oneOf": [
+                {
+                    "type": "string",
+                    "enum": ["First"],
+                },
+                {
+                    "type": "object",
+                    "properties": { "Second": { "$ref": "#/definitions/Part" } },
+                    "required": ["Second"],
+                    "additionalProperties": false,
+                },
+                {
+                    "type": "object",
+                    "properties": { "Third": { "type": "boolean" } },
+                    "required": ["Third"],
+                    "additionalProperties": false,
+                },
+            ],
+            "definitions": {
+                "Part": {
+                    "type": "string",
+                    "enum": ["One", "Two"],
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn generic_key() {
+    assert_eq!(Scoped::<Part>::schema_name(), "Scoped_for_Part");
+    assert_eq!(Part::schema_name(), "Part");
+}
+
+#[test]
+fn empty_key() {
+    let schema = schema_for!(Empty);
+    assert_eq!(
+        serde_json::to_value(schema.schema).unwrap(),
+        json!({ "title": "Empty", "not": {} })
+    );
+}
+
+#[test]
+fn map() {
+    let schema = schema_for!(Map<Part, u32>);
+
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Map_of_Part_and_uint32",
+            "type": "object",
+            "properties": {
+                "One": { "type": "integer", "format": "uint32", "minimum": 0.0 },
+                "Two": { "type": "integer", "format": "uint32", "minimum": 0.0 },
+            },
+            "additionalProperties": false,
+        })
+    );
+}
+
+#[test]
+fn composite_map() {
+    let schema = schema_for!(Map<Composite, bool>);
+    let object = serde_json::to_value(schema).unwrap();
+
+    assert_eq!(
+        object["properties"],
+        json!({ "First": { "type": "boolean" } })
+    );
+    // Composite variants aren't properties, so they're left to the schema of
+    // additional properties.
+    assert_eq!(object["additionalProperties"], json!({ "type": "boolean" }));
+}
+
+#[test]
+fn builtin_map() {
+    let schema = schema_for!(Map<bool, u8>);
+    let object = serde_json::to_value(schema).unwrap();
+
+    assert_eq!(object["type"], json!("object"));
+    assert_eq!(
+        object["additionalProperties"],
+        json!({ "type": "integer", "format": "uint8", "minimum": 0.0 })
+    );
+}
+
+#[test]
+fn set() {
+    let schema = schema_for!(Set<Part>);
+
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Set_of_Part",
+            "type": "array",
+            "items": { "$ref": "#/definitions/Part" },
+            "uniqueItems": true,
+            "definitions": {
+                "Part": {
+                    "type": "string",
+                    "enum": ["One", "Two"],
+                },
+            },
+        })
+    );
+}
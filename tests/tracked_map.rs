@@ -0,0 +1,94 @@
+use fixed_map::map::{OccupiedEntry, VacantEntry};
+use fixed_map::tracked_map::Entry;
+use fixed_map::{Key, Map, Set, TrackedMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(bitset)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Event {
+    Start,
+    Part(Part),
+}
+
+#[test]
+fn bitset() {
+    let mut map = TrackedMap::new();
+    map.insert(Part::One, 1);
+    map.insert(Part::Three, 3);
+    assert_eq!(map.dirty().as_raw(), 0b101);
+    assert_eq!(map.take_dirty(), Set::from([Part::One, Part::Three]));
+
+    assert_eq!(map.remove(Part::Two), None);
+    assert!(map.get_mut(Part::Two).is_none());
+    assert!(map.dirty().is_empty());
+
+    match map.entry(Part::Two) {
+        Entry::Vacant(entry) => {
+            entry.insert(2);
+        }
+        Entry::Occupied(..) => unreachable!(),
+    }
+
+    map.retain(|_, value| *value != 1);
+    assert_eq!(map.take_dirty(), Set::from([Part::One, Part::Two]));
+
+    map.clear();
+    assert_eq!(map.take_dirty(), Set::from([Part::Two, Part::Three]));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn composite() {
+    let mut map = TrackedMap::from(Map::from_iter([(Event::Start, 0)]));
+    assert!(map.dirty().is_empty());
+
+    *map.get_mut(Event::Start).unwrap() += 1;
+    map.insert(Event::Part(Part::Two), 2);
+
+    assert!(map.is_dirty(Event::Part(Part::Two)));
+    assert!(!map.is_dirty(Event::Part(Part::One)));
+    assert_eq!(
+        map.take_dirty(),
+        Set::from([Event::Start, Event::Part(Part::Two)])
+    );
+
+    let map = Map::from(map);
+    assert_eq!(map.get(Event::Start), Some(&1));
+}
+
+#[test]
+fn entry() {
+    let mut map = TrackedMap::from(Map::from_iter([(Event::Start, 0)]));
+
+    assert_eq!(*map.entry(Event::Start).or_insert(1), 0);
+    assert_eq!(
+        map.entry(Event::Part(Part::One)).key(),
+        Event::Part(Part::One)
+    );
+    assert!(map.dirty().is_empty());
+
+    match map.entry(Event::Start) {
+        Entry::Occupied(mut entry) => {
+            assert_eq!(entry.insert(2), 0);
+        }
+        Entry::Vacant(..) => unreachable!(),
+    }
+
+    assert_eq!(map.take_dirty(), Set::from([Event::Start]));
+
+    match map.entry(Event::Start) {
+        Entry::Occupied(entry) => {
+            assert_eq!(entry.remove(), 2);
+        }
+        Entry::Vacant(..) => unreachable!(),
+    }
+
+    assert_eq!(map.take_dirty(), Set::from([Event::Start]));
+    assert!(map.is_empty());
+}
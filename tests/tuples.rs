@@ -0,0 +1,114 @@
+use core::cmp::Ordering;
+
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[test]
+fn map() {
+    let mut map: Map<(Part, bool), u32> = Map::new();
+
+    assert_eq!(map.insert((Part::Two, true), 1), None);
+    assert_eq!(map.insert((Part::One, false), 2), None);
+    assert_eq!(map.insert((Part::Two, true), 3), Some(1));
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get((Part::Two, true)), Some(&3));
+    assert_eq!(map.get((Part::Two, false)), None);
+
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        [((Part::One, false), &2), ((Part::Two, true), &3)]
+    );
+    assert_eq!(
+        map.keys().rev().collect::<Vec<_>>(),
+        [(Part::Two, true), (Part::One, false)]
+    );
+
+    assert_eq!(map.remove((Part::One, false)), Some(2));
+    assert_eq!(map.remove((Part::One, false)), None);
+    assert_eq!(map.len(), 1);
+
+    map.clear();
+    assert!(map.is_empty());
+}
+
+#[test]
+fn map_entry() {
+    let mut map: Map<(Part, Option<bool>), u32> = Map::new();
+
+    *map.entry((Part::One, None)).or_default() += 1;
+    *map.entry((Part::One, None)).or_default() += 1;
+    *map.entry((Part::One, Some(true))).or_default() += 1;
+
+    assert_eq!(map.get((Part::One, None)), Some(&2));
+    assert_eq!(map.get((Part::One, Some(true))), Some(&1));
+    assert_eq!(map.len(), 2);
+
+    let mut other = Map::new();
+    other.insert((Part::One, Some(true)), 1);
+    other.insert((Part::One, None), 2);
+    assert_eq!(map, other);
+}
+
+#[test]
+fn map_triple() {
+    let mut map: Map<(Part, Ordering, bool), u32> = Map::new();
+
+    map.insert((Part::Two, Ordering::Less, true), 1);
+    map.insert((Part::One, Ordering::Greater, false), 2);
+    map.insert((Part::One, Ordering::Equal, true), 3);
+
+    map.retain(|(_, ordering, _), _| ordering.is_ne());
+
+    assert_eq!(
+        map.into_iter().collect::<Vec<_>>(),
+        [
+            ((Part::One, Ordering::Greater, false), 2),
+            ((Part::Two, Ordering::Less, true), 1)
+        ]
+    );
+}
+
+#[test]
+fn set() {
+    let mut set: Set<(Part, Result<bool, Part>)> = Set::new();
+
+    assert!(set.insert((Part::Two, Ok(true))));
+    assert!(set.insert((Part::Two, Err(Part::One))));
+    assert!(set.insert((Part::One, Ok(false))));
+    assert!(!set.insert((Part::One, Ok(false))));
+
+    assert_eq!(set.len(), 3);
+    assert!(set.contains((Part::Two, Err(Part::One))));
+    assert!(!set.contains((Part::One, Err(Part::One))));
+
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        [
+            (Part::One, Ok(false)),
+            (Part::Two, Ok(true)),
+            (Part::Two, Err(Part::One))
+        ]
+    );
+    assert_eq!(
+        set.iter().rev().collect::<Vec<_>>(),
+        [
+            (Part::Two, Err(Part::One)),
+            (Part::Two, Ok(true)),
+            (Part::One, Ok(false))
+        ]
+    );
+
+    set.retain(|(part, _)| part == Part::Two);
+    assert_eq!(set.len(), 2);
+
+    assert!(set.remove((Part::Two, Ok(true))));
+    assert!(set.remove((Part::Two, Err(Part::One))));
+    assert!(set.is_empty());
+    assert_eq!(set.iter().count(), 0);
+}
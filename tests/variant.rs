@@ -0,0 +1,134 @@
+use fixed_map::map::Variant;
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum MyKey {
+    Simple,
+    Composite(Part),
+    Flag(Option<bool>),
+    First(bool),
+    Second(bool),
+}
+
+#[test]
+fn variant() {
+    let mut map = Map::new();
+    map.insert(MyKey::Simple, 1);
+    map.insert(MyKey::Composite(Part::One), 2);
+    map.insert(MyKey::Composite(Part::Three), 3);
+    map.insert(MyKey::Flag(Some(true)), 4);
+    map.insert(MyKey::First(true), 5);
+
+    let composite = map.variant(MyKey::COMPOSITE);
+    assert_eq!(composite.len(), 2);
+    assert_eq!(composite.get(Part::Three), Some(&3));
+    assert!(!composite.contains_key(Part::Two));
+    assert!(composite.keys().eq([Part::One, Part::Three]));
+    assert_eq!(format!("{composite:?}"), "{One: 2, Three: 3}");
+
+    let mut flag = map.variant_mut(MyKey::FLAG);
+    *flag.get_mut(Some(true)).unwrap() += 40;
+    flag.insert(None, 6);
+
+    for value in flag.values_mut() {
+        *value += 1;
+    }
+
+    assert!(map.variant(MyKey::FLAG).values().eq(&[45, 7]));
+
+    let mut composite = map.variant_mut(MyKey::COMPOSITE);
+    composite.retain(|part, _| part != Part::One);
+    assert_eq!(composite.remove(Part::Three), Some(3));
+    assert!(composite.is_empty());
+    assert!(!map.contains_key(MyKey::Composite(Part::Three)));
+
+    let flags = map.into_variant(MyKey::FLAG);
+    assert!(flags.variant(Variant::SOME).iter().eq([(true, &45)]));
+    assert_eq!(flags.len(), 2);
+}
+
+#[test]
+fn shared_field_types() {
+    let mut map = Map::new();
+    map.insert(MyKey::First(true), 1);
+    map.insert(MyKey::Second(true), 2);
+    map.insert(MyKey::Second(false), 3);
+
+    assert!(map.variant(MyKey::FIRST).iter().eq([(true, &1)]));
+    let second = map.variant(MyKey::SECOND);
+    assert_eq!(second.len(), 2);
+    assert_eq!(second.get(true), Some(&2));
+    assert_eq!(second.get(false), Some(&3));
+
+    map.variant_mut(MyKey::SECOND).clear();
+    assert!(map.into_variant(MyKey::FIRST).into_iter().eq([(true, 1)]));
+}
+
+#[test]
+fn aliased_field_types() {
+    type Alias = Part;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Aliased {
+        Plain(Part),
+        Alias(Alias),
+        Path(self::Part),
+    }
+
+    let mut map = Map::new();
+    map.insert(Aliased::Plain(Part::One), 1);
+    map.insert(Aliased::Alias(Part::Two), 2);
+    map.insert(Aliased::Path(Part::Three), 3);
+
+    assert!(map.variant(Aliased::PLAIN).keys().eq([Part::One]));
+    assert!(map.variant(Aliased::ALIAS).keys().eq([Part::Two]));
+    assert!(map.variant(Aliased::PATH).keys().eq([Part::Three]));
+}
+
+#[test]
+fn generic_key() {
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Scoped<T> {
+        Global,
+        Local(T),
+        Remote(T),
+    }
+
+    let mut map = Map::new();
+    map.insert(Scoped::Global, 1);
+    map.insert(Scoped::Local(Part::One), 2);
+    map.insert(Scoped::Remote(Part::One), 3);
+
+    assert!(map.variant(Scoped::LOCAL).iter().eq([(Part::One, &2)]));
+    assert!(map.variant(Scoped::REMOTE).iter().eq([(Part::One, &3)]));
+}
+
+#[test]
+fn selector_names() {
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    #[allow(clippy::upper_case_acronyms)]
+    enum Names {
+        HttpStatus(bool),
+        IOError(bool),
+        Level2(bool),
+        r#Type(bool),
+    }
+
+    let mut map = Map::new();
+    map.insert(Names::HttpStatus(true), 1);
+    map.insert(Names::IOError(true), 2);
+    map.insert(Names::Level2(true), 3);
+    map.insert(Names::Type(true), 4);
+
+    assert!(map.variant(Names::HTTP_STATUS).values().eq(&[1]));
+    assert!(map.variant(Names::IO_ERROR).values().eq(&[2]));
+    assert!(map.variant(Names::LEVEL2).values().eq(&[3]));
+    assert!(map.variant(Names::TYPE).values().eq(&[4]));
+}